@@ -0,0 +1,64 @@
+#![feature(test)]
+
+extern crate test;
+use crate::test::Bencher;
+
+use {
+    futures::{channel::spsc, stream::StreamExt, task::Poll},
+    futures_test::task::noop_context,
+};
+
+/// Compare with `sync_mpsc::unbounded_uncontended`
+#[bench]
+fn uncontended(b: &mut Bencher) {
+    let mut cx = noop_context();
+    b.iter(|| {
+        let (mut tx, mut rx) = spsc::channel(1000);
+
+        for i in 0..1000 {
+            tx.try_send(i).expect("send");
+            // No need to create a task, because poll is not going to park.
+            assert_eq!(Poll::Ready(Some(i)), rx.poll_next_unpin(&mut cx));
+        }
+    })
+}
+
+/// Compare with `sync_mpsc::bounded_1_tx`
+#[bench]
+fn bounded_1(b: &mut Bencher) {
+    let mut cx = noop_context();
+    b.iter(|| {
+        let (mut tx, mut rx) = spsc::channel(1);
+
+        for i in 0..1000 {
+            // Poll, not ready, park
+            assert_eq!(Poll::Pending, rx.poll_next_unpin(&mut cx));
+
+            assert_eq!(Poll::Ready(Ok(())), tx.poll_ready(&mut cx));
+            tx.start_send(i).unwrap();
+            // The channel is full until the message is received.
+            assert_eq!(Poll::Pending, tx.poll_ready(&mut cx));
+
+            assert_eq!(Poll::Ready(Some(i)), rx.poll_next_unpin(&mut cx));
+        }
+    })
+}
+
+/// Sends and receives batches of messages, as a producer running ahead of
+/// its consumer does.
+#[bench]
+fn bounded_batches(b: &mut Bencher) {
+    let mut cx = noop_context();
+    b.iter(|| {
+        let (mut tx, mut rx) = spsc::channel(100);
+
+        for _ in 0..10 {
+            for i in 0..100 {
+                tx.try_send(i).unwrap();
+            }
+            for i in 0..100 {
+                assert_eq!(Poll::Ready(Some(i)), rx.poll_next_unpin(&mut cx));
+            }
+        }
+    })
+}
@@ -0,0 +1,494 @@
+//! A bounded multi-producer, multi-consumer channel where each value is
+//! delivered to every receiver.
+//!
+//! A [`Sender`] sends values to all the [`Receiver`]s subscribed to the
+//! channel at the time of the send, each of them receiving its own clone of
+//! the value. New receivers are created with [`Sender::subscribe`] and
+//! [`Receiver::resubscribe`], and only receive the values sent after their
+//! creation.
+//!
+//! # Lagging
+//!
+//! Sending never waits: the channel holds up to `capacity` values which
+//! haven't been received by every receiver yet, and when it's full, sending
+//! drops the oldest of them. A receiver which was too slow to receive the
+//! dropped values is said to lag behind. It can handle this in two ways:
+//!
+//! - [`Receiver::recv`], [`Receiver::poll_recv`] and [`Receiver::try_recv`]
+//!   return [`RecvError::Lagged`] with the number of values it missed, then
+//!   continue with the oldest value still in the channel;
+//! - the [`Stream`] implementation of `Receiver` silently skips the missed
+//!   values.
+//!
+//! # Disconnection
+//!
+//! Once every `Sender` is dropped, receivers receive the remaining values,
+//! then the stream terminates. Sending fails once every `Receiver` is
+//! dropped.
+
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::__internal::coop;
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Creates a bounded broadcast channel holding up to `capacity` values.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::broadcast;
+/// use futures::executor::block_on;
+///
+/// let (tx, mut rx1) = broadcast::channel(16);
+/// let mut rx2 = tx.subscribe();
+///
+/// tx.send(10).unwrap();
+/// assert_eq!(block_on(rx1.recv()), Ok(10));
+/// assert_eq!(block_on(rx2.recv()), Ok(10));
+/// ```
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "broadcast channel capacity must be greater than zero");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(capacity),
+            head: 0,
+            capacity,
+            num_senders: 1,
+            num_receivers: 0,
+            next_id: 0,
+            wakers: HashMap::new(),
+        }),
+    });
+    let rx = Receiver::new(shared.clone());
+    (Sender { shared }, rx)
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        // The state stays consistent if a clone panics.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+struct State<T> {
+    // Values not yet received by every receiver, oldest first.
+    queue: VecDeque<Slot<T>>,
+
+    // Position of the front of `queue` among all the values ever sent.
+    head: u64,
+
+    capacity: usize,
+    num_senders: usize,
+    num_receivers: usize,
+    next_id: u64,
+
+    // Wakers of the receivers waiting for a value, by receiver id.
+    wakers: HashMap<u64, Waker>,
+}
+
+struct Slot<T> {
+    value: T,
+
+    // Number of receivers which still have to receive this value.
+    remaining: usize,
+}
+
+impl<T> State<T> {
+    fn tail(&self) -> u64 {
+        self.head + self.queue.len() as u64
+    }
+
+    fn is_closed(&self) -> bool {
+        self.num_senders == 0
+    }
+
+    // Drops the values received by every receiver from the front of the queue.
+    fn pop_received(&mut self) {
+        while self.queue.front().map_or(false, |slot| slot.remaining == 0) {
+            self.queue.pop_front();
+            self.head += 1;
+        }
+    }
+
+    fn wake_all(&mut self) {
+        for (_, waker) in self.wakers.drain() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: Clone> State<T> {
+    // Receives the value at position `next`, for a receiver.
+    fn recv(&mut self, next: &mut u64) -> Result<T, TryRecvError> {
+        if *next < self.head {
+            let missed = self.head - *next;
+            *next = self.head;
+            return Err(TryRecvError::Lagged(missed));
+        }
+        if *next == self.tail() {
+            return Err(if self.is_closed() { TryRecvError::Closed } else { TryRecvError::Empty });
+        }
+
+        let index = (*next - self.head) as usize;
+        *next += 1;
+        let slot = &mut self.queue[index];
+        slot.remaining -= 1;
+        if slot.remaining == 0 {
+            // Receivers receive values in order, so this is the front.
+            debug_assert_eq!(index, 0);
+            self.head += 1;
+            Ok(self.queue.pop_front().unwrap().value)
+        } else {
+            Ok(slot.value.clone())
+        }
+    }
+}
+
+/// The sending side of a broadcast channel, created by [`channel`].
+///
+/// Senders can be cloned to send from multiple tasks.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a value to every receiver of the channel, without waiting.
+    ///
+    /// Returns the number of receivers the value was sent to. If the channel
+    /// is full, the oldest value in it is dropped, making the receivers which
+    /// didn't receive it yet lag behind.
+    ///
+    /// Fails, returning the value, if there are no receivers.
+    pub fn send(&self, value: T) -> Result<usize, SendError<T>> {
+        let mut state = self.shared.lock();
+        let receivers = state.num_receivers;
+        if receivers == 0 {
+            return Err(SendError { value });
+        }
+        if state.queue.len() == state.capacity {
+            state.queue.pop_front();
+            state.head += 1;
+        }
+        state.queue.push_back(Slot { value, remaining: receivers });
+        state.wake_all();
+        Ok(receivers)
+    }
+
+    /// Creates a new receiver, which receives the values sent after this
+    /// call.
+    pub fn subscribe(&self) -> Receiver<T> {
+        Receiver::new(self.shared.clone())
+    }
+
+    /// Returns the number of receivers of the channel.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.lock().num_receivers
+    }
+
+    /// Returns whether every receiver of the channel was dropped.
+    ///
+    /// Subscribing a new receiver reopens the channel.
+    pub fn is_closed(&self) -> bool {
+        self.receiver_count() == 0
+    }
+
+    /// Returns whether the senders send to the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().num_senders += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            state.wake_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("receivers", &self.receiver_count()).finish()
+    }
+}
+
+/// The receiving side of a broadcast channel, created by [`channel`] and
+/// [`Sender::subscribe`].
+///
+/// Each receiver receives every value sent after its creation. See the
+/// [module documentation](self) for how receivers lagging behind are
+/// handled.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    id: u64,
+
+    // Position of the next value to receive.
+    next: u64,
+}
+
+impl<T> Receiver<T> {
+    fn new(shared: Arc<Shared<T>>) -> Self {
+        let (id, next) = {
+            let mut state = shared.lock();
+            state.num_receivers += 1;
+            let id = state.next_id;
+            state.next_id += 1;
+            (id, state.tail())
+        };
+        Self { shared, id, next }
+    }
+
+    /// Creates a new receiver for the same channel, which receives the
+    /// values sent after this call.
+    ///
+    /// The values this receiver hasn't received yet aren't received by the
+    /// new one.
+    pub fn resubscribe(&self) -> Self {
+        Self::new(self.shared.clone())
+    }
+
+    /// Returns the number of values this receiver can receive without
+    /// waiting.
+    ///
+    /// This doesn't include the values it missed by lagging behind.
+    pub fn len(&self) -> usize {
+        let state = self.shared.lock();
+        (state.tail() - self.next.max(state.head)) as usize
+    }
+
+    /// Returns whether this receiver has no value to receive without waiting.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether every sender of the channel was dropped.
+    ///
+    /// The receiver may still have values to receive.
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().is_closed()
+    }
+
+    /// Returns whether the receivers receive from the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Tries to receive the next value without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        self.shared.lock().recv(&mut self.next)
+    }
+
+    /// Polls for the next value, registering the current task to be woken
+    /// when one is sent.
+    ///
+    /// Returns [`RecvError::Lagged`] if values were dropped before this
+    /// receiver received them, and [`RecvError::Closed`] once every sender
+    /// was dropped and every value was received.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
+        let coop = ready!(coop::poll_proceed(cx));
+        let mut state = self.shared.lock();
+        let res = match state.recv(&mut self.next) {
+            Ok(value) => Ok(value),
+            Err(TryRecvError::Lagged(n)) => Err(RecvError::Lagged(n)),
+            Err(TryRecvError::Closed) => Err(RecvError::Closed),
+            Err(TryRecvError::Empty) => {
+                match state.wakers.get_mut(&self.id) {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    Some(waker) => *waker = cx.waker().clone(),
+                    None => {
+                        state.wakers.insert(self.id, cx.waker().clone());
+                    }
+                }
+                return Poll::Pending;
+            }
+        };
+        coop.made_progress();
+        Poll::Ready(res)
+    }
+
+    /// Receives the next value.
+    ///
+    /// See [`poll_recv`](Receiver::poll_recv) for the possible errors.
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: Some(self) }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_receivers -= 1;
+        state.wakers.remove(&self.id);
+        let start = self.next.saturating_sub(state.head) as usize;
+        for slot in state.queue.iter_mut().skip(start) {
+            slot.remaining -= 1;
+        }
+        state.pop_received();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").field("len", &self.len()).finish()
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+
+    /// Polls for the next value, skipping the values the receiver missed by
+    /// lagging behind.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        loop {
+            match ready!(this.poll_recv(cx)) {
+                Ok(value) => return Poll::Ready(Some(value)),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let state = self.shared.lock();
+        let len = (state.tail() - self.next.max(state.head)) as usize;
+        if state.is_closed() {
+            (len, Some(len))
+        } else {
+            (len, None)
+        }
+    }
+}
+
+impl<T: Clone> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        let state = self.shared.lock();
+        state.is_closed() && self.next == state.tail()
+    }
+}
+
+/// Future for the [`Receiver::recv`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Recv<'a, T> {
+    receiver: Option<&'a mut Receiver<T>>,
+}
+
+impl<T: Clone> Future for Recv<'_, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let receiver = self.receiver.as_mut().expect("`Recv` polled after completion");
+        let res = ready!(receiver.poll_recv(cx));
+        self.receiver = None;
+        Poll::Ready(res)
+    }
+}
+
+impl<T: Clone> FusedFuture for Recv<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_none()
+    }
+}
+
+impl<T> fmt::Debug for Recv<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recv").finish()
+    }
+}
+
+/// The error returned by [`Sender::send`] when there are no receivers,
+/// holding the value which couldn't be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T> {
+    value: T,
+}
+
+impl<T> SendError<T> {
+    /// Returns the value which couldn't be sent.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "send failed because there are no receivers")
+    }
+}
+
+impl<T: core::any::Any> std::error::Error for SendError<T> {}
+
+/// The error returned by [`Receiver::recv`] and [`Receiver::poll_recv`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecvError {
+    /// The receiver lagged behind and missed this number of values, which
+    /// were dropped to make room for new ones.
+    Lagged(u64),
+    /// Every sender was dropped and every value was received.
+    Closed,
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lagged(n) => write!(f, "receiver lagged behind and missed {} values", n),
+            Self::Closed => write!(f, "channel is closed"),
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+/// The error returned by [`Receiver::try_recv`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// There is no value to receive yet.
+    Empty,
+    /// The receiver lagged behind and missed this number of values, which
+    /// were dropped to make room for new ones.
+    Lagged(u64),
+    /// Every sender was dropped and every value was received.
+    Closed,
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "channel is empty"),
+            Self::Lagged(n) => write!(f, "receiver lagged behind and missed {} values", n),
+            Self::Closed => write!(f, "channel is closed"),
+        }
+    }
+}
+
+impl std::error::Error for TryRecvError {}
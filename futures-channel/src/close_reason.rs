@@ -0,0 +1,89 @@
+//! The reason given by a receiver for closing its channel.
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::any::Any;
+use core::fmt;
+use core::ptr;
+
+use crate::loom::atomic::AtomicPtr;
+use crate::loom::atomic::Ordering::SeqCst;
+
+/// The reason a receiver gave for closing its channel, reported to the
+/// senders.
+///
+/// The reason can be any value which is `Send + Sync + 'static`, for example
+/// an enum telling a graceful shutdown apart from a failure. The senders get
+/// it back with [`downcast_ref`](CloseReason::downcast_ref).
+///
+/// Cloning a reason is cheap: its clones share the same value, and compare
+/// equal to each other.
+#[derive(Clone)]
+pub struct CloseReason(Arc<dyn Any + Send + Sync>);
+
+impl CloseReason {
+    /// Creates a close reason holding the given value.
+    pub fn new<R: Any + Send + Sync>(reason: R) -> Self {
+        Self(Arc::new(reason))
+    }
+
+    /// Returns whether the reason holds a value of type `R`.
+    pub fn is<R: Any>(&self) -> bool {
+        self.0.is::<R>()
+    }
+
+    /// Returns a reference to the value of the reason, if it's of type `R`.
+    pub fn downcast_ref<R: Any>(&self) -> Option<&R> {
+        self.0.downcast_ref()
+    }
+}
+
+impl PartialEq for CloseReason {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CloseReason {}
+
+impl fmt::Debug for CloseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CloseReason(..)")
+    }
+}
+
+/// A slot for the close reason of a channel, which is set at most once and
+/// only freed along with the channel.
+pub(crate) struct ReasonSlot {
+    reason: AtomicPtr<CloseReason>,
+}
+
+impl ReasonSlot {
+    pub(crate) fn new() -> Self {
+        Self { reason: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    /// Sets the reason, unless it's already set.
+    pub(crate) fn set(&self, reason: CloseReason) {
+        let new = Box::into_raw(Box::new(reason));
+        if self.reason.compare_exchange(ptr::null_mut(), new, SeqCst, SeqCst).is_err() {
+            drop(unsafe { Box::from_raw(new) });
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<CloseReason> {
+        let reason = self.reason.load(SeqCst);
+        // Safety: a reason which is set is never modified before the slot is
+        // dropped.
+        unsafe { reason.as_ref() }.cloned()
+    }
+}
+
+impl Drop for ReasonSlot {
+    fn drop(&mut self) {
+        let reason = self.reason.load(SeqCst);
+        if !reason.is_null() {
+            drop(unsafe { Box::from_raw(reason) });
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Instrumentation of the channels, enabled by the `tracing` feature.
+//!
+//! With the feature enabled, the [`mpsc`](crate::mpsc) and
+//! [`oneshot`](crate::oneshot) channels emit [`tracing`] events with the
+//! `futures_channel` target when they're created or closed, at the debug
+//! level, and when a message is sent or received, at the trace level. The
+//! events have a `channel.id` field identifying the channel, and a
+//! `channel.kind` field.
+//!
+//! A [`Hook`] installed with [`set_hook`] is called for the same events, for
+//! custom instrumentation such as metrics.
+//!
+//! Without the feature, none of this is compiled in.
+
+use alloc::boxed::Box;
+use core::fmt;
+use core::ptr;
+use core::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
+use core::sync::atomic::{AtomicPtr, AtomicUsize};
+
+/// Identifies a channel in the instrumentation events.
+///
+/// Each channel created gets a new identifier, shared by all of its halves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChannelId(u64);
+
+impl ChannelId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT_ID.fetch_add(1, Relaxed) as u64)
+    }
+
+    /// Returns the identifier as an integer.
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for ChannelId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The kind of an instrumented channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ChannelKind {
+    /// A channel created by [`mpsc::channel`](crate::mpsc::channel).
+    Bounded,
+    /// A channel created by [`mpsc::unbounded`](crate::mpsc::unbounded).
+    Unbounded,
+    /// A channel created by [`oneshot::channel`](crate::oneshot::channel).
+    Oneshot,
+}
+
+impl ChannelKind {
+    /// Returns the name of the kind, as found in the `channel.kind` field of
+    /// the events.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bounded => "mpsc",
+            Self::Unbounded => "mpsc::unbounded",
+            Self::Oneshot => "oneshot",
+        }
+    }
+}
+
+impl fmt::Display for ChannelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Custom instrumentation of the channels, installed with [`set_hook`].
+///
+/// The methods are called synchronously by the channel operations, so they
+/// should be cheap and must not block. They do nothing by default.
+pub trait Hook: Send + Sync {
+    /// Called when a channel is created.
+    fn on_create(&self, id: ChannelId, kind: ChannelKind) {
+        let _ = (id, kind);
+    }
+
+    /// Called when a message is sent into a channel.
+    fn on_send(&self, id: ChannelId, kind: ChannelKind) {
+        let _ = (id, kind);
+    }
+
+    /// Called when a message is received from a channel.
+    fn on_recv(&self, id: ChannelId, kind: ChannelKind) {
+        let _ = (id, kind);
+    }
+
+    /// Called when a channel is closed, by either of its halves.
+    fn on_close(&self, id: ChannelId, kind: ChannelKind) {
+        let _ = (id, kind);
+    }
+}
+
+// The installed hook, leaked so that it can be used without synchronization.
+static HOOK: AtomicPtr<Box<dyn Hook>> = AtomicPtr::new(ptr::null_mut());
+
+/// Installs the hook called for the events of all channels.
+///
+/// The hook can only be installed once, and fails if one was installed
+/// already.
+pub fn set_hook<H: Hook + 'static>(hook: H) -> Result<(), SetHookError> {
+    if !HOOK.load(Acquire).is_null() {
+        return Err(SetHookError { _priv: () });
+    }
+    let hook: Box<Box<dyn Hook>> = Box::new(Box::new(hook));
+    let hook = Box::into_raw(hook);
+    match HOOK.compare_exchange(ptr::null_mut(), hook, AcqRel, Acquire) {
+        Ok(_) => Ok(()),
+        Err(_) => {
+            drop(unsafe { Box::from_raw(hook) });
+            Err(SetHookError { _priv: () })
+        }
+    }
+}
+
+/// The error returned by [`set_hook`] when a hook was installed already.
+#[derive(Debug)]
+pub struct SetHookError {
+    _priv: (),
+}
+
+impl fmt::Display for SetHookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a channel hook was already installed")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetHookError {}
+
+fn with_hook(f: impl FnOnce(&dyn Hook)) {
+    let hook = HOOK.load(Acquire);
+    // Safety: an installed hook is never freed.
+    if let Some(hook) = unsafe { hook.as_ref() } {
+        f(&**hook);
+    }
+}
+
+// The instrumentation of a channel, held by its shared state.
+#[derive(Debug)]
+pub(crate) struct Probe {
+    id: ChannelId,
+    kind: ChannelKind,
+}
+
+impl Probe {
+    fn new(kind: ChannelKind) -> Self {
+        let id = ChannelId::next();
+        tracing::debug!(
+            target: "futures_channel",
+            { channel.id = id.0, channel.kind = kind.as_str() },
+            "channel created"
+        );
+        with_hook(|hook| hook.on_create(id, kind));
+        Self { id, kind }
+    }
+
+    pub(crate) fn bounded() -> Self {
+        Self::new(ChannelKind::Bounded)
+    }
+
+    pub(crate) fn unbounded() -> Self {
+        Self::new(ChannelKind::Unbounded)
+    }
+
+    pub(crate) fn oneshot() -> Self {
+        Self::new(ChannelKind::Oneshot)
+    }
+
+    pub(crate) fn sent(&self) {
+        tracing::trace!(
+            target: "futures_channel",
+            { channel.id = self.id.0, channel.kind = self.kind.as_str() },
+            "message sent"
+        );
+        with_hook(|hook| hook.on_send(self.id, self.kind));
+    }
+
+    pub(crate) fn received(&self) {
+        tracing::trace!(
+            target: "futures_channel",
+            { channel.id = self.id.0, channel.kind = self.kind.as_str() },
+            "message received"
+        );
+        with_hook(|hook| hook.on_recv(self.id, self.kind));
+    }
+
+    pub(crate) fn closed(&self) {
+        tracing::debug!(
+            target: "futures_channel",
+            { channel.id = self.id.0, channel.kind = self.kind.as_str() },
+            "channel closed"
+        );
+        with_hook(|hook| hook.on_close(self.id, self.kind));
+    }
+}
@@ -1,15 +1,41 @@
 //! Asynchronous channels.
 //!
 //! Like threads, concurrent tasks sometimes need to communicate with each
-//! other. This module contains two basic abstractions for doing so:
+//! other. This module contains a few basic abstractions for doing so:
 //!
 //! - [oneshot], a way of sending a single value from one task to another.
+//! - [reusable], a oneshot channel which can be reused for successive
+//!   exchanges.
 //! - [mpsc], a multi-producer, single-consumer channel for sending values
 //!   between tasks, analogous to the similarly-named structure in the standard
 //!   library.
+//! - [spsc], a single-producer, single-consumer channel, cheaper than
+//!   [mpsc] when there is only one sender.
+//! - [mpmc], a multi-producer, multi-consumer channel delivering each value
+//!   to one of the receivers, for sharing work between tasks.
+//! - [broadcast], a multi-producer, multi-consumer channel delivering each
+//!   value to every receiver.
+//! - [priority], a multi-producer, single-consumer channel delivering
+//!   messages of higher priority first.
+//! - [weighted], a multi-producer, single-consumer channel whose capacity is
+//!   a total weight, such as a number of bytes, rather than a number of
+//!   messages.
+//! - [watch], a channel holding a single value, whose receivers are notified
+//!   when it changes.
 //!
 //! All items are only available when the `std` or `alloc` feature of this
 //! library is activated, and it is activated by default.
+//!
+//! With only the `alloc` feature, for `no_std` targets, [oneshot], [mpsc]
+//! and [spsc] are available, without the methods which block the thread or
+//! take their time from a timer. The short critical sections of [mpsc] then
+//! spin instead of blocking the thread. The other channels require the `std`
+//! feature.
+//!
+//! The `portable-atomic` feature makes the channels use the atomics of the
+//! [portable-atomic](https://docs.rs/portable-atomic) crate, as the
+//! `AtomicWaker` of futures-core does with its own `portable-atomic`
+//! feature, for targets lacking some native atomic operations.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(
@@ -31,12 +57,45 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub mod broadcast;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+mod close_reason;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "tracing")]
+pub mod instrument;
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 mod lock;
 #[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+mod loom;
+#[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "std")]
+pub mod mpmc;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
 pub mod mpsc;
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 pub mod oneshot;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub mod priority;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+mod probe;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub mod reusable;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+pub mod spsc;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub mod watch;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub mod weighted;
@@ -4,10 +4,11 @@
 //! thing that ever blocks, so this is assisted with a fast user-space
 //! implementation of a lock that can only have a `try_lock` operation.
 
-use core::cell::UnsafeCell;
 use core::ops::{Deref, DerefMut};
-use core::sync::atomic::AtomicBool;
-use core::sync::atomic::Ordering::SeqCst;
+
+use crate::loom::atomic::AtomicBool;
+use crate::loom::atomic::Ordering::SeqCst;
+use crate::loom::UnsafeCell;
 
 /// A "mutex" around a value, similar to `std::sync::Mutex<T>`.
 ///
@@ -63,7 +64,7 @@ impl<T> Deref for TryLock<'_, T> {
     fn deref(&self) -> &T {
         // The existence of `TryLock` represents that we own the lock, so we
         // can safely access the data here.
-        unsafe { &*self.__ptr.data.get() }
+        self.__ptr.data.with(|data| unsafe { &*data })
     }
 }
 
@@ -74,7 +75,7 @@ impl<T> DerefMut for TryLock<'_, T> {
         //
         // Additionally, we're the *only* `TryLock` in existence so mutable
         // access should be ok.
-        unsafe { &mut *self.__ptr.data.get() }
+        self.__ptr.data.with_mut(|data| unsafe { &mut *data })
     }
 }
 
@@ -84,6 +85,56 @@ impl<T> Drop for TryLock<'_, T> {
     }
 }
 
+/// A spinning mutex, used in place of `std::sync::Mutex` when the `std`
+/// feature is disabled.
+///
+/// It mirrors the API of `std::sync::Mutex`, so that the channels can use
+/// either, but it's never poisoned.
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub(crate) struct Mutex<T> {
+    lock: Lock<T>,
+}
+
+/// The error of a poisoned [`Mutex`], which never happens.
+#[cfg(not(feature = "std"))]
+pub(crate) struct PoisonError<G> {
+    never: core::convert::Infallible,
+    _guard: core::marker::PhantomData<G>,
+}
+
+#[cfg(not(feature = "std"))]
+impl<T> Mutex<T> {
+    pub(crate) fn new(t: T) -> Self {
+        Self { lock: Lock::new(t) }
+    }
+
+    pub(crate) fn lock(&self) -> Result<TryLock<'_, T>, PoisonError<TryLock<'_, T>>> {
+        loop {
+            if let Some(guard) = self.lock.try_lock() {
+                return Ok(guard);
+            }
+            // `core::hint::spin_loop` requires Rust 1.49.
+            #[allow(deprecated)]
+            core::sync::atomic::spin_loop_hint();
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<G> PoisonError<G> {
+    pub(crate) fn into_inner(self) -> G {
+        match self.never {}
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<G> core::fmt::Debug for PoisonError<G> {
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.never {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Lock;
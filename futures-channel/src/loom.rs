@@ -0,0 +1,102 @@
+//! The synchronization primitives used by this crate.
+//!
+//! When building with `RUSTFLAGS="--cfg loom"`, these are swapped for the
+//! types of the [loom](https://docs.rs/loom) model checker, so that the
+//! channels of this crate can be exercised under loom by the tests of crates
+//! depending on it.
+//!
+//! Note that loom models `SeqCst` accesses as `AcqRel`, which is weaker than
+//! what the handshakes between the two halves of the channels rely on, so
+//! loom may report false positives for those.
+
+#[cfg(loom)]
+pub(crate) use ::loom::sync::{atomic, Arc};
+#[cfg(not(loom))]
+pub(crate) use alloc::sync::{Arc, Weak};
+#[cfg(not(loom))]
+#[cfg(not(feature = "portable-atomic"))]
+pub(crate) use core::sync::atomic;
+#[cfg(not(loom))]
+#[cfg(feature = "portable-atomic")]
+pub(crate) use portable_atomic as atomic;
+
+#[cfg(not(loom))]
+#[cfg(not(feature = "std"))]
+pub(crate) use crate::lock::Mutex;
+#[cfg(loom)]
+#[cfg(feature = "std")]
+pub(crate) use ::loom::{sync::Mutex, thread};
+#[cfg(not(loom))]
+#[cfg(feature = "std")]
+pub(crate) use std::{sync::Mutex, thread};
+
+/// Without the `std` feature, there's no thread to yield to the scheduler,
+/// so waiting for another thread spins instead.
+#[cfg(not(loom))]
+#[cfg(not(feature = "std"))]
+pub(crate) mod thread {
+    pub(crate) fn yield_now() {
+        // `core::hint::spin_loop` requires Rust 1.49.
+        #[allow(deprecated)]
+        core::sync::atomic::spin_loop_hint();
+    }
+}
+
+/// Like `Arc::downgrade`.
+#[cfg(not(loom))]
+pub(crate) fn downgrade<T>(this: &Arc<T>) -> Weak<T> {
+    Arc::downgrade(this)
+}
+
+/// loom's `Arc` has no weak references, so this `Weak` keeps the value alive
+/// instead, and always upgrades.
+///
+/// This is enough for the weak senders of the mpsc channels, which don't
+/// rely on the channel being freed: upgrading one succeeds only if the
+/// separate count of senders isn't zero yet.
+#[cfg(loom)]
+#[derive(Debug)]
+pub(crate) struct Weak<T>(Arc<T>);
+
+#[cfg(loom)]
+impl<T> Weak<T> {
+    pub(crate) fn upgrade(&self) -> Option<Arc<T>> {
+        Some(self.0.clone())
+    }
+}
+
+#[cfg(loom)]
+impl<T> Clone for Weak<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Like `Arc::downgrade`.
+#[cfg(loom)]
+pub(crate) fn downgrade<T>(this: &Arc<T>) -> Weak<T> {
+    Weak(this.clone())
+}
+
+#[cfg(loom)]
+pub(crate) use ::loom::cell::UnsafeCell;
+
+/// `core::cell::UnsafeCell` with the closure-based API of loom's `UnsafeCell`.
+#[cfg(not(loom))]
+#[derive(Debug)]
+pub(crate) struct UnsafeCell<T>(core::cell::UnsafeCell<T>);
+
+#[cfg(not(loom))]
+impl<T> UnsafeCell<T> {
+    pub(crate) const fn new(data: T) -> Self {
+        Self(core::cell::UnsafeCell::new(data))
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    pub(crate) fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
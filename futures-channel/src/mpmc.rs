@@ -0,0 +1,584 @@
+//! A multi-producer, multi-consumer queue distributing messages across
+//! asynchronous tasks.
+//!
+//! Unlike with [`mpsc`](crate::mpsc), receivers can be cloned, and each
+//! message is received by exactly one of them, making the channel a work
+//! queue shared by a pool of workers. Receivers waiting for a message are
+//! served in the order they started waiting, so that the messages are spread
+//! fairly across them.
+//!
+//! As with `mpsc`, channels are either bounded, created by [`channel`], or
+//! unbounded, created by [`unbounded`].
+//!
+//! # Disconnection
+//!
+//! Once every sender is dropped, the receivers receive the remaining
+//! messages, then terminate. Sending fails once every receiver is dropped,
+//! or once the channel is closed by [`Receiver::close`].
+
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::__internal::coop;
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use crate::mpsc::{SendError, TryRecvError, TrySendError};
+
+/// Creates a bounded mpmc channel, holding up to `buffer` messages.
+///
+/// # Panics
+///
+/// Panics if `buffer` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpmc;
+/// use futures::executor::block_on;
+/// use futures::stream::StreamExt;
+///
+/// let (tx, rx1) = mpmc::channel(4);
+/// let mut rx2 = rx1.clone();
+///
+/// block_on(tx.send(1)).unwrap();
+/// block_on(tx.send(2)).unwrap();
+/// assert_eq!(block_on(rx2.next()), Some(1));
+/// drop(tx);
+/// assert_eq!(block_on(rx1.collect::<Vec<_>>()), [2]);
+/// ```
+pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer > 0, "mpmc channel buffer must be greater than zero");
+    let shared = Shared::new(Some(buffer));
+    (Sender { shared: shared.clone() }, Receiver::new(shared))
+}
+
+/// Creates an unbounded mpmc channel.
+///
+/// **Note** that the amount of available system memory is an implicit bound
+/// to the channel, as for [`mpsc::unbounded`](crate::mpsc::unbounded).
+pub fn unbounded<T>() -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+    let shared = Shared::new(None);
+    (UnboundedSender { shared: shared.clone() }, UnboundedReceiver(Receiver::new(shared)))
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+
+    // `None` for unbounded channels.
+    capacity: Option<usize>,
+
+    num_senders: usize,
+    num_receivers: usize,
+
+    // `true` once a receiver closed the channel.
+    closed: bool,
+
+    next_id: u64,
+
+    // Tasks waiting to receive or to send, by id, in the order they started
+    // waiting.
+    recv_waiters: VecDeque<(u64, Waker)>,
+    send_waiters: VecDeque<(u64, Waker)>,
+}
+
+impl<T> Shared<T> {
+    fn new(capacity: Option<usize>) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                capacity,
+                num_senders: 1,
+                num_receivers: 0,
+                closed: false,
+                next_id: 0,
+                recv_waiters: VecDeque::new(),
+                send_waiters: VecDeque::new(),
+            }),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T> State<T> {
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn is_disconnected(&self) -> bool {
+        self.closed || self.num_receivers == 0
+    }
+
+    fn is_terminated(&self) -> bool {
+        self.queue.is_empty() && (self.closed || self.num_senders == 0)
+    }
+
+    fn is_full(&self) -> bool {
+        self.capacity.map_or(false, |capacity| self.queue.len() >= capacity)
+    }
+
+    fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        if self.is_disconnected() {
+            return Err(TrySendError::new(SendError::disconnected(), msg));
+        }
+        if self.is_full() {
+            return Err(TrySendError::new(SendError::full(), msg));
+        }
+        self.queue.push_back(msg);
+        wake_one(&mut self.recv_waiters);
+        Ok(())
+    }
+
+    fn try_recv(&mut self) -> Option<T> {
+        let msg = self.queue.pop_front()?;
+        wake_one(&mut self.send_waiters);
+        Some(msg)
+    }
+
+    // Called when a sender or receiver notified by a waiter queue goes away:
+    // passes the notification on if there is still something to do.
+    fn pass_on(&mut self) {
+        if !self.queue.is_empty() {
+            wake_one(&mut self.recv_waiters);
+        }
+        if !self.is_full() {
+            wake_one(&mut self.send_waiters);
+        }
+    }
+}
+
+fn wake_one(waiters: &mut VecDeque<(u64, Waker)>) {
+    if let Some((_, waker)) = waiters.pop_front() {
+        waker.wake();
+    }
+}
+
+fn wake_all(waiters: &mut VecDeque<(u64, Waker)>) {
+    for (_, waker) in waiters.drain(..) {
+        waker.wake();
+    }
+}
+
+// Registers the waker of the task `id`, keeping its place if it's already
+// waiting.
+fn register(waiters: &mut VecDeque<(u64, Waker)>, id: u64, cx: &mut Context<'_>) {
+    match waiters.iter_mut().find(|(waiter, _)| *waiter == id) {
+        Some((_, waker)) if waker.will_wake(cx.waker()) => {}
+        Some((_, waker)) => *waker = cx.waker().clone(),
+        None => waiters.push_back((id, cx.waker().clone())),
+    }
+}
+
+// Unregisters the task `id`, returning whether it was waiting.
+fn unregister(waiters: &mut VecDeque<(u64, Waker)>, id: u64) -> bool {
+    match waiters.iter().position(|(waiter, _)| *waiter == id) {
+        Some(index) => {
+            waiters.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// The transmission end of a bounded mpmc channel.
+///
+/// This value is created by the [`channel`] function.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Attempts to send a message on this channel, returning it back if it
+    /// could not be sent.
+    ///
+    /// Fails with an error for which [`TrySendError::is_full`] returns `true`
+    /// if the channel is full, or one for which
+    /// [`TrySendError::is_disconnected`] returns `true` if every receiver was
+    /// dropped or the channel was closed.
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        self.shared.lock().try_send(msg)
+    }
+
+    /// Sends a message on this channel, waiting for the channel to have
+    /// capacity for it.
+    ///
+    /// Senders waiting for capacity are served in the order they started
+    /// waiting. The future fails, returning the message, if every receiver
+    /// was dropped or the channel was closed.
+    pub fn send(&self, msg: T) -> Send<'_, T> {
+        Send { sender: self, msg: Some(msg), id: None }
+    }
+
+    /// Returns whether every receiver was dropped or the channel was closed.
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().is_disconnected()
+    }
+
+    /// Returns the number of messages buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns whether no message is buffered in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the senders send to the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().num_senders += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        drop_sender(&self.shared);
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("closed", &self.is_closed()).finish()
+    }
+}
+
+fn drop_sender<T>(shared: &Shared<T>) {
+    let mut state = shared.lock();
+    state.num_senders -= 1;
+    if state.num_senders == 0 {
+        wake_all(&mut state.recv_waiters);
+    }
+}
+
+/// Future for the [`Sender::send`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Send<'a, T> {
+    sender: &'a Sender<T>,
+    msg: Option<T>,
+
+    // Id of the task in the waiter queue, once it waited.
+    id: Option<u64>,
+}
+
+// `Pin<&mut Send<'_, T>>` is never projected to `Pin<&mut T>`
+impl<T> Unpin for Send<'_, T> {}
+
+impl<T> Future for Send<'_, T> {
+    type Output = Result<(), TrySendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let msg = this.msg.take().expect("`Send` polled after completion");
+        let mut state = this.sender.shared.lock();
+        match state.try_send(msg) {
+            Err(err) if err.is_full() => {
+                this.msg = Some(err.into_inner());
+                let id = *this.id.get_or_insert_with(|| state.next_id());
+                register(&mut state.send_waiters, id, cx);
+                Poll::Pending
+            }
+            res => {
+                if let Some(id) = this.id.take() {
+                    unregister(&mut state.send_waiters, id);
+                }
+                Poll::Ready(res)
+            }
+        }
+    }
+}
+
+impl<T> FusedFuture for Send<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.msg.is_none()
+    }
+}
+
+impl<T> Drop for Send<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            let mut state = self.sender.shared.lock();
+            if !unregister(&mut state.send_waiters, id) {
+                state.pass_on();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Send<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Send").field("sender", &self.sender).finish()
+    }
+}
+
+/// The transmission end of an unbounded mpmc channel.
+///
+/// This value is created by the [`unbounded`] function.
+pub struct UnboundedSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> UnboundedSender<T> {
+    /// Sends a message along this channel.
+    ///
+    /// Fails, returning the message, if every receiver was dropped or the
+    /// channel was closed.
+    pub fn unbounded_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        self.shared.lock().try_send(msg)
+    }
+
+    /// Returns whether every receiver was dropped or the channel was closed.
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().is_disconnected()
+    }
+
+    /// Returns the number of messages buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns whether no message is buffered in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the senders send to the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for UnboundedSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().num_senders += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for UnboundedSender<T> {
+    fn drop(&mut self) {
+        drop_sender(&self.shared);
+    }
+}
+
+impl<T> fmt::Debug for UnboundedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedSender").field("closed", &self.is_closed()).finish()
+    }
+}
+
+/// The receiving end of a bounded mpmc channel.
+///
+/// This value is created by the [`channel`] function. Each message sent is
+/// received by exactly one of the clones of the receiver.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    id: u64,
+}
+
+impl<T> Receiver<T> {
+    fn new(shared: Arc<Shared<T>>) -> Self {
+        let id = {
+            let mut state = shared.lock();
+            state.num_receivers += 1;
+            state.next_id()
+        };
+        Self { shared, id }
+    }
+
+    /// Closes the channel, without dropping the receivers.
+    ///
+    /// This prevents any further messages from being sent on the channel
+    /// while still enabling the receivers to drain the messages that are
+    /// buffered.
+    pub fn close(&self) {
+        let mut state = self.shared.lock();
+        state.closed = true;
+        wake_all(&mut state.send_waiters);
+        wake_all(&mut state.recv_waiters);
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// This function returns:
+    /// * `Ok(Some(t))` when a message is fetched
+    /// * `Ok(None)` when the channel is closed and no messages are left
+    /// * `Err(e)` when there are no messages available, but the channel is
+    ///   not yet closed
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.try_recv() {
+            Some(msg) => Ok(Some(msg)),
+            None if state.is_terminated() => Ok(None),
+            None => Err(TryRecvError::new()),
+        }
+    }
+
+    /// Returns the number of messages buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns whether no message is buffered in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the receivers receive from the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.shared.clone())
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let coop = ready!(coop::poll_proceed(cx));
+        let mut state = self.shared.lock();
+        let msg = match state.try_recv() {
+            Some(msg) => Some(msg),
+            None if state.is_terminated() => None,
+            None => {
+                register(&mut state.recv_waiters, self.id, cx);
+                return Poll::Pending;
+            }
+        };
+        unregister(&mut state.recv_waiters, self.id);
+        coop.made_progress();
+        Poll::Ready(msg)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Other receivers may receive the buffered messages.
+        let state = self.shared.lock();
+        if state.is_terminated() {
+            (0, Some(0))
+        } else {
+            (0, None)
+        }
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.shared.lock().is_terminated()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_receivers -= 1;
+        if state.num_receivers == 0 {
+            wake_all(&mut state.send_waiters);
+            // Nobody can receive the buffered messages anymore.
+            let queue = std::mem::take(&mut state.queue);
+            drop(state);
+            drop(queue);
+        } else if !unregister(&mut state.recv_waiters, self.id) {
+            state.pass_on();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").field("len", &self.len()).finish()
+    }
+}
+
+/// The receiving end of an unbounded mpmc channel.
+///
+/// This value is created by the [`unbounded`] function. Each message sent is
+/// received by exactly one of the clones of the receiver.
+pub struct UnboundedReceiver<T>(Receiver<T>);
+
+impl<T> UnboundedReceiver<T> {
+    /// Closes the channel, without dropping the receivers.
+    ///
+    /// This prevents any further messages from being sent on the channel
+    /// while still enabling the receivers to drain the messages that are
+    /// buffered.
+    pub fn close(&self) {
+        self.0.close()
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// See [`Receiver::try_next`] for details.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        self.0.try_next()
+    }
+
+    /// Returns the number of messages buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether no message is buffered in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns whether the receivers receive from the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        self.0.same_channel(&other.0)
+    }
+}
+
+impl<T> Clone for UnboundedReceiver<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Stream for UnboundedReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> FusedStream for UnboundedReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
+
+impl<T> fmt::Debug for UnboundedReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedReceiver").field("len", &self.len()).finish()
+    }
+}
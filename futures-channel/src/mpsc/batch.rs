@@ -0,0 +1,134 @@
+use alloc::collections::VecDeque;
+use core::fmt;
+use core::iter::Fuse;
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::ready;
+use futures_core::stream::{Stream, TryStream};
+use futures_core::task::{Context, Poll};
+
+use super::{SendError, Sender};
+
+// The maximum number of messages taken from the source at once, before
+// sending them.
+const BATCH_LEN: usize = 32;
+
+/// Future for the [`Sender::send_batch`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendBatch<'a, T, I> {
+    sender: &'a mut Sender<T>,
+    msgs: Fuse<I>,
+    buffered: VecDeque<T>,
+}
+
+impl<'a, T, I: Iterator<Item = T>> SendBatch<'a, T, I> {
+    pub(super) fn new(sender: &'a mut Sender<T>, msgs: I) -> Self {
+        Self { sender, msgs: msgs.fuse(), buffered: VecDeque::new() }
+    }
+}
+
+// The messages are never pinned.
+impl<T, I> Unpin for SendBatch<'_, T, I> {}
+
+impl<T, I> fmt::Debug for SendBatch<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendBatch")
+            .field("sender", &self.sender)
+            .field("buffered", &self.buffered.len())
+            .finish()
+    }
+}
+
+impl<T, I: Iterator<Item = T>> Future for SendBatch<'_, T, I> {
+    type Output = Result<(), SendError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        loop {
+            let missing = BATCH_LEN.saturating_sub(this.buffered.len());
+            this.buffered.extend(this.msgs.by_ref().take(missing));
+            if this.buffered.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+
+            ready!(this.sender.poll_ready(cx))?;
+            this.sender.start_send_batch(&mut this.buffered)?;
+        }
+    }
+}
+
+/// Future for the [`Sender::send_all`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendAll<'a, T, St> {
+    sender: &'a mut Sender<T>,
+    stream: St,
+    stream_done: bool,
+    buffered: VecDeque<T>,
+
+    // An error yielded by the stream, returned once the messages before it
+    // are sent.
+    error: Option<SendError>,
+}
+
+impl<'a, T, St> SendAll<'a, T, St> {
+    pub(super) fn new(sender: &'a mut Sender<T>, stream: St) -> Self {
+        Self { sender, stream, stream_done: false, buffered: VecDeque::new(), error: None }
+    }
+}
+
+// The stream is the only pinned field.
+impl<T, St: Unpin> Unpin for SendAll<'_, T, St> {}
+
+impl<T, St: fmt::Debug> fmt::Debug for SendAll<'_, T, St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendAll")
+            .field("sender", &self.sender)
+            .field("stream", &self.stream)
+            .field("buffered", &self.buffered.len())
+            .finish()
+    }
+}
+
+impl<T, St> Future for SendAll<'_, T, St>
+where
+    St: TryStream<Ok = T, Error = SendError> + Stream,
+{
+    type Output = Result<(), SendError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: the stream is never moved out of the future, and the other
+        // fields are never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            // Take the messages the stream has ready, up to a batch.
+            while !this.stream_done && this.buffered.len() < BATCH_LEN {
+                match stream.as_mut().try_poll_next(cx) {
+                    Poll::Ready(Some(Ok(msg))) => this.buffered.push_back(msg),
+                    Poll::Ready(Some(Err(e))) => {
+                        this.error = Some(e);
+                        this.stream_done = true;
+                    }
+                    Poll::Ready(None) => this.stream_done = true,
+                    Poll::Pending => break,
+                }
+            }
+
+            if this.buffered.is_empty() {
+                if let Some(e) = this.error.take() {
+                    return Poll::Ready(Err(e));
+                }
+                // Flush the sender, like its `Sink` implementation does.
+                match ready!(this.sender.poll_ready(cx)) {
+                    Err(ref e) if e.is_disconnected() => {}
+                    res => res?,
+                }
+                return if this.stream_done { Poll::Ready(Ok(())) } else { Poll::Pending };
+            }
+
+            ready!(this.sender.poll_ready(cx))?;
+            this.sender.start_send_batch(&mut this.buffered)?;
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::task::{Context, Poll};
+
+use super::{Sender, UnboundedSender};
+
+/// Future for the [`Sender::closed`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Closed<'a, T> {
+    sender: Option<&'a Sender<T>>,
+}
+
+impl<'a, T> Closed<'a, T> {
+    pub(super) fn new(sender: &'a Sender<T>) -> Self {
+        Self { sender: Some(sender) }
+    }
+}
+
+impl<T> fmt::Debug for Closed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Closed").field("sender", &self.sender).finish()
+    }
+}
+
+impl<T> Future for Closed<'_, T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let sender = self.sender.expect("`Closed` polled after completion");
+        ready!(sender.poll_closed(cx));
+        self.sender = None;
+        Poll::Ready(())
+    }
+}
+
+impl<T> FusedFuture for Closed<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.sender.is_none()
+    }
+}
+
+/// Future for the [`UnboundedSender::closed`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UnboundedClosed<'a, T> {
+    sender: Option<&'a UnboundedSender<T>>,
+}
+
+impl<'a, T> UnboundedClosed<'a, T> {
+    pub(super) fn new(sender: &'a UnboundedSender<T>) -> Self {
+        Self { sender: Some(sender) }
+    }
+}
+
+impl<T> fmt::Debug for UnboundedClosed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedClosed").field("sender", &self.sender).finish()
+    }
+}
+
+impl<T> Future for UnboundedClosed<'_, T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let sender = self.sender.expect("`UnboundedClosed` polled after completion");
+        ready!(sender.poll_closed(cx));
+        self.sender = None;
+        Poll::Ready(())
+    }
+}
+
+impl<T> FusedFuture for UnboundedClosed<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.sender.is_none()
+    }
+}
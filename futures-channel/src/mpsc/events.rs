@@ -0,0 +1,150 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+
+use crate::loom::atomic::AtomicUsize;
+use crate::loom::atomic::Ordering::SeqCst;
+use crate::loom::{Arc, Mutex};
+
+use super::{register_task, BoundedInner, UnboundedInner};
+
+/// A change of the number of senders of an mpsc channel, yielded by
+/// [`SenderEvents`] and [`UnboundedSenderEvents`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SenderEvent {
+    /// A sender was added, bringing the number of senders to the given
+    /// count.
+    Increment(usize),
+    /// A sender was dropped, bringing the number of senders to the given
+    /// count.
+    Decrement(usize),
+}
+
+impl SenderEvent {
+    /// Returns the number of senders after this event.
+    pub fn count(&self) -> usize {
+        match *self {
+            Self::Increment(count) | Self::Decrement(count) => count,
+        }
+    }
+}
+
+// Steps `seen` towards the current number of senders, registering the task
+// to be woken when it changes. Returns `None` once there are no senders left,
+// as the channel can't get new ones then.
+fn poll_event(
+    seen: &mut usize,
+    num_senders: &AtomicUsize,
+    sender_tasks: &Mutex<Vec<Waker>>,
+    cx: &mut Context<'_>,
+) -> Poll<Option<SenderEvent>> {
+    let mut count = num_senders.load(SeqCst);
+    if count == *seen {
+        register_task(sender_tasks, cx);
+        // Check again, in case the count changed before registering.
+        count = num_senders.load(SeqCst);
+    }
+
+    if count > *seen {
+        *seen += 1;
+        Poll::Ready(Some(SenderEvent::Increment(*seen)))
+    } else if count < *seen {
+        *seen -= 1;
+        Poll::Ready(Some(SenderEvent::Decrement(*seen)))
+    } else if count == 0 {
+        Poll::Ready(None)
+    } else {
+        Poll::Pending
+    }
+}
+
+/// Stream for the [`Receiver::sender_events`](super::Receiver::sender_events)
+/// method.
+#[must_use = "streams do nothing unless polled"]
+pub struct SenderEvents<T> {
+    inner: Option<Arc<BoundedInner<T>>>,
+    seen: usize,
+}
+
+impl<T> SenderEvents<T> {
+    pub(super) fn new(inner: Option<Arc<BoundedInner<T>>>) -> Self {
+        let seen = inner.as_ref().map_or(0, |inner| inner.num_senders.load(SeqCst));
+        Self { inner, seen }
+    }
+}
+
+impl<T> fmt::Debug for SenderEvents<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SenderEvents").field("seen", &self.seen).finish()
+    }
+}
+
+impl<T> Stream for SenderEvents<T> {
+    type Item = SenderEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<SenderEvent>> {
+        let this = &mut *self;
+        let inner = match &this.inner {
+            Some(inner) => inner,
+            None => return Poll::Ready(None),
+        };
+        let event = poll_event(&mut this.seen, &inner.num_senders, &inner.sender_tasks, cx);
+        if let Poll::Ready(None) = event {
+            this.inner = None;
+        }
+        event
+    }
+}
+
+impl<T> FusedStream for SenderEvents<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+/// Stream for the
+/// [`UnboundedReceiver::sender_events`](super::UnboundedReceiver::sender_events)
+/// method.
+#[must_use = "streams do nothing unless polled"]
+pub struct UnboundedSenderEvents<T> {
+    inner: Option<Arc<UnboundedInner<T>>>,
+    seen: usize,
+}
+
+impl<T> UnboundedSenderEvents<T> {
+    pub(super) fn new(inner: Option<Arc<UnboundedInner<T>>>) -> Self {
+        let seen = inner.as_ref().map_or(0, |inner| inner.num_senders.load(SeqCst));
+        Self { inner, seen }
+    }
+}
+
+impl<T> fmt::Debug for UnboundedSenderEvents<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedSenderEvents").field("seen", &self.seen).finish()
+    }
+}
+
+impl<T> Stream for UnboundedSenderEvents<T> {
+    type Item = SenderEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<SenderEvent>> {
+        let this = &mut *self;
+        let inner = match &this.inner {
+            Some(inner) => inner,
+            None => return Poll::Ready(None),
+        };
+        let event = poll_event(&mut this.seen, &inner.num_senders, &inner.sender_tasks, cx);
+        if let Poll::Ready(None) = event {
+            this.inner = None;
+        }
+        event
+    }
+}
+
+impl<T> FusedStream for UnboundedSenderEvents<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
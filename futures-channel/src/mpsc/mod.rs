@@ -18,6 +18,8 @@
 //! possible to send values into the channel. This is considered the termination
 //! event of the stream. As such, [`Receiver::poll_next`]
 //! will return `Ok(Ready(None))`.
+//! [`WeakSender`] handles, created by [`Sender::downgrade`], don't count
+//! towards this.
 //!
 //! If the [`Receiver`] handle is dropped, then messages can no longer
 //! be read out of the channel. In this case, all further attempts to send will
@@ -43,8 +45,8 @@
 // capacity limits and handle back pressure, a secondary FIFO queue is used to
 // send parked task handles.
 //
-// The general idea is that the channel is created with a `buffer` size of `n`.
-// The channel capacity is `n + num-senders`. Each sender gets one "guaranteed"
+// The general idea is that the channel is created with a `buffer` size of `n`,
+// which can later be changed. The channel capacity is `n + num-senders`. Each sender gets one "guaranteed"
 // slot to hold a message. This allows `Sender` to know for a fact that a send
 // will succeed *before* starting to do the actual work of sending the value.
 // Since most of this work is lock-free, once the work starts, it is impossible
@@ -53,6 +55,12 @@
 // If the sender is unable to process a send operation, then the current
 // task is parked and the handle is sent on the parked task queue.
 //
+// The parked senders are unparked in the order they were parked. While some
+// are parked, a sender which sends a message parks behind them even if the
+// buffer has room, so that it can't send again before they were unparked:
+// otherwise, a sender sending in a loop could take every slot freed by the
+// receiver before the parked senders get to run.
+//
 // Note that the implementation guarantees that the channel capacity will never
 // exceed the configured limit, however there is no *strict* guarantee that the
 // receiver will wake up a parked task *immediately* when a slot becomes
@@ -78,19 +86,54 @@
 // happens-before semantics required for the acquire / release semantics used
 // by the queue structure.
 
-use futures_core::stream::{FusedStream, Stream};
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::fmt;
+use core::pin::Pin;
+#[cfg(feature = "std")]
+use core::time::Duration;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream, TryStream};
+#[cfg(feature = "std")]
+use futures_core::task::__internal::coop;
 use futures_core::task::__internal::AtomicWaker;
 use futures_core::task::{Context, Poll, Waker};
-use std::fmt;
-use std::pin::Pin;
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering::SeqCst;
-use std::sync::{Arc, Mutex};
-use std::thread;
+#[cfg(feature = "std")]
+use futures_task::Timer;
 
+use crate::close_reason::ReasonSlot;
+use crate::loom::atomic::Ordering::SeqCst;
+use crate::loom::atomic::{AtomicBool, AtomicUsize};
+use crate::loom::{self, thread, Arc, Mutex};
 use crate::mpsc::queue::Queue;
+use crate::probe::Probe;
 
 mod queue;
+mod recv_many;
+pub use self::recv_many::{RecvMany, UnboundedRecvMany};
+mod peek;
+pub use self::peek::{Peek, UnboundedPeek};
+mod reserve;
+pub use self::reserve::{Permit, Reserve};
+mod weak;
+pub use self::weak::{WeakSender, WeakUnboundedSender};
+mod closed;
+pub use self::closed::{Closed, UnboundedClosed};
+mod receiver_set;
+pub use self::receiver_set::ReceiverSet;
+mod batch;
+pub use self::batch::{SendAll, SendBatch};
+#[cfg(feature = "std")]
+mod timeout;
+#[cfg(feature = "std")]
+pub use self::timeout::{
+    Elapsed, RecvTimeout, SendTimeout, SendTimeoutError, UnboundedRecvTimeout,
+};
+mod events;
+pub use self::events::{SenderEvent, SenderEvents, UnboundedSenderEvents};
+
+pub use crate::close_reason::CloseReason;
 #[cfg(feature = "sink")]
 mod sink_impl;
 
@@ -135,6 +178,14 @@ impl AssertKinds for UnboundedSender<u32> {}
 /// This value is created by the [`channel`](channel) function.
 pub struct Receiver<T> {
     inner: Option<Arc<BoundedInner<T>>>,
+
+    // Number of parked senders to unpark for the messages received. They're
+    // unparked in a batch once the receiver is done receiving.
+    unparks: usize,
+
+    // The message received by `poll_peek`, to be returned by the next
+    // receive.
+    peeked: Option<T>,
 }
 
 /// The receiving end of an unbounded mpsc channel.
@@ -142,15 +193,25 @@ pub struct Receiver<T> {
 /// This value is created by the [`unbounded`](unbounded) function.
 pub struct UnboundedReceiver<T> {
     inner: Option<Arc<UnboundedInner<T>>>,
+
+    // The message received by `poll_peek`, to be returned by the next
+    // receive.
+    peeked: Option<T>,
 }
 
 // `Pin<&mut UnboundedReceiver<T>>` is never projected to `Pin<&mut T>`
 impl<T> Unpin for UnboundedReceiver<T> {}
 
+// The peeked message is only accessible through `&mut self`, so sharing a
+// `&Receiver` or a `&UnboundedReceiver` doesn't share it.
+unsafe impl<T: Send> Sync for Receiver<T> {}
+unsafe impl<T: Send> Sync for UnboundedReceiver<T> {}
+
 /// The error type for [`Sender`s](Sender) used as `Sink`s.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SendError {
     kind: SendErrorKind,
+    reason: Option<CloseReason>,
 }
 
 /// The error type returned from [`try_send`](Sender::try_send).
@@ -166,6 +227,28 @@ enum SendErrorKind {
     Disconnected,
 }
 
+/// What a bounded channel does with a message sent while it's full, given to
+/// [`channel_with_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Make the sender wait for capacity, as [`channel`] does.
+    Block,
+    /// Drop the oldest message of the channel to make room for the new one,
+    /// like a ring buffer.
+    DropOldest,
+    /// Drop the new message.
+    DropNewest,
+    /// Fail to send the new message, with an error for which
+    /// [`SendError::is_full`] returns `true`, giving it back to the sender.
+    Reject,
+}
+
+impl Default for OverflowPolicy {
+    fn default() -> Self {
+        Self::Block
+    }
+}
+
 /// The error type returned from [`try_next`](Receiver::try_next).
 pub struct TryRecvError {
     _priv: (),
@@ -181,9 +264,22 @@ impl fmt::Display for SendError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for SendError {}
 
 impl SendError {
+    pub(crate) fn full() -> Self {
+        Self { kind: SendErrorKind::Full, reason: None }
+    }
+
+    pub(crate) fn disconnected() -> Self {
+        Self { kind: SendErrorKind::Disconnected, reason: None }
+    }
+
+    fn closed(reason: &ReasonSlot) -> Self {
+        Self { kind: SendErrorKind::Disconnected, reason: reason.get() }
+    }
+
     /// Returns `true` if this error is a result of the channel being full.
     pub fn is_full(&self) -> bool {
         matches!(self.kind, SendErrorKind::Full)
@@ -193,6 +289,13 @@ impl SendError {
     pub fn is_disconnected(&self) -> bool {
         matches!(self.kind, SendErrorKind::Disconnected)
     }
+
+    /// Returns the reason the receiver gave for closing the channel, if it
+    /// closed it with [`Receiver::close_with`] or
+    /// [`UnboundedReceiver::close_with`].
+    pub fn reason(&self) -> Option<&CloseReason> {
+        self.reason.as_ref()
+    }
 }
 
 impl<T> fmt::Debug for TrySendError<T> {
@@ -211,9 +314,14 @@ impl<T> fmt::Display for TrySendError<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: core::any::Any> std::error::Error for TrySendError<T> {}
 
 impl<T> TrySendError<T> {
+    pub(crate) fn new(err: SendError, val: T) -> Self {
+        Self { err, val }
+    }
+
     /// Returns `true` if this error is a result of the channel being full.
     pub fn is_full(&self) -> bool {
         self.err.is_full()
@@ -224,6 +332,12 @@ impl<T> TrySendError<T> {
         self.err.is_disconnected()
     }
 
+    /// Returns the reason the receiver gave for closing the channel, if any.
+    /// See [`SendError::reason`].
+    pub fn reason(&self) -> Option<&CloseReason> {
+        self.err.reason()
+    }
+
     /// Returns the message that was attempted to be sent but failed.
     pub fn into_inner(self) -> T {
         self.val
@@ -241,12 +355,19 @@ impl fmt::Debug for TryRecvError {
     }
 }
 
+impl TryRecvError {
+    pub(crate) fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
 impl fmt::Display for TryRecvError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "receiver channel is empty")
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for TryRecvError {}
 
 struct UnboundedInner<T> {
@@ -262,11 +383,26 @@ struct UnboundedInner<T> {
 
     // Handle to the receiver's task.
     recv_task: AtomicWaker,
+
+    // Handles to the tasks waiting for the channel to be closed.
+    closed_tasks: Mutex<Vec<Waker>>,
+
+    // Handles to the tasks watching the number of senders.
+    sender_tasks: Mutex<Vec<Waker>>,
+
+    // The reason the receiver gave for closing the channel.
+    close_reason: ReasonSlot,
+
+    probe: Probe,
 }
 
 struct BoundedInner<T> {
-    // Max buffer size of the channel.
-    buffer: usize,
+    // Max buffer size of the channel, which can be changed at runtime.
+    buffer: AtomicUsize,
+
+    // Number of parked senders to unpark because the buffer grew. Only the
+    // receiver can pop the parked queue, so it does the unparking.
+    pending_unparks: AtomicUsize,
 
     // Internal channel state. Consists of the number of messages stored in the
     // channel as well as a flag signalling that the channel is closed.
@@ -278,11 +414,44 @@ struct BoundedInner<T> {
     // Atomic, FIFO queue used to send parked task handles to the receiver.
     parked_queue: Queue<Arc<Mutex<SenderTask>>>,
 
+    // Number of task handles pushed on `parked_queue` and not popped yet.
+    num_parked: AtomicUsize,
+
     // Number of senders in existence
     num_senders: AtomicUsize,
 
     // Handle to the receiver's task.
     recv_task: AtomicWaker,
+
+    // `true` when the receiver is waiting for a message. Senders only wake the
+    // receiver when it is, so that a burst of messages wakes it only once.
+    recv_waiting: AtomicBool,
+
+    // Handles to the tasks waiting for the channel to be closed.
+    closed_tasks: Mutex<Vec<Waker>>,
+
+    // Handles to the tasks watching the number of senders.
+    sender_tasks: Mutex<Vec<Waker>>,
+
+    // The reason the receiver gave for closing the channel.
+    close_reason: ReasonSlot,
+
+    probe: Probe,
+
+    // Set for rendezvous channels. Held while a sender parks and pushes its
+    // message, so that the parked tasks are in the order of the messages,
+    // and the receiver unparks the sender of each message it takes.
+    rendezvous: Option<Mutex<()>>,
+
+    policy: OverflowPolicy,
+
+    // Set for the `DropOldest` policy. Held while popping a message, as the
+    // senders pop the oldest message when the channel is full.
+    pop_lock: Option<Mutex<()>>,
+
+    // Number of messages dropped by the `DropOldest` and `DropNewest`
+    // policies.
+    dropped: AtomicUsize,
 }
 
 // Struct representation of `Inner::state`.
@@ -337,6 +506,11 @@ impl SenderTask {
 /// guaranteed slot in the channel capacity, and on top of that there are
 /// `buffer` "first come, first serve" slots available to all senders.
 ///
+/// Senders waiting for capacity are woken in the order they started waiting.
+/// While some senders wait, a sender sending a message waits behind them
+/// afterwards, even if the buffer has room, so that a sender sending in a loop
+/// can't starve the others.
+///
 /// The [`Receiver`](Receiver) returned implements the
 /// [`Stream`](futures_core::stream::Stream) trait, while [`Sender`](Sender) implements
 /// `Sink`.
@@ -345,13 +519,80 @@ pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
     // size permitted by the system.
     assert!(buffer < MAX_BUFFER, "requested buffer size too large");
 
+    bounded(buffer, None, OverflowPolicy::Block)
+}
+
+/// Creates a bounded mpsc channel which handles the messages sent while it's
+/// full according to `policy`.
+///
+/// With [`OverflowPolicy::Block`], this is the same as [`channel`].
+/// Otherwise, senders never wait for capacity, and the channel holds at most
+/// `buffer` messages, without the guaranteed slot of each sender:
+///
+/// - With [`OverflowPolicy::DropOldest`] and [`OverflowPolicy::DropNewest`],
+///   sending always succeeds while the receiver is alive, dropping a message
+///   if the channel is full. The number of messages dropped is returned by
+///   [`Sender::dropped_count`] and [`Receiver::dropped_count`].
+/// - With [`OverflowPolicy::Reject`], sending fails if the channel is full,
+///   returning the message in the error of [`Sender::try_send`].
+///
+/// [`Sender::poll_ready`] is always ready for these policies, and
+/// [`Sender::reserve`] doesn't reserve capacity: the message sent with the
+/// permit is handled by the policy.
+///
+/// # Panics
+///
+/// Panics if `buffer` is too large, as [`channel`] does, or if it's 0 with a
+/// policy other than [`OverflowPolicy::Block`].
+pub fn channel_with_policy<T>(buffer: usize, policy: OverflowPolicy) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer < MAX_BUFFER, "requested buffer size too large");
+    assert!(
+        buffer > 0 || policy == OverflowPolicy::Block,
+        "a channel which doesn't block needs a buffer"
+    );
+
+    bounded(buffer, None, policy)
+}
+
+/// Creates a rendezvous mpsc channel, where sending a message completes only
+/// once the receiver took it.
+///
+/// Unlike [`channel(0)`](channel), where a sender can be woken because
+/// another sender's message was received, each sender waits for its own
+/// message: [`Sender::poll_ready`] and the `flush` of the `Sink`
+/// implementation, which completes `SinkExt::send`, return `Pending` until
+/// the receiver took the last message of the sender. As with any bounded
+/// channel, they return once the receiver is dropped or closed, whether or
+/// not the message was taken.
+///
+/// The capacity of the channel can't be changed with `set_capacity`.
+pub fn rendezvous<T>() -> (Sender<T>, Receiver<T>) {
+    bounded(0, Some(Mutex::new(())), OverflowPolicy::Block)
+}
+
+fn bounded<T>(
+    buffer: usize,
+    rendezvous: Option<Mutex<()>>,
+    policy: OverflowPolicy,
+) -> (Sender<T>, Receiver<T>) {
     let inner = Arc::new(BoundedInner {
-        buffer,
+        buffer: AtomicUsize::new(buffer),
+        pending_unparks: AtomicUsize::new(0),
         state: AtomicUsize::new(INIT_STATE),
         message_queue: Queue::new(),
         parked_queue: Queue::new(),
+        num_parked: AtomicUsize::new(0),
         num_senders: AtomicUsize::new(1),
         recv_task: AtomicWaker::new(),
+        recv_waiting: AtomicBool::new(false),
+        closed_tasks: Mutex::new(Vec::new()),
+        sender_tasks: Mutex::new(Vec::new()),
+        close_reason: ReasonSlot::new(),
+        probe: Probe::bounded(),
+        rendezvous,
+        policy,
+        pop_lock: if policy == OverflowPolicy::DropOldest { Some(Mutex::new(())) } else { None },
+        dropped: AtomicUsize::new(0),
     });
 
     let tx = BoundedSenderInner {
@@ -360,7 +601,7 @@ pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
         maybe_parked: false,
     };
 
-    let rx = Receiver { inner: Some(inner) };
+    let rx = Receiver { inner: Some(inner), unparks: 0, peeked: None };
 
     (Sender(Some(tx)), rx)
 }
@@ -381,11 +622,15 @@ pub fn unbounded<T>() -> (UnboundedSender<T>, UnboundedReceiver<T>) {
         message_queue: Queue::new(),
         num_senders: AtomicUsize::new(1),
         recv_task: AtomicWaker::new(),
+        closed_tasks: Mutex::new(Vec::new()),
+        sender_tasks: Mutex::new(Vec::new()),
+        close_reason: ReasonSlot::new(),
+        probe: Probe::unbounded(),
     });
 
     let tx = UnboundedSenderInner { inner: inner.clone() };
 
-    let rx = UnboundedReceiver { inner: Some(inner) };
+    let rx = UnboundedReceiver { inner: Some(inner), peeked: None };
 
     (UnboundedSender(Some(tx)), rx)
 }
@@ -402,7 +647,7 @@ impl<T> UnboundedSenderInner<T> {
         if state.is_open {
             Poll::Ready(Ok(()))
         } else {
-            Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }))
+            Poll::Ready(Err(SendError::closed(&self.inner.close_reason)))
         }
     }
 
@@ -410,6 +655,7 @@ impl<T> UnboundedSenderInner<T> {
     fn queue_push_and_signal(&self, msg: T) {
         // Push the message onto the message queue
         self.inner.message_queue.push(msg);
+        self.inner.probe.sent();
 
         // Signal to the receiver that a message has been enqueued. If the
         // receiver is parked, this will unpark the task.
@@ -486,7 +732,7 @@ impl<T> BoundedSenderInner<T> {
     fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
         // If the sender is currently blocked, reject the message
         if !self.poll_unparked(None).is_ready() {
-            return Err(TrySendError { err: SendError { kind: SendErrorKind::Full }, val: msg });
+            return Err(TrySendError { err: SendError::full(), val: msg });
         }
 
         // The channel has capacity to accept the message, so send it
@@ -500,6 +746,10 @@ impl<T> BoundedSenderInner<T> {
         // but assert here for tests as a sanity check.
         debug_assert!(self.poll_unparked(None).is_ready());
 
+        if self.inner.policy != OverflowPolicy::Block {
+            return self.do_send_overflow(msg);
+        }
+
         // First, increment the number of messages contained by the channel.
         // This operation will also atomically determine if the sender task
         // should be parked.
@@ -510,12 +760,13 @@ impl<T> BoundedSenderInner<T> {
         let park_self = match self.inc_num_messages() {
             Some(num_messages) => {
                 // Block if the current number of pending messages has exceeded
-                // the configured buffer size
-                num_messages > self.inner.buffer
+                // the configured buffer size, or to wait for the senders
+                // parked before
+                num_messages > self.inner.buffer.load(SeqCst) || self.inner.has_parked()
             }
             None => {
                 return Err(TrySendError {
-                    err: SendError { kind: SendErrorKind::Disconnected },
+                    err: SendError::closed(&self.inner.close_reason),
                     val: msg,
                 })
             }
@@ -528,11 +779,14 @@ impl<T> BoundedSenderInner<T> {
         // `task::current()` can't be called safely. In this case, in order to
         // maintain internal consistency, a blank message is pushed onto the
         // parked task queue.
-        if park_self {
-            self.park();
-        }
+        let guard = self.inner.rendezvous.as_ref().map(|lock| lock.lock().unwrap());
+        let parked = park_self && self.park();
 
         self.queue_push_and_signal(msg);
+        drop(guard);
+        if park_self {
+            self.maybe_parked = parked;
+        }
 
         Ok(())
     }
@@ -541,10 +795,150 @@ impl<T> BoundedSenderInner<T> {
     fn queue_push_and_signal(&self, msg: T) {
         // Push the message onto the message queue
         self.inner.message_queue.push(msg);
+        self.inner.probe.sent();
 
-        // Signal to the receiver that a message has been enqueued. If the
-        // receiver is parked, this will unpark the task.
-        self.inner.recv_task.wake();
+        self.signal();
+    }
+
+    // Signal to the receiver that messages have been enqueued if it's waiting
+    // for one. The load avoids contending on the flag while the receiver is
+    // busy.
+    fn signal(&self) {
+        if self.inner.recv_waiting.load(SeqCst) && self.inner.recv_waiting.swap(false, SeqCst) {
+            self.inner.recv_task.wake();
+        }
+    }
+
+    // Send messages from the front of `msgs` without failing, as many as the
+    // channel has room for, updating the channel state once for all of them.
+    // Can be called only when the sender isn't parked, with at least one
+    // message.
+    fn do_send_batch(&mut self, msgs: &mut VecDeque<T>) -> Result<(), SendError> {
+        debug_assert!(self.poll_unparked(None).is_ready());
+        debug_assert!(!msgs.is_empty());
+
+        if self.inner.policy != OverflowPolicy::Block {
+            while let Some(msg) = msgs.pop_front() {
+                if let Err(e) = self.do_send_overflow(msg) {
+                    msgs.push_front(e.val);
+                    return Err(e.err);
+                }
+            }
+            return Ok(());
+        }
+
+        // While other senders wait, only the message of the guaranteed slot
+        // can be sent before parking behind them.
+        let waiting = self.inner.has_parked();
+        let max = if waiting { 1 } else { msgs.len() };
+        let (count, park_self) = match self.inc_num_messages_by(max) {
+            Some((count, over)) => (count, over || waiting),
+            None => return Err(SendError::closed(&self.inner.close_reason)),
+        };
+
+        // As in `do_send_b`, the task handle must be on the parked task queue
+        // before the message which made the channel exceed its capacity.
+        let guard = self.inner.rendezvous.as_ref().map(|lock| lock.lock().unwrap());
+        let parked = park_self && self.park();
+
+        for msg in msgs.drain(..count) {
+            self.inner.message_queue.push(msg);
+            self.inner.probe.sent();
+        }
+        self.signal();
+        drop(guard);
+        if park_self {
+            self.maybe_parked = parked;
+        }
+
+        Ok(())
+    }
+
+    // Send a message without ever parking, handling the channel being full
+    // according to its overflow policy, which isn't `Block`.
+    fn do_send_overflow(&self, msg: T) -> Result<(), TrySendError<T>> {
+        let mut curr = self.inner.state.load(SeqCst);
+
+        loop {
+            let mut state = decode_state(curr);
+            if !state.is_open {
+                let err = SendError::closed(&self.inner.close_reason);
+                return Err(TrySendError { err, val: msg });
+            }
+
+            if state.num_messages < self.inner.buffer.load(SeqCst) {
+                state.num_messages += 1;
+                let next = encode_state(&state);
+                match self.inner.state.compare_exchange(curr, next, SeqCst, SeqCst) {
+                    Ok(_) => {
+                        self.queue_push_and_signal(msg);
+                        return Ok(());
+                    }
+                    Err(actual) => {
+                        curr = actual;
+                        continue;
+                    }
+                }
+            }
+
+            match self.inner.policy {
+                OverflowPolicy::DropNewest => {
+                    self.inner.dropped.fetch_add(1, SeqCst);
+                    return Ok(());
+                }
+                OverflowPolicy::DropOldest => {
+                    // Replace the oldest message, leaving the number of
+                    // messages unchanged. The queue is empty if the receiver
+                    // took a message but didn't count it yet, so try again.
+                    if let Some(oldest) = unsafe { self.inner.pop_message() } {
+                        self.inner.dropped.fetch_add(1, SeqCst);
+                        self.queue_push_and_signal(msg);
+                        drop(oldest);
+                        return Ok(());
+                    }
+                    curr = self.inner.state.load(SeqCst);
+                }
+                OverflowPolicy::Reject | OverflowPolicy::Block => {
+                    return Err(TrySendError { err: SendError::full(), val: msg });
+                }
+            }
+        }
+    }
+
+    // Increment the number of queued messages by up to `max`, as many as the
+    // buffer has room for plus the slot of this sender. Returns the number of
+    // messages added and whether the channel exceeds its capacity.
+    fn inc_num_messages_by(&self, max: usize) -> Option<(usize, bool)> {
+        let mut curr = self.inner.state.load(SeqCst);
+
+        loop {
+            let mut state = decode_state(curr);
+
+            // The receiver end closed the channel.
+            if !state.is_open {
+                return None;
+            }
+
+            assert!(
+                state.num_messages < MAX_CAPACITY,
+                "buffer space \
+                    exhausted; sending this messages would overflow the state"
+            );
+
+            let buffer = self.inner.buffer.load(SeqCst);
+            let count = if state.num_messages < buffer {
+                max.min(buffer - state.num_messages + 1)
+            } else {
+                1
+            };
+            state.num_messages += count;
+
+            let next = encode_state(&state);
+            match self.inner.state.compare_exchange(curr, next, SeqCst, SeqCst) {
+                Ok(_) => return Some((count, state.num_messages > buffer)),
+                Err(actual) => curr = actual,
+            }
+        }
     }
 
     // Increment the number of queued messages. Returns the resulting number.
@@ -578,7 +972,9 @@ impl<T> BoundedSenderInner<T> {
         }
     }
 
-    fn park(&mut self) {
+    // Returns whether the sender may be parked, that is whether the channel
+    // is still open.
+    fn park(&self) -> bool {
         {
             let mut sender = self.sender_task.lock().unwrap();
             sender.task = None;
@@ -587,12 +983,13 @@ impl<T> BoundedSenderInner<T> {
 
         // Send handle over queue
         let t = self.sender_task.clone();
+        self.inner.num_parked.fetch_add(1, SeqCst);
         self.inner.parked_queue.push(t);
 
         // Check to make sure we weren't closed after we sent our task on the
         // queue
         let state = decode_state(self.inner.state.load(SeqCst));
-        self.maybe_parked = state.is_open;
+        state.is_open
     }
 
     /// Polls the channel to determine if there is guaranteed capacity to send
@@ -610,7 +1007,7 @@ impl<T> BoundedSenderInner<T> {
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
         let state = decode_state(self.inner.state.load(SeqCst));
         if !state.is_open {
-            return Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }));
+            return Poll::Ready(Err(SendError::closed(&self.inner.close_reason)));
         }
 
         self.poll_unparked(Some(cx)).map(Ok)
@@ -682,7 +1079,7 @@ impl<T> Sender<T> {
         if let Some(inner) = &mut self.0 {
             inner.try_send(msg)
         } else {
-            Err(TrySendError { err: SendError { kind: SendErrorKind::Disconnected }, val: msg })
+            Err(TrySendError { err: SendError::disconnected(), val: msg })
         }
     }
 
@@ -695,6 +1092,40 @@ impl<T> Sender<T> {
         self.try_send(msg).map_err(|e| e.err)
     }
 
+    /// Sends the messages of `msgs`, waiting for capacity as needed.
+    ///
+    /// Unlike sending the messages one at a time, this updates the state of
+    /// the channel once for as many messages as it has room for, and wakes
+    /// the receiver once for them.
+    ///
+    /// The returned future fails if the receiver has been dropped, in which
+    /// case the messages not sent yet are dropped.
+    pub fn send_batch<I>(&mut self, msgs: I) -> SendBatch<'_, T, I::IntoIter>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        SendBatch::new(self, msgs.into_iter())
+    }
+
+    /// Sends the messages of `stream` into the channel, like
+    /// [`SinkExt::send_all`], but sending the messages which are ready in
+    /// batches, as [`send_batch`](Sender::send_batch) does.
+    ///
+    /// [`SinkExt::send_all`]: https://docs.rs/futures/0.3/futures/sink/trait.SinkExt.html#method.send_all
+    pub fn send_all<St>(&mut self, stream: St) -> SendAll<'_, T, St>
+    where
+        St: TryStream<Ok = T, Error = SendError> + Stream,
+    {
+        SendAll::new(self, stream)
+    }
+
+    // Send messages from the front of `msgs`, as many as the channel has room
+    // for, once `poll_ready` returned `Poll::Ready(Ok(()))`.
+    fn start_send_batch(&mut self, msgs: &mut VecDeque<T>) -> Result<(), SendError> {
+        let inner = self.0.as_mut().ok_or_else(SendError::disconnected)?;
+        inner.do_send_batch(msgs)
+    }
+
     /// Polls the channel to determine if there is guaranteed capacity to send
     /// at least one item without waiting.
     ///
@@ -708,15 +1139,124 @@ impl<T> Sender<T> {
     ///   capacity is available;
     /// - `Poll::Ready(Err(SendError))` if the receiver has been dropped.
     pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
-        let inner = self.0.as_mut().ok_or(SendError { kind: SendErrorKind::Disconnected })?;
+        let inner = self.0.as_mut().ok_or_else(SendError::disconnected)?;
         inner.poll_ready(cx)
     }
 
+    /// Waits for capacity in the channel and reserves a slot for a message.
+    ///
+    /// This allows waiting for capacity before building the message, so that
+    /// the work isn't wasted when the channel is full. The returned
+    /// [`Permit`] sends the message without failing, and releases the slot
+    /// if it's dropped unused.
+    ///
+    /// The future fails if the receiver has been dropped.
+    pub fn reserve(&mut self) -> Reserve<'_, T> {
+        Reserve::new(self)
+    }
+
+    /// Sends a message, waiting for capacity in the channel at most for
+    /// `timeout`, as measured by `timer`.
+    ///
+    /// The returned future fails with an error for which
+    /// [`SendTimeoutError::is_elapsed`] returns `true` if the timeout elapsed
+    /// first, or with one for which [`SendTimeoutError::is_disconnected`]
+    /// returns `true` if the receiver has been dropped. The message can be
+    /// taken back from the error with [`SendTimeoutError::into_inner`].
+    ///
+    /// This method is only available when the `std` feature of this library
+    /// is activated, and it is activated by default.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn send_timeout<Tm>(&mut self, msg: T, timeout: Duration, timer: &Tm) -> SendTimeout<'_, T>
+    where
+        Tm: Timer + ?Sized,
+    {
+        SendTimeout::new(self, msg, timer.sleep(timeout))
+    }
+
+    /// Tries to reserve a slot for a message without waiting, like
+    /// [`reserve`](Sender::reserve).
+    ///
+    /// Fails with an error for which [`SendError::is_full`] returns `true`
+    /// if the channel has no capacity, or with one for which
+    /// [`SendError::is_disconnected`] returns `true` if the receiver has been
+    /// dropped.
+    pub fn try_reserve(&mut self) -> Result<Permit<'_, T>, SendError> {
+        let inner = self.0.as_mut().ok_or_else(SendError::disconnected)?;
+        if inner.is_closed() {
+            return Err(SendError::closed(&inner.inner.close_reason));
+        }
+        if inner.poll_unparked(None).is_pending() {
+            return Err(SendError::full());
+        }
+        Ok(Permit::new(self))
+    }
+
     /// Returns whether this channel is closed without needing a context.
     pub fn is_closed(&self) -> bool {
         self.0.as_ref().map(BoundedSenderInner::is_closed).unwrap_or(true)
     }
 
+    /// Polls whether this channel is closed, registering the current task to
+    /// be notified once it is.
+    ///
+    /// The channel is closed once the receiver is closed or dropped, or
+    /// once [`close_channel`](Sender::close_channel) is called.
+    pub fn poll_closed(&self, cx: &mut Context<'_>) -> Poll<()> {
+        match &self.0 {
+            Some(inner) if !inner.inner.register_closed(cx) => Poll::Pending,
+            _ => Poll::Ready(()),
+        }
+    }
+
+    /// Waits for this channel to be closed, without sending a message.
+    ///
+    /// This lets a producer stop its work once the receiver is gone. See
+    /// [`poll_closed`](Sender::poll_closed).
+    pub fn closed(&self) -> Closed<'_, T> {
+        Closed::new(self)
+    }
+
+    /// Returns the number of messages in the channel.
+    ///
+    /// The value is approximate if other senders or the receiver use the
+    /// channel concurrently.
+    pub fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |inner| inner.inner.len())
+    }
+
+    /// Returns whether there is no message in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity of the channel, which is its buffer size plus
+    /// the number of senders, as explained for [`channel`], or only its
+    /// buffer size if it was created with an [`OverflowPolicy`] which doesn't
+    /// block.
+    ///
+    /// Returns 0 if this sender is disconnected.
+    pub fn capacity(&self) -> usize {
+        self.0.as_ref().map_or(0, |inner| inner.inner.capacity())
+    }
+
+    /// Returns whether the channel is at capacity, in which case no sender
+    /// can send without waiting.
+    ///
+    /// Like [`len`](Sender::len), this is approximate under concurrency.
+    pub fn is_full(&self) -> bool {
+        self.0.as_ref().map_or(false, |inner| inner.inner.is_full())
+    }
+
+    /// Returns the number of messages dropped by the [`OverflowPolicy`] of
+    /// the channel.
+    ///
+    /// Returns 0 if this sender is disconnected.
+    pub fn dropped_count(&self) -> usize {
+        self.0.as_ref().map_or(0, |inner| inner.inner.dropped.load(SeqCst))
+    }
+
     /// Closes this channel from the sender side, preventing any new messages.
     pub fn close_channel(&mut self) {
         if let Some(inner) = &mut self.0 {
@@ -729,6 +1269,15 @@ impl<T> Sender<T> {
         self.0 = None;
     }
 
+    /// Creates a [`WeakSender`] for this channel, which doesn't keep the
+    /// channel open.
+    ///
+    /// This is useful to hold a sender in state owned by the receiving side
+    /// without preventing the receiver from terminating.
+    pub fn downgrade(&self) -> WeakSender<T> {
+        WeakSender::new(self.0.as_ref().map(|inner| loom::downgrade(&inner.inner)))
+    }
+
     /// Returns whether the senders send to the same receiver.
     pub fn same_receiver(&self, other: &Self) -> bool {
         match (&self.0, &other.0) {
@@ -748,19 +1297,36 @@ impl<T> Sender<T> {
     /// Hashes the receiver into the provided hasher
     pub fn hash_receiver<H>(&self, hasher: &mut H)
     where
-        H: std::hash::Hasher,
+        H: core::hash::Hasher,
     {
-        use std::hash::Hash;
+        use core::hash::Hash;
 
         let ptr = self.0.as_ref().map(|inner| inner.ptr());
         ptr.hash(hasher);
     }
+
+    /// Changes the buffer size of the channel, as given to [`channel`].
+    ///
+    /// When the buffer grows, the senders waiting for capacity are woken, by
+    /// the receiver once it's polled. When it shrinks, the messages already
+    /// in the channel are kept, and senders wait until the receiver has
+    /// drained the channel below the new size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is too large, as [`channel`] does, or if the
+    /// channel was created by [`rendezvous`].
+    pub fn set_capacity(&self, buffer: usize) {
+        if let Some(inner) = &self.0 {
+            inner.inner.set_buffer(buffer);
+        }
+    }
 }
 
 impl<T> UnboundedSender<T> {
     /// Check if the channel is ready to receive a message.
     pub fn poll_ready(&self, _: &mut Context<'_>) -> Poll<Result<(), SendError>> {
-        let inner = self.0.as_ref().ok_or(SendError { kind: SendErrorKind::Disconnected })?;
+        let inner = self.0.as_ref().ok_or_else(SendError::disconnected)?;
         inner.poll_ready_nb()
     }
 
@@ -769,6 +1335,26 @@ impl<T> UnboundedSender<T> {
         self.0.as_ref().map(UnboundedSenderInner::is_closed).unwrap_or(true)
     }
 
+    /// Polls whether this channel is closed, registering the current task to
+    /// be notified once it is.
+    ///
+    /// The channel is closed once the receiver is closed or dropped, or
+    /// once [`close_channel`](UnboundedSender::close_channel) is called.
+    pub fn poll_closed(&self, cx: &mut Context<'_>) -> Poll<()> {
+        match &self.0 {
+            Some(inner) if !inner.inner.register_closed(cx) => Poll::Pending,
+            _ => Poll::Ready(()),
+        }
+    }
+
+    /// Waits for this channel to be closed, without sending a message.
+    ///
+    /// This lets a producer stop its work once the receiver is gone. See
+    /// [`poll_closed`](UnboundedSender::poll_closed).
+    pub fn closed(&self) -> UnboundedClosed<'_, T> {
+        UnboundedClosed::new(self)
+    }
+
     /// Closes this channel from the sender side, preventing any new messages.
     pub fn close_channel(&self) {
         if let Some(inner) = &self.0 {
@@ -781,6 +1367,15 @@ impl<T> UnboundedSender<T> {
         self.0 = None;
     }
 
+    /// Creates a [`WeakUnboundedSender`] for this channel, which doesn't keep
+    /// the channel open.
+    ///
+    /// This is useful to hold a sender in state owned by the receiving side
+    /// without preventing the receiver from terminating.
+    pub fn downgrade(&self) -> WeakUnboundedSender<T> {
+        WeakUnboundedSender::new(self.0.as_ref().map(|inner| loom::downgrade(&inner.inner)))
+    }
+
     // Do the send without parking current task.
     fn do_send_nb(&self, msg: T) -> Result<(), TrySendError<T>> {
         if let Some(inner) = &self.0 {
@@ -788,9 +1383,11 @@ impl<T> UnboundedSender<T> {
                 inner.queue_push_and_signal(msg);
                 return Ok(());
             }
+            let err = SendError::closed(&inner.inner.close_reason);
+            return Err(TrySendError { err, val: msg });
         }
 
-        Err(TrySendError { err: SendError { kind: SendErrorKind::Disconnected }, val: msg })
+        Err(TrySendError { err: SendError::disconnected(), val: msg })
     }
 
     /// Send a message on the channel.
@@ -829,9 +1426,9 @@ impl<T> UnboundedSender<T> {
     /// Hashes the receiver into the provided hasher
     pub fn hash_receiver<H>(&self, hasher: &mut H)
     where
-        H: std::hash::Hasher,
+        H: core::hash::Hasher,
     {
-        use std::hash::Hash;
+        use core::hash::Hash;
 
         let ptr = self.0.as_ref().map(|inner| inner.ptr());
         ptr.hash(hasher);
@@ -870,6 +1467,7 @@ impl<T> Clone for UnboundedSenderInner<T> {
                 Ok(_) => {
                     // The ABA problem doesn't matter here. We only care that the
                     // number of senders never exceeds the maximum.
+                    wake_tasks(&self.inner.sender_tasks);
                     return Self { inner: self.inner.clone() };
                 }
                 Err(actual) => curr = actual,
@@ -898,6 +1496,7 @@ impl<T> Clone for BoundedSenderInner<T> {
                 Ok(_) => {
                     // The ABA problem doesn't matter here. We only care that the
                     // number of senders never exceeds the maximum.
+                    wake_tasks(&self.inner.sender_tasks);
                     return Self {
                         inner: self.inner.clone(),
                         sender_task: Arc::new(Mutex::new(SenderTask::new())),
@@ -914,6 +1513,7 @@ impl<T> Drop for UnboundedSenderInner<T> {
     fn drop(&mut self) {
         // Ordering between variables don't matter here
         let prev = self.inner.num_senders.fetch_sub(1, SeqCst);
+        wake_tasks(&self.inner.sender_tasks);
 
         if prev == 1 {
             self.close_channel();
@@ -925,6 +1525,7 @@ impl<T> Drop for BoundedSenderInner<T> {
     fn drop(&mut self) {
         // Ordering between variables don't matter here
         let prev = self.inner.num_senders.fetch_sub(1, SeqCst);
+        wake_tasks(&self.inner.sender_tasks);
 
         if prev == 1 {
             self.close_channel();
@@ -961,12 +1562,23 @@ impl<T> Receiver<T> {
 
             // Wake up any threads waiting as they'll see that we've closed the
             // channel and will continue on their merry way.
-            while let Some(task) = unsafe { inner.parked_queue.pop_spin() } {
-                task.lock().unwrap().notify();
-            }
+            while unsafe { inner.unpark_one() } {}
         }
     }
 
+    /// Closes the receiving half of a channel like [`close`](Self::close),
+    /// giving the senders a reason for it.
+    ///
+    /// The sends failing because of the closure return an error whose
+    /// [`reason`](SendError::reason) holds `reason`. It's ignored if the
+    /// channel is already closed.
+    pub fn close_with<R: Any + Send + Sync>(&mut self, reason: R) {
+        if let Some(inner) = &self.inner {
+            inner.set_close_reason(CloseReason::new(reason));
+        }
+        self.close();
+    }
+
     /// Tries to receive the next message without notifying a context if empty.
     ///
     /// It is not recommended to call this function from inside of a future,
@@ -978,23 +1590,223 @@ impl<T> Receiver<T> {
     /// * `Ok(None)` when channel is closed and no messages left in the queue
     /// * `Err(e)` when there are no messages available, but channel is not yet closed
     pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
-        match self.next_message() {
+        let msg = self.next_message();
+        self.unpark_senders();
+        match msg {
             Poll::Ready(msg) => Ok(msg),
             Poll::Pending => Err(TryRecvError { _priv: () }),
         }
     }
 
+    /// Closes the receiving half of a channel, like [`close`](Self::close),
+    /// and returns all the messages buffered in it.
+    ///
+    /// This doesn't wait for an asynchronous task: it's meant for teardown
+    /// code collecting the remaining messages at shutdown. Once this returns,
+    /// the receiver is terminated.
+    pub fn close_and_drain(&mut self) -> Vec<T> {
+        self.close();
+        let mut msgs = Vec::new();
+        loop {
+            match self.next_message() {
+                Poll::Ready(Some(msg)) => msgs.push(msg),
+                Poll::Ready(None) => return msgs,
+                // A sender counted its message but didn't push it yet.
+                Poll::Pending => thread::yield_now(),
+            }
+        }
+    }
+
+    /// Polls for up to `limit` messages, moving them to the end of `buf`.
+    ///
+    /// Draining a busy channel this way avoids the overhead of polling the
+    /// receiver once per message.
+    ///
+    /// This method returns:
+    /// * `Poll::Ready(n)` with `n > 0` when `n` messages were received,
+    /// * `Poll::Ready(0)` when the channel is closed and no messages are left
+    ///   in the queue, or when `limit` is 0,
+    /// * `Poll::Pending` when there are no messages available but the channel
+    ///   is not yet closed, in which case the current task is notified when a
+    ///   message is sent.
+    pub fn poll_recv_many(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut Vec<T>,
+        limit: usize,
+    ) -> Poll<usize> {
+        if limit == 0 {
+            return Poll::Ready(0);
+        }
+        ready!(self.poll_fill_peeked(cx));
+        let first = match self.peeked.take() {
+            Some(msg) => msg,
+            None => return Poll::Ready(0),
+        };
+        buf.push(first);
+        let mut received = 1;
+        while received < limit {
+            match self.next_message() {
+                Poll::Ready(Some(msg)) => {
+                    buf.push(msg);
+                    received += 1;
+                }
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+        self.unpark_senders();
+        Poll::Ready(received)
+    }
+
+    /// Receives up to `limit` messages, moving them to the end of `buf`.
+    ///
+    /// The returned future resolves with the number of messages received,
+    /// which is 0 only if the channel is closed and empty, or if `limit` is
+    /// 0. See [`poll_recv_many`](Receiver::poll_recv_many).
+    pub fn recv_many<'a>(&'a mut self, buf: &'a mut Vec<T>, limit: usize) -> RecvMany<'a, T> {
+        RecvMany::new(self, buf, limit)
+    }
+
+    /// Polls for the next message without receiving it, registering the
+    /// current task to be woken when a message is sent, like `poll_next`.
+    ///
+    /// Once this returns `Poll::Ready(Some(_))`, the message is left in the
+    /// receiver, to be returned by the next receive, so that the caller can
+    /// look at it before deciding what to do with it. Returns
+    /// `Poll::Ready(None)` when the channel is closed and no messages are
+    /// left.
+    ///
+    /// The peeked message no longer counts towards the capacity of the
+    /// channel, so a sender waiting for capacity may be woken, but it still
+    /// counts towards [`len`](Receiver::len).
+    pub fn poll_peek(&mut self, cx: &mut Context<'_>) -> Poll<Option<&T>> {
+        ready!(self.poll_fill_peeked(cx));
+        Poll::Ready(self.peeked.as_ref())
+    }
+
+    /// Waits for the next message without receiving it.
+    ///
+    /// The returned future resolves with the message left in the receiver,
+    /// or `None` if the channel is closed and no messages are left. See
+    /// [`poll_peek`](Receiver::poll_peek).
+    pub fn peek(&mut self) -> Peek<'_, T> {
+        Peek::new(self)
+    }
+
+    /// Waits for the next message at most for `timeout`, as measured by
+    /// `timer`.
+    ///
+    /// The returned future resolves with the message, or `None` if the
+    /// channel is closed and no messages are left, like
+    /// [`next`](futures_core::stream::Stream). It fails with [`Elapsed`] if
+    /// the timeout elapsed first.
+    ///
+    /// This method is only available when the `std` feature of this library
+    /// is activated, and it is activated by default.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn recv_timeout<Tm>(&mut self, timeout: Duration, timer: &Tm) -> RecvTimeout<'_, T>
+    where
+        Tm: Timer + ?Sized,
+    {
+        RecvTimeout::new(self, timer.sleep(timeout))
+    }
+
+    /// Changes the buffer size of the channel, as given to [`channel`].
+    ///
+    /// When the buffer grows, the senders waiting for capacity are woken.
+    /// When it shrinks, the messages already in the channel are kept, and
+    /// senders wait until the receiver has drained the channel below the new
+    /// size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is too large, as [`channel`] does, or if the
+    /// channel was created by [`rendezvous`].
+    pub fn set_capacity(&mut self, buffer: usize) {
+        if let Some(inner) = &self.inner {
+            inner.set_buffer(buffer);
+            self.unpark_senders();
+        }
+    }
+
+    /// Returns the number of messages in the channel.
+    ///
+    /// The value is approximate if other senders or the receiver use the
+    /// channel concurrently.
+    pub fn len(&self) -> usize {
+        let peeked = self.peeked.is_some() as usize;
+        self.inner.as_ref().map_or(0, |inner| inner.len()) + peeked
+    }
+
+    /// Returns whether there is no message in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity of the channel, which is its buffer size plus
+    /// the number of senders, as explained for [`channel`], or only its
+    /// buffer size if it was created with an [`OverflowPolicy`] which doesn't
+    /// block.
+    ///
+    /// Returns 0 if the receiver is terminated.
+    pub fn capacity(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.capacity())
+    }
+
+    /// Returns whether the channel is at capacity, in which case no sender can
+    /// send without waiting.
+    ///
+    /// Like [`len`](Receiver::len), this is approximate under concurrency.
+    pub fn is_full(&self) -> bool {
+        self.inner.as_ref().map_or(false, |inner| inner.is_full())
+    }
+
+    /// Returns the number of messages dropped by the [`OverflowPolicy`] of
+    /// the channel.
+    ///
+    /// Returns 0 if the receiver is terminated.
+    pub fn dropped_count(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.dropped.load(SeqCst))
+    }
+
+    /// Returns the number of senders of the channel.
+    ///
+    /// Weak senders aren't counted. Returns 0 if the receiver is terminated.
+    pub fn sender_count(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.num_senders.load(SeqCst))
+    }
+
+    /// Returns a stream of the changes of the number of senders of the
+    /// channel, from now on.
+    ///
+    /// The stream yields a [`SenderEvent`] each time a sender is added or
+    /// dropped, and ends once no sender is left, at which point the channel
+    /// can't get new ones. Changes happening between two polls of the stream
+    /// are coalesced: a sender cloned then dropped meanwhile yields no event,
+    /// but the events always lead to the current
+    /// [`sender_count`](Receiver::sender_count).
+    pub fn sender_events(&self) -> SenderEvents<T> {
+        SenderEvents::new(self.inner.clone())
+    }
+
+    // Callers must call `unpark_senders` once they're done receiving.
     fn next_message(&mut self) -> Poll<Option<T>> {
+        if let Some(msg) = self.peeked.take() {
+            return Poll::Ready(Some(msg));
+        }
         let inner = match self.inner.as_mut() {
             None => return Poll::Ready(None),
             Some(inner) => inner,
         };
         // Pop off a message
-        match unsafe { inner.message_queue.pop_spin() } {
+        match unsafe { inner.pop_message() } {
             Some(msg) => {
+                inner.probe.received();
+
                 // If there are any parked task handles in the parked queue,
-                // pop one and unpark it.
-                self.unpark_one();
+                // one of them is unparked by `unpark_senders`.
+                self.unparks += 1;
 
                 // Decrement number of messages
                 self.dec_num_messages();
@@ -1022,11 +1834,50 @@ impl<T> Receiver<T> {
         }
     }
 
-    // Unpark a single task handle if there is one pending in the parked queue
-    fn unpark_one(&mut self) {
-        if let Some(inner) = &mut self.inner {
-            if let Some(task) = unsafe { inner.parked_queue.pop_spin() } {
-                task.lock().unwrap().notify();
+    fn poll_message(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        // Try to read a message off of the message queue.
+        let msg = match self.next_message() {
+            Poll::Ready(msg) => Poll::Ready(msg),
+            Poll::Pending => {
+                // There are no messages to read, in this case, park.
+                let inner = self.inner.as_ref().unwrap();
+                inner.recv_waiting.store(true, SeqCst);
+                inner.recv_task.register(cx.waker());
+                // Check queue again after parking to prevent race condition:
+                // a message could be added to the queue after previous `next_message`
+                // before `register` call.
+                self.next_message()
+            }
+        };
+        self.unpark_senders();
+        msg
+    }
+
+    // Receives the next message into `peeked`, unless there's one already.
+    fn poll_fill_peeked(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.peeked.is_none() {
+            #[cfg(feature = "std")]
+            let coop = ready!(coop::poll_proceed(cx));
+            self.peeked = ready!(self.poll_message(cx));
+            #[cfg(feature = "std")]
+            coop.made_progress();
+        }
+        Poll::Ready(())
+    }
+
+    // Unpark a parked sender for each message received since the last call,
+    // and the senders which can send since the buffer grew
+    fn unpark_senders(&mut self) {
+        let received = core::mem::replace(&mut self.unparks, 0);
+        if let Some(inner) = &self.inner {
+            let mut unparks = received;
+            if inner.pending_unparks.load(SeqCst) != 0 {
+                unparks += inner.pending_unparks.swap(0, SeqCst);
+            }
+            for _ in 0..unparks {
+                if !unsafe { inner.unpark_one() } {
+                    break;
+                }
             }
         }
     }
@@ -1054,28 +1905,17 @@ impl<T> Stream for Receiver<T> {
     type Item = T;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
-        // Try to read a message off of the message queue.
-        match self.next_message() {
-            Poll::Ready(msg) => {
-                if msg.is_none() {
-                    self.inner = None;
-                }
-                Poll::Ready(msg)
-            }
-            Poll::Pending => {
-                // There are no messages to read, in this case, park.
-                self.inner.as_ref().unwrap().recv_task.register(cx.waker());
-                // Check queue again after parking to prevent race condition:
-                // a message could be added to the queue after previous `next_message`
-                // before `register` call.
-                self.next_message()
-            }
-        }
+        // Going through `peeked` charges the coop budget once per message,
+        // even if it was peeked first.
+        ready!(self.poll_fill_peeked(cx));
+        Poll::Ready(self.peeked.take())
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         if let Some(inner) = &self.inner {
-            decode_state(inner.state.load(SeqCst)).size_hint()
+            let peeked = self.peeked.is_some() as usize;
+            let (lower, upper) = decode_state(inner.state.load(SeqCst)).size_hint();
+            (lower + peeked, upper.map(|upper| upper + peeked))
         } else {
             (0, Some(0))
         }
@@ -1135,6 +1975,19 @@ impl<T> UnboundedReceiver<T> {
         }
     }
 
+    /// Closes the receiving half of a channel like [`close`](Self::close),
+    /// giving the senders a reason for it.
+    ///
+    /// The sends failing because of the closure return an error whose
+    /// [`reason`](SendError::reason) holds `reason`. It's ignored if the
+    /// channel is already closed.
+    pub fn close_with<R: Any + Send + Sync>(&mut self, reason: R) {
+        if let Some(inner) = &self.inner {
+            inner.set_close_reason(CloseReason::new(reason));
+        }
+        self.close();
+    }
+
     /// Tries to receive the next message without notifying a context if empty.
     ///
     /// It is not recommended to call this function from inside of a future,
@@ -1152,7 +2005,150 @@ impl<T> UnboundedReceiver<T> {
         }
     }
 
+    /// Closes the receiving half of a channel, like [`close`](Self::close),
+    /// and returns all the messages buffered in it.
+    ///
+    /// This doesn't wait for an asynchronous task: it's meant for teardown
+    /// code collecting the remaining messages at shutdown. Once this returns,
+    /// the receiver is terminated.
+    pub fn close_and_drain(&mut self) -> Vec<T> {
+        self.close();
+        let mut msgs = Vec::new();
+        loop {
+            match self.next_message() {
+                Poll::Ready(Some(msg)) => msgs.push(msg),
+                Poll::Ready(None) => return msgs,
+                // A sender counted its message but didn't push it yet.
+                Poll::Pending => thread::yield_now(),
+            }
+        }
+    }
+
+    /// Polls for up to `limit` messages, moving them to the end of `buf`.
+    ///
+    /// Draining a busy channel this way avoids the overhead of polling the
+    /// receiver once per message.
+    ///
+    /// This method returns:
+    /// * `Poll::Ready(n)` with `n > 0` when `n` messages were received,
+    /// * `Poll::Ready(0)` when the channel is closed and no messages are left
+    ///   in the queue, or when `limit` is 0,
+    /// * `Poll::Pending` when there are no messages available but the channel
+    ///   is not yet closed, in which case the current task is notified when a
+    ///   message is sent.
+    pub fn poll_recv_many(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut Vec<T>,
+        limit: usize,
+    ) -> Poll<usize> {
+        if limit == 0 {
+            return Poll::Ready(0);
+        }
+        ready!(self.poll_fill_peeked(cx));
+        let first = match self.peeked.take() {
+            Some(msg) => msg,
+            None => return Poll::Ready(0),
+        };
+        buf.push(first);
+        let mut received = 1;
+        while received < limit {
+            match self.next_message() {
+                Poll::Ready(Some(msg)) => {
+                    buf.push(msg);
+                    received += 1;
+                }
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+        Poll::Ready(received)
+    }
+
+    /// Receives up to `limit` messages, moving them to the end of `buf`.
+    ///
+    /// The returned future resolves with the number of messages received,
+    /// which is 0 only if the channel is closed and empty, or if `limit` is
+    /// 0. See [`poll_recv_many`](UnboundedReceiver::poll_recv_many).
+    pub fn recv_many<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<T>,
+        limit: usize,
+    ) -> UnboundedRecvMany<'a, T> {
+        UnboundedRecvMany::new(self, buf, limit)
+    }
+
+    /// Polls for the next message without receiving it, registering the
+    /// current task to be woken when a message is sent, like `poll_next`.
+    ///
+    /// Once this returns `Poll::Ready(Some(_))`, the message is left in the
+    /// receiver, to be returned by the next receive, so that the caller can
+    /// look at it before deciding what to do with it. Returns
+    /// `Poll::Ready(None)` when the channel is closed and no messages are
+    /// left.
+    pub fn poll_peek(&mut self, cx: &mut Context<'_>) -> Poll<Option<&T>> {
+        ready!(self.poll_fill_peeked(cx));
+        Poll::Ready(self.peeked.as_ref())
+    }
+
+    /// Waits for the next message without receiving it.
+    ///
+    /// The returned future resolves with the message left in the receiver,
+    /// or `None` if the channel is closed and no messages are left. See
+    /// [`poll_peek`](UnboundedReceiver::poll_peek).
+    pub fn peek(&mut self) -> UnboundedPeek<'_, T> {
+        UnboundedPeek::new(self)
+    }
+
+    /// Waits for the next message at most for `timeout`, as measured by
+    /// `timer`.
+    ///
+    /// The returned future resolves with the message, or `None` if the
+    /// channel is closed and no messages are left, like
+    /// [`next`](futures_core::stream::Stream). It fails with [`Elapsed`] if
+    /// the timeout elapsed first.
+    ///
+    /// This method is only available when the `std` feature of this library
+    /// is activated, and it is activated by default.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn recv_timeout<Tm>(&mut self, timeout: Duration, timer: &Tm) -> UnboundedRecvTimeout<'_, T>
+    where
+        Tm: Timer + ?Sized,
+    {
+        UnboundedRecvTimeout::new(self, timer.sleep(timeout))
+    }
+
+    /// Returns the number of senders of the channel.
+    ///
+    /// Weak senders aren't counted. Returns 0 if the receiver is terminated.
+    pub fn sender_count(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.num_senders.load(SeqCst))
+    }
+
+    /// Returns a stream of the changes of the number of senders of the
+    /// channel, from now on.
+    ///
+    /// See [`Receiver::sender_events`] for details.
+    pub fn sender_events(&self) -> UnboundedSenderEvents<T> {
+        UnboundedSenderEvents::new(self.inner.clone())
+    }
+
+    /// Releases the memory which held the messages already received.
+    ///
+    /// The channel buffers messages in blocks, which are released as the
+    /// messages are received, so the memory used by a burst of messages is
+    /// released once they're all received. This releases it right away,
+    /// instead of on the next attempt to receive a message.
+    pub fn shrink_to_fit(&mut self) {
+        if let Some(inner) = &self.inner {
+            unsafe { inner.message_queue.shrink() }
+        }
+    }
+
     fn next_message(&mut self) -> Poll<Option<T>> {
+        if let Some(msg) = self.peeked.take() {
+            return Poll::Ready(Some(msg));
+        }
         let inner = match self.inner.as_mut() {
             None => return Poll::Ready(None),
             Some(inner) => inner,
@@ -1160,6 +2156,8 @@ impl<T> UnboundedReceiver<T> {
         // Pop off a message
         match unsafe { inner.message_queue.pop_spin() } {
             Some(msg) => {
+                inner.probe.received();
+
                 // Decrement number of messages
                 self.dec_num_messages();
 
@@ -1186,6 +2184,38 @@ impl<T> UnboundedReceiver<T> {
         }
     }
 
+    fn poll_message(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        // Try to read a message off of the message queue.
+        match self.next_message() {
+            Poll::Ready(msg) => {
+                if msg.is_none() {
+                    self.inner = None;
+                }
+                Poll::Ready(msg)
+            }
+            Poll::Pending => {
+                // There are no messages to read, in this case, park.
+                self.inner.as_ref().unwrap().recv_task.register(cx.waker());
+                // Check queue again after parking to prevent race condition:
+                // a message could be added to the queue after previous `next_message`
+                // before `register` call.
+                self.next_message()
+            }
+        }
+    }
+
+    // Receives the next message into `peeked`, unless there's one already.
+    fn poll_fill_peeked(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.peeked.is_none() {
+            #[cfg(feature = "std")]
+            let coop = ready!(coop::poll_proceed(cx));
+            self.peeked = ready!(self.poll_message(cx));
+            #[cfg(feature = "std")]
+            coop.made_progress();
+        }
+        Poll::Ready(())
+    }
+
     fn dec_num_messages(&self) {
         if let Some(inner) = &self.inner {
             // OPEN_MASK is highest bit, so it's unaffected by subtraction
@@ -1206,28 +2236,17 @@ impl<T> Stream for UnboundedReceiver<T> {
     type Item = T;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
-        // Try to read a message off of the message queue.
-        match self.next_message() {
-            Poll::Ready(msg) => {
-                if msg.is_none() {
-                    self.inner = None;
-                }
-                Poll::Ready(msg)
-            }
-            Poll::Pending => {
-                // There are no messages to read, in this case, park.
-                self.inner.as_ref().unwrap().recv_task.register(cx.waker());
-                // Check queue again after parking to prevent race condition:
-                // a message could be added to the queue after previous `next_message`
-                // before `register` call.
-                self.next_message()
-            }
-        }
+        // Going through `peeked` charges the coop budget once per message,
+        // even if it was peeked first.
+        ready!(self.poll_fill_peeked(cx));
+        Poll::Ready(self.peeked.take())
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         if let Some(inner) = &self.inner {
-            decode_state(inner.state.load(SeqCst)).size_hint()
+            let peeked = self.peeked.is_some() as usize;
+            let (lower, upper) = decode_state(inner.state.load(SeqCst)).size_hint();
+            (lower + peeked, upper.map(|upper| upper + peeked))
         } else {
             (0, Some(0))
         }
@@ -1285,12 +2304,27 @@ impl<T> fmt::Debug for UnboundedReceiver<T> {
 impl<T> UnboundedInner<T> {
     // Clear `open` flag in the state, keep `num_messages` intact.
     fn set_closed(&self) {
-        let curr = self.state.load(SeqCst);
-        if !decode_state(curr).is_open {
+        // Only the call which actually closes the channel reports it.
+        let prev = self.state.fetch_and(!OPEN_MASK, SeqCst);
+        if !decode_state(prev).is_open {
             return;
         }
 
-        self.state.fetch_and(!OPEN_MASK, SeqCst);
+        self.probe.closed();
+        wake_tasks(&self.closed_tasks);
+    }
+
+    // Record the reason for closing the channel, unless it's already closed.
+    fn set_close_reason(&self, reason: CloseReason) {
+        if decode_state(self.state.load(SeqCst)).is_open {
+            self.close_reason.set(reason);
+        }
+    }
+
+    // Register a task to be woken once the channel is closed, returning
+    // whether it's already closed.
+    fn register_closed(&self, cx: &mut Context<'_>) -> bool {
+        register_closed_task(&self.state, &self.closed_tasks, cx)
     }
 }
 
@@ -1298,17 +2332,116 @@ impl<T> BoundedInner<T> {
     // The return value is such that the total number of messages that can be
     // enqueued into the channel will never exceed MAX_CAPACITY
     fn max_senders(&self) -> usize {
-        MAX_CAPACITY - self.buffer
+        MAX_CAPACITY - self.buffer.load(SeqCst)
+    }
+
+    fn len(&self) -> usize {
+        decode_state(self.state.load(SeqCst)).num_messages
+    }
+
+    fn capacity(&self) -> usize {
+        let buffer = self.buffer.load(SeqCst);
+        if self.policy == OverflowPolicy::Block {
+            buffer + self.num_senders.load(SeqCst)
+        } else {
+            buffer
+        }
+    }
+
+    // Pops a message. This function is unsafe because only one thread can
+    // call it at a time, unless the channel has a pop lock.
+    unsafe fn pop_message(&self) -> Option<T> {
+        let _guard = self.pop_lock.as_ref().map(|lock| lock.lock().unwrap());
+        self.message_queue.pop_spin()
+    }
+
+    fn is_full(&self) -> bool {
+        self.len() >= self.capacity()
+    }
+
+    // Whether senders are parked, waiting to be unparked by the receiver.
+    fn has_parked(&self) -> bool {
+        self.num_parked.load(SeqCst) != 0
+    }
+
+    // Unparks the sender parked first, returning whether there was one. This
+    // function is unsafe because only the receiver can call it.
+    unsafe fn unpark_one(&self) -> bool {
+        match self.parked_queue.pop_spin() {
+            Some(task) => {
+                // Decremented first, so that the sender doesn't park again
+                // behind itself.
+                self.num_parked.fetch_sub(1, SeqCst);
+                task.lock().unwrap().notify();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Sets the buffer size, and asks the receiver to unpark as many senders
+    // as the buffer grew.
+    fn set_buffer(&self, buffer: usize) {
+        assert!(buffer < MAX_BUFFER, "requested buffer size too large");
+        assert!(self.rendezvous.is_none(), "can't change the capacity of a rendezvous channel");
+        let prev = self.buffer.swap(buffer, SeqCst);
+        if buffer > prev {
+            self.pending_unparks.fetch_add(buffer - prev, SeqCst);
+            self.recv_task.wake();
+        }
     }
 
     // Clear `open` flag in the state, keep `num_messages` intact.
     fn set_closed(&self) {
-        let curr = self.state.load(SeqCst);
-        if !decode_state(curr).is_open {
+        // Only the call which actually closes the channel reports it.
+        let prev = self.state.fetch_and(!OPEN_MASK, SeqCst);
+        if !decode_state(prev).is_open {
             return;
         }
 
-        self.state.fetch_and(!OPEN_MASK, SeqCst);
+        self.probe.closed();
+        wake_tasks(&self.closed_tasks);
+    }
+
+    // Record the reason for closing the channel, unless it's already closed.
+    fn set_close_reason(&self, reason: CloseReason) {
+        if decode_state(self.state.load(SeqCst)).is_open {
+            self.close_reason.set(reason);
+        }
+    }
+
+    // Register a task to be woken once the channel is closed, returning
+    // whether it's already closed.
+    fn register_closed(&self, cx: &mut Context<'_>) -> bool {
+        register_closed_task(&self.state, &self.closed_tasks, cx)
+    }
+}
+
+fn register_closed_task(
+    state: &AtomicUsize,
+    closed_tasks: &Mutex<Vec<Waker>>,
+    cx: &mut Context<'_>,
+) -> bool {
+    if !decode_state(state.load(SeqCst)).is_open {
+        return true;
+    }
+    register_task(closed_tasks, cx);
+    // Check again, in case the channel was closed before registering.
+    !decode_state(state.load(SeqCst)).is_open
+}
+
+// Register a task to be woken by `wake_tasks`, unless it already is.
+fn register_task(tasks: &Mutex<Vec<Waker>>, cx: &mut Context<'_>) {
+    let mut tasks = tasks.lock().unwrap();
+    if !tasks.iter().any(|task| task.will_wake(cx.waker())) {
+        tasks.push(cx.waker().clone());
+    }
+}
+
+fn wake_tasks(tasks: &Mutex<Vec<Waker>>) {
+    let tasks = core::mem::take(&mut *tasks.lock().unwrap());
+    for task in tasks {
+        task.wake();
     }
 }
 
@@ -0,0 +1,63 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::ready;
+use futures_core::task::{Context, Poll};
+
+use super::{Receiver, UnboundedReceiver};
+
+/// Future for the [`Receiver::peek`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Peek<'a, T> {
+    receiver: Option<&'a mut Receiver<T>>,
+}
+
+impl<'a, T> Peek<'a, T> {
+    pub(super) fn new(receiver: &'a mut Receiver<T>) -> Self {
+        Self { receiver: Some(receiver) }
+    }
+}
+
+impl<T> fmt::Debug for Peek<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Peek").field("receiver", &self.receiver).finish()
+    }
+}
+
+impl<'a, T> Future for Peek<'a, T> {
+    type Output = Option<&'a T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<&'a T>> {
+        let receiver = self.receiver.as_mut().expect("`Peek` polled after completion");
+        ready!(receiver.poll_fill_peeked(cx));
+        Poll::Ready(self.receiver.take().unwrap().peeked.as_ref())
+    }
+}
+
+/// Future for the [`UnboundedReceiver::peek`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UnboundedPeek<'a, T> {
+    receiver: Option<&'a mut UnboundedReceiver<T>>,
+}
+
+impl<'a, T> UnboundedPeek<'a, T> {
+    pub(super) fn new(receiver: &'a mut UnboundedReceiver<T>) -> Self {
+        Self { receiver: Some(receiver) }
+    }
+}
+
+impl<T> fmt::Debug for UnboundedPeek<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedPeek").field("receiver", &self.receiver).finish()
+    }
+}
+
+impl<'a, T> Future for UnboundedPeek<'a, T> {
+    type Output = Option<&'a T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<&'a T>> {
+        let receiver = self.receiver.as_mut().expect("`UnboundedPeek` polled after completion");
+        ready!(receiver.poll_fill_peeked(cx));
+        Poll::Ready(self.receiver.take().unwrap().peeked.as_ref())
+    }
+}
@@ -1,52 +1,27 @@
-/* Copyright (c) 2010-2011 Dmitry Vyukov. All rights reserved.
- * Redistribution and use in source and binary forms, with or without
- * modification, are permitted provided that the following conditions are met:
- *
- *    1. Redistributions of source code must retain the above copyright notice,
- *       this list of conditions and the following disclaimer.
- *
- *    2. Redistributions in binary form must reproduce the above copyright
- *       notice, this list of conditions and the following disclaimer in the
- *       documentation and/or other materials provided with the distribution.
- *
- * THIS SOFTWARE IS PROVIDED BY DMITRY VYUKOV "AS IS" AND ANY EXPRESS OR IMPLIED
- * WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF
- * MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT
- * SHALL DMITRY VYUKOV OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT,
- * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
- * LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
- * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
- * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE
- * OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF
- * ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
- *
- * The views and conclusions contained in the software and documentation are
- * those of the authors and should not be interpreted as representing official
- * policies, either expressed or implied, of Dmitry Vyukov.
- */
-
 //! A mostly lock-free multi-producer, single consumer queue for sending
 //! messages between asynchronous tasks.
 //!
-//! The queue implementation is essentially the same one used for mpsc channels
-//! in the standard library.
+//! The queue is a linked list of blocks, each holding `BLOCK_CAP` slots.
+//! Pushers claim a slot by incrementing a shared tail position, write their
+//! message into it and mark it as ready, so that a block is only allocated
+//! once every `BLOCK_CAP` messages, and the pointer to the tail block is only
+//! updated once per block. The popper reads the slots in order, and frees
+//! each block once it has read all of its slots and no pusher can still
+//! access it.
 //!
 //! Note that the current implementation of this queue has a caveat of the `pop`
 //! method, and see the method for more information about it. Due to this
 //! caveat, this queue may not be appropriate for all use-cases.
 
-// http://www.1024cores.net/home/lock-free-algorithms
-//                         /queues/non-intrusive-mpsc-node-based-queue
-
-// NOTE: this implementation is lifted from the standard library and only
-//       slightly modified
-
 pub(super) use self::PopResult::*;
 
-use std::cell::UnsafeCell;
-use std::ptr;
-use std::sync::atomic::{AtomicPtr, Ordering};
-use std::thread;
+use alloc::boxed::Box;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::loom::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release, SeqCst};
+use crate::loom::atomic::{AtomicBool, AtomicPtr, AtomicUsize};
+use crate::loom::{thread, UnsafeCell};
 
 /// A result of the `pop` function.
 pub(super) enum PopResult<T> {
@@ -61,43 +36,151 @@ pub(super) enum PopResult<T> {
     Inconsistent,
 }
 
-struct Node<T> {
+// The number of slots of a block. The ready bits of a block are stored in a
+// `usize`, so this must not exceed 32.
+const BLOCK_CAP: usize = 32;
+
+// The ready bits of a block whose slots were all written.
+const ALL_READY: usize = u32::MAX as usize;
+
+struct Block<T> {
+    // The position of the first slot of the block in the queue.
+    start_index: usize,
+
     next: AtomicPtr<Self>,
-    value: Option<T>,
+
+    // Bit `i` is set once the message of slot `i` is written.
+    ready_slots: AtomicUsize,
+
+    // Set once the tail block pointer moved past this block, after
+    // `observed_tail` is stored. Every pusher which may still access the
+    // block claimed a position before `observed_tail`.
+    released: AtomicBool,
+    observed_tail: AtomicUsize,
+
+    values: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+impl<T> Block<T> {
+    fn new(start_index: usize) -> *mut Self {
+        Box::into_raw(Box::new(Self {
+            start_index,
+            next: AtomicPtr::new(ptr::null_mut()),
+            ready_slots: AtomicUsize::new(0),
+            released: AtomicBool::new(false),
+            observed_tail: AtomicUsize::new(0),
+            values: (0..BLOCK_CAP).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+        }))
+    }
+
+    /// Returns the block following this one, allocating it if needed.
+    fn next_or_grow(&self) -> *mut Self {
+        let next = self.next.load(Acquire);
+        if !next.is_null() {
+            return next;
+        }
+        let new = Self::new(self.start_index.wrapping_add(BLOCK_CAP));
+        match self.next.compare_exchange(ptr::null_mut(), new, AcqRel, Acquire) {
+            Ok(_) => new,
+            Err(next) => {
+                drop(unsafe { Box::from_raw(new) });
+                next
+            }
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        self.ready_slots.load(Acquire) == ALL_READY
+    }
+
+    fn is_ready(&self, slot: usize) -> bool {
+        self.ready_slots.load(Acquire) & (1 << slot) != 0
+    }
+}
+
+// The state of the popper.
+struct Head<T> {
+    // The block holding the next message.
+    block: *mut Block<T>,
+
+    // The oldest block not freed yet.
+    free_head: *mut Block<T>,
+
+    // The position of the next message.
+    index: usize,
 }
 
 /// The multi-producer single-consumer structure. This is not cloneable, but it
 /// may be safely shared so long as it is guaranteed that there is only one
 /// popper at a time (many pushers are allowed).
 pub(super) struct Queue<T> {
-    head: AtomicPtr<Node<T>>,
-    tail: UnsafeCell<*mut Node<T>>,
+    // The position of the next slot to claim.
+    tail_position: AtomicUsize,
+
+    // The block from which pushers look for their slot. It's only moved past
+    // a block once all of its slots are written.
+    tail_block: AtomicPtr<Block<T>>,
+
+    head: UnsafeCell<Head<T>>,
 }
 
 unsafe impl<T: Send> Send for Queue<T> {}
 unsafe impl<T: Send> Sync for Queue<T> {}
 
-impl<T> Node<T> {
-    unsafe fn new(v: Option<T>) -> *mut Self {
-        Box::into_raw(Box::new(Self { next: AtomicPtr::new(ptr::null_mut()), value: v }))
-    }
-}
-
 impl<T> Queue<T> {
     /// Creates a new queue that is safe to share among multiple producers and
     /// one consumer.
     pub(super) fn new() -> Self {
-        let stub = unsafe { Node::new(None) };
-        Self { head: AtomicPtr::new(stub), tail: UnsafeCell::new(stub) }
+        let block = Block::new(0);
+        Self {
+            tail_position: AtomicUsize::new(0),
+            tail_block: AtomicPtr::new(block),
+            head: UnsafeCell::new(Head { block, free_head: block, index: 0 }),
+        }
     }
 
     /// Pushes a new value onto this queue.
     pub(super) fn push(&self, t: T) {
+        let index = self.tail_position.fetch_add(1, SeqCst);
         unsafe {
-            let n = Node::new(Some(t));
-            let prev = self.head.swap(n, Ordering::AcqRel);
-            (*prev).next.store(n, Ordering::Release);
+            let block = &*self.find_block(index);
+            let slot = index.wrapping_sub(block.start_index);
+            block.values[slot].with_mut(|value| ptr::write(value, MaybeUninit::new(t)));
+            block.ready_slots.fetch_or(1 << slot, Release);
+        }
+    }
+
+    // Returns the block holding the slot at `index`, moving the tail block
+    // pointer past the blocks whose slots are all written on the way.
+    //
+    // The blocks reachable from the tail block pointer can't be freed before
+    // the message at `index` is popped, as the pointer is loaded after
+    // `index` is claimed.
+    unsafe fn find_block(&self, index: usize) -> *mut Block<T> {
+        let mut block = self.tail_block.load(SeqCst);
+        let mut try_advance = true;
+        while index.wrapping_sub((*block).start_index) >= BLOCK_CAP {
+            let next = (*block).next_or_grow();
+            try_advance = try_advance && self.try_advance_tail(block, next);
+            block = next;
         }
+        block
+    }
+
+    // Moves the tail block pointer from `block`, whose slots must all be
+    // written, to `next`, and releases `block`. Returns whether the pointer
+    // was moved.
+    unsafe fn try_advance_tail(&self, block: *mut Block<T>, next: *mut Block<T>) -> bool {
+        if !(*block).is_final()
+            || self.tail_block.compare_exchange(block, next, SeqCst, SeqCst).is_err()
+        {
+            return false;
+        }
+        // The pushers which loaded `block` from the tail block pointer all
+        // claimed a position before this one.
+        (*block).observed_tail.store(self.tail_position.load(SeqCst), Relaxed);
+        (*block).released.store(true, Release);
+        true
     }
 
     /// Pops some data from this queue.
@@ -106,32 +189,88 @@ impl<T> Queue<T> {
     /// return `Option<T>`. It is possible for this queue to be in an
     /// inconsistent state where many pushes have succeeded and completely
     /// finished, but pops cannot return `Some(t)`. This inconsistent state
-    /// happens when a pusher is preempted at an inopportune moment.
+    /// happens when a pusher is preempted between claiming its slot and
+    /// writing its message.
     ///
     /// This inconsistent state means that this queue does indeed have data, but
     /// it does not currently have access to it at this time.
     ///
     /// This function is unsafe because only one thread can call it at a time.
     pub(super) unsafe fn pop(&self) -> PopResult<T> {
-        let tail = *self.tail.get();
-        let next = (*tail).next.load(Ordering::Acquire);
+        self.head.with_mut(|head| {
+            let head = &mut *head;
+            self.reclaim_blocks(head);
 
-        if !next.is_null() {
-            *self.tail.get() = next;
-            assert!((*tail).value.is_none());
-            assert!((*next).value.is_some());
-            let ret = (*next).value.take().unwrap();
-            drop(Box::from_raw(tail));
-            return Data(ret);
-        }
+            let mut slot = head.index.wrapping_sub((*head.block).start_index);
+            if slot == BLOCK_CAP {
+                let next = (*head.block).next.load(Acquire);
+                if next.is_null() {
+                    return self.empty_or_inconsistent(head.index);
+                }
+                head.block = next;
+                slot = 0;
+            }
+
+            let block = &*head.block;
+            if !block.is_ready(slot) {
+                return self.empty_or_inconsistent(head.index);
+            }
+            let value = block.values[slot].with(|value| ptr::read(value).assume_init());
+            head.index = head.index.wrapping_add(1);
+            Data(value)
+        })
+    }
 
-        if self.head.load(Ordering::Acquire) == tail {
+    fn empty_or_inconsistent(&self, index: usize) -> PopResult<T> {
+        if self.tail_position.load(SeqCst) == index {
             Empty
         } else {
             Inconsistent
         }
     }
 
+    /// Frees the blocks whose messages were all popped, without waiting for
+    /// the next pop.
+    ///
+    /// This function is unsafe because only one thread can call it at a time.
+    pub(super) unsafe fn shrink(&self) {
+        self.head.with_mut(|head| {
+            let head = &mut *head;
+            let slot = head.index.wrapping_sub((*head.block).start_index);
+            if slot == BLOCK_CAP {
+                let next = (*head.block).next.load(Acquire);
+                if !next.is_null() {
+                    head.block = next;
+                }
+            }
+            self.reclaim_blocks(head);
+        })
+    }
+
+    // Frees the blocks before the current one which no pusher can access
+    // anymore, that is whose observed tail position was popped.
+    unsafe fn reclaim_blocks(&self, head: &mut Head<T>) {
+        while head.free_head != head.block {
+            let block = &*head.free_head;
+            // Pushers only move the tail block pointer when they look for a
+            // slot past it, so the blocks filled by the end of a burst of
+            // messages wouldn't be released before the next burst: move it
+            // for them.
+            if !block.released.load(Acquire)
+                && !self.try_advance_tail(head.free_head, block.next.load(Acquire))
+            {
+                return;
+            }
+            let observed_tail = block.observed_tail.load(Relaxed);
+            if head.index.wrapping_sub(observed_tail) > usize::MAX / 2 {
+                return;
+            }
+            let next = block.next.load(Acquire);
+            drop(Box::from_raw(head.free_head));
+            head.free_head = next;
+        }
+    }
+
     /// Pop an element similarly to `pop` function, but spin-wait on inconsistent
     /// queue state instead of returning `Inconsistent`.
     ///
@@ -162,13 +301,29 @@ impl<T> Queue<T> {
 
 impl<T> Drop for Queue<T> {
     fn drop(&mut self) {
-        unsafe {
-            let mut cur = *self.tail.get();
+        self.head.with_mut(|head| unsafe {
+            let head = &*head;
+            // The messages of the blocks before the current one were all
+            // popped, and those of the blocks after it weren't.
+            let mut before_head = true;
+            let mut cur = head.free_head;
             while !cur.is_null() {
-                let next = (*cur).next.load(Ordering::Relaxed);
-                drop(Box::from_raw(cur));
-                cur = next;
+                let block = Box::from_raw(cur);
+                let popped = if !before_head {
+                    0
+                } else if cur == head.block {
+                    before_head = false;
+                    head.index.wrapping_sub(block.start_index)
+                } else {
+                    BLOCK_CAP
+                };
+                for slot in popped..BLOCK_CAP {
+                    if block.is_ready(slot) {
+                        block.values[slot].with_mut(|value| ptr::drop_in_place(value as *mut T));
+                    }
+                }
+                cur = block.next.load(Relaxed);
             }
-        }
+        })
     }
 }
@@ -0,0 +1,292 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::__internal::AtomicWaker;
+use futures_core::task::{Context, Poll, Waker};
+
+use crate::loom::atomic::AtomicBool;
+use crate::loom::atomic::Ordering::SeqCst;
+use crate::loom::{Arc, Mutex};
+
+/// A set of receivers, yielding the messages of all of them along with the
+/// index of the receiver they come from.
+///
+/// Unlike selecting over the receivers with `select_all`, each receiver is
+/// polled with its own waker, registered once, so that only the receivers
+/// which received a message are polled again. Receivers with messages ready
+/// are polled in turn, one message at a time, so that a busy receiver can't
+/// starve the others.
+///
+/// The set is usually made of [`Receiver`](super::Receiver)s or
+/// [`UnboundedReceiver`](super::UnboundedReceiver)s, but it can hold any
+/// [`Stream`] which is [`Unpin`]. A receiver is removed from the set once it
+/// terminates, and the set terminates once it is empty.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpsc::{self, ReceiverSet};
+/// use futures::executor::block_on;
+/// use futures::stream::StreamExt;
+///
+/// let (tx1, rx1) = mpsc::unbounded();
+/// let (tx2, rx2) = mpsc::unbounded();
+///
+/// let mut set = ReceiverSet::new();
+/// let i1 = set.insert(rx1);
+/// let i2 = set.insert(rx2);
+///
+/// tx2.unbounded_send("b").unwrap();
+/// tx1.unbounded_send("a").unwrap();
+/// drop((tx1, tx2));
+///
+/// let mut messages = block_on(set.collect::<Vec<_>>());
+/// messages.sort();
+/// assert_eq!(messages, [(i1, "a"), (i2, "b")]);
+/// ```
+pub struct ReceiverSet<R> {
+    entries: Vec<Option<Entry<R>>>,
+
+    // Indices of the empty entries, to be reused.
+    free: Vec<usize>,
+
+    len: usize,
+    shared: Arc<Shared>,
+    is_terminated: bool,
+}
+
+struct Entry<R> {
+    receiver: R,
+    task: Arc<Task>,
+    waker: Waker,
+}
+
+struct Shared {
+    // Indices of the receivers to poll, in the order they were woken.
+    ready: Mutex<VecDeque<usize>>,
+
+    // The task polling the set.
+    waker: AtomicWaker,
+}
+
+// The state behind the waker of a receiver of the set.
+struct Task {
+    index: usize,
+
+    // Whether the index is in the ready queue, to only push it once.
+    queued: AtomicBool,
+
+    shared: Arc<Shared>,
+}
+
+impl Task {
+    // Pushes the index onto the ready queue, returning whether it wasn't
+    // already in it.
+    fn push(&self) -> bool {
+        if self.queued.swap(true, SeqCst) {
+            return false;
+        }
+        self.shared.ready.lock().unwrap_or_else(|e| e.into_inner()).push_back(self.index);
+        true
+    }
+
+    fn enqueue(&self) {
+        if self.push() {
+            self.shared.waker.wake();
+        }
+    }
+}
+
+impl<R> ReceiverSet<R> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            shared: Arc::new(Shared {
+                ready: Mutex::new(VecDeque::new()),
+                waker: AtomicWaker::new(),
+            }),
+            is_terminated: false,
+        }
+    }
+
+    /// Adds a receiver to the set, returning the index identifying it in the
+    /// messages yielded by the set.
+    ///
+    /// The index of a receiver removed from the set may be reused for a
+    /// receiver inserted later.
+    pub fn insert(&mut self, receiver: R) -> usize {
+        let index = self.free.pop().unwrap_or(self.entries.len());
+        let task =
+            Arc::new(Task { index, queued: AtomicBool::new(false), shared: self.shared.clone() });
+        // The receiver is polled for the first time on the next poll of the
+        // set.
+        task.enqueue();
+        let entry = Entry { receiver, waker: task_waker::new(task.clone()), task };
+        if index == self.entries.len() {
+            self.entries.push(Some(entry));
+        } else {
+            self.entries[index] = Some(entry);
+        }
+        self.len += 1;
+        self.is_terminated = false;
+        index
+    }
+
+    /// Removes the receiver with the given index from the set, returning it
+    /// if it was in the set.
+    pub fn remove(&mut self, index: usize) -> Option<R> {
+        let entry = self.entries.get_mut(index)?.take()?;
+        self.free.push(index);
+        self.len -= 1;
+        Some(entry.receiver)
+    }
+
+    /// Returns whether the set holds a receiver with the given index.
+    pub fn contains(&self, index: usize) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// Returns a reference to the receiver with the given index, if it's in
+    /// the set.
+    pub fn get(&self, index: usize) -> Option<&R> {
+        self.entries.get(index)?.as_ref().map(|entry| &entry.receiver)
+    }
+
+    /// Returns a mutable reference to the receiver with the given index, if
+    /// it's in the set.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut R> {
+        self.entries.get_mut(index)?.as_mut().map(|entry| &mut entry.receiver)
+    }
+
+    /// Returns the number of receivers in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the set holds no receivers.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn pop_ready(&self) -> Option<usize> {
+        self.shared.ready.lock().unwrap_or_else(|e| e.into_inner()).pop_front()
+    }
+}
+
+// The receivers are never pinned, as they must be `Unpin` to be polled.
+impl<R> Unpin for ReceiverSet<R> {}
+
+impl<R> Default for ReceiverSet<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R: Stream + Unpin> Stream for ReceiverSet<R> {
+    type Item = (usize, R::Item);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        this.shared.waker.register(cx.waker());
+
+        // Receivers which are woken while being polled are pushed back onto
+        // the ready queue, so poll as many receivers as the set holds before
+        // yielding to the executor, instead of looping forever.
+        for _ in 0..this.len {
+            let index = match this.pop_ready() {
+                Some(index) => index,
+                None => break,
+            };
+            let entry = match this.entries.get_mut(index) {
+                Some(Some(entry)) => entry,
+                // The receiver was removed.
+                _ => continue,
+            };
+            entry.task.queued.store(false, SeqCst);
+
+            let mut entry_cx = Context::from_waker(&entry.waker);
+            match Pin::new(&mut entry.receiver).poll_next(&mut entry_cx) {
+                Poll::Ready(Some(msg)) => {
+                    // The receiver may hold more messages, so poll it again
+                    // after the other ready receivers.
+                    entry.task.push();
+                    return Poll::Ready(Some((index, msg)));
+                }
+                Poll::Ready(None) => {
+                    this.remove(index);
+                }
+                Poll::Pending => {}
+            }
+        }
+
+        if this.is_empty() {
+            this.is_terminated = true;
+            return Poll::Ready(None);
+        }
+        if !this.shared.ready.lock().unwrap_or_else(|e| e.into_inner()).is_empty() {
+            cx.waker().wake_by_ref();
+        }
+        Poll::Pending
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.is_empty() {
+            (0, Some(0))
+        } else {
+            (0, None)
+        }
+    }
+}
+
+impl<R: Stream + Unpin> FusedStream for ReceiverSet<R> {
+    fn is_terminated(&self) -> bool {
+        self.is_terminated
+    }
+}
+
+impl<R> fmt::Debug for ReceiverSet<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReceiverSet").field("len", &self.len).finish()
+    }
+}
+
+mod task_waker {
+    use crate::loom::Arc;
+    use core::mem::ManuallyDrop;
+    use core::task::{RawWaker, RawWakerVTable, Waker};
+
+    use super::Task;
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+    /// Creates a waker pushing the receiver of `task` onto the ready queue of
+    /// its set.
+    pub(super) fn new(task: Arc<Task>) -> Waker {
+        // Safety: the vtable functions uphold the `RawWaker` contract, for a
+        // pointer obtained from `Arc::<Task>::into_raw`.
+        unsafe { Waker::from_raw(RawWaker::new(Arc::into_raw(task) as *const (), &VTABLE)) }
+    }
+
+    unsafe fn clone(data: *const ()) -> RawWaker {
+        let task = ManuallyDrop::new(Arc::from_raw(data as *const Task));
+        let clone = Arc::clone(&task);
+        RawWaker::new(Arc::into_raw(clone) as *const (), &VTABLE)
+    }
+
+    unsafe fn wake(data: *const ()) {
+        Arc::from_raw(data as *const Task).enqueue();
+    }
+
+    unsafe fn wake_by_ref(data: *const ()) {
+        (*(data as *const Task)).enqueue();
+    }
+
+    unsafe fn drop(data: *const ()) {
+        core::mem::drop(Arc::from_raw(data as *const Task));
+    }
+}
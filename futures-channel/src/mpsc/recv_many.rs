@@ -0,0 +1,69 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+
+use super::{Receiver, UnboundedReceiver};
+
+/// Future for the [`Receiver::recv_many`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecvMany<'a, T> {
+    receiver: &'a mut Receiver<T>,
+    buf: &'a mut Vec<T>,
+    limit: usize,
+}
+
+impl<'a, T> RecvMany<'a, T> {
+    pub(super) fn new(receiver: &'a mut Receiver<T>, buf: &'a mut Vec<T>, limit: usize) -> Self {
+        Self { receiver, buf, limit }
+    }
+}
+
+impl<T> fmt::Debug for RecvMany<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvMany").field("limit", &self.limit).finish()
+    }
+}
+
+impl<T> Future for RecvMany<'_, T> {
+    type Output = usize;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        let this = &mut *self;
+        this.receiver.poll_recv_many(cx, this.buf, this.limit)
+    }
+}
+
+/// Future for the [`UnboundedReceiver::recv_many`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UnboundedRecvMany<'a, T> {
+    receiver: &'a mut UnboundedReceiver<T>,
+    buf: &'a mut Vec<T>,
+    limit: usize,
+}
+
+impl<'a, T> UnboundedRecvMany<'a, T> {
+    pub(super) fn new(
+        receiver: &'a mut UnboundedReceiver<T>,
+        buf: &'a mut Vec<T>,
+        limit: usize,
+    ) -> Self {
+        Self { receiver, buf, limit }
+    }
+}
+
+impl<T> fmt::Debug for UnboundedRecvMany<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedRecvMany").field("limit", &self.limit).finish()
+    }
+}
+
+impl<T> Future for UnboundedRecvMany<'_, T> {
+    type Output = usize;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+        let this = &mut *self;
+        this.receiver.poll_recv_many(cx, this.buf, this.limit)
+    }
+}
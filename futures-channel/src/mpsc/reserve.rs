@@ -0,0 +1,67 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::ready;
+use futures_core::task::{Context, Poll};
+
+use super::{SendError, Sender};
+
+/// A slot reserved in a bounded channel, returned by [`Sender::reserve`] and
+/// [`Sender::try_reserve`].
+///
+/// The permit holds on to the sender, whose guaranteed slot it reserves.
+/// Dropping the permit without sending releases the slot.
+#[must_use = "a permit reserves a slot in the channel until it's dropped"]
+pub struct Permit<'a, T> {
+    sender: &'a mut Sender<T>,
+}
+
+impl<'a, T> Permit<'a, T> {
+    pub(super) fn new(sender: &'a mut Sender<T>) -> Self {
+        Self { sender }
+    }
+
+    /// Sends a message in the reserved slot.
+    ///
+    /// This never fails for lack of capacity. If the receiver was dropped or
+    /// closed since the slot was reserved, the message is dropped.
+    pub fn send(self, msg: T) {
+        if let Some(inner) = &mut self.sender.0 {
+            let _ = inner.do_send_b(msg);
+        }
+    }
+}
+
+impl<T> fmt::Debug for Permit<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Permit").field("sender", &self.sender).finish()
+    }
+}
+
+/// Future for the [`Sender::reserve`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Reserve<'a, T> {
+    sender: Option<&'a mut Sender<T>>,
+}
+
+impl<'a, T> Reserve<'a, T> {
+    pub(super) fn new(sender: &'a mut Sender<T>) -> Self {
+        Self { sender: Some(sender) }
+    }
+}
+
+impl<T> fmt::Debug for Reserve<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reserve").field("sender", &self.sender).finish()
+    }
+}
+
+impl<'a, T> Future for Reserve<'a, T> {
+    type Output = Result<Permit<'a, T>, SendError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let sender = self.sender.as_mut().expect("`Reserve` polled after completion");
+        ready!(sender.poll_ready(cx))?;
+        Poll::Ready(Ok(Permit::new(self.sender.take().unwrap())))
+    }
+}
@@ -0,0 +1,205 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
+use futures_task::Sleep;
+
+use super::{CloseReason, Receiver, SendError, Sender, TrySendError, UnboundedReceiver};
+
+/// The error type returned from [`send_timeout`](Sender::send_timeout).
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendTimeoutError<T> {
+    // `None` if the timeout elapsed.
+    err: Option<SendError>,
+    val: T,
+}
+
+/// The error type returned from [`recv_timeout`](Receiver::recv_timeout)
+/// when no message was received before the timeout elapsed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed {
+    _priv: (),
+}
+
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendTimeoutError").field("err", &self.err).finish()
+    }
+}
+
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_elapsed() {
+            write!(f, "send failed because the timeout elapsed")
+        } else {
+            write!(f, "send failed because receiver is gone")
+        }
+    }
+}
+
+impl<T: core::any::Any> std::error::Error for SendTimeoutError<T> {}
+
+impl<T> From<TrySendError<T>> for SendTimeoutError<T> {
+    fn from(err: TrySendError<T>) -> Self {
+        Self::new(Some(err.err), err.val)
+    }
+}
+
+impl<T> SendTimeoutError<T> {
+    fn new(err: Option<SendError>, val: T) -> Self {
+        Self { err, val }
+    }
+
+    /// Returns `true` if this error is a result of the timeout elapsing
+    /// before the channel had capacity for the message.
+    pub fn is_elapsed(&self) -> bool {
+        self.err.is_none()
+    }
+
+    /// Returns `true` if this error is a result of the receiver being dropped.
+    pub fn is_disconnected(&self) -> bool {
+        self.err.as_ref().map_or(false, SendError::is_disconnected)
+    }
+
+    /// Returns the reason the receiver gave for closing the channel, if any.
+    /// See [`SendError::reason`].
+    pub fn reason(&self) -> Option<&CloseReason> {
+        self.err.as_ref().and_then(SendError::reason)
+    }
+
+    /// Returns the message that was attempted to be sent but failed.
+    pub fn into_inner(self) -> T {
+        self.val
+    }
+}
+
+impl fmt::Debug for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Elapsed").finish()
+    }
+}
+
+impl Elapsed {
+    fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receive failed because the timeout elapsed")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+/// Future for the [`Sender::send_timeout`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendTimeout<'a, T> {
+    sender: &'a mut Sender<T>,
+    msg: Option<T>,
+    sleep: Sleep,
+}
+
+impl<'a, T> SendTimeout<'a, T> {
+    pub(super) fn new(sender: &'a mut Sender<T>, msg: T, sleep: Sleep) -> Self {
+        Self { sender, msg: Some(msg), sleep }
+    }
+}
+
+// The message is never pinned.
+impl<T> Unpin for SendTimeout<'_, T> {}
+
+impl<T> fmt::Debug for SendTimeout<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendTimeout").field("sender", &self.sender).finish()
+    }
+}
+
+impl<T> Future for SendTimeout<'_, T> {
+    type Output = Result<(), SendTimeoutError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let msg = this.msg.take().expect("`SendTimeout` polled after completion");
+
+        // The message is sent if the channel has capacity, even if the
+        // timeout elapsed meanwhile.
+        match this.sender.poll_ready(cx) {
+            Poll::Ready(Ok(())) => {
+                Poll::Ready(this.sender.try_send(msg).map_err(SendTimeoutError::from))
+            }
+            Poll::Ready(Err(err)) => Poll::Ready(Err(SendTimeoutError::new(Some(err), msg))),
+            Poll::Pending => match this.sleep.as_mut().poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Err(SendTimeoutError::new(None, msg))),
+                Poll::Pending => {
+                    this.msg = Some(msg);
+                    Poll::Pending
+                }
+            },
+        }
+    }
+}
+
+/// Future for the [`Receiver::recv_timeout`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecvTimeout<'a, T> {
+    receiver: &'a mut Receiver<T>,
+    sleep: Sleep,
+}
+
+impl<'a, T> RecvTimeout<'a, T> {
+    pub(super) fn new(receiver: &'a mut Receiver<T>, sleep: Sleep) -> Self {
+        Self { receiver, sleep }
+    }
+}
+
+impl<T> fmt::Debug for RecvTimeout<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvTimeout").field("receiver", &self.receiver).finish()
+    }
+}
+
+impl<T> Future for RecvTimeout<'_, T> {
+    type Output = Result<Option<T>, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        match Pin::new(&mut *this.receiver).poll_next(cx) {
+            Poll::Ready(msg) => Poll::Ready(Ok(msg)),
+            Poll::Pending => this.sleep.as_mut().poll(cx).map(|()| Err(Elapsed::new())),
+        }
+    }
+}
+
+/// Future for the [`UnboundedReceiver::recv_timeout`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct UnboundedRecvTimeout<'a, T> {
+    receiver: &'a mut UnboundedReceiver<T>,
+    sleep: Sleep,
+}
+
+impl<'a, T> UnboundedRecvTimeout<'a, T> {
+    pub(super) fn new(receiver: &'a mut UnboundedReceiver<T>, sleep: Sleep) -> Self {
+        Self { receiver, sleep }
+    }
+}
+
+impl<T> fmt::Debug for UnboundedRecvTimeout<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedRecvTimeout").field("receiver", &self.receiver).finish()
+    }
+}
+
+impl<T> Future for UnboundedRecvTimeout<'_, T> {
+    type Output = Result<Option<T>, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        match Pin::new(&mut *this.receiver).poll_next(cx) {
+            Poll::Ready(msg) => Poll::Ready(Ok(msg)),
+            Poll::Pending => this.sleep.as_mut().poll(cx).map(|()| Err(Elapsed::new())),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use core::fmt;
+
+use crate::loom::atomic::AtomicUsize;
+use crate::loom::atomic::Ordering::SeqCst;
+use crate::loom::{Arc, Mutex, Weak};
+
+use super::{
+    wake_tasks, BoundedInner, BoundedSenderInner, Sender, SenderTask, UnboundedInner,
+    UnboundedSender, UnboundedSenderInner, MAX_BUFFER,
+};
+
+/// A sender which doesn't keep its bounded mpsc channel open, created by
+/// [`Sender::downgrade`].
+///
+/// Weak senders aren't counted as senders of the channel: once every
+/// [`Sender`] is dropped, the channel is closed and the receiver terminates,
+/// even if weak senders remain. A weak sender must be upgraded back into a
+/// `Sender` to send messages.
+pub struct WeakSender<T> {
+    inner: Option<Weak<BoundedInner<T>>>,
+}
+
+/// A sender which doesn't keep its unbounded mpsc channel open, created by
+/// [`UnboundedSender::downgrade`].
+///
+/// See [`WeakSender`] for details.
+pub struct WeakUnboundedSender<T> {
+    inner: Option<Weak<UnboundedInner<T>>>,
+}
+
+/// Increments the number of senders of a channel, unless it's already zero.
+fn inc_num_senders(num_senders: &AtomicUsize, max: usize) -> bool {
+    let mut curr = num_senders.load(SeqCst);
+    loop {
+        // The last sender closed the channel when it was dropped.
+        if curr == 0 {
+            return false;
+        }
+        if curr == max {
+            panic!("cannot clone `Sender` -- too many outstanding senders");
+        }
+        match num_senders.compare_exchange(curr, curr + 1, SeqCst, SeqCst) {
+            Ok(_) => return true,
+            Err(actual) => curr = actual,
+        }
+    }
+}
+
+impl<T> WeakSender<T> {
+    pub(super) fn new(inner: Option<Weak<BoundedInner<T>>>) -> Self {
+        Self { inner }
+    }
+
+    /// Tries to upgrade this weak sender into a [`Sender`].
+    ///
+    /// Returns `None` if every `Sender` of the channel was dropped. The
+    /// returned sender may still find the channel closed, if the receiver was
+    /// dropped or closed.
+    pub fn upgrade(&self) -> Option<Sender<T>> {
+        let inner = self.inner.as_ref()?.upgrade()?;
+        if !inc_num_senders(&inner.num_senders, inner.max_senders()) {
+            return None;
+        }
+        wake_tasks(&inner.sender_tasks);
+        Some(Sender(Some(BoundedSenderInner {
+            inner,
+            sender_task: Arc::new(Mutex::new(SenderTask::new())),
+            maybe_parked: false,
+        })))
+    }
+}
+
+impl<T> WeakUnboundedSender<T> {
+    pub(super) fn new(inner: Option<Weak<UnboundedInner<T>>>) -> Self {
+        Self { inner }
+    }
+
+    /// Tries to upgrade this weak sender into an [`UnboundedSender`].
+    ///
+    /// Returns `None` if every `UnboundedSender` of the channel was dropped.
+    /// The returned sender may still find the channel closed, if the receiver
+    /// was dropped or closed.
+    pub fn upgrade(&self) -> Option<UnboundedSender<T>> {
+        let inner = self.inner.as_ref()?.upgrade()?;
+        if !inc_num_senders(&inner.num_senders, MAX_BUFFER) {
+            return None;
+        }
+        wake_tasks(&inner.sender_tasks);
+        Some(UnboundedSender(Some(UnboundedSenderInner { inner })))
+    }
+}
+
+impl<T> Clone for WeakSender<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Clone for WeakUnboundedSender<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T> fmt::Debug for WeakSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakSender").finish()
+    }
+}
+
+impl<T> fmt::Debug for WeakUnboundedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakUnboundedSender").finish()
+    }
+}
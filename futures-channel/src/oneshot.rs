@@ -2,15 +2,23 @@
 //!
 //! This is a single-producer, single-consumer channel.
 
-use alloc::sync::Arc;
+use core::any::Any;
 use core::fmt;
 use core::pin::Pin;
-use core::sync::atomic::AtomicBool;
-use core::sync::atomic::Ordering::SeqCst;
 use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+#[cfg(feature = "std")]
+use futures_core::task::__internal::coop;
 use futures_core::task::{Context, Poll, Waker};
 
+use crate::close_reason::ReasonSlot;
 use crate::lock::Lock;
+use crate::loom::atomic::AtomicBool;
+use crate::loom::atomic::Ordering::SeqCst;
+use crate::loom::Arc;
+use crate::probe::Probe;
+
+pub use crate::close_reason::CloseReason;
 
 /// A future for a value that will be provided by another asynchronous task.
 ///
@@ -18,6 +26,10 @@ use crate::lock::Lock;
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct Receiver<T> {
     inner: Arc<Inner<T>>,
+
+    // The value received by `poll_peek` or `try_peek`, to be returned by the
+    // next receive.
+    peeked: Option<T>,
 }
 
 /// A means of transmitting a single value to another task.
@@ -31,6 +43,10 @@ pub struct Sender<T> {
 impl<T> Unpin for Receiver<T> {}
 impl<T> Unpin for Sender<T> {}
 
+// The peeked value is only accessible through `&mut Receiver`, so sharing a
+// `&Receiver` doesn't share it.
+unsafe impl<T: Send> Sync for Receiver<T> {}
+
 /// Internal state of the `Receiver`/`Sender` pair above. This is all used as
 /// the internal synchronization between the two for send/recv operations.
 struct Inner<T> {
@@ -65,6 +81,11 @@ struct Inner<T> {
     /// Like `rx_task` above, except for the task blocked in
     /// `Sender::poll_canceled`. Additionally, `Lock` cannot be `UnsafeCell`.
     tx_task: Lock<Option<Waker>>,
+
+    /// The reason given by `Receiver::close_with`, set before `complete`.
+    close_reason: ReasonSlot,
+
+    probe: Probe,
 }
 
 /// Creates a new one-shot channel for sending a single value across asynchronous tasks.
@@ -103,7 +124,7 @@ struct Inner<T> {
 /// ```
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
     let inner = Arc::new(Inner::new());
-    let receiver = Receiver { inner: inner.clone() };
+    let receiver = Receiver { inner: inner.clone(), peeked: None };
     let sender = Sender { inner };
     (sender, receiver)
 }
@@ -115,6 +136,8 @@ impl<T> Inner<T> {
             data: Lock::new(None),
             rx_task: Lock::new(None),
             tx_task: Lock::new(None),
+            close_reason: ReasonSlot::new(),
+            probe: Probe::oneshot(),
         }
     }
 
@@ -144,6 +167,7 @@ impl<T> Inner<T> {
                     }
                 }
             }
+            self.probe.sent();
             Ok(())
         } else {
             // Must have been closed
@@ -210,7 +234,7 @@ impl<T> Inner<T> {
         // then it would not necessarily synchronize with `inner.complete`
         // and deadlock might be possible, as was observed in
         // https://github.com/rust-lang/futures-rs/pull/219.
-        self.complete.store(true, SeqCst);
+        self.set_complete();
 
         if let Some(mut slot) = self.rx_task.try_lock() {
             if let Some(task) = slot.take() {
@@ -226,10 +250,17 @@ impl<T> Inner<T> {
         }
     }
 
+    // Flag our completion, which closes the oneshot the first time.
+    fn set_complete(&self) {
+        if !self.complete.swap(true, SeqCst) {
+            self.probe.closed();
+        }
+    }
+
     fn close_rx(&self) {
         // Flag our completion and then attempt to wake up the sender if it's
         // blocked. See comments in `drop` below for more info
-        self.complete.store(true, SeqCst);
+        self.set_complete();
         if let Some(mut handle) = self.tx_task.try_lock() {
             if let Some(task) = handle.take() {
                 drop(handle);
@@ -244,6 +275,7 @@ impl<T> Inner<T> {
         if self.complete.load(SeqCst) {
             if let Some(mut slot) = self.data.try_lock() {
                 if let Some(data) = slot.take() {
+                    self.probe.received();
                     return Ok(Some(data));
                 }
             }
@@ -288,6 +320,7 @@ impl<T> Inner<T> {
             // will treat the send as a failure.
             if let Some(mut slot) = self.data.try_lock() {
                 if let Some(data) = slot.take() {
+                    self.probe.received();
                     return Poll::Ready(Ok(data));
                 }
             }
@@ -300,7 +333,7 @@ impl<T> Inner<T> {
     fn drop_rx(&self) {
         // Indicate to the `Sender` that we're done, so any future calls to
         // `poll_canceled` are weeded out.
-        self.complete.store(true, SeqCst);
+        self.set_complete();
 
         // If we've blocked a task then there's no need for it to stick around,
         // so we need to drop it. If this lock acquisition fails, though, then
@@ -377,6 +410,15 @@ impl<T> Sender<T> {
         self.inner.is_canceled()
     }
 
+    /// Returns the reason the [`Receiver`](Receiver) gave for closing the
+    /// channel, if it closed it with [`close_with`](Receiver::close_with).
+    ///
+    /// As [`send`](Sender::send) consumes the sender, check this before
+    /// sending to know why a send would fail.
+    pub fn close_reason(&self) -> Option<CloseReason> {
+        self.inner.close_reason.get()
+    }
+
     /// Tests to see whether this `Sender` is connected to the given `Receiver`. That is, whether
     /// they were created by the same call to `channel`.
     pub fn is_connected_to(&self, receiver: &Receiver<T>) -> bool {
@@ -439,6 +481,18 @@ impl<T> Receiver<T> {
         self.inner.close_rx()
     }
 
+    /// Gracefully close this receiver like [`close`](Receiver::close), giving
+    /// the sender a reason for it.
+    ///
+    /// The reason is returned by [`Sender::close_reason`]. It's ignored if the
+    /// channel is already complete.
+    pub fn close_with<R: Any + Send + Sync>(&mut self, reason: R) {
+        if !self.inner.complete.load(SeqCst) {
+            self.inner.close_reason.set(CloseReason::new(reason));
+        }
+        self.close()
+    }
+
     /// Attempts to receive a message outside of the context of a task.
     ///
     /// Does not schedule a task wakeup or have any other side effects.
@@ -448,20 +502,123 @@ impl<T> Receiver<T> {
     ///
     /// Returns an error if the sender was dropped.
     pub fn try_recv(&mut self) -> Result<Option<T>, Canceled> {
+        if let Some(t) = self.peeked.take() {
+            return Ok(Some(t));
+        }
         self.inner.try_recv()
     }
+
+    /// Attempts to receive a message without consuming it, outside of the
+    /// context of a task.
+    ///
+    /// Like [`try_recv`](Receiver::try_recv), but the message is left in the
+    /// receiver, to be returned by the next receive.
+    pub fn try_peek(&mut self) -> Result<Option<&T>, Canceled> {
+        if self.peeked.is_none() {
+            self.peeked = self.inner.try_recv()?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    /// Polls for a message without consuming it, registering the current task
+    /// to be woken when it's sent.
+    ///
+    /// Once this returns `Poll::Ready(Ok(_))`, the message is left in the
+    /// receiver, to be returned by the next receive, so that a state machine
+    /// can look at it before deciding to receive it. Returns an error if the
+    /// sender was dropped without sending a message.
+    pub fn poll_peek(&mut self, cx: &mut Context<'_>) -> Poll<Result<&T, Canceled>> {
+        if self.peeked.is_none() {
+            self.peeked = Some(ready!(self.inner.recv(cx))?);
+        }
+        Poll::Ready(Ok(self.peeked.as_ref().unwrap()))
+    }
+
+    /// Blocks the current thread until a message is sent, returning it.
+    ///
+    /// The thread is parked until the sender sends a message or is dropped,
+    /// without the need for an executor. This is meant for synchronous code
+    /// waiting for a result computed by an asynchronous task; calling it from
+    /// an asynchronous task blocks its executor.
+    ///
+    /// Returns an error if the sender was dropped without sending a message.
+    ///
+    /// This method is only available when the `std` feature of this library
+    /// is activated, and it is activated by default.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn blocking_recv(mut self) -> Result<T, Canceled> {
+        if let Some(t) = self.peeked.take() {
+            return Ok(t);
+        }
+        let waker = thread_waker::current();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(res) = self.inner.recv(&mut cx) {
+                return res;
+            }
+            std::thread::park();
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod thread_waker {
+    use core::task::{RawWaker, RawWakerVTable, Waker};
+    use std::mem::ManuallyDrop;
+    use std::sync::Arc;
+    use std::thread::{self, Thread};
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+    /// Creates a waker unparking the current thread.
+    pub(super) fn current() -> Waker {
+        let thread = Arc::new(thread::current());
+        // Safety: the vtable functions uphold the `RawWaker` contract, for a
+        // pointer obtained from `Arc::<Thread>::into_raw`.
+        unsafe { Waker::from_raw(RawWaker::new(Arc::into_raw(thread) as *const (), &VTABLE)) }
+    }
+
+    unsafe fn clone(data: *const ()) -> RawWaker {
+        let thread = ManuallyDrop::new(Arc::from_raw(data as *const Thread));
+        let clone = Arc::clone(&thread);
+        RawWaker::new(Arc::into_raw(clone) as *const (), &VTABLE)
+    }
+
+    unsafe fn wake(data: *const ()) {
+        Arc::from_raw(data as *const Thread).unpark();
+    }
+
+    unsafe fn wake_by_ref(data: *const ()) {
+        (*(data as *const Thread)).unpark();
+    }
+
+    unsafe fn drop(data: *const ()) {
+        core::mem::drop(Arc::from_raw(data as *const Thread));
+    }
 }
 
 impl<T> Future for Receiver<T> {
     type Output = Result<T, Canceled>;
 
-    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, Canceled>> {
-        self.inner.recv(cx)
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, Canceled>> {
+        if let Some(t) = self.peeked.take() {
+            return Poll::Ready(Ok(t));
+        }
+        #[cfg(feature = "std")]
+        let coop = ready!(coop::poll_proceed(cx));
+        let result = ready!(self.inner.recv(cx));
+        #[cfg(feature = "std")]
+        coop.made_progress();
+        Poll::Ready(result)
     }
 }
 
 impl<T> FusedFuture for Receiver<T> {
     fn is_terminated(&self) -> bool {
+        if self.peeked.is_some() {
+            return false;
+        }
         if self.inner.complete.load(SeqCst) {
             if let Some(slot) = self.inner.data.try_lock() {
                 if slot.is_some() {
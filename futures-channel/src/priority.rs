@@ -0,0 +1,325 @@
+//! An unbounded multi-producer, single-consumer channel delivering messages
+//! by priority.
+//!
+//! Each message is sent with a priority level, from `0` to `levels - 1`. The
+//! [`Receiver`] always receives the messages of the highest level available
+//! first, and the messages of a same level in the order they were sent. This
+//! lets urgent messages, like control messages, overtake the bulk of the
+//! messages sent on the same channel.
+//!
+//! As with [`mpsc`](crate::mpsc), the receiver terminates once every
+//! [`Sender`] is dropped and every message is received, and sending fails
+//! once the receiver is closed or dropped.
+
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::__internal::coop;
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Creates an unbounded priority channel with `levels` priority levels.
+///
+/// # Panics
+///
+/// Panics if `levels` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::priority;
+/// use futures::executor::block_on;
+/// use futures::stream::StreamExt;
+///
+/// let (tx, rx) = priority::unbounded(2);
+/// tx.send(0, "data").unwrap();
+/// tx.send(1, "control").unwrap();
+/// tx.send(0, "more data").unwrap();
+/// drop(tx);
+///
+/// assert_eq!(block_on(rx.collect::<Vec<_>>()), ["control", "data", "more data"]);
+/// ```
+pub fn unbounded<T>(levels: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(levels > 0, "priority channel must have at least one level");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queues: (0..levels).map(|_| VecDeque::new()).collect(),
+            len: 0,
+            num_senders: 1,
+            open: true,
+            recv_task: None,
+        }),
+    });
+    (Sender { shared: shared.clone() }, Receiver { shared })
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+struct State<T> {
+    // One FIFO queue per priority level, lowest level first.
+    queues: Vec<VecDeque<T>>,
+
+    // Total number of messages in `queues`.
+    len: usize,
+
+    num_senders: usize,
+
+    // `false` once the receiver is closed or dropped.
+    open: bool,
+
+    recv_task: Option<Waker>,
+}
+
+impl<T> State<T> {
+    fn pop(&mut self) -> Option<T> {
+        let msg = self.queues.iter_mut().rev().find_map(VecDeque::pop_front)?;
+        self.len -= 1;
+        Some(msg)
+    }
+
+    fn is_terminated(&self) -> bool {
+        self.len == 0 && (self.num_senders == 0 || !self.open)
+    }
+
+    fn wake_receiver(&mut self) {
+        if let Some(waker) = self.recv_task.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// The transmission end of a priority channel, created by [`unbounded`].
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a message with the given priority level, higher levels being
+    /// received first.
+    ///
+    /// Fails, returning the message, if the receiver was closed or dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` isn't lower than the number of levels of the
+    /// channel.
+    pub fn send(&self, level: usize, msg: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        assert!(
+            level < state.queues.len(),
+            "priority level {} out of range for a channel with {} levels",
+            level,
+            state.queues.len()
+        );
+        if !state.open {
+            return Err(SendError { msg });
+        }
+        state.queues[level].push_back(msg);
+        state.len += 1;
+        state.wake_receiver();
+        Ok(())
+    }
+
+    /// Returns the number of priority levels of the channel.
+    pub fn levels(&self) -> usize {
+        self.shared.lock().queues.len()
+    }
+
+    /// Returns whether the receiver was closed or dropped.
+    pub fn is_closed(&self) -> bool {
+        !self.shared.lock().open
+    }
+
+    /// Returns whether the senders send to the same receiver.
+    pub fn same_receiver(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().num_senders += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            state.wake_receiver();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("closed", &self.is_closed()).finish()
+    }
+}
+
+/// The receiving end of a priority channel, created by [`unbounded`].
+///
+/// The receiver implements [`Stream`], yielding the messages of the highest
+/// priority level first.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Closes the receiving half of the channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel
+    /// while still enabling the receiver to drain the messages that are
+    /// buffered.
+    pub fn close(&mut self) {
+        self.shared.lock().open = false;
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// This function returns:
+    /// * `Ok(Some(t))` when a message is fetched
+    /// * `Ok(None)` when the channel is closed and no messages are left
+    /// * `Err(e)` when there are no messages available, but the channel is
+    ///   not yet closed
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.pop() {
+            Some(msg) => Ok(Some(msg)),
+            None if state.is_terminated() => Ok(None),
+            None => Err(TryRecvError { _priv: () }),
+        }
+    }
+
+    /// Returns the number of messages buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().len
+    }
+
+    /// Returns whether no message is buffered in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages buffered in the channel with the given
+    /// priority level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` isn't lower than the number of levels of the
+    /// channel.
+    pub fn level_len(&self, level: usize) -> usize {
+        self.shared.lock().queues[level].len()
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let coop = ready!(coop::poll_proceed(cx));
+        let mut state = self.shared.lock();
+        let msg = match state.pop() {
+            Some(msg) => Some(msg),
+            None if state.is_terminated() => None,
+            None => {
+                match &state.recv_task {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    _ => state.recv_task = Some(cx.waker().clone()),
+                }
+                return Poll::Pending;
+            }
+        };
+        coop.made_progress();
+        Poll::Ready(msg)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let state = self.shared.lock();
+        if state.num_senders == 0 || !state.open {
+            (state.len, Some(state.len))
+        } else {
+            (state.len, None)
+        }
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.shared.lock().is_terminated()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.open = false;
+        // Drop the buffered messages now rather than with the last sender.
+        let queues = state.queues.iter_mut().map(std::mem::take).collect::<Vec<_>>();
+        state.len = 0;
+        drop(state);
+        drop(queues);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").field("len", &self.len()).finish()
+    }
+}
+
+/// The error returned by [`Sender::send`] when the receiver was closed or
+/// dropped, holding the message which couldn't be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T> {
+    msg: T,
+}
+
+impl<T> SendError<T> {
+    /// Returns the message which couldn't be sent.
+    pub fn into_inner(self) -> T {
+        self.msg
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "send failed because receiver is gone")
+    }
+}
+
+impl<T: core::any::Any> std::error::Error for SendError<T> {}
+
+/// The error returned by [`Receiver::try_next`] when no message is
+/// available.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TryRecvError {
+    _priv: (),
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiver channel is empty")
+    }
+}
+
+impl std::error::Error for TryRecvError {}
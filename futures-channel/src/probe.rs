@@ -0,0 +1,33 @@
+//! The instrumentation points of the channels, which compile to nothing
+//! unless the `tracing` feature is enabled.
+
+#[cfg(feature = "tracing")]
+pub(crate) use crate::instrument::Probe;
+
+#[cfg(not(feature = "tracing"))]
+#[derive(Debug)]
+pub(crate) struct Probe;
+
+#[cfg(not(feature = "tracing"))]
+impl Probe {
+    pub(crate) fn bounded() -> Self {
+        Self
+    }
+
+    pub(crate) fn unbounded() -> Self {
+        Self
+    }
+
+    pub(crate) fn oneshot() -> Self {
+        Self
+    }
+
+    #[inline]
+    pub(crate) fn sent(&self) {}
+
+    #[inline]
+    pub(crate) fn received(&self) {}
+
+    #[inline]
+    pub(crate) fn closed(&self) {}
+}
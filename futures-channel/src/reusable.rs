@@ -0,0 +1,253 @@
+//! A oneshot channel which can be reused for successive exchanges.
+//!
+//! Like a [`oneshot`](crate::oneshot) channel, a reusable channel holds at
+//! most one value at a time, but once the value is received, the channel is
+//! re-armed and the [`Sender`] can send a new one. Request/response loops
+//! can thus keep a single channel, and its allocation, instead of creating a
+//! oneshot channel per exchange.
+
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::task::{Context, Poll, Waker};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use crate::oneshot::Canceled;
+
+/// Creates a new reusable oneshot channel.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::reusable;
+/// use futures::executor::block_on;
+///
+/// let (mut tx, mut rx) = reusable::channel();
+/// for i in 0..3 {
+///     tx.send(i).unwrap();
+///     assert_eq!(block_on(rx.recv()), Ok(i));
+/// }
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            value: None,
+            tx_alive: true,
+            rx_alive: true,
+            rx_task: None,
+            tx_task: None,
+        }),
+    });
+    (Sender { inner: inner.clone() }, Receiver { inner })
+}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    // The value sent and not yet received.
+    value: Option<T>,
+
+    tx_alive: bool,
+    rx_alive: bool,
+
+    // The task waiting for a value.
+    rx_task: Option<Waker>,
+
+    // The task waiting for the channel to be re-armed or canceled.
+    tx_task: Option<Waker>,
+}
+
+impl<T> Inner<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn register(task: &mut Option<Waker>, cx: &mut Context<'_>) {
+    match task {
+        Some(waker) if waker.will_wake(cx.waker()) => {}
+        _ => *task = Some(cx.waker().clone()),
+    }
+}
+
+fn wake(task: &mut Option<Waker>) {
+    if let Some(waker) = task.take() {
+        waker.wake();
+    }
+}
+
+/// The sending half of a reusable oneshot channel, created by [`channel`].
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a value to the receiver.
+    ///
+    /// Fails, returning the value, if the previous value wasn't received
+    /// yet, or if the receiver was dropped. See
+    /// [`poll_ready`](Sender::poll_ready) to wait for the channel to be
+    /// re-armed.
+    pub fn send(&mut self, value: T) -> Result<(), T> {
+        let mut state = self.inner.lock();
+        if !state.rx_alive || state.value.is_some() {
+            return Err(value);
+        }
+        state.value = Some(value);
+        wake(&mut state.rx_task);
+        Ok(())
+    }
+
+    /// Polls whether a value can be sent, that is whether the previous value
+    /// was received, registering the current task to be woken when it is.
+    ///
+    /// Returns an error if the receiver was dropped.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Canceled>> {
+        let mut state = self.inner.lock();
+        if !state.rx_alive {
+            return Poll::Ready(Err(Canceled));
+        }
+        if state.value.is_none() {
+            return Poll::Ready(Ok(()));
+        }
+        register(&mut state.tx_task, cx);
+        Poll::Pending
+    }
+
+    /// Polls whether the receiver was dropped, registering the current task
+    /// to be woken when it is.
+    pub fn poll_canceled(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.inner.lock();
+        if !state.rx_alive {
+            return Poll::Ready(());
+        }
+        register(&mut state.tx_task, cx);
+        Poll::Pending
+    }
+
+    /// Returns whether the receiver was dropped.
+    pub fn is_canceled(&self) -> bool {
+        !self.inner.lock().rx_alive
+    }
+
+    /// Returns whether the sender sends to this receiver.
+    pub fn is_connected_to(&self, receiver: &Receiver<T>) -> bool {
+        Arc::ptr_eq(&self.inner, &receiver.inner)
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        state.tx_alive = false;
+        wake(&mut state.rx_task);
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("canceled", &self.is_canceled()).finish()
+    }
+}
+
+/// The receiving half of a reusable oneshot channel, created by [`channel`].
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Polls for the value sent, registering the current task to be woken
+    /// when one is sent.
+    ///
+    /// Receiving the value re-arms the channel, waking the sender if it
+    /// waits for it. Returns an error if the sender was dropped without
+    /// sending a value.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, Canceled>> {
+        let mut state = self.inner.lock();
+        if let Some(value) = state.value.take() {
+            wake(&mut state.tx_task);
+            return Poll::Ready(Ok(value));
+        }
+        if !state.tx_alive {
+            return Poll::Ready(Err(Canceled));
+        }
+        register(&mut state.rx_task, cx);
+        Poll::Pending
+    }
+
+    /// Receives the next value sent.
+    ///
+    /// See [`poll_recv`](Receiver::poll_recv).
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: Some(self) }
+    }
+
+    /// Attempts to receive a value without waiting.
+    ///
+    /// Returns `Ok(None)` if no value was sent yet, and an error if the
+    /// sender was dropped without sending a value.
+    pub fn try_recv(&mut self) -> Result<Option<T>, Canceled> {
+        let mut state = self.inner.lock();
+        match state.value.take() {
+            Some(value) => {
+                wake(&mut state.tx_task);
+                Ok(Some(value))
+            }
+            None if state.tx_alive => Ok(None),
+            None => Err(Canceled),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        state.rx_alive = false;
+        let value = state.value.take();
+        wake(&mut state.tx_task);
+        drop(state);
+        drop(value);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.lock();
+        f.debug_struct("Receiver")
+            .field("ready", &state.value.is_some())
+            .field("canceled", &!state.tx_alive)
+            .finish()
+    }
+}
+
+/// Future for the [`Receiver::recv`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Recv<'a, T> {
+    receiver: Option<&'a mut Receiver<T>>,
+}
+
+impl<T> Future for Recv<'_, T> {
+    type Output = Result<T, Canceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let receiver = self.receiver.as_mut().expect("`Recv` polled after completion");
+        let res = ready!(receiver.poll_recv(cx));
+        self.receiver = None;
+        Poll::Ready(res)
+    }
+}
+
+impl<T> FusedFuture for Recv<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_none()
+    }
+}
+
+impl<T> fmt::Debug for Recv<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recv").finish()
+    }
+}
@@ -0,0 +1,486 @@
+//! A single-producer, single-consumer queue for sending values between
+//! asynchronous tasks.
+//!
+//! Unlike with [`mpsc`](crate::mpsc), neither the [`Sender`] nor the
+//! [`Receiver`] can be cloned. As only one task sends and one task receives,
+//! the channel stores its messages in a ring buffer allocated once, indexed by
+//! two positions which are each written by a single side. Sending and
+//! receiving thus take no lock, and no read-modify-write operation unless the
+//! other side is waiting, making the channel cheaper than `mpsc` when there
+//! is a single producer.
+//!
+//! # Disconnection
+//!
+//! Once the `Sender` is dropped, the `Receiver` receives the remaining
+//! messages, then terminates. Sending fails once the `Receiver` is dropped.
+
+use alloc::boxed::Box;
+use core::fmt;
+use core::marker;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::pin::Pin;
+use core::ptr;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+#[cfg(feature = "std")]
+use futures_core::task::__internal::coop;
+use futures_core::task::__internal::AtomicWaker;
+use futures_core::task::{Context, Poll};
+
+use crate::loom::atomic::Ordering::SeqCst;
+use crate::loom::atomic::{AtomicBool, AtomicUsize};
+use crate::loom::{Arc, UnsafeCell};
+
+pub use crate::mpsc::{SendError, TryRecvError, TrySendError};
+
+/// Creates a bounded spsc channel, holding up to `buffer` messages.
+///
+/// # Panics
+///
+/// Panics if `buffer` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::spsc;
+/// use futures::executor::block_on;
+/// use futures::stream::StreamExt;
+///
+/// let (mut tx, rx) = spsc::channel(4);
+///
+/// block_on(tx.send(1)).unwrap();
+/// block_on(tx.send(2)).unwrap();
+/// drop(tx);
+/// assert_eq!(block_on(rx.collect::<Vec<_>>()), [1, 2]);
+/// ```
+pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer > 0, "spsc channel buffer must be greater than zero");
+    // The slots are allocated in a power of two, so that positions map to
+    // slots with a mask, and keep doing so when they wrap around.
+    let slots = buffer.checked_next_power_of_two().expect("requested buffer size too large");
+    let inner = Arc::new(Inner {
+        head: CachePadded(AtomicUsize::new(0)),
+        tail: CachePadded(AtomicUsize::new(0)),
+        buffer,
+        mask: slots - 1,
+        slots: (0..slots).map(|_| UnsafeCell::new(MaybeUninit::uninit())).collect(),
+        tx_dropped: AtomicBool::new(false),
+        rx_dropped: AtomicBool::new(false),
+        recv_task: AtomicWaker::new(),
+        recv_waiting: AtomicBool::new(false),
+        send_task: AtomicWaker::new(),
+        send_waiting: AtomicBool::new(false),
+    });
+    let tx = Sender { inner: inner.clone(), tail: 0, cached_head: 0 };
+    let rx = Receiver { inner: Some(inner), head: 0, cached_tail: 0 };
+    (tx, rx)
+}
+
+// Aligns a value to a cache line, so that the positions written by the two
+// sides don't share one.
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+struct Inner<T> {
+    // The position of the next message to receive. Only written by the
+    // receiver.
+    head: CachePadded<AtomicUsize>,
+
+    // The position of the next message to send. Only written by the sender.
+    tail: CachePadded<AtomicUsize>,
+
+    // The maximum number of messages in the channel.
+    buffer: usize,
+
+    // Maps a position to its slot.
+    mask: usize,
+
+    // The messages of the positions from `head` to `tail` are initialized.
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+
+    tx_dropped: AtomicBool,
+    rx_dropped: AtomicBool,
+
+    // Handle to the receiver's task, and whether it waits for a message.
+    // The sender only wakes the receiver when it does, so that a burst of
+    // messages wakes it only once.
+    recv_task: AtomicWaker,
+    recv_waiting: AtomicBool,
+
+    // Handle to the sender's task, and whether it waits for capacity.
+    send_task: AtomicWaker,
+    send_waiting: AtomicBool,
+}
+
+// The slots are only accessed by the side owning their position: the sender
+// writes the slots from `tail`, and the receiver reads the slots from `head`.
+unsafe impl<T: marker::Send> marker::Send for Inner<T> {}
+unsafe impl<T: marker::Send> Sync for Inner<T> {}
+
+impl<T> Inner<T> {
+    fn len(&self) -> usize {
+        self.tail.load(SeqCst).wrapping_sub(self.head.load(SeqCst))
+    }
+}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        let tail = self.tail.load(SeqCst);
+        let mut head = self.head.load(SeqCst);
+        while head != tail {
+            self.slots[head & self.mask]
+                .with_mut(|slot| unsafe { ptr::drop_in_place(slot as *mut T) });
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+// Wakes the task waiting on the other side, if it is.
+fn wake_waiting(task: &AtomicWaker, waiting: &AtomicBool) {
+    if waiting.load(SeqCst) && waiting.swap(false, SeqCst) {
+        task.wake();
+    }
+}
+
+/// The transmission end of an spsc channel.
+///
+/// This value is created by the [`channel`] function.
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+
+    // The position of the next message to send.
+    tail: usize,
+
+    // The position of the next message to receive, as last seen. The
+    // receiver's position is only loaded again once the channel seems full.
+    cached_head: usize,
+}
+
+impl<T> Sender<T> {
+    /// Attempts to send a message on this `Sender`, returning the message
+    /// if there was an error.
+    ///
+    /// Fails with an error for which [`TrySendError::is_full`] returns `true`
+    /// if the channel is full, and with one for which
+    /// [`TrySendError::is_disconnected`] returns `true` if the receiver was
+    /// dropped.
+    pub fn try_send(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        if self.inner.rx_dropped.load(SeqCst) {
+            return Err(TrySendError::new(SendError::disconnected(), msg));
+        }
+        if !self.has_room() {
+            return Err(TrySendError::new(SendError::full(), msg));
+        }
+        self.push(msg);
+        Ok(())
+    }
+
+    /// Send a message on the channel.
+    ///
+    /// This function should only be called after
+    /// [`poll_ready`](Sender::poll_ready) has reported that the channel is
+    /// ready to receive a message.
+    pub fn start_send(&mut self, msg: T) -> Result<(), SendError> {
+        self.try_send(msg).map_err(|e| e.into_send_error())
+    }
+
+    /// Polls the channel to determine if there is guaranteed capacity to send
+    /// at least one item without waiting, registering the current task to be
+    /// woken when there is.
+    ///
+    /// Returns an error if the receiver was dropped.
+    pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError>> {
+        if let Poll::Ready(res) = self.poll_ready_nb() {
+            return Poll::Ready(res);
+        }
+        self.inner.send_task.register(cx.waker());
+        self.inner.send_waiting.store(true, SeqCst);
+        // Check again, as the receiver may have made room before the task was
+        // registered.
+        self.poll_ready_nb()
+    }
+
+    /// Sends a message, waiting for capacity if the channel is full.
+    ///
+    /// The returned future fails, giving the message back, if the receiver
+    /// was dropped.
+    pub fn send(&mut self, msg: T) -> Send<'_, T> {
+        Send { sender: self, msg: Some(msg) }
+    }
+
+    /// Returns whether the receiver was dropped.
+    pub fn is_closed(&self) -> bool {
+        self.inner.rx_dropped.load(SeqCst)
+    }
+
+    /// Returns the number of messages in the channel.
+    ///
+    /// The value is approximate if the receiver receives concurrently.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether there is no message in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity of the channel, as given to [`channel`].
+    pub fn capacity(&self) -> usize {
+        self.inner.buffer
+    }
+
+    /// Returns whether the sender sends to this receiver.
+    pub fn is_connected_to(&self, receiver: &Receiver<T>) -> bool {
+        receiver.inner.as_ref().map_or(false, |inner| Arc::ptr_eq(&self.inner, inner))
+    }
+
+    fn poll_ready_nb(&mut self) -> Poll<Result<(), SendError>> {
+        if self.inner.rx_dropped.load(SeqCst) {
+            Poll::Ready(Err(SendError::disconnected()))
+        } else if self.has_room() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn has_room(&mut self) -> bool {
+        if self.tail.wrapping_sub(self.cached_head) < self.inner.buffer {
+            return true;
+        }
+        self.cached_head = self.inner.head.load(SeqCst);
+        self.tail.wrapping_sub(self.cached_head) < self.inner.buffer
+    }
+
+    // Callers must check that the channel has room for the message.
+    fn push(&mut self, msg: T) {
+        self.inner.slots[self.tail & self.inner.mask]
+            .with_mut(|slot| unsafe { ptr::write(slot, MaybeUninit::new(msg)) });
+        self.tail = self.tail.wrapping_add(1);
+        self.inner.tail.store(self.tail, SeqCst);
+        wake_waiting(&self.inner.recv_task, &self.inner.recv_waiting);
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.inner.tx_dropped.store(true, SeqCst);
+        self.inner.recv_task.wake();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("closed", &self.is_closed()).finish()
+    }
+}
+
+/// Future for the [`Sender::send`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Send<'a, T> {
+    sender: &'a mut Sender<T>,
+    msg: Option<T>,
+}
+
+// `Pin<&mut Send<'_, T>>` is never projected to `Pin<&mut T>`
+impl<T> Unpin for Send<'_, T> {}
+
+impl<T> Future for Send<'_, T> {
+    type Output = Result<(), TrySendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        assert!(this.msg.is_some(), "`Send` polled after completion");
+        let res = ready!(this.sender.poll_ready(cx));
+        let msg = this.msg.take().unwrap();
+        Poll::Ready(match res {
+            Ok(()) => {
+                this.sender.push(msg);
+                Ok(())
+            }
+            Err(err) => Err(TrySendError::new(err, msg)),
+        })
+    }
+}
+
+impl<T> FusedFuture for Send<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.msg.is_none()
+    }
+}
+
+impl<T> fmt::Debug for Send<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Send").field("sender", &self.sender).finish()
+    }
+}
+
+/// The receiving end of an spsc channel.
+///
+/// This value is created by the [`channel`] function.
+pub struct Receiver<T> {
+    inner: Option<Arc<Inner<T>>>,
+
+    // The position of the next message to receive.
+    head: usize,
+
+    // The position of the next message to send, as last seen. The sender's
+    // position is only loaded again once the channel seems empty.
+    cached_tail: usize,
+}
+
+// `Pin<&mut Receiver<T>>` is never projected to `Pin<&mut T>`
+impl<T> Unpin for Receiver<T> {}
+
+impl<T> Receiver<T> {
+    /// Tries to receive the next message without notifying a context if empty.
+    ///
+    /// This function returns:
+    /// * `Ok(Some(t))` when message is fetched
+    /// * `Ok(None)` when the sender was dropped and no messages are left
+    /// * `Err(e)` when there are no messages available, but the sender is
+    ///   still alive
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message() {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError::new()),
+        }
+    }
+
+    /// Returns the number of messages in the channel.
+    ///
+    /// The value is approximate if the sender sends concurrently.
+    pub fn len(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.len())
+    }
+
+    /// Returns whether there is no message in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity of the channel, as given to [`channel`].
+    ///
+    /// Returns 0 if the receiver is terminated.
+    pub fn capacity(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.buffer)
+    }
+
+    fn next_message(&mut self) -> Poll<Option<T>> {
+        let inner = match &self.inner {
+            None => return Poll::Ready(None),
+            Some(inner) => inner,
+        };
+        if self.head == self.cached_tail {
+            self.cached_tail = inner.tail.load(SeqCst);
+            if self.head == self.cached_tail {
+                if !inner.tx_dropped.load(SeqCst) {
+                    return Poll::Pending;
+                }
+                // The sender may have sent a last message before it was
+                // dropped.
+                self.cached_tail = inner.tail.load(SeqCst);
+                if self.head == self.cached_tail {
+                    self.inner = None;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+        let msg = inner.slots[self.head & inner.mask]
+            .with(|slot| unsafe { ptr::read(slot).assume_init() });
+        self.head = self.head.wrapping_add(1);
+        inner.head.store(self.head, SeqCst);
+        wake_waiting(&inner.send_task, &inner.send_waiting);
+        Poll::Ready(Some(msg))
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        #[cfg(feature = "std")]
+        let coop = ready!(coop::poll_proceed(cx));
+        let msg = match self.next_message() {
+            Poll::Ready(msg) => msg,
+            Poll::Pending => {
+                let inner = self.inner.as_ref().unwrap();
+                inner.recv_task.register(cx.waker());
+                inner.recv_waiting.store(true, SeqCst);
+                // Check again, as a message may have been sent before the
+                // task was registered.
+                ready!(self.next_message())
+            }
+        };
+        #[cfg(feature = "std")]
+        coop.made_progress();
+        Poll::Ready(msg)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            None => (0, Some(0)),
+            Some(inner) if inner.tx_dropped.load(SeqCst) => {
+                let len = inner.len();
+                (len, Some(len))
+            }
+            Some(inner) => (inner.len(), None),
+        }
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = &self.inner {
+            inner.rx_dropped.store(true, SeqCst);
+            inner.send_task.wake();
+            // Drop the messages sent so far right away. Those sent
+            // concurrently are dropped along with the channel.
+            while let Poll::Ready(Some(_)) = self.next_message() {}
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").field("len", &self.len()).finish()
+    }
+}
+
+#[cfg(feature = "sink")]
+impl<T> futures_sink::Sink<T> for Sender<T> {
+    type Error = SendError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        (*self).poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, msg: T) -> Result<(), Self::Error> {
+        (*self).start_send(msg)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
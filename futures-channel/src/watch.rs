@@ -0,0 +1,366 @@
+//! A single-producer, multi-consumer channel holding a single value, which
+//! receivers watch for changes.
+//!
+//! The [`Sender`] replaces the value of the channel, and the [`Receiver`]s
+//! are notified that it changed. Receivers only see the latest value: the
+//! values replaced before a receiver looked at them are never seen by it.
+//! This makes the channel suited to propagating configuration or state,
+//! where only the current value matters.
+//!
+//! # Disconnection
+//!
+//! Once the `Sender` is dropped, [`Receiver::changed`] fails, but the last
+//! value can still be borrowed. Sending fails once every `Receiver` is
+//! dropped.
+
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard};
+
+/// Creates a watch channel holding the value `init`.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::watch;
+/// use futures::executor::block_on;
+///
+/// let (tx, mut rx) = watch::channel("hello");
+/// assert_eq!(*rx.borrow(), "hello");
+///
+/// tx.send("world").unwrap();
+/// block_on(rx.changed()).unwrap();
+/// assert_eq!(*rx.borrow_and_update(), "world");
+/// ```
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        value: RwLock::new(init),
+        state: Mutex::new(State {
+            version: 0,
+            closed: false,
+            num_receivers: 0,
+            next_id: 0,
+            wakers: HashMap::new(),
+        }),
+    });
+    let rx = Receiver::new(shared.clone());
+    (Sender { shared }, rx)
+}
+
+struct Shared<T> {
+    value: RwLock<T>,
+    state: Mutex<State>,
+}
+
+struct State {
+    // Incremented each time the value is replaced.
+    version: u64,
+
+    // `true` once the sender is dropped.
+    closed: bool,
+
+    num_receivers: usize,
+    next_id: u64,
+
+    // Wakers of the receivers waiting for a change, by receiver id.
+    wakers: HashMap<u64, Waker>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn borrow(&self) -> Ref<'_, T> {
+        Ref { guard: self.value.read().unwrap_or_else(|e| e.into_inner()) }
+    }
+
+    fn replace(&self, value: T) -> T {
+        let old = {
+            let mut guard = self.value.write().unwrap_or_else(|e| e.into_inner());
+            std::mem::replace(&mut *guard, value)
+        };
+        let mut state = self.lock();
+        state.version += 1;
+        for (_, waker) in state.wakers.drain() {
+            waker.wake();
+        }
+        old
+    }
+}
+
+/// The sending side of a watch channel, created by [`channel`].
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Replaces the value of the channel, notifying the receivers.
+    ///
+    /// Fails, returning the value, if there are no receivers.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.is_closed() {
+            return Err(SendError { value });
+        }
+        self.shared.replace(value);
+        Ok(())
+    }
+
+    /// Replaces the value of the channel, notifying the receivers, and
+    /// returns the previous value.
+    ///
+    /// Unlike [`send`](Sender::send), this replaces the value even if there
+    /// are no receivers, for the receivers subscribed later.
+    pub fn send_replace(&self, value: T) -> T {
+        self.shared.replace(value)
+    }
+
+    /// Borrows the current value of the channel.
+    ///
+    /// The value is locked for reading while the returned reference is held,
+    /// so it shouldn't be held across a send.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.shared.borrow()
+    }
+
+    /// Creates a new receiver, for which the current value is already seen.
+    pub fn subscribe(&self) -> Receiver<T> {
+        Receiver::new(self.shared.clone())
+    }
+
+    /// Returns the number of receivers of the channel.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.lock().num_receivers
+    }
+
+    /// Returns whether every receiver of the channel was dropped.
+    pub fn is_closed(&self) -> bool {
+        self.receiver_count() == 0
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.closed = true;
+        for (_, waker) in state.wakers.drain() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("receivers", &self.receiver_count()).finish()
+    }
+}
+
+/// The receiving side of a watch channel, created by [`channel`] and
+/// [`Sender::subscribe`].
+///
+/// Receivers can be cloned, the clones having seen the same values as the
+/// original.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    id: u64,
+
+    // Version of the last value seen by this receiver.
+    seen: u64,
+}
+
+impl<T> Receiver<T> {
+    fn new(shared: Arc<Shared<T>>) -> Self {
+        let (id, seen) = {
+            let mut state = shared.lock();
+            state.num_receivers += 1;
+            let id = state.next_id;
+            state.next_id += 1;
+            (id, state.version)
+        };
+        Self { shared, id, seen }
+    }
+
+    /// Borrows the current value of the channel, without marking it as seen.
+    ///
+    /// The value is locked for reading while the returned reference is held,
+    /// so it shouldn't be held across an `.await`.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.shared.borrow()
+    }
+
+    /// Borrows the current value of the channel, and marks it as seen.
+    pub fn borrow_and_update(&mut self) -> Ref<'_, T> {
+        let value = self.shared.borrow();
+        // The value can't be replaced while it's borrowed.
+        self.seen = self.shared.lock().version;
+        value
+    }
+
+    /// Returns whether the value changed since it was last seen by this
+    /// receiver.
+    ///
+    /// Fails if the sender was dropped.
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        let state = self.shared.lock();
+        if state.closed {
+            return Err(RecvError { _priv: () });
+        }
+        Ok(state.version != self.seen)
+    }
+
+    /// Polls for a change of the value since it was last seen by this
+    /// receiver, registering the current task to be woken when it changes.
+    ///
+    /// Once this returns `Poll::Ready(Ok(()))`, the new value is marked as
+    /// seen. Fails if the sender was dropped without the value changing.
+    pub fn poll_changed(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), RecvError>> {
+        let mut state = self.shared.lock();
+        if state.version != self.seen {
+            self.seen = state.version;
+            return Poll::Ready(Ok(()));
+        }
+        if state.closed {
+            return Poll::Ready(Err(RecvError { _priv: () }));
+        }
+        match state.wakers.get_mut(&self.id) {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            Some(waker) => *waker = cx.waker().clone(),
+            None => {
+                state.wakers.insert(self.id, cx.waker().clone());
+            }
+        }
+        Poll::Pending
+    }
+
+    /// Waits for a change of the value since it was last seen by this
+    /// receiver, and marks the new value as seen.
+    ///
+    /// See [`poll_changed`](Receiver::poll_changed) for details.
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed { receiver: Some(self) }
+    }
+
+    /// Returns whether the receivers receive from the same channel.
+    pub fn same_channel(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        let mut receiver = Self::new(self.shared.clone());
+        receiver.seen = self.seen;
+        receiver
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_receivers -= 1;
+        state.wakers.remove(&self.id);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").field("has_changed", &self.has_changed()).finish()
+    }
+}
+
+/// A reference to the value of a watch channel, returned by
+/// [`Sender::borrow`], [`Receiver::borrow`] and
+/// [`Receiver::borrow_and_update`].
+pub struct Ref<'a, T> {
+    guard: RwLockReadGuard<'a, T>,
+}
+
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// Future for the [`Receiver::changed`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Changed<'a, T> {
+    receiver: Option<&'a mut Receiver<T>>,
+}
+
+impl<T> Future for Changed<'_, T> {
+    type Output = Result<(), RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let receiver = self.receiver.as_mut().expect("`Changed` polled after completion");
+        let res = ready!(receiver.poll_changed(cx));
+        self.receiver = None;
+        Poll::Ready(res)
+    }
+}
+
+impl<T> FusedFuture for Changed<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_none()
+    }
+}
+
+impl<T> fmt::Debug for Changed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Changed").finish()
+    }
+}
+
+/// The error returned by [`Sender::send`] when there are no receivers,
+/// holding the value which couldn't be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T> {
+    value: T,
+}
+
+impl<T> SendError<T> {
+    /// Returns the value which couldn't be sent.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError").finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "send failed because there are no receivers")
+    }
+}
+
+impl<T: core::any::Any> std::error::Error for SendError<T> {}
+
+/// The error returned by [`Receiver::changed`] when the sender was dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvError {
+    _priv: (),
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sender was dropped")
+    }
+}
+
+impl std::error::Error for RecvError {}
@@ -0,0 +1,449 @@
+//! A bounded multi-producer, single-consumer channel whose capacity is a
+//! total weight rather than a number of messages.
+//!
+//! The channel is created with a weigher, a function giving the weight of
+//! each message, for instance its size in bytes. A message is sent once the
+//! weights of the buffered messages and its own add up to at most the
+//! capacity of the channel, so that the memory held by the channel stays
+//! bounded even when messages range from a few bytes to megabytes. A message
+//! weighing more than the whole capacity is sent once the channel is empty.
+//!
+//! Senders waiting for capacity are served in the order they started
+//! waiting, so that a heavy message isn't starved by lighter messages sent
+//! after it.
+//!
+//! As with [`mpsc`](crate::mpsc), the receiver terminates once every
+//! [`Sender`] is dropped and every message is received, and sending fails
+//! once the receiver is closed or dropped.
+
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::__internal::coop;
+use futures_core::task::{Context, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use crate::mpsc::{SendError, TryRecvError, TrySendError};
+
+/// Creates a weighted channel holding messages weighing up to `capacity` in
+/// total, the weight of each message being given by `weigher`.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::weighted;
+/// use futures::executor::block_on;
+/// use futures::stream::StreamExt;
+///
+/// let (tx, mut rx) = weighted::channel(10, |msg: &String| msg.len());
+/// tx.try_send("hello".to_string()).unwrap();
+/// tx.try_send("world".to_string()).unwrap();
+/// assert!(tx.try_send("!".to_string()).unwrap_err().is_full());
+///
+/// assert_eq!(block_on(rx.next()).unwrap(), "hello");
+/// tx.try_send("!".to_string()).unwrap();
+/// ```
+pub fn channel<T, F>(capacity: usize, weigher: F) -> (Sender<T>, Receiver<T>)
+where
+    F: Fn(&T) -> usize + marker::Send + Sync + 'static,
+{
+    let shared = Arc::new(Shared {
+        weigher: Box::new(weigher),
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            weight: 0,
+            capacity,
+            num_senders: 1,
+            open: true,
+            recv_task: None,
+            next_id: 0,
+            send_waiters: VecDeque::new(),
+        }),
+    });
+    (Sender { shared: shared.clone() }, Receiver { shared })
+}
+
+type Weigher<T> = Box<dyn Fn(&T) -> usize + marker::Send + Sync>;
+
+struct Shared<T> {
+    weigher: Weigher<T>,
+    state: Mutex<State<T>>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+struct State<T> {
+    // The buffered messages, with their weights.
+    queue: VecDeque<(T, usize)>,
+
+    // Total weight of the messages in `queue`.
+    weight: usize,
+
+    capacity: usize,
+
+    num_senders: usize,
+
+    // `false` once the receiver is closed or dropped.
+    open: bool,
+
+    recv_task: Option<Waker>,
+
+    next_id: u64,
+
+    // Tasks waiting to send, by id, with the weight of their message, in the
+    // order they started waiting. A task keeps its place until it sends, so
+    // that the first one can't be overtaken.
+    send_waiters: VecDeque<Waiter>,
+}
+
+struct Waiter {
+    id: u64,
+    weight: usize,
+    waker: Waker,
+}
+
+impl<T> State<T> {
+    fn fits(&self, weight: usize) -> bool {
+        self.weight == 0 || self.weight.checked_add(weight).map_or(false, |w| w <= self.capacity)
+    }
+
+    // Whether the task `id`, or a task which isn't waiting, may send a
+    // message of the given weight now.
+    fn can_send(&self, id: Option<u64>, weight: usize) -> bool {
+        let first = self.send_waiters.front().map(|waiter| waiter.id);
+        (first.is_none() || first == id) && self.fits(weight)
+    }
+
+    fn push(&mut self, msg: T, weight: usize) {
+        self.queue.push_back((msg, weight));
+        self.weight += weight;
+        if let Some(waker) = self.recv_task.take() {
+            waker.wake();
+        }
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let (msg, weight) = self.queue.pop_front()?;
+        self.weight -= weight;
+        self.wake_first_sender();
+        Some(msg)
+    }
+
+    // Wakes the first waiting sender if its message fits.
+    fn wake_first_sender(&self) {
+        if let Some(waiter) = self.send_waiters.front() {
+            if self.fits(waiter.weight) {
+                waiter.waker.wake_by_ref();
+            }
+        }
+    }
+
+    // Unregisters the task `id`, returning whether it was the first.
+    fn remove_waiter(&mut self, id: u64) -> bool {
+        match self.send_waiters.iter().position(|waiter| waiter.id == id) {
+            Some(index) => {
+                self.send_waiters.remove(index);
+                index == 0
+            }
+            None => false,
+        }
+    }
+
+    // Unregisters the task `id`, waking the next one if it was the first.
+    fn unregister(&mut self, id: u64) {
+        if self.remove_waiter(id) {
+            self.wake_first_sender();
+        }
+    }
+
+    fn is_terminated(&self) -> bool {
+        self.queue.is_empty() && (self.num_senders == 0 || !self.open)
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        for waiter in &self.send_waiters {
+            waiter.waker.wake_by_ref();
+        }
+    }
+}
+
+/// The transmission end of a weighted channel, created by [`channel`].
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Attempts to send a message on this channel, returning it back if it
+    /// could not be sent.
+    ///
+    /// Fails with an error for which [`TrySendError::is_full`] returns `true`
+    /// if the message doesn't fit in the channel, or if other senders are
+    /// waiting for capacity, and with one for which
+    /// [`TrySendError::is_disconnected`] returns `true` if the receiver was
+    /// closed or dropped.
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        let weight = (self.shared.weigher)(&msg);
+        let mut state = self.shared.lock();
+        if !state.open {
+            return Err(TrySendError::new(SendError::disconnected(), msg));
+        }
+        if !state.can_send(None, weight) {
+            return Err(TrySendError::new(SendError::full(), msg));
+        }
+        state.push(msg, weight);
+        Ok(())
+    }
+
+    /// Sends a message on this channel, waiting for the channel to have
+    /// capacity for its weight.
+    ///
+    /// Senders waiting for capacity are served in the order they started
+    /// waiting. The future fails, returning the message, if the receiver was
+    /// closed or dropped.
+    pub fn send(&self, msg: T) -> Send<'_, T> {
+        let weight = (self.shared.weigher)(&msg);
+        Send { sender: self, msg: Some(msg), weight, id: None }
+    }
+
+    /// Returns whether the receiver was closed or dropped.
+    pub fn is_closed(&self) -> bool {
+        !self.shared.lock().open
+    }
+
+    /// Returns the total weight of the messages buffered in the channel.
+    pub fn weight(&self) -> usize {
+        self.shared.lock().weight
+    }
+
+    /// Returns the capacity of the channel, as given to [`channel`].
+    pub fn capacity(&self) -> usize {
+        self.shared.lock().capacity
+    }
+
+    /// Returns whether the senders send to the same receiver.
+    pub fn same_receiver(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.shared, &other.shared)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().num_senders += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            if let Some(waker) = state.recv_task.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").field("closed", &self.is_closed()).finish()
+    }
+}
+
+/// Future for the [`Sender::send`] method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Send<'a, T> {
+    sender: &'a Sender<T>,
+    msg: Option<T>,
+    weight: usize,
+
+    // Id of the task in the waiter queue, once it waited.
+    id: Option<u64>,
+}
+
+// `Pin<&mut Send<'_, T>>` is never projected to `Pin<&mut T>`
+impl<T> Unpin for Send<'_, T> {}
+
+impl<T> Future for Send<'_, T> {
+    type Output = Result<(), TrySendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        assert!(this.msg.is_some(), "`Send` polled after completion");
+        let mut state = this.sender.shared.lock();
+        let res = if !state.open {
+            Err(SendError::disconnected())
+        } else if state.can_send(this.id, this.weight) {
+            Ok(())
+        } else {
+            let id = *this.id.get_or_insert_with(|| {
+                let id = state.next_id;
+                state.next_id += 1;
+                id
+            });
+            match state.send_waiters.iter_mut().find(|waiter| waiter.id == id) {
+                Some(waiter) if waiter.waker.will_wake(cx.waker()) => {}
+                Some(waiter) => waiter.waker = cx.waker().clone(),
+                None => state.send_waiters.push_back(Waiter {
+                    id,
+                    weight: this.weight,
+                    waker: cx.waker().clone(),
+                }),
+            }
+            return Poll::Pending;
+        };
+        if let Some(id) = this.id.take() {
+            state.remove_waiter(id);
+        }
+        let msg = this.msg.take().unwrap();
+        let res = match res {
+            Ok(()) => {
+                state.push(msg, this.weight);
+                Ok(())
+            }
+            Err(err) => Err(TrySendError::new(err, msg)),
+        };
+        // The next sender may fit in the remaining capacity.
+        state.wake_first_sender();
+        Poll::Ready(res)
+    }
+}
+
+impl<T> FusedFuture for Send<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.msg.is_none()
+    }
+}
+
+impl<T> Drop for Send<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            self.sender.shared.lock().unregister(id);
+        }
+    }
+}
+
+impl<T> fmt::Debug for Send<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Send").field("sender", &self.sender).finish()
+    }
+}
+
+/// The receiving end of a weighted channel, created by [`channel`].
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Closes the receiving half of the channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel
+    /// while still enabling the receiver to drain the messages that are
+    /// buffered.
+    pub fn close(&mut self) {
+        self.shared.lock().close();
+    }
+
+    /// Tries to receive the next message without notifying a context if
+    /// empty.
+    ///
+    /// This function returns:
+    /// * `Ok(Some(t))` when a message is fetched
+    /// * `Ok(None)` when the channel is closed and no messages are left
+    /// * `Err(e)` when there are no messages available, but the channel is
+    ///   not yet closed
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.pop() {
+            Some(msg) => Ok(Some(msg)),
+            None if state.is_terminated() => Ok(None),
+            None => Err(TryRecvError::new()),
+        }
+    }
+
+    /// Returns the number of messages buffered in the channel.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns whether no message is buffered in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total weight of the messages buffered in the channel.
+    pub fn weight(&self) -> usize {
+        self.shared.lock().weight
+    }
+
+    /// Returns the capacity of the channel, as given to [`channel`].
+    pub fn capacity(&self) -> usize {
+        self.shared.lock().capacity
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let coop = ready!(coop::poll_proceed(cx));
+        let mut state = self.shared.lock();
+        let msg = match state.pop() {
+            Some(msg) => Some(msg),
+            None if state.is_terminated() => None,
+            None => {
+                match &state.recv_task {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    _ => state.recv_task = Some(cx.waker().clone()),
+                }
+                return Poll::Pending;
+            }
+        };
+        coop.made_progress();
+        Poll::Ready(msg)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let state = self.shared.lock();
+        let len = state.queue.len();
+        if state.num_senders == 0 || !state.open {
+            (len, Some(len))
+        } else {
+            (len, None)
+        }
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.shared.lock().is_terminated()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.close();
+        // Drop the buffered messages now rather than with the last sender.
+        let queue = std::mem::take(&mut state.queue);
+        state.weight = 0;
+        drop(state);
+        drop(queue);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").field("len", &self.len()).finish()
+    }
+}
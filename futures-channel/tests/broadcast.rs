@@ -0,0 +1,132 @@
+use futures::channel::broadcast::{self, RecvError, TryRecvError};
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures_test::task::new_count_waker;
+use std::task::Context;
+use std::thread;
+
+#[test]
+fn every_receiver_gets_every_value() {
+    let (tx, rx1) = broadcast::channel(4);
+    let rx2 = tx.subscribe();
+    assert_eq!(tx.receiver_count(), 2);
+
+    for i in 0..3 {
+        assert_eq!(tx.send(i), Ok(2));
+    }
+    drop(tx);
+    assert_eq!(block_on(rx1.collect::<Vec<_>>()), [0, 1, 2]);
+    assert_eq!(block_on(rx2.collect::<Vec<_>>()), [0, 1, 2]);
+}
+
+#[test]
+fn subscribers_only_get_later_values() {
+    let (tx, mut rx1) = broadcast::channel(4);
+    tx.send(1).unwrap();
+    let mut rx2 = tx.subscribe();
+    let mut rx3 = rx1.resubscribe();
+    tx.send(2).unwrap();
+
+    assert_eq!(rx1.try_recv(), Ok(1));
+    assert_eq!(rx1.try_recv(), Ok(2));
+    assert_eq!(rx2.try_recv(), Ok(2));
+    assert_eq!(rx3.try_recv(), Ok(2));
+    assert_eq!(rx3.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn lagged_receiver_gets_error() {
+    let (tx, mut rx) = broadcast::channel(2);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+    assert_eq!(rx.len(), 2);
+    assert_eq!(block_on(rx.recv()), Err(RecvError::Lagged(3)));
+    assert_eq!(block_on(rx.recv()), Ok(3));
+    assert_eq!(rx.try_recv(), Ok(4));
+    drop(tx);
+    assert_eq!(block_on(rx.recv()), Err(RecvError::Closed));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[test]
+fn lagged_stream_skips_values() {
+    let (tx, rx) = broadcast::channel(2);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [3, 4]);
+}
+
+#[test]
+fn slow_receiver_does_not_block_others() {
+    let (tx, mut fast) = broadcast::channel(1);
+    let mut slow = tx.subscribe();
+    for i in 0..3 {
+        tx.send(i).unwrap();
+        assert_eq!(fast.try_recv(), Ok(i));
+    }
+    assert_eq!(slow.try_recv(), Err(TryRecvError::Lagged(2)));
+    assert_eq!(slow.try_recv(), Ok(2));
+}
+
+#[test]
+fn send_without_receivers() {
+    let (tx, rx) = broadcast::channel(1);
+    drop(rx);
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(1).unwrap_err().into_inner(), 1);
+
+    let mut rx = tx.subscribe();
+    tx.send(2).unwrap();
+    assert_eq!(rx.try_recv(), Ok(2));
+}
+
+#[test]
+fn dropped_receiver_releases_values() {
+    let (tx, mut rx1) = broadcast::channel(2);
+    let rx2 = tx.subscribe();
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    drop(rx2);
+
+    assert_eq!(rx1.try_recv(), Ok(1));
+    tx.send(3).unwrap();
+    assert_eq!(rx1.try_recv(), Ok(2));
+    assert_eq!(rx1.try_recv(), Ok(3));
+}
+
+#[test]
+fn recv_wakes_on_send() {
+    let (tx, mut rx) = broadcast::channel(1);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut recv = rx.recv();
+    assert!(recv.poll_unpin(&mut cx).is_pending());
+    tx.send(1).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(recv.poll_unpin(&mut cx), std::task::Poll::Ready(Ok(1)));
+}
+
+#[test]
+fn recv_across_threads() {
+    let (tx, rx) = broadcast::channel(16);
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let rx = rx.resubscribe();
+            thread::spawn(move || block_on(rx.collect::<Vec<_>>()))
+        })
+        .collect();
+    drop(rx);
+
+    for i in 0..10 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), (0..10).collect::<Vec<_>>());
+    }
+}
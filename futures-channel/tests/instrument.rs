@@ -0,0 +1,78 @@
+#![cfg(feature = "tracing")]
+
+use futures::channel::{mpsc, oneshot};
+use futures::executor::block_on;
+use futures::stream::StreamExt;
+use futures_channel::instrument::{self, ChannelId, ChannelKind, Hook};
+use std::sync::{Arc, Mutex};
+
+type Events = Arc<Mutex<Vec<(ChannelId, ChannelKind, &'static str)>>>;
+
+struct Recorder(Events);
+
+impl Hook for Recorder {
+    fn on_create(&self, id: ChannelId, kind: ChannelKind) {
+        self.0.lock().unwrap().push((id, kind, "create"));
+    }
+
+    fn on_send(&self, id: ChannelId, kind: ChannelKind) {
+        self.0.lock().unwrap().push((id, kind, "send"));
+    }
+
+    fn on_recv(&self, id: ChannelId, kind: ChannelKind) {
+        self.0.lock().unwrap().push((id, kind, "recv"));
+    }
+
+    fn on_close(&self, id: ChannelId, kind: ChannelKind) {
+        self.0.lock().unwrap().push((id, kind, "close"));
+    }
+}
+
+fn take(events: &Events) -> Vec<(ChannelKind, &'static str)> {
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    // All the events of a channel have the same id.
+    assert!(events.windows(2).all(|w| w[0].0 == w[1].0));
+    events.into_iter().map(|(_, kind, event)| (kind, event)).collect()
+}
+
+// The hook is global, so the channels are exercised in a single test.
+#[test]
+fn hook_events() {
+    let events = Events::default();
+    instrument::set_hook(Recorder(events.clone())).unwrap();
+    assert!(instrument::set_hook(Recorder(events.clone())).is_err());
+
+    let (mut tx, rx) = mpsc::channel(1);
+    tx.try_send(1).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1]);
+    let bounded = ChannelKind::Bounded;
+    assert_eq!(
+        take(&events),
+        [(bounded, "create"), (bounded, "send"), (bounded, "close"), (bounded, "recv")]
+    );
+
+    let (tx, mut rx) = mpsc::unbounded();
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(block_on(rx.next()), Some(1));
+    drop(rx);
+    let unbounded = ChannelKind::Unbounded;
+    assert_eq!(
+        take(&events),
+        [(unbounded, "create"), (unbounded, "send"), (unbounded, "recv"), (unbounded, "close")]
+    );
+
+    let (tx, rx) = oneshot::channel();
+    tx.send(1).unwrap();
+    assert_eq!(block_on(rx), Ok(1));
+    let oneshot = ChannelKind::Oneshot;
+    assert_eq!(
+        take(&events),
+        [(oneshot, "create"), (oneshot, "send"), (oneshot, "close"), (oneshot, "recv")]
+    );
+
+    let (tx, mut rx) = oneshot::channel::<i32>();
+    rx.close();
+    drop((tx, rx));
+    assert_eq!(take(&events), [(oneshot, "create"), (oneshot, "close")]);
+}
@@ -0,0 +1,105 @@
+use futures::channel::mpmc;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::{Context, Poll};
+use std::thread;
+
+#[test]
+fn each_message_received_once() {
+    let (tx, rx1) = mpmc::unbounded();
+    let mut rx2 = rx1.clone();
+    for i in 0..4 {
+        tx.unbounded_send(i).unwrap();
+    }
+    assert_eq!(block_on(rx2.next()), Some(0));
+    drop(tx);
+    assert_eq!(block_on(rx1.collect::<Vec<_>>()), [1, 2, 3]);
+    assert_eq!(block_on(rx2.next()), None);
+}
+
+#[test]
+fn waiting_receivers_served_in_order() {
+    let (tx, mut rx1) = mpmc::channel(4);
+    let mut rx2 = rx1.clone();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    assert!(rx1.poll_next_unpin(&mut Context::from_waker(&waker1)).is_pending());
+    assert!(rx2.poll_next_unpin(&mut Context::from_waker(&waker2)).is_pending());
+
+    tx.try_send(1).unwrap();
+    assert_eq!((count1.get(), count2.get()), (1, 0));
+    tx.try_send(2).unwrap();
+    assert_eq!((count1.get(), count2.get()), (1, 1));
+}
+
+#[test]
+fn dropped_receiver_passes_wakeup_on() {
+    let (tx, mut rx1) = mpmc::channel(4);
+    let mut rx2 = rx1.clone();
+    let (waker1, _count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    assert!(rx1.poll_next_unpin(&mut Context::from_waker(&waker1)).is_pending());
+    assert!(rx2.poll_next_unpin(&mut Context::from_waker(&waker2)).is_pending());
+    tx.try_send(1).unwrap();
+    drop(rx1);
+    assert_eq!(count2, 1);
+    assert_eq!(rx2.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+}
+
+#[test]
+fn bounded_send_waits_for_capacity() {
+    let (tx, mut rx) = mpmc::channel(1);
+    tx.try_send(1).unwrap();
+    assert!(tx.try_send(2).unwrap_err().is_full());
+
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut send = tx.send(2);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(count, 1);
+    assert!(matches!(send.poll_unpin(&mut cx), Poll::Ready(Ok(()))));
+    drop(send);
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+    assert!(rx.try_next().is_err());
+}
+
+#[test]
+fn close_and_disconnect() {
+    let (tx, rx) = mpmc::channel(2);
+    tx.try_send(1).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    let err = block_on(tx.send(2)).unwrap_err();
+    assert!(err.is_disconnected());
+    assert_eq!(err.into_inner(), 2);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1]);
+    assert!(tx.try_send(3).unwrap_err().is_disconnected());
+}
+
+#[test]
+fn work_shared_across_threads() {
+    let (tx, rx) = mpmc::channel(8);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || block_on(rx.collect::<Vec<u32>>()))
+        })
+        .collect();
+    drop(rx);
+
+    block_on(async {
+        for i in 0..1000 {
+            tx.send(i).await.unwrap();
+        }
+    });
+    drop(tx);
+
+    let mut received: Vec<_> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+    received.sort_unstable();
+    assert_eq!(received, (0..1000).collect::<Vec<_>>());
+}
@@ -0,0 +1,86 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::{Context, Poll};
+
+#[test]
+fn grow_capacity_unparks_senders() {
+    let (mut tx, mut rx) = mpsc::channel(0);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    tx.try_send(1).unwrap();
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+
+    rx.set_capacity(2);
+    assert_eq!(count, 1);
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    tx.try_send(2).unwrap();
+    tx.try_send(3).unwrap();
+    assert!(tx.try_send(4).unwrap_err().is_full());
+
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1, 2, 3]);
+}
+
+#[test]
+fn grow_capacity_from_sender() {
+    let (mut tx, mut rx) = mpsc::channel(0);
+    let tx2 = tx.clone();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    tx.try_send(1).unwrap();
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+
+    // The senders are unparked by the receiver once polled.
+    tx2.set_capacity(1);
+    assert_eq!(count, 0);
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(count, 1);
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn shrink_capacity_drains_naturally() {
+    let (mut tx, mut rx) = mpsc::channel(3);
+    for i in 0..4 {
+        tx.try_send(i).unwrap();
+    }
+    tx.set_capacity(0);
+
+    // The buffered messages are kept, but new sends wait for them to drain.
+    let (waker, _count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+    for i in 0..4 {
+        assert_eq!(block_on(rx.next()), Some(i));
+    }
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    tx.try_send(4).unwrap();
+    assert!(tx.try_send(5).unwrap_err().is_full());
+}
+
+#[test]
+fn send_through_capacity_changes() {
+    let (mut tx, mut rx) = mpsc::channel(1);
+    let sender = std::thread::spawn(move || {
+        block_on(async {
+            for i in 0..100 {
+                tx.send(i).await.unwrap();
+            }
+        })
+    });
+    let received = block_on(async {
+        let mut received = Vec::new();
+        while let Some(i) = rx.next().await {
+            rx.set_capacity(i % 7);
+            received.push(i);
+        }
+        received
+    });
+    sender.join().unwrap();
+    assert_eq!(received, (0..100).collect::<Vec<_>>());
+}
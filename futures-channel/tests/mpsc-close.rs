@@ -297,3 +297,57 @@ fn bounded_try_next_after_none() {
     // None received, check we can call `try_next` again.
     assert_eq!(Ok(None), rx.try_next().map_err(|_| ()));
 }
+
+#[derive(Debug, PartialEq)]
+enum Shutdown {
+    Graceful,
+    Failed(&'static str),
+}
+
+#[test]
+fn bounded_close_with_reason() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+    rx.close_with(Shutdown::Failed("decode error"));
+
+    let err = tx.try_send(1).unwrap_err();
+    assert!(err.is_disconnected());
+    let reason = err.reason().unwrap();
+    assert!(reason.is::<Shutdown>());
+    assert_eq!(reason.downcast_ref(), Some(&Shutdown::Failed("decode error")));
+
+    let err = block_on(tx.send(2)).unwrap_err();
+    assert_eq!(err.reason().unwrap().downcast_ref(), Some(&Shutdown::Failed("decode error")));
+}
+
+#[test]
+fn unbounded_close_with_reason() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    rx.close_with(Shutdown::Graceful);
+
+    let err = tx.unbounded_send(1).unwrap_err();
+    assert_eq!(err.reason().unwrap().downcast_ref(), Some(&Shutdown::Graceful));
+    assert_eq!(err.reason().unwrap().downcast_ref::<u32>(), None);
+}
+
+#[test]
+fn close_with_keeps_first_closure() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+    rx.close();
+    rx.close_with(Shutdown::Graceful);
+    assert!(tx.try_send(1).unwrap_err().reason().is_none());
+
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+    rx.close_with(Shutdown::Graceful);
+    rx.close_with(Shutdown::Failed("late"));
+    let err = tx.try_send(1).unwrap_err();
+    assert_eq!(err.reason().unwrap().downcast_ref(), Some(&Shutdown::Graceful));
+}
+
+#[test]
+fn dropped_receiver_has_no_reason() {
+    let (mut tx, rx) = mpsc::channel::<i32>(1);
+    drop(rx);
+    let err = tx.try_send(1).unwrap_err();
+    assert!(err.is_disconnected());
+    assert!(err.reason().is_none());
+}
@@ -0,0 +1,47 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::{FusedStream, StreamExt};
+use std::thread;
+
+#[test]
+fn bounded_close_and_drain() {
+    let (mut tx, mut rx) = mpsc::channel(4);
+    for i in 0..3 {
+        tx.try_send(i).unwrap();
+    }
+    assert_eq!(rx.close_and_drain(), [0, 1, 2]);
+    assert!(rx.is_terminated());
+    assert!(tx.is_closed());
+    assert!(tx.try_send(3).unwrap_err().is_disconnected());
+    assert_eq!(rx.close_and_drain(), Vec::<i32>::new());
+}
+
+#[test]
+fn bounded_close_and_drain_unparks_senders() {
+    let (mut tx, mut rx) = mpsc::channel(0);
+    tx.try_send(1).unwrap();
+    let handle = thread::spawn(move || block_on(tx.send(2)));
+    let drained = rx.close_and_drain();
+    let res = handle.join().unwrap();
+    // The second message is either drained or rejected.
+    match res {
+        Ok(()) => assert_eq!(drained, [1, 2]),
+        Err(err) => {
+            assert!(err.is_disconnected());
+            assert_eq!(drained, [1]);
+        }
+    }
+}
+
+#[test]
+fn unbounded_close_and_drain() {
+    let (tx, mut rx) = mpsc::unbounded();
+    for i in 0..3 {
+        tx.unbounded_send(i).unwrap();
+    }
+    assert_eq!(block_on(rx.next()), Some(0));
+    assert_eq!(rx.close_and_drain(), [1, 2]);
+    assert!(rx.is_terminated());
+    assert!(tx.unbounded_send(3).is_err());
+}
@@ -0,0 +1,67 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures_test::task::new_count_waker;
+use std::task::{Context, Poll};
+use std::thread;
+
+#[test]
+fn closed_resolves_when_receiver_dropped() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    // `poll_closed` is given its own waker, so that it doesn't depend on
+    // the waker of `closed` being deduplicated.
+    let (poll_waker, poll_count) = new_count_waker();
+    let mut poll_cx = Context::from_waker(&poll_waker);
+
+    let mut closed = tx.closed();
+    assert_eq!(closed.poll_unpin(&mut cx), Poll::Pending);
+    assert_eq!(tx.poll_closed(&mut poll_cx), Poll::Pending);
+    drop(rx);
+    assert_eq!(count, 1);
+    assert_eq!(poll_count, 1);
+    assert_eq!(closed.poll_unpin(&mut cx), Poll::Ready(()));
+    assert_eq!(tx.poll_closed(&mut poll_cx), Poll::Ready(()));
+}
+
+#[test]
+fn closed_resolves_when_receiver_closed() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(tx.poll_closed(&mut cx), Poll::Pending);
+    rx.close();
+    assert_eq!(count, 1);
+    assert_eq!(tx.poll_closed(&mut cx), Poll::Ready(()));
+    block_on(tx.clone().closed());
+}
+
+#[test]
+fn closed_wakes_every_sender() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    let handles: Vec<_> = (0..3)
+        .map(|_| {
+            let tx = tx.clone();
+            thread::spawn(move || block_on(tx.closed()))
+        })
+        .collect();
+    drop(rx);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(tx.is_closed());
+}
+
+#[test]
+fn closed_after_disconnect() {
+    let (mut tx, _rx) = mpsc::channel::<i32>(1);
+    tx.disconnect();
+    block_on(tx.closed());
+
+    let (tx, _rx) = mpsc::unbounded::<i32>();
+    tx.close_channel();
+    block_on(tx.closed());
+}
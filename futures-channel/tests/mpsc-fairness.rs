@@ -0,0 +1,64 @@
+use futures::channel::mpsc;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::{Context, Poll};
+
+#[test]
+fn senders_unparked_in_order() {
+    let (mut tx1, mut rx) = mpsc::channel(0);
+    let mut tx2 = tx1.clone();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    tx1.try_send(1).unwrap();
+    assert_eq!(tx1.poll_ready(&mut Context::from_waker(&waker1)), Poll::Pending);
+    tx2.try_send(2).unwrap();
+    assert_eq!(tx2.poll_ready(&mut Context::from_waker(&waker2)), Poll::Pending);
+
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!((count1.get(), count2.get()), (1, 0));
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(2)));
+    assert_eq!((count1.get(), count2.get()), (1, 1));
+}
+
+#[test]
+fn sender_parks_behind_waiting_senders() {
+    let (mut tx1, mut rx) = mpsc::channel(0);
+    let mut tx2 = tx1.clone();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    tx1.try_send(1).unwrap();
+    assert_eq!(tx1.poll_ready(&mut cx), Poll::Pending);
+
+    // The buffer has room, but `tx1` isn't unparked until the receiver is
+    // polled, so `tx2` may send only its guaranteed message.
+    tx2.set_capacity(2);
+    tx2.try_send(2).unwrap();
+    assert!(tx2.try_send(3).unwrap_err().is_full());
+    assert_eq!(tx2.poll_ready(&mut cx), Poll::Pending);
+
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(count, 2);
+    assert_eq!(tx1.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(tx2.poll_ready(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn send_batch_parks_behind_waiting_senders() {
+    let (mut tx1, mut rx) = mpsc::channel(0);
+    let mut tx2 = tx1.clone();
+
+    tx1.try_send(1).unwrap();
+    assert_eq!(tx1.poll_ready(&mut noop_context()), Poll::Pending);
+
+    tx2.set_capacity(4);
+    let mut send = tx2.send_batch(2..5);
+    assert!(send.poll_unpin(&mut noop_context()).is_pending());
+    drop(send);
+    assert_eq!(rx.len(), 2);
+
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(2)));
+}
@@ -0,0 +1,55 @@
+use futures::channel::mpsc;
+
+#[test]
+fn len_tracks_messages() {
+    let (mut tx, mut rx) = mpsc::channel(2);
+    assert_eq!(tx.len(), 0);
+    assert!(tx.is_empty());
+    assert!(rx.is_empty());
+
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert_eq!(tx.len(), 2);
+    assert_eq!(rx.len(), 2);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(tx.len(), 1);
+    assert_eq!(rx.len(), 1);
+}
+
+#[test]
+fn capacity_counts_senders() {
+    let (tx, rx) = mpsc::channel::<i32>(2);
+    assert_eq!(tx.capacity(), 3);
+    assert_eq!(rx.capacity(), 3);
+
+    let tx2 = tx.clone();
+    assert_eq!(tx.capacity(), 4);
+    drop(tx2);
+    assert_eq!(rx.capacity(), 3);
+}
+
+#[test]
+fn is_full_at_capacity() {
+    let (mut tx, rx) = mpsc::channel(1);
+    tx.try_send(1).unwrap();
+    assert!(!tx.is_full());
+    tx.try_send(2).unwrap();
+    assert!(tx.is_full());
+    assert!(rx.is_full());
+    assert!(tx.try_send(3).unwrap_err().is_full());
+}
+
+#[test]
+fn disconnected_metrics() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+    tx.disconnect();
+    assert_eq!(tx.len(), 0);
+    assert_eq!(tx.capacity(), 0);
+    assert!(!tx.is_full());
+
+    assert_eq!(rx.try_next().unwrap(), None);
+    assert_eq!(rx.len(), 0);
+    assert_eq!(rx.capacity(), 0);
+    assert!(!rx.is_full());
+}
@@ -0,0 +1,141 @@
+use futures::channel::mpsc::{self, OverflowPolicy};
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::noop_context;
+use std::thread;
+
+fn drain<T>(rx: &mut mpsc::Receiver<T>) -> Vec<T> {
+    let mut msgs = Vec::new();
+    while let Ok(Some(msg)) = rx.try_next() {
+        msgs.push(msg);
+    }
+    msgs
+}
+
+#[test]
+fn drop_newest() {
+    let (mut tx, mut rx) = mpsc::channel_with_policy(2, OverflowPolicy::DropNewest);
+    for i in 0..5 {
+        tx.try_send(i).unwrap();
+    }
+    assert_eq!(tx.dropped_count(), 3);
+    assert_eq!(rx.dropped_count(), 3);
+    assert_eq!(drain(&mut rx), [0, 1]);
+
+    tx.try_send(5).unwrap();
+    assert_eq!(drain(&mut rx), [5]);
+}
+
+#[test]
+fn drop_oldest() {
+    let (mut tx, mut rx) = mpsc::channel_with_policy(2, OverflowPolicy::DropOldest);
+    for i in 0..5 {
+        tx.try_send(i).unwrap();
+    }
+    assert_eq!(rx.dropped_count(), 3);
+    assert_eq!(rx.len(), 2);
+    assert_eq!(drain(&mut rx), [3, 4]);
+}
+
+#[test]
+fn reject() {
+    let (mut tx, mut rx) = mpsc::channel_with_policy(2, OverflowPolicy::Reject);
+    tx.try_send(0).unwrap();
+    tx.try_send(1).unwrap();
+    let err = tx.try_send(2).unwrap_err();
+    assert!(err.is_full());
+    assert_eq!(err.into_inner(), 2);
+    assert_eq!(tx.dropped_count(), 0);
+
+    // The sender never waits, the sends fail instead.
+    let mut cx = noop_context();
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    assert!(block_on(tx.send(3)).unwrap_err().is_full());
+
+    assert_eq!(drain(&mut rx), [0, 1]);
+}
+
+#[test]
+fn no_slot_per_sender() {
+    let (mut tx1, rx) = mpsc::channel_with_policy(1, OverflowPolicy::Reject);
+    let mut tx2 = tx1.clone();
+    assert_eq!(rx.capacity(), 1);
+    tx1.try_send(1).unwrap();
+    assert!(tx2.try_send(2).unwrap_err().is_full());
+    assert!(rx.is_full());
+}
+
+#[test]
+fn block_policy() {
+    let (mut tx, _rx) = mpsc::channel_with_policy(0, OverflowPolicy::Block);
+    tx.try_send(1).unwrap();
+    assert!(tx.try_send(2).unwrap_err().is_full());
+    assert_eq!(tx.capacity(), 1);
+}
+
+#[test]
+#[should_panic(expected = "needs a buffer")]
+fn drop_policy_without_buffer() {
+    let _ = mpsc::channel_with_policy::<i32>(0, OverflowPolicy::DropOldest);
+}
+
+#[test]
+fn disconnected() {
+    let (mut tx, rx) = mpsc::channel_with_policy(1, OverflowPolicy::DropNewest);
+    drop(rx);
+    assert!(tx.try_send(1).unwrap_err().is_disconnected());
+    assert_eq!(tx.dropped_count(), 0);
+}
+
+#[test]
+fn send_batch_drop_oldest() {
+    let (mut tx, mut rx) = mpsc::channel_with_policy(3, OverflowPolicy::DropOldest);
+    block_on(tx.send_batch(0..10)).unwrap();
+    assert_eq!(rx.dropped_count(), 7);
+    assert_eq!(drain(&mut rx), [7, 8, 9]);
+}
+
+#[test]
+fn send_batch_reject() {
+    let (mut tx, mut rx) = mpsc::channel_with_policy(3, OverflowPolicy::Reject);
+    assert!(block_on(tx.send_batch(0..10)).unwrap_err().is_full());
+    assert_eq!(drain(&mut rx), [0, 1, 2]);
+}
+
+#[test]
+fn stress_drop_oldest() {
+    const SENDERS: usize = 4;
+    const MESSAGES: usize = 10_000;
+
+    let (tx, rx) = mpsc::channel_with_policy(8, OverflowPolicy::DropOldest);
+    let senders = (0..SENDERS)
+        .map(|i| {
+            let mut tx = tx.clone();
+            thread::spawn(move || {
+                for j in 0..MESSAGES {
+                    tx.try_send((i, j)).unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let mut rx = rx;
+    let mut last = [None; SENDERS];
+    let mut received = 0;
+    let mut dropped = 0;
+    while let Some((i, j)) = block_on(rx.next()) {
+        // The messages of a sender which aren't dropped arrive in order.
+        assert!(last[i].map_or(true, |last| last < j));
+        last[i] = Some(j);
+        received += 1;
+        // Messages are only dropped to make room for a message to receive.
+        dropped = rx.dropped_count();
+    }
+    for t in senders {
+        t.join().unwrap();
+    }
+    assert_eq!(received + dropped, SENDERS * MESSAGES);
+}
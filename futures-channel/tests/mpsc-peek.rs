@@ -0,0 +1,74 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::stream::{Stream, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+
+#[test]
+fn poll_peek_leaves_message() {
+    let (mut tx, mut rx) = mpsc::channel(4);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Pending);
+    tx.try_send(1).unwrap();
+    assert_eq!(count, 1);
+    tx.try_send(2).unwrap();
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Some(&1)));
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Some(&1)));
+    assert_eq!(rx.len(), 2);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Some(&2)));
+    assert_eq!(block_on(rx.next()), Some(2));
+    assert!(rx.is_empty());
+}
+
+#[test]
+fn peek_closed() {
+    let (tx, mut rx) = mpsc::channel::<i32>(4);
+    drop(tx);
+    assert_eq!(rx.poll_peek(&mut noop_context()), Poll::Ready(None));
+    assert!(futures::stream::FusedStream::is_terminated(&rx));
+}
+
+#[test]
+fn peeked_message_survives_close() {
+    let (mut tx, mut rx) = mpsc::channel(4);
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+
+    assert_eq!(block_on(rx.peek()), Some(&1));
+    assert_eq!(rx.size_hint(), (2, None));
+    assert_eq!(rx.close_and_drain(), [1, 2]);
+}
+
+#[test]
+fn peek_unparks_sender() {
+    let (mut tx, mut rx) = mpsc::channel(0);
+    tx.try_send(1).unwrap();
+    assert!(tx.try_send(2).unwrap_err().is_full());
+
+    assert_eq!(rx.poll_peek(&mut noop_context()), Poll::Ready(Some(&1)));
+    tx.try_send(2).unwrap();
+    assert_eq!(rx.len(), 2);
+    assert_eq!(block_on(rx.by_ref().take(2).collect::<Vec<_>>()), [1, 2]);
+}
+
+#[test]
+fn unbounded_peek() {
+    let (tx, mut rx) = mpsc::unbounded();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    drop(tx);
+
+    assert_eq!(block_on(rx.peek()), Some(&1));
+    assert_eq!(block_on(rx.peek()), Some(&1));
+    assert_eq!(rx.size_hint(), (2, Some(2)));
+
+    let mut buf = Vec::new();
+    assert_eq!(block_on(rx.recv_many(&mut buf, 10)), 2);
+    assert_eq!(buf, [1, 2]);
+    assert_eq!(block_on(rx.peek()), None);
+}
@@ -0,0 +1,140 @@
+use futures::channel::mpsc::{self, ReceiverSet};
+use futures::executor::block_on;
+use futures::stream::{FusedStream, Stream, StreamExt};
+use futures_test::task::{new_count_waker, noop_context};
+use std::cell::Cell;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+struct CountPolls<S> {
+    stream: S,
+    polls: Rc<Cell<usize>>,
+}
+
+impl<S: Stream + Unpin> Stream for CountPolls<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        self.polls.set(self.polls.get() + 1);
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+#[test]
+fn yields_messages_with_index() {
+    let (tx1, rx1) = mpsc::unbounded();
+    let (tx2, rx2) = mpsc::unbounded();
+    let mut set = ReceiverSet::new();
+    let i1 = set.insert(rx1);
+    let i2 = set.insert(rx2);
+    assert_ne!(i1, i2);
+    assert_eq!(set.len(), 2);
+
+    let mut cx = noop_context();
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Pending);
+
+    tx2.unbounded_send(2).unwrap();
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(Some((i2, 2))));
+    tx1.unbounded_send(1).unwrap();
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(Some((i1, 1))));
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Pending);
+}
+
+#[test]
+fn only_polls_woken_receivers() {
+    let mut set = ReceiverSet::new();
+    let mut senders = Vec::new();
+    let mut polls = Vec::new();
+    for _ in 0..10 {
+        let (tx, rx) = mpsc::unbounded::<i32>();
+        let count = Rc::new(Cell::new(0));
+        set.insert(CountPolls { stream: rx, polls: count.clone() });
+        senders.push(tx);
+        polls.push(count);
+    }
+
+    let mut cx = noop_context();
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Pending);
+    assert!(polls.iter().all(|count| count.get() == 1));
+
+    senders[3].unbounded_send(3).unwrap();
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(Some((3, 3))));
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Pending);
+    for (i, count) in polls.iter().enumerate() {
+        assert_eq!(count.get(), if i == 3 { 3 } else { 1 });
+    }
+}
+
+#[test]
+fn wakes_on_message() {
+    let (tx, rx) = mpsc::unbounded();
+    let mut set = ReceiverSet::new();
+    set.insert(rx);
+
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count, 0);
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(Some((0, 1))));
+}
+
+#[test]
+fn alternates_between_ready_receivers() {
+    let (tx1, rx1) = mpsc::unbounded();
+    let (tx2, rx2) = mpsc::unbounded();
+    for i in 0..3 {
+        tx1.unbounded_send(i).unwrap();
+        tx2.unbounded_send(i).unwrap();
+    }
+    drop((tx1, tx2));
+
+    let mut set = ReceiverSet::new();
+    set.insert(rx1);
+    set.insert(rx2);
+    let messages = block_on(set.collect::<Vec<_>>());
+    assert_eq!(messages, [(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]);
+}
+
+#[test]
+fn remove_and_reuse_index() {
+    let (tx1, rx1) = mpsc::unbounded();
+    let (tx2, rx2) = mpsc::unbounded();
+    let mut set = ReceiverSet::new();
+    let i1 = set.insert(rx1);
+    tx1.unbounded_send(1).unwrap();
+
+    let mut rx1 = set.remove(i1).unwrap();
+    assert!(!set.contains(i1));
+    assert!(set.remove(i1).is_none());
+    assert_eq!(rx1.try_next().unwrap(), Some(1));
+
+    let i2 = set.insert(rx2);
+    assert_eq!(i2, i1);
+    tx2.unbounded_send(2).unwrap();
+    let mut cx = noop_context();
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(Some((i2, 2))));
+}
+
+#[test]
+fn terminates_once_empty() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    let mut set = ReceiverSet::new();
+    set.insert(rx);
+
+    let mut cx = noop_context();
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(set.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert!(set.is_empty());
+    assert!(set.is_terminated());
+
+    let (tx, rx) = mpsc::channel(1);
+    set.insert(rx);
+    assert!(!set.is_terminated());
+    drop(tx);
+    assert_eq!(block_on(set.collect::<Vec<_>>()), []);
+}
@@ -0,0 +1,69 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+use std::thread;
+
+#[test]
+fn unbounded_recv_many() {
+    let (tx, mut rx) = mpsc::unbounded();
+    for i in 0..5 {
+        tx.unbounded_send(i).unwrap();
+    }
+
+    let mut buf = Vec::new();
+    assert_eq!(block_on(rx.recv_many(&mut buf, 3)), 3);
+    assert_eq!(buf, [0, 1, 2]);
+    assert_eq!(block_on(rx.recv_many(&mut buf, 10)), 2);
+    assert_eq!(buf, [0, 1, 2, 3, 4]);
+
+    drop(tx);
+    assert_eq!(block_on(rx.recv_many(&mut buf, 10)), 0);
+    assert_eq!(buf.len(), 5);
+}
+
+#[test]
+fn bounded_recv_many_unparks_senders() {
+    let (mut tx, mut rx) = mpsc::channel(2);
+    let handle = thread::spawn(move || {
+        block_on(async {
+            for i in 0..100 {
+                futures::SinkExt::send(&mut tx, i).await.unwrap();
+            }
+        })
+    });
+
+    let mut buf = Vec::new();
+    while block_on(rx.recv_many(&mut buf, 8)) != 0 {
+        assert!(buf.len() <= 100);
+    }
+    handle.join().unwrap();
+    assert_eq!(buf, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn poll_recv_many_pending() {
+    let (mut tx, mut rx) = mpsc::channel(4);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut buf = Vec::new();
+
+    assert_eq!(rx.poll_recv_many(&mut cx, &mut buf, 4), Poll::Pending);
+    tx.try_send(1).unwrap();
+    assert_eq!(count, 1);
+    tx.try_send(2).unwrap();
+    assert_eq!(rx.poll_recv_many(&mut cx, &mut buf, 4), Poll::Ready(2));
+    assert_eq!(buf, [1, 2]);
+}
+
+#[test]
+fn recv_many_zero_limit() {
+    let (tx, mut rx) = mpsc::unbounded();
+    tx.unbounded_send(1).unwrap();
+
+    let mut buf = Vec::new();
+    assert_eq!(rx.poll_recv_many(&mut noop_context(), &mut buf, 0), Poll::Ready(0));
+    assert!(buf.is_empty());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+}
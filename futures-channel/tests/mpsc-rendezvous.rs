@@ -0,0 +1,102 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::noop_context;
+use std::thread;
+
+#[test]
+fn send_completes_once_received() {
+    let (mut tx, mut rx) = mpsc::rendezvous();
+    let mut cx = noop_context();
+
+    let mut send = tx.send(1);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    assert!(send.poll_unpin(&mut cx).is_pending());
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(send.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn each_sender_waits_for_its_message() {
+    let (mut tx1, mut rx) = mpsc::rendezvous();
+    let mut tx2 = tx1.clone();
+    let mut cx = noop_context();
+
+    let mut send1 = tx1.send(1);
+    let mut send2 = tx2.send(2);
+    assert!(send1.poll_unpin(&mut cx).is_pending());
+    assert!(send2.poll_unpin(&mut cx).is_pending());
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(send1.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+    assert!(send2.poll_unpin(&mut cx).is_pending());
+
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+    assert_eq!(send2.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn try_send_waits_for_receive() {
+    let (mut tx, mut rx) = mpsc::rendezvous();
+    let mut cx = noop_context();
+
+    tx.try_send(1).unwrap();
+    assert!(tx.try_send(2).unwrap_err().is_full());
+    assert!(tx.poll_ready(&mut cx).is_pending());
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn receiver_dropped_completes_send() {
+    let (mut tx, rx) = mpsc::rendezvous();
+    let mut cx = noop_context();
+
+    let mut send = tx.send(1);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    drop(rx);
+    assert_eq!(send.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+    assert!(tx.try_send(2).unwrap_err().is_disconnected());
+}
+
+#[test]
+#[should_panic(expected = "rendezvous")]
+fn set_capacity_panics() {
+    let (tx, _rx) = mpsc::rendezvous::<i32>();
+    tx.set_capacity(1);
+}
+
+#[test]
+fn stress_per_sender_order() {
+    const SENDERS: usize = 4;
+    const MESSAGES: usize = 500;
+
+    let (tx, rx) = mpsc::rendezvous();
+    let senders = (0..SENDERS)
+        .map(|i| {
+            let mut tx = tx.clone();
+            thread::spawn(move || {
+                for j in 0..MESSAGES {
+                    block_on(tx.send((i, j))).unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let mut next = vec![0; SENDERS];
+    block_on(rx.for_each(|(i, j)| {
+        assert_eq!(next[i], j);
+        next[i] += 1;
+        futures::future::ready(())
+    }));
+    for t in senders {
+        t.join().unwrap();
+    }
+    assert_eq!(next, [MESSAGES; SENDERS]);
+}
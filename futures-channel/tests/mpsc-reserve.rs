@@ -0,0 +1,73 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+
+#[test]
+fn reserve_then_send() {
+    let (mut tx, rx) = mpsc::channel(1);
+    let permit = block_on(tx.reserve()).unwrap();
+    permit.send(1);
+    tx.try_reserve().unwrap().send(2);
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1, 2]);
+}
+
+#[test]
+fn try_reserve_full() {
+    let (mut tx, mut rx) = mpsc::channel(0);
+    tx.try_reserve().unwrap().send(1);
+    let err = tx.try_reserve().unwrap_err();
+    assert!(err.is_full());
+
+    assert_eq!(block_on(rx.next()), Some(1));
+    tx.try_reserve().unwrap().send(2);
+    assert_eq!(block_on(rx.next()), Some(2));
+}
+
+#[test]
+fn reserve_waits_for_capacity() {
+    let (mut tx, mut rx) = mpsc::channel(0);
+    tx.try_send(1).unwrap();
+
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut reserve = tx.reserve();
+    assert!(reserve.poll_unpin(&mut cx).is_pending());
+
+    assert_eq!(block_on(rx.next()), Some(1));
+    assert_eq!(count, 1);
+    match reserve.poll_unpin(&mut cx) {
+        Poll::Ready(Ok(permit)) => permit.send(2),
+        _ => panic!("expected a permit"),
+    }
+    assert_eq!(block_on(rx.next()), Some(2));
+}
+
+#[test]
+fn dropped_permit_releases_slot() {
+    let (mut tx, mut rx) = mpsc::channel(0);
+    drop(tx.try_reserve().unwrap());
+    drop(block_on(tx.reserve()).unwrap());
+    tx.try_reserve().unwrap().send(1);
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+}
+
+#[test]
+fn reserve_disconnected() {
+    let (mut tx, rx) = mpsc::channel::<i32>(1);
+    drop(rx);
+    assert!(block_on(tx.reserve()).unwrap_err().is_disconnected());
+    assert!(tx.try_reserve().unwrap_err().is_disconnected());
+}
+
+#[test]
+fn permit_send_after_receiver_dropped() {
+    let (mut tx, rx) = mpsc::channel(1);
+    let permit = tx.try_reserve().unwrap();
+    drop(rx);
+    permit.send(1);
+}
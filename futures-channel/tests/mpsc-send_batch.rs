@@ -0,0 +1,126 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::{join, FutureExt};
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::noop_context;
+use std::thread;
+
+#[test]
+fn send_batch_in_order() {
+    let (mut tx, rx) = mpsc::channel(100);
+    block_on(tx.send_batch(0..100)).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn send_batch_respects_capacity() {
+    let (mut tx, mut rx) = mpsc::channel(2);
+    let mut cx = noop_context();
+
+    let mut send = tx.send_batch(0..10);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    drop(send);
+    // The buffer plus the guaranteed slot of the sender.
+    assert_eq!(rx.len(), 3);
+    assert!(tx.poll_ready(&mut cx).is_pending());
+
+    for i in 0..3 {
+        assert_eq!(rx.try_next().unwrap(), Some(i));
+    }
+    assert!(tx.poll_ready(&mut cx).is_ready());
+}
+
+#[test]
+fn send_batch_rendezvous() {
+    let (mut tx, rx) = mpsc::channel(0);
+    let (res, msgs) = block_on(join(
+        async move {
+            let res = tx.send_batch(0..50).await;
+            drop(tx);
+            res
+        },
+        rx.collect::<Vec<_>>(),
+    ));
+    res.unwrap();
+    assert_eq!(msgs, (0..50).collect::<Vec<_>>());
+}
+
+#[test]
+fn send_batch_disconnected() {
+    let (mut tx, rx) = mpsc::channel(4);
+    drop(rx);
+    let err = block_on(tx.send_batch(vec![1, 2, 3])).unwrap_err();
+    assert!(err.is_disconnected());
+}
+
+#[test]
+fn send_batch_empty() {
+    let (mut tx, rx) = mpsc::channel::<i32>(0);
+    drop(rx);
+    assert!(block_on(tx.send_batch(Vec::new())).is_ok());
+}
+
+#[test]
+fn send_all_in_order() {
+    let (mut tx, rx) = mpsc::channel(1);
+    let t = thread::spawn(move || block_on(rx.collect::<Vec<_>>()));
+    block_on(tx.send_all(stream::iter(0..1000).map(Ok))).unwrap();
+    drop(tx);
+    assert_eq!(t.join().unwrap(), (0..1000).collect::<Vec<_>>());
+}
+
+#[test]
+fn send_all_by_reference() {
+    let (mut tx, rx) = mpsc::channel(10);
+    let mut msgs = stream::iter(vec![1, 2, 3]).map(Ok);
+    block_on(tx.send_all(&mut msgs)).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1, 2, 3]);
+}
+
+#[test]
+fn send_all_stream_error() {
+    let (mut tx, rx) = mpsc::channel(10);
+    let (err_tx, err_rx) = mpsc::channel::<i32>(0);
+    drop(err_rx);
+    let err = err_tx.clone().try_send(0).unwrap_err().into_send_error();
+
+    let msgs = stream::iter(vec![Ok(1), Ok(2), Err(err), Ok(3)]);
+    let res = block_on(tx.send_all(msgs));
+    assert!(res.unwrap_err().is_disconnected());
+    drop(tx);
+    // The messages before the error are sent.
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1, 2]);
+}
+
+#[test]
+fn send_all_receiver_dropped() {
+    let (mut tx, rx) = mpsc::channel(10);
+    drop(rx);
+    let res = block_on(tx.send_all(stream::iter(vec![1, 2]).map(Ok)));
+    assert!(res.unwrap_err().is_disconnected());
+
+    // An empty stream only flushes, which doesn't fail.
+    assert!(block_on(tx.send_all(stream::empty().map(Ok))).is_ok());
+}
+
+#[test]
+fn send_all_pending_stream() {
+    let (mut tx, mut rx) = mpsc::channel(10);
+    let (src_tx, src_rx) = mpsc::unbounded();
+    let mut msgs = src_rx.map(Ok);
+    let mut cx = noop_context();
+
+    let mut send = tx.send_all(&mut msgs);
+    src_tx.unbounded_send(1).unwrap();
+    src_tx.unbounded_send(2).unwrap();
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    src_tx.unbounded_send(3).unwrap();
+    src_tx.close_channel();
+    assert_eq!(send.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+    drop(tx);
+
+    assert_eq!(block_on(rx.by_ref().collect::<Vec<_>>()), [1, 2, 3]);
+}
@@ -0,0 +1,72 @@
+use futures::channel::mpsc::{self, SenderEvent};
+use futures::executor::block_on;
+use futures::stream::{FusedStream, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+
+#[test]
+fn sender_count() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    assert_eq!(rx.sender_count(), 1);
+    let tx2 = tx.clone();
+    let weak = tx.downgrade();
+    assert_eq!(rx.sender_count(), 2);
+    drop(tx);
+    assert_eq!(rx.sender_count(), 1);
+    let tx3 = weak.upgrade().unwrap();
+    assert_eq!(rx.sender_count(), 2);
+    drop((tx2, tx3));
+    assert_eq!(rx.sender_count(), 0);
+}
+
+#[test]
+fn sender_events() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    let mut events = rx.sender_events();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(events.poll_next_unpin(&mut cx), Poll::Pending);
+
+    let tx2 = tx.clone();
+    assert_eq!(count, 1);
+    assert_eq!(events.poll_next_unpin(&mut cx), Poll::Ready(Some(SenderEvent::Increment(2))));
+    assert_eq!(events.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop(tx);
+    assert_eq!(count, 2);
+    assert_eq!(events.poll_next_unpin(&mut cx), Poll::Ready(Some(SenderEvent::Decrement(1))));
+
+    // The last sender gone, no sender can appear anymore.
+    drop(tx2);
+    assert_eq!(block_on(events.by_ref().collect::<Vec<_>>()), [SenderEvent::Decrement(0)]);
+    assert!(events.is_terminated());
+}
+
+#[test]
+fn sender_events_coalesced() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut events = rx.sender_events();
+    let mut cx = noop_context();
+
+    // Changes between two polls only yield their net effect.
+    drop(tx.clone());
+    let tx2 = tx.clone();
+    let tx3 = tx.clone();
+    assert_eq!(events.poll_next_unpin(&mut cx), Poll::Ready(Some(SenderEvent::Increment(2))));
+    assert_eq!(events.poll_next_unpin(&mut cx), Poll::Ready(Some(SenderEvent::Increment(3))));
+    assert_eq!(events.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop((tx, tx2, tx3));
+    let counts = block_on(events.map(|event| event.count()).collect::<Vec<_>>());
+    assert_eq!(counts, [2, 1, 0]);
+}
+
+#[test]
+fn sender_events_of_terminated_receiver() {
+    let (tx, mut rx) = mpsc::channel::<i32>(1);
+    drop(tx);
+    assert_eq!(block_on(rx.next()), None);
+    assert_eq!(rx.sender_count(), 0);
+    assert_eq!(block_on(rx.sender_events().next()), None);
+}
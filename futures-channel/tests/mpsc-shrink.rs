@@ -0,0 +1,68 @@
+use futures::channel::mpsc;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocated_since(before: usize) -> usize {
+    ALLOCATED.load(Ordering::SeqCst).saturating_sub(before)
+}
+
+// A single test, as the allocations of concurrent tests would be counted.
+#[test]
+fn burst_memory_is_released() {
+    const BURST: usize = 25_000;
+    const THREADS: usize = 4;
+    let (tx, mut rx) = mpsc::unbounded::<[u64; 4]>();
+    let before = ALLOCATED.load(Ordering::SeqCst);
+
+    for _ in 0..BURST {
+        tx.unbounded_send([0; 4]).unwrap();
+    }
+    assert!(allocated_since(before) > BURST * 32);
+
+    for _ in 0..BURST {
+        rx.try_next().unwrap().unwrap();
+    }
+    rx.shrink_to_fit();
+    // Only the block holding the next messages is left.
+    assert!(allocated_since(before) < 4096);
+
+    let threads: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                for _ in 0..BURST {
+                    tx.unbounded_send([1; 4]).unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert!(allocated_since(before) > THREADS * BURST * 32);
+
+    for _ in 0..THREADS * BURST {
+        assert_eq!(rx.try_next().unwrap(), Some([1; 4]));
+    }
+    rx.shrink_to_fit();
+    assert!(allocated_since(before) < 4096);
+}
@@ -0,0 +1,103 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::task::Poll;
+use futures_test::task::{noop_context, MockClock};
+use std::time::Duration;
+
+#[test]
+fn send_timeout_sends_with_capacity() {
+    let clock = MockClock::new();
+    let (mut tx, mut rx) = mpsc::channel(1);
+
+    block_on(tx.send_timeout(1, Duration::from_secs(1), &clock)).unwrap();
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+}
+
+#[test]
+fn send_timeout_elapses_when_full() {
+    let clock = MockClock::new();
+    let (mut tx, mut rx) = mpsc::channel(0);
+    let mut cx = noop_context();
+    tx.try_send(1).unwrap();
+
+    let mut send = tx.send_timeout(2, Duration::from_secs(1), &clock);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    clock.advance(Duration::from_millis(999));
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    clock.advance(Duration::from_millis(1));
+    let err = match send.poll_unpin(&mut cx) {
+        Poll::Ready(Err(err)) => err,
+        res => panic!("unexpected result: {:?}", res),
+    };
+    assert!(err.is_elapsed());
+    assert!(!err.is_disconnected());
+    assert_eq!(err.into_inner(), 2);
+
+    drop(send);
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert!(rx.try_next().is_err());
+}
+
+#[test]
+fn send_timeout_sends_once_capacity_frees() {
+    let clock = MockClock::new();
+    let (mut tx, mut rx) = mpsc::channel(0);
+    let mut cx = noop_context();
+    tx.try_send(1).unwrap();
+
+    let mut send = tx.send_timeout(2, Duration::from_secs(1), &clock);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+
+    // Capacity wins over the elapsed timeout.
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(send.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+}
+
+#[test]
+fn send_timeout_disconnected() {
+    let clock = MockClock::new();
+    let (mut tx, rx) = mpsc::channel(0);
+    drop(rx);
+
+    let err = block_on(tx.send_timeout(1, Duration::from_secs(1), &clock)).unwrap_err();
+    assert!(err.is_disconnected());
+    assert!(!err.is_elapsed());
+    assert_eq!(err.into_inner(), 1);
+}
+
+#[test]
+fn recv_timeout() {
+    let clock = MockClock::new();
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+    let mut cx = noop_context();
+
+    let mut recv = rx.recv_timeout(Duration::from_secs(1), &clock);
+    assert!(recv.poll_unpin(&mut cx).is_pending());
+    clock.advance(Duration::from_secs(1));
+    assert!(matches!(recv.poll_unpin(&mut cx), Poll::Ready(Err(_))));
+    drop(recv);
+
+    tx.try_send(1).unwrap();
+    assert_eq!(block_on(rx.recv_timeout(Duration::from_secs(1), &clock)), Ok(Some(1)));
+    drop(tx);
+    assert_eq!(block_on(rx.recv_timeout(Duration::from_secs(1), &clock)), Ok(None));
+}
+
+#[test]
+fn unbounded_recv_timeout() {
+    let clock = MockClock::new();
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    let mut cx = noop_context();
+
+    let mut recv = rx.recv_timeout(Duration::from_secs(1), &clock);
+    assert!(recv.poll_unpin(&mut cx).is_pending());
+    clock.advance(Duration::from_secs(1));
+    assert!(matches!(recv.poll_unpin(&mut cx), Poll::Ready(Err(_))));
+    drop(recv);
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(block_on(rx.recv_timeout(Duration::from_secs(1), &clock)), Ok(Some(1)));
+}
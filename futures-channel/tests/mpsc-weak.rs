@@ -0,0 +1,58 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::StreamExt;
+
+#[test]
+fn weak_sender_does_not_keep_channel_open() {
+    let (tx, mut rx) = mpsc::channel::<i32>(1);
+    let weak = tx.downgrade();
+    drop(tx);
+    assert_eq!(block_on(rx.next()), None);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_sender_upgrade() {
+    let (tx, rx) = mpsc::channel(1);
+    let weak = tx.downgrade();
+    let mut upgraded = weak.upgrade().unwrap();
+    assert!(upgraded.same_receiver(&tx));
+    drop(tx);
+
+    block_on(upgraded.send(1)).unwrap();
+    drop(upgraded);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1]);
+    assert!(weak.clone().upgrade().is_none());
+}
+
+#[test]
+fn weak_sender_after_receiver_dropped() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    let weak = tx.downgrade();
+    drop(rx);
+    let upgraded = weak.upgrade().unwrap();
+    assert!(upgraded.is_closed());
+    drop(tx);
+    drop(upgraded);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_sender_of_disconnected_sender() {
+    let (mut tx, _rx) = mpsc::channel::<i32>(1);
+    tx.disconnect();
+    assert!(tx.downgrade().upgrade().is_none());
+}
+
+#[test]
+fn weak_unbounded_sender() {
+    let (tx, mut rx) = mpsc::unbounded();
+    let weak = tx.downgrade();
+    weak.upgrade().unwrap().unbounded_send(1).unwrap();
+    drop(tx);
+
+    assert_eq!(block_on(rx.next()), Some(1));
+    assert_eq!(block_on(rx.next()), None);
+    assert!(weak.upgrade().is_none());
+}
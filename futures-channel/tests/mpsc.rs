@@ -630,3 +630,83 @@ fn send_backpressure_multi_senders() {
     let item = block_on(rx.next()).unwrap();
     assert_eq!(item, 2);
 }
+
+#[test]
+fn stress_unbounded_order_per_sender() {
+    const AMT: u32 = if cfg!(miri) { 100 } else { 10000 };
+    const NTHREADS: u32 = 4;
+    let (tx, rx) = mpsc::unbounded::<(u32, u32)>();
+
+    for id in 0..NTHREADS {
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for i in 0..AMT {
+                tx.unbounded_send((id, i)).unwrap();
+            }
+        });
+    }
+    drop(tx);
+
+    let mut next = vec![0; NTHREADS as usize];
+    for (id, i) in block_on_stream(rx) {
+        assert_eq!(next[id as usize], i);
+        next[id as usize] += 1;
+    }
+    assert_eq!(next, vec![AMT; NTHREADS as usize]);
+}
+
+#[test]
+fn drop_receiver_drops_buffered_messages() {
+    let msg = Arc::new(());
+    let (tx, mut rx) = mpsc::unbounded();
+    for _ in 0..100 {
+        tx.unbounded_send(msg.clone()).unwrap();
+    }
+    for _ in 0..40 {
+        assert!(rx.try_next().unwrap().is_some());
+    }
+    assert_eq!(Arc::strong_count(&msg), 61);
+
+    drop(rx);
+    assert_eq!(Arc::strong_count(&msg), 1);
+}
+
+#[test]
+fn burst_wakes_receiver_once() {
+    let (mut tx, mut rx) = mpsc::channel(8);
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+
+    for i in 0..5 {
+        tx.try_send(i).unwrap();
+    }
+    assert_eq!(counter, 1);
+
+    for i in 0..5 {
+        assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some(i)));
+    }
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    tx.try_send(5).unwrap();
+    assert_eq!(counter, 2);
+}
+
+#[test]
+fn recv_many_unparks_parked_senders() {
+    let (tx, mut rx) = mpsc::channel(0);
+    let mut senders = vec![tx.clone(), tx.clone(), tx];
+    let (waker, counter) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    for (i, tx) in senders.iter_mut().enumerate() {
+        tx.try_send(i).unwrap();
+        assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+    }
+
+    let mut buf = Vec::new();
+    assert_eq!(block_on(rx.recv_many(&mut buf, 3)), 3);
+    assert_eq!(buf, [0, 1, 2]);
+    assert_eq!(counter, 3);
+    for tx in &mut senders {
+        assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    }
+}
@@ -1,6 +1,6 @@
 use futures::channel::oneshot::{self, Sender};
 use futures::executor::block_on;
-use futures::future::{poll_fn, FutureExt};
+use futures::future::{poll_fn, FusedFuture, FutureExt};
 use futures::task::{Context, Poll};
 use futures_test::task::panic_waker_ref;
 use std::sync::mpsc;
@@ -254,3 +254,75 @@ fn cancel_sends() {
 //         },
 //     }
 // }
+
+#[test]
+fn blocking_recv() {
+    let (tx, rx) = oneshot::channel::<u32>();
+    tx.send(1).unwrap();
+    assert_eq!(rx.blocking_recv(), Ok(1));
+
+    let (tx, rx) = oneshot::channel::<u32>();
+    let handle = thread::spawn(move || {
+        thread::sleep(std::time::Duration::from_millis(10));
+        tx.send(2).unwrap();
+    });
+    assert_eq!(rx.blocking_recv(), Ok(2));
+    handle.join().unwrap();
+}
+
+#[test]
+fn blocking_recv_canceled() {
+    let (tx, rx) = oneshot::channel::<u32>();
+    let handle = thread::spawn(move || drop(tx));
+    assert_eq!(rx.blocking_recv(), Err(oneshot::Canceled));
+    handle.join().unwrap();
+}
+
+#[test]
+fn poll_peek_leaves_value() {
+    let (tx, mut rx) = oneshot::channel::<u32>();
+    let mut cx = futures_test::task::noop_context();
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Pending);
+
+    tx.send(1).unwrap();
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Ok(&1)));
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Ok(&1)));
+    assert!(!rx.is_terminated());
+    assert_eq!(block_on(rx), Ok(1));
+}
+
+#[test]
+fn poll_peek_canceled() {
+    let (tx, mut rx) = oneshot::channel::<u32>();
+    drop(tx);
+    let mut cx = futures_test::task::noop_context();
+    assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Err(oneshot::Canceled)));
+}
+
+#[test]
+fn try_peek() {
+    let (tx, mut rx) = oneshot::channel::<u32>();
+    assert_eq!(rx.try_peek(), Ok(None));
+    tx.send(1).unwrap();
+    assert_eq!(rx.try_peek(), Ok(Some(&1)));
+    assert_eq!(rx.try_recv(), Ok(Some(1)));
+    assert_eq!(rx.try_peek(), Err(oneshot::Canceled));
+}
+
+#[test]
+fn close_with_reason() {
+    let (tx, mut rx) = oneshot::channel::<u32>();
+    assert!(tx.close_reason().is_none());
+    rx.close_with("shutting down");
+    assert!(tx.is_canceled());
+    assert_eq!(tx.close_reason().unwrap().downcast_ref(), Some(&"shutting down"));
+    assert_eq!(tx.send(1), Err(1));
+}
+
+#[test]
+fn close_with_after_send_is_ignored() {
+    let (tx, mut rx) = oneshot::channel::<u32>();
+    tx.send(1).unwrap();
+    rx.close_with("late");
+    assert_eq!(rx.try_recv(), Ok(Some(1)));
+}
@@ -0,0 +1,96 @@
+use futures::channel::priority;
+use futures::executor::block_on;
+use futures::future::poll_fn;
+use futures::stream::StreamExt;
+use futures_test::task::new_count_waker;
+use std::task::{Context, Poll};
+use std::thread;
+
+#[test]
+fn highest_level_first_fifo_within_level() {
+    let (tx, mut rx) = priority::unbounded(3);
+    tx.send(0, "low 1").unwrap();
+    tx.send(2, "high 1").unwrap();
+    tx.send(1, "mid").unwrap();
+    tx.send(0, "low 2").unwrap();
+    tx.send(2, "high 2").unwrap();
+    assert_eq!(rx.len(), 5);
+    assert_eq!(rx.level_len(2), 2);
+
+    assert_eq!(rx.try_next(), Ok(Some("high 1")));
+    tx.send(1, "mid 2").unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), ["high 2", "mid", "mid 2", "low 1", "low 2"]);
+}
+
+#[test]
+fn send_wakes_receiver() {
+    let (tx, mut rx) = priority::unbounded(2);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    assert!(rx.try_next().is_err());
+    tx.send(1, 1).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(count, 2);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(rx.try_next(), Ok(None));
+}
+
+#[test]
+fn close_drains_buffered_messages() {
+    let (tx, mut rx) = priority::unbounded(2);
+    tx.send(0, 1).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(1, 2).unwrap_err().into_inner(), 2);
+    assert_eq!(block_on(rx.next()), Some(1));
+    assert_eq!(block_on(rx.next()), None);
+}
+
+#[test]
+fn send_after_receiver_dropped() {
+    let (tx, rx) = priority::unbounded(1);
+    drop(rx);
+    assert!(tx.send(0, ()).is_err());
+}
+
+#[test]
+#[should_panic(expected = "priority level 2 out of range")]
+fn send_level_out_of_range() {
+    let (tx, _rx) = priority::unbounded(2);
+    let _ = tx.send(2, ());
+}
+
+#[test]
+fn send_from_many_threads() {
+    let (tx, mut rx) = priority::unbounded(2);
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for j in 0..100 {
+                    tx.send(j % 2, (i, j)).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let mut received = 0;
+    block_on(poll_fn(|cx| {
+        while let Some((_, _)) = futures::ready!(rx.poll_next_unpin(cx)) {
+            received += 1;
+        }
+        Poll::Ready(())
+    }));
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(received, 400);
+}
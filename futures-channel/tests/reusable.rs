@@ -0,0 +1,92 @@
+use futures::channel::oneshot::Canceled;
+use futures::channel::reusable;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures_test::task::new_count_waker;
+use std::task::{Context, Poll};
+use std::thread;
+
+#[test]
+fn reuse_for_many_exchanges() {
+    let (mut tx, mut rx) = reusable::channel();
+    assert!(tx.is_connected_to(&rx));
+    for i in 0..10 {
+        assert_eq!(rx.try_recv(), Ok(None));
+        tx.send(i).unwrap();
+        assert_eq!(block_on(rx.recv()), Ok(i));
+    }
+}
+
+#[test]
+fn send_before_receive_fails() {
+    let (mut tx, mut rx) = reusable::channel();
+    tx.send(1).unwrap();
+    assert_eq!(tx.send(2), Err(2));
+
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Pending);
+    assert_eq!(rx.try_recv(), Ok(Some(1)));
+    assert_eq!(count, 1);
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Ok(())));
+    tx.send(2).unwrap();
+}
+
+#[test]
+fn recv_wakes_on_send() {
+    let (mut tx, mut rx) = reusable::channel();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut recv = rx.recv();
+    assert_eq!(recv.poll_unpin(&mut cx), Poll::Pending);
+    tx.send(1).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(recv.poll_unpin(&mut cx), Poll::Ready(Ok(1)));
+}
+
+#[test]
+fn sender_dropped() {
+    let (mut tx, mut rx) = reusable::channel();
+    tx.send(1).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.recv()), Ok(1));
+    assert_eq!(block_on(rx.recv()), Err(Canceled));
+    assert_eq!(rx.try_recv(), Err(Canceled));
+}
+
+#[test]
+fn receiver_dropped() {
+    let (mut tx, rx) = reusable::channel::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(tx.poll_canceled(&mut cx), Poll::Pending);
+    drop(rx);
+    assert_eq!(count, 1);
+    assert!(tx.is_canceled());
+    assert_eq!(tx.poll_canceled(&mut cx), Poll::Ready(()));
+    assert_eq!(tx.poll_ready(&mut cx), Poll::Ready(Err(Canceled)));
+    assert_eq!(tx.send(1), Err(1));
+}
+
+#[test]
+fn request_response_across_threads() {
+    let (mut req_tx, mut req_rx) = reusable::channel::<u32>();
+    let (mut resp_tx, mut resp_rx) = reusable::channel::<u32>();
+    let server = thread::spawn(move || {
+        block_on(async {
+            while let Ok(req) = req_rx.recv().await {
+                resp_tx.send(req * 2).unwrap();
+            }
+        })
+    });
+    block_on(async {
+        for i in 0..100 {
+            req_tx.send(i).unwrap();
+            assert_eq!(resp_rx.recv().await, Ok(i * 2));
+        }
+    });
+    drop(req_tx);
+    server.join().unwrap();
+}
@@ -0,0 +1,143 @@
+use futures::channel::spsc;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::{Stream, StreamExt};
+use futures_test::task::{new_count_waker, noop_context};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread;
+
+#[test]
+fn send_recv() {
+    let (mut tx, mut rx) = spsc::channel(3);
+    for i in 0..3 {
+        tx.try_send(i).unwrap();
+    }
+    assert!(tx.try_send(3).unwrap_err().is_full());
+    assert_eq!(rx.len(), 3);
+    assert_eq!(rx.size_hint(), (3, None));
+
+    assert_eq!(rx.try_next().unwrap(), Some(0));
+    tx.try_send(3).unwrap();
+    drop(tx);
+    assert_eq!(rx.size_hint(), (3, Some(3)));
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), [1, 2, 3]);
+}
+
+#[test]
+fn receiver_woken_by_send() {
+    let (mut tx, mut rx) = spsc::channel(2);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    tx.try_send(1).unwrap();
+    assert_eq!(count, 1);
+    tx.try_send(2).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(count, 2);
+    assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert!(futures::stream::FusedStream::is_terminated(&rx));
+}
+
+#[test]
+fn send_waits_for_capacity() {
+    let (mut tx, mut rx) = spsc::channel(1);
+    tx.try_send(1).unwrap();
+
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut send = tx.send(2);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(count, 1);
+    assert_eq!(send.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+    assert!(rx.try_next().is_err());
+}
+
+#[test]
+fn send_fails_once_receiver_dropped() {
+    let (mut tx, rx) = spsc::channel(1);
+    tx.try_send(1).unwrap();
+
+    let (waker, count) = new_count_waker();
+    assert!(tx.poll_ready(&mut Context::from_waker(&waker)).is_pending());
+    drop(rx);
+    assert_eq!(count, 1);
+    assert!(tx.is_closed());
+    let err = block_on(tx.send(2)).unwrap_err();
+    assert!(err.is_disconnected());
+    assert_eq!(err.into_inner(), 2);
+}
+
+#[test]
+fn messages_dropped_with_channel() {
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut tx, rx) = spsc::channel(4);
+    for _ in 0..3 {
+        tx.try_send(Counted(drops.clone())).ok().unwrap();
+    }
+    drop(rx);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+
+    let (mut tx, rx) = spsc::channel(4);
+    tx.try_send(Counted(drops.clone())).ok().unwrap();
+    drop(tx);
+    drop(rx);
+    assert_eq!(drops.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn stress_threads() {
+    const AMT: u32 = 10_000;
+
+    let (mut tx, rx) = spsc::channel(3);
+    let handle = thread::spawn(move || {
+        block_on(async {
+            for i in 0..AMT {
+                tx.send(i).await.unwrap();
+            }
+        })
+    });
+
+    let received = block_on(rx.collect::<Vec<_>>());
+    handle.join().unwrap();
+    assert_eq!(received, (0..AMT).collect::<Vec<_>>());
+}
+
+#[test]
+fn try_next_empty() {
+    let (tx, mut rx) = spsc::channel::<i32>(1);
+    assert!(rx.try_next().is_err());
+    assert_eq!(rx.poll_next_unpin(&mut noop_context()), Poll::Pending);
+    drop(tx);
+    assert_eq!(rx.try_next().unwrap(), None);
+}
+
+#[test]
+fn sink_send_all() {
+    use futures::sink::SinkExt;
+
+    let (mut tx, rx) = spsc::channel(2);
+    let handle = thread::spawn(move || {
+        let mut msgs = futures::stream::iter(0..100).map(Ok);
+        block_on(tx.send_all(&mut msgs)).unwrap();
+    });
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), (0..100).collect::<Vec<_>>());
+    handle.join().unwrap();
+}
@@ -0,0 +1,95 @@
+use futures::channel::watch;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures_test::task::new_count_waker;
+use std::task::{Context, Poll};
+use std::thread;
+
+#[test]
+fn changed_sees_latest_value() {
+    let (tx, mut rx) = watch::channel(0);
+    assert_eq!(rx.has_changed(), Ok(false));
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(rx.has_changed(), Ok(true));
+    assert_eq!(*rx.borrow(), 2);
+    block_on(rx.changed()).unwrap();
+    assert_eq!(rx.has_changed(), Ok(false));
+    assert_eq!(*tx.borrow(), 2);
+}
+
+#[test]
+fn changed_wakes_on_send() {
+    let (tx, mut rx) = watch::channel(0);
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut changed = rx.changed();
+    assert!(changed.poll_unpin(&mut cx).is_pending());
+    tx.send(1).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(changed.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn borrow_and_update_marks_seen() {
+    let (tx, mut rx) = watch::channel("a");
+    tx.send("b").unwrap();
+    assert_eq!(*rx.borrow_and_update(), "b");
+    assert_eq!(rx.has_changed(), Ok(false));
+}
+
+#[test]
+fn sender_dropped() {
+    let (tx, mut rx) = watch::channel(0);
+    tx.send(1).unwrap();
+    drop(tx);
+
+    // The last change is still seen.
+    assert!(rx.has_changed().is_err());
+    assert_eq!(block_on(rx.changed()), Ok(()));
+    assert!(block_on(rx.changed()).is_err());
+    assert_eq!(*rx.borrow(), 1);
+}
+
+#[test]
+fn send_without_receivers() {
+    let (tx, rx) = watch::channel(0);
+    drop(rx);
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(1).unwrap_err().into_inner(), 1);
+    assert_eq!(tx.send_replace(2), 0);
+
+    let rx = tx.subscribe();
+    assert_eq!(rx.has_changed(), Ok(false));
+    assert_eq!(*rx.borrow(), 2);
+}
+
+#[test]
+fn cloned_receivers() {
+    let (tx, rx1) = watch::channel(0);
+    tx.send(1).unwrap();
+    let mut rx2 = rx1.clone();
+    assert_eq!(tx.receiver_count(), 2);
+    assert!(rx1.same_channel(&rx2));
+    assert_eq!(rx2.has_changed(), Ok(true));
+    block_on(rx2.changed()).unwrap();
+    assert_eq!(rx1.has_changed(), Ok(true));
+}
+
+#[test]
+fn watch_across_threads() {
+    let (tx, mut rx) = watch::channel(0);
+    let handle = thread::spawn(move || {
+        block_on(async {
+            while *rx.borrow_and_update() < 100 {
+                rx.changed().await.unwrap();
+            }
+        })
+    });
+    for i in 1..=100 {
+        tx.send(i).unwrap();
+    }
+    handle.join().unwrap();
+}
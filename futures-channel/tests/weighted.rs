@@ -0,0 +1,131 @@
+use futures::channel::weighted;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::{Context, Poll};
+use std::thread;
+
+#[test]
+fn capacity_is_weight() {
+    let (tx, mut rx) = weighted::channel(10, |msg: &Vec<u8>| msg.len());
+    tx.try_send(vec![0; 4]).unwrap();
+    tx.try_send(vec![0; 6]).unwrap();
+    assert!(tx.try_send(vec![0; 1]).unwrap_err().is_full());
+    assert_eq!((rx.len(), rx.weight()), (2, 10));
+
+    assert_eq!(rx.try_next().unwrap().unwrap().len(), 4);
+    assert!(tx.try_send(vec![0; 5]).unwrap_err().is_full());
+    tx.try_send(vec![0; 4]).unwrap();
+    assert_eq!(tx.weight(), 10);
+}
+
+#[test]
+fn oversized_message_sent_once_empty() {
+    let (tx, mut rx) = weighted::channel(10, |msg: &usize| *msg);
+    tx.try_send(1).unwrap();
+    assert!(tx.try_send(20).unwrap_err().is_full());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    tx.try_send(20).unwrap();
+    assert!(tx.try_send(0).unwrap_err().is_full());
+    assert_eq!(rx.try_next().unwrap(), Some(20));
+}
+
+#[test]
+fn send_waits_for_weight() {
+    let (tx, mut rx) = weighted::channel(10, |msg: &usize| *msg);
+    tx.try_send(3).unwrap();
+    tx.try_send(6).unwrap();
+
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut send = tx.send(5);
+    assert!(send.poll_unpin(&mut cx).is_pending());
+
+    // Receiving the light message doesn't make room for the waiting one.
+    assert_eq!(rx.try_next().unwrap(), Some(3));
+    assert_eq!(count, 0);
+    assert_eq!(rx.try_next().unwrap(), Some(6));
+    assert_eq!(count, 1);
+    assert_eq!(send.poll_unpin(&mut cx), Poll::Ready(Ok(())));
+    assert_eq!(rx.weight(), 5);
+}
+
+#[test]
+fn waiting_senders_not_overtaken() {
+    let (tx, mut rx) = weighted::channel(10, |msg: &usize| *msg);
+    tx.try_send(8).unwrap();
+
+    let mut heavy = tx.send(10);
+    assert!(heavy.poll_unpin(&mut noop_context()).is_pending());
+    // A light message fits, but would starve the heavy one.
+    assert!(tx.try_send(1).unwrap_err().is_full());
+    let mut light = tx.send(1);
+    assert!(light.poll_unpin(&mut noop_context()).is_pending());
+
+    assert_eq!(rx.try_next().unwrap(), Some(8));
+    assert!(light.poll_unpin(&mut noop_context()).is_pending());
+    assert_eq!(heavy.poll_unpin(&mut noop_context()), Poll::Ready(Ok(())));
+    assert!(light.poll_unpin(&mut noop_context()).is_pending());
+    assert_eq!(rx.try_next().unwrap(), Some(10));
+    assert_eq!(light.poll_unpin(&mut noop_context()), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn dropped_send_passes_turn_on() {
+    let (tx, mut rx) = weighted::channel(10, |msg: &usize| *msg);
+    tx.try_send(10).unwrap();
+
+    let mut first = tx.send(10);
+    assert!(first.poll_unpin(&mut noop_context()).is_pending());
+    let (waker, count) = new_count_waker();
+    let mut second = tx.send(5);
+    assert!(second.poll_unpin(&mut Context::from_waker(&waker)).is_pending());
+
+    assert_eq!(rx.try_next().unwrap(), Some(10));
+    assert_eq!(count, 0);
+    drop(first);
+    assert_eq!(count, 1);
+    assert_eq!(second.poll_unpin(&mut noop_context()), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn send_fails_once_receiver_dropped() {
+    let (tx, rx) = weighted::channel(1, |_: &i32| 1);
+    tx.try_send(1).unwrap();
+
+    let (waker, count) = new_count_waker();
+    let mut send = tx.send(2);
+    assert!(send.poll_unpin(&mut Context::from_waker(&waker)).is_pending());
+    drop(rx);
+    assert_eq!(count, 1);
+    let err = block_on(send).unwrap_err();
+    assert!(err.is_disconnected());
+    assert_eq!(err.into_inner(), 2);
+    assert!(tx.is_closed());
+}
+
+#[test]
+fn stress_threads() {
+    let (tx, rx) = weighted::channel(64, |msg: &usize| *msg % 32);
+    let handles = (0..4)
+        .map(|t| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                block_on(async {
+                    for i in 0..1000 {
+                        tx.send(t * 1000 + i).await.unwrap();
+                    }
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let mut received = block_on(rx.collect::<Vec<_>>());
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    received.sort_unstable();
+    assert_eq!(received, (0..4000).collect::<Vec<_>>());
+}
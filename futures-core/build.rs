@@ -37,5 +37,8 @@ fn main() {
         println!("cargo:rustc-cfg=futures_no_atomic_cas");
     }
 
+    // `--cfg loom` swaps the synchronization primitives for loom's.
+    println!("cargo:rustc-check-cfg=cfg(loom)");
+
     println!("cargo:rerun-if-changed=no_atomic_cas.rs");
 }
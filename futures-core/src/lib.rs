@@ -15,6 +15,11 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+// Only used by the lock-free `AtomicWaker`.
+#[cfg(not(feature = "critical-section"))]
+#[cfg(not(futures_no_atomic_cas))]
+mod loom;
+
 pub mod future;
 #[doc(no_inline)]
 pub use self::future::{FusedFuture, Future, TryFuture};
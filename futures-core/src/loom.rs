@@ -0,0 +1,47 @@
+//! The synchronization primitives used by this crate.
+//!
+//! When building with `RUSTFLAGS="--cfg loom"`, these are swapped for the
+//! types of the [loom](https://docs.rs/loom) model checker, so that the
+//! primitives of this crate can be exercised under loom, both by the tests of
+//! this crate and by the tests of crates depending on it.
+
+#[cfg(loom)]
+pub(crate) use ::loom::sync::atomic;
+#[cfg(not(loom))]
+#[cfg(not(feature = "portable-atomic"))]
+pub(crate) use core::sync::atomic;
+#[cfg(not(loom))]
+#[cfg(feature = "portable-atomic")]
+pub(crate) use portable_atomic as atomic;
+
+#[cfg(loom)]
+pub(crate) use ::loom::cell::UnsafeCell;
+
+/// `core::cell::UnsafeCell` with the closure-based API of loom's `UnsafeCell`.
+#[cfg(not(loom))]
+pub(crate) struct UnsafeCell<T>(core::cell::UnsafeCell<T>);
+
+#[cfg(not(loom))]
+impl<T> UnsafeCell<T> {
+    pub(crate) const fn new(data: T) -> Self {
+        Self(core::cell::UnsafeCell::new(data))
+    }
+
+    pub(crate) fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
+
+/// Signals that the caller is busy-waiting for another thread.
+#[cfg(loom)]
+pub(crate) fn spin_loop() {
+    ::loom::thread::yield_now();
+}
+
+/// Signals that the caller is busy-waiting for another thread.
+#[cfg(not(loom))]
+pub(crate) fn spin_loop() {
+    // `core::hint::spin_loop` requires Rust 1.49.
+    #[allow(deprecated)]
+    core::sync::atomic::spin_loop_hint();
+}
@@ -1,15 +1,9 @@
-use core::cell::UnsafeCell;
 use core::fmt;
 use core::task::Waker;
 
-use atomic::AtomicUsize;
-use atomic::Ordering::{AcqRel, Acquire, Release};
-
-#[cfg(feature = "portable-atomic")]
-use portable_atomic as atomic;
-
-#[cfg(not(feature = "portable-atomic"))]
-use core::sync::atomic;
+use crate::loom::atomic::Ordering::{AcqRel, Acquire, Release};
+use crate::loom::atomic::{AtomicBool, AtomicUsize};
+use crate::loom::{spin_loop, UnsafeCell};
 
 /// A synchronization primitive for task wakeup.
 ///
@@ -96,6 +90,9 @@ use core::sync::atomic;
 pub struct AtomicWaker {
     state: AtomicUsize,
     waker: UnsafeCell<Option<Waker>>,
+    /// Whether `waker` currently holds a value. Only written while holding
+    /// either lock, so that it can be read without touching `state`.
+    registered: AtomicBool,
 }
 
 // `AtomicWaker` is a multi-consumer, single-producer transfer cell. The cell
@@ -168,6 +165,14 @@ pub struct AtomicWaker {
 // If another thread is in the `wake` critical section, then it will handle
 // waking the task.
 //
+// # Querying the registration
+//
+// The waker cell can't be inspected without taking one of the locks, and
+// taking either of them for a read-only query would interfere with concurrent
+// calls to `register` or `wake`. Instead, both critical sections mirror whether
+// the cell holds a waker in the separate `registered` flag before releasing
+// their lock, and `is_registered` only ever loads that flag.
+//
 // # A potential race (is safely handled).
 //
 // Imagine the following situation:
@@ -204,12 +209,30 @@ const WAKING: usize = 0b10;
 
 impl AtomicWaker {
     /// Create an `AtomicWaker`.
+    #[cfg(not(loom))]
     pub const fn new() -> Self {
         // Make sure that task is Sync
         trait AssertSync: Sync {}
         impl AssertSync for Waker {}
 
-        Self { state: AtomicUsize::new(WAITING), waker: UnsafeCell::new(None) }
+        Self {
+            state: AtomicUsize::new(WAITING),
+            waker: UnsafeCell::new(None),
+            registered: AtomicBool::new(false),
+        }
+    }
+
+    /// Create an `AtomicWaker`.
+    ///
+    /// This is not a `const fn` under `--cfg loom`, as loom's atomics can't be
+    /// created in constant expressions.
+    #[cfg(loom)]
+    pub fn new() -> Self {
+        Self {
+            state: AtomicUsize::new(WAITING),
+            waker: UnsafeCell::new(None),
+            registered: AtomicBool::new(false),
+        }
     }
 
     /// Registers the waker to be notified on calls to `wake`.
@@ -230,6 +253,14 @@ impl AtomicWaker {
     /// tasks to be notified. One of the callers will win and have its task set,
     /// but there is no guarantee as to which caller will succeed.
     ///
+    /// If the currently registered waker [`will_wake`](Waker::will_wake) the
+    /// same task as `waker`, the stored waker is kept as is: `waker` is not
+    /// cloned and the old waker is not dropped. The registering lock is still
+    /// taken, as the stored waker can't be compared to `waker` otherwise: a
+    /// concurrent call to `wake` may be taking it out of the cell. Avoiding
+    /// that compare-and-swap would need an atomic copy of the identity of the
+    /// stored waker, which requires `Waker::as_raw` (Rust 1.83).
+    ///
     /// # Examples
     ///
     /// Here is how `register` is used when implementing a flag.
@@ -263,6 +294,90 @@ impl AtomicWaker {
     /// }
     /// ```
     pub fn register(&self, waker: &Waker) {
+        let res = self.register_with(|slot| {
+            // Avoid cloning the waker if the old waker will awaken the same task.
+            match slot {
+                Some(old_waker) if old_waker.will_wake(waker) => (),
+                _ => *slot = Some(waker.clone()),
+            }
+        });
+        match res {
+            Ok(()) => {}
+            Err(WAKING) => {
+                // Currently in the process of waking the task, i.e.,
+                // `wake` is currently being called on the old task handle.
+                //
+                // memory ordering: we acquired the state for all
+                // concurrent wakes, but future wakes might still
+                // need to wake us in case we can't make progress
+                // from the pending wakes.
+                //
+                // So we simply schedule to come back later (we
+                // could also spin here trying to acquire the lock
+                // to register).
+                waker.wake_by_ref();
+            }
+            Err(state) => {
+                // In this case, a concurrent thread is holding the
+                // "registering" lock. This probably indicates a bug in the
+                // caller's code as racing to call `register` doesn't make much
+                // sense.
+                //
+                // memory ordering: don't care. a concurrent register() is going
+                // to succeed and provide proper memory ordering.
+                //
+                // We just want to maintain memory safety. It is ok to drop the
+                // call to `register`.
+                debug_assert!(state == REGISTERING || state == REGISTERING | WAKING);
+            }
+        }
+    }
+
+    /// Registers the waker to be notified on calls to `wake`, returning the
+    /// waker that was previously registered.
+    ///
+    /// This behaves like [`register`](Self::register), except that the old
+    /// waker is handed back to the caller instead of being dropped, and the
+    /// new waker is always stored. The old waker is replaced in the same
+    /// locked section, so no wake can be lost in between.
+    ///
+    /// The race semantics differ from `register`'s in one way: where
+    /// `register` gives up if a concurrent call to `wake` (or `take`) or
+    /// `register` holds the lock, `swap` waits for that call to leave its
+    /// critical section, which only takes the waker out of the cell or puts
+    /// one in, and then installs `waker`. A concurrent `wake` therefore
+    /// either takes and wakes the old waker before the swap, in which case
+    /// `None` is returned, or wakes `waker` right after it is installed, as
+    /// with `register`.
+    ///
+    /// Returns `None` if no waker was registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::task::{noop_waker, AtomicWaker};
+    ///
+    /// let atomic_waker = AtomicWaker::new();
+    /// let waker = noop_waker();
+    ///
+    /// assert!(atomic_waker.swap(&waker).is_none());
+    /// assert!(atomic_waker.swap(&waker).is_some());
+    /// ```
+    pub fn swap(&self, waker: &Waker) -> Option<Waker> {
+        loop {
+            match self.register_with(|slot| slot.replace(waker.clone())) {
+                Ok(old) => return old,
+                Err(_) => spin_loop(),
+            }
+        }
+    }
+
+    /// Acquires the registering lock and calls `update` on the waker cell.
+    ///
+    /// Returns the state which prevented it without calling `update` if the
+    /// lock could not be acquired. If a concurrent `wake` is observed while
+    /// holding the lock, the waker left in the cell by `update` is woken.
+    fn register_with<R>(&self, update: impl FnOnce(&mut Option<Waker>) -> R) -> Result<R, usize> {
         match self
             .state
             .compare_exchange(WAITING, REGISTERING, Acquire, Acquire)
@@ -271,7 +386,11 @@ impl AtomicWaker {
             WAITING => {
                 unsafe {
                     // Locked acquired, update the waker cell
-                    *self.waker.get() = Some(waker.clone());
+                    let ret = self.waker.with_mut(|waker| {
+                        let ret = update(&mut *waker);
+                        self.registered.store((*waker).is_some(), Release);
+                        ret
+                    });
 
                     // Release the lock. If the state transitioned to include
                     // the `WAKING` bit, this means that at least one wake has
@@ -302,7 +421,8 @@ impl AtomicWaker {
 
                             // Take the waker to wake once the atomic operation has
                             // completed.
-                            let waker = (*self.waker.get()).take().unwrap();
+                            let waker = self.waker.with_mut(|waker| (*waker).take().unwrap());
+                            self.registered.store(false, Release);
 
                             // We need to return to WAITING state (clear our lock and
                             // concurrent WAKING flag). This needs to acquire all
@@ -320,35 +440,11 @@ impl AtomicWaker {
                             waker.wake();
                         }
                     }
+
+                    Ok(ret)
                 }
             }
-            WAKING => {
-                // Currently in the process of waking the task, i.e.,
-                // `wake` is currently being called on the old task handle.
-                //
-                // memory ordering: we acquired the state for all
-                // concurrent wakes, but future wakes might still
-                // need to wake us in case we can't make progress
-                // from the pending wakes.
-                //
-                // So we simply schedule to come back later (we
-                // could also spin here trying to acquire the lock
-                // to register).
-                waker.wake_by_ref();
-            }
-            state => {
-                // In this case, a concurrent thread is holding the
-                // "registering" lock. This probably indicates a bug in the
-                // caller's code as racing to call `register` doesn't make much
-                // sense.
-                //
-                // memory ordering: don't care. a concurrent register() is going
-                // to succeed and provide proper memory ordering.
-                //
-                // We just want to maintain memory safety. It is ok to drop the
-                // call to `register`.
-                debug_assert!(state == REGISTERING || state == REGISTERING | WAKING);
-            }
+            state => Err(state),
         }
     }
 
@@ -356,8 +452,22 @@ impl AtomicWaker {
     ///
     /// If `register` has not been called yet, then this does nothing.
     pub fn wake(&self) {
-        if let Some(waker) = self.take() {
-            waker.wake();
+        self.wake_if_registered();
+    }
+
+    /// Calls `wake` on the last `Waker` passed to `register`, like
+    /// [`wake`](Self::wake), and reports whether there was one.
+    ///
+    /// Returns `true` if a registered waker was woken, and `false` if there was
+    /// nothing to wake. The latter is also the case when the wake is left to a
+    /// concurrent call to `register` or `wake`.
+    pub fn wake_if_registered(&self) -> bool {
+        match self.take() {
+            Some(waker) => {
+                waker.wake();
+                true
+            }
+            None => false,
         }
     }
 
@@ -376,7 +486,8 @@ impl AtomicWaker {
         match self.state.fetch_or(WAKING, AcqRel) {
             WAITING => {
                 // The waking lock has been acquired.
-                let waker = unsafe { (*self.waker.get()).take() };
+                let waker = self.waker.with_mut(|waker| unsafe { (*waker).take() });
+                self.registered.store(false, Release);
 
                 // Release the lock
                 self.state.fetch_and(!WAKING, Release);
@@ -398,6 +509,39 @@ impl AtomicWaker {
             }
         }
     }
+
+    /// Returns whether a waker is currently registered.
+    ///
+    /// This is a cheap check that doesn't interfere with concurrent calls to
+    /// `register` or `wake`, meant for producers deciding how to hand work
+    /// over: for example, to enqueue it eagerly and wake the consumer only if
+    /// it is parked, and to batch it up otherwise. Because of those
+    /// concurrent calls, the result is only a hint which may already be
+    /// outdated by the time it is returned, so a producer must still call
+    /// `wake` after publishing work the consumer may be waiting for.
+    ///
+    /// There is no `will_wake` counterpart comparing the registered waker to
+    /// a given one: reading the registered waker races with `wake` taking it
+    /// out of the cell unless the registering lock is held, and holding that
+    /// lock would make concurrent calls to `register` fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::task::{noop_waker, AtomicWaker};
+    ///
+    /// let atomic_waker = AtomicWaker::new();
+    /// assert!(!atomic_waker.is_registered());
+    ///
+    /// atomic_waker.register(&noop_waker());
+    /// assert!(atomic_waker.is_registered());
+    ///
+    /// atomic_waker.wake();
+    /// assert!(!atomic_waker.is_registered());
+    /// ```
+    pub fn is_registered(&self) -> bool {
+        self.registered.load(Acquire)
+    }
 }
 
 impl Default for AtomicWaker {
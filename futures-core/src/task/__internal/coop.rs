@@ -0,0 +1,120 @@
+//! Cooperative scheduling budget.
+//!
+//! Executors give each poll of a task a budget by calling [`budget`]. Leaf
+//! futures and streams, such as channel receivers, consume one unit of it with
+//! [`poll_proceed`] each time they make progress. Once the budget is exhausted
+//! they return `Poll::Pending` after waking the task, forcing the task to yield
+//! back to the executor, even if it only awaits futures that are always ready.
+//!
+//! While a unit consumed by `poll_proceed` is held, nested calls to it, such
+//! as the ones made by a channel receiver polled by `StreamExt::next`, don't
+//! consume any more budget, so that each item is only charged once.
+//!
+//! Outside of a call to `budget`, or inside of [`with_unconstrained`], the
+//! budget is unlimited.
+
+use core::cell::Cell;
+use core::task::{Context, Poll};
+
+/// The budget given to each poll of a task by [`budget`].
+pub const INITIAL_BUDGET: u8 = 128;
+
+std::thread_local! {
+    static CURRENT: Cell<Option<u8>> = Cell::new(None);
+    // Whether a unit of the current budget is held by a `Proceed` guard.
+    static CHARGING: Cell<bool> = Cell::new(false);
+}
+
+/// Calls `f` with the current budget set to `budget`, restoring the previous
+/// budget afterwards.
+fn with_budget<R>(budget: Option<u8>, f: impl FnOnce() -> R) -> R {
+    struct Reset(Option<u8>, bool);
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            let _ = CURRENT.try_with(|current| current.set(self.0));
+            let _ = CHARGING.try_with(|charging| charging.set(self.1));
+        }
+    }
+
+    let _reset = Reset(
+        CURRENT.with(|current| current.replace(budget)),
+        CHARGING.with(|charging| charging.replace(false)),
+    );
+    f()
+}
+
+/// Runs `f` with a fresh budget of [`INITIAL_BUDGET`] units.
+///
+/// Executors call this around each poll of a task.
+pub fn budget<R>(f: impl FnOnce() -> R) -> R {
+    with_budget(Some(INITIAL_BUDGET), f)
+}
+
+/// Runs `f` with an unlimited budget.
+pub fn with_unconstrained<R>(f: impl FnOnce() -> R) -> R {
+    with_budget(None, f)
+}
+
+/// Returns `true` if the current budget isn't exhausted.
+pub fn has_budget_remaining() -> bool {
+    CURRENT.with(|current| current.get() != Some(0))
+}
+
+/// Consumes one unit of the current budget.
+///
+/// If the budget is exhausted, this wakes the task and returns
+/// `Poll::Pending`, in which case the caller should return `Poll::Pending`
+/// as well. Otherwise it returns a [`Proceed`] guard, which gives the unit
+/// back when it is dropped unless [`made_progress`](Proceed::made_progress)
+/// is called, so that polls that don't make progress don't consume budget.
+///
+/// While the guard is alive, nested calls return guards which don't consume
+/// any budget.
+pub fn poll_proceed(cx: &mut Context<'_>) -> Poll<Proceed> {
+    if CHARGING.with(Cell::get) {
+        return Poll::Ready(Proceed { consumed: false, outer: false });
+    }
+    CURRENT.with(|current| match current.get() {
+        Some(0) => {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+        Some(budget) => {
+            current.set(Some(budget - 1));
+            CHARGING.with(|charging| charging.set(true));
+            Poll::Ready(Proceed { consumed: true, outer: true })
+        }
+        None => Poll::Ready(Proceed { consumed: false, outer: false }),
+    })
+}
+
+/// A unit of budget consumed by [`poll_proceed`].
+#[derive(Debug)]
+#[must_use = "the unit of budget is given back immediately if the guard is dropped"]
+pub struct Proceed {
+    consumed: bool,
+    outer: bool,
+}
+
+impl Proceed {
+    /// Keeps the unit of budget consumed.
+    pub fn made_progress(mut self) {
+        self.consumed = false;
+    }
+}
+
+impl Drop for Proceed {
+    fn drop(&mut self) {
+        if self.outer {
+            let _ = CHARGING.try_with(|charging| charging.set(false));
+        }
+        if self.consumed {
+            let _ = CURRENT.try_with(|current| {
+                if let Some(budget) = current.get() {
+                    current.set(Some(budget.saturating_add(1)));
+                }
+            });
+        }
+    }
+}
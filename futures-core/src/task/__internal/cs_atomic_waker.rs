@@ -0,0 +1,169 @@
+use core::cell::RefCell;
+use core::fmt;
+use core::task::Waker;
+
+use critical_section::Mutex;
+
+/// A synchronization primitive for task wakeup.
+///
+/// This is the implementation used when the `critical-section` feature is
+/// enabled. Instead of the lock-free protocol used by default, every access to
+/// the registered waker happens inside a critical section, which makes it
+/// usable on targets without atomic CAS operations (such as `thumbv6m`), and
+/// from interrupt handlers on single-core targets.
+///
+/// The API and the semantics are the same as those of the default
+/// implementation: consumers should call `register` before checking the result
+/// of a computation and producers should call `wake` after producing the
+/// computation. Wakers are only ever cloned, woken and dropped outside of the
+/// critical section.
+///
+/// # Examples
+///
+/// Here is a simple example providing a `Flag` that can be signalled manually
+/// when it is ready.
+///
+/// ```
+/// use futures::future::Future;
+/// use futures::task::{Context, Poll, AtomicWaker};
+/// use std::sync::Arc;
+/// use std::sync::atomic::AtomicBool;
+/// use std::sync::atomic::Ordering::Relaxed;
+/// use std::pin::Pin;
+///
+/// struct Inner {
+///     waker: AtomicWaker,
+///     set: AtomicBool,
+/// }
+///
+/// #[derive(Clone)]
+/// struct Flag(Arc<Inner>);
+///
+/// impl Flag {
+///     pub fn new() -> Self {
+///         Self(Arc::new(Inner {
+///             waker: AtomicWaker::new(),
+///             set: AtomicBool::new(false),
+///         }))
+///     }
+///
+///     pub fn signal(&self) {
+///         self.0.set.store(true, Relaxed);
+///         self.0.waker.wake();
+///     }
+/// }
+///
+/// impl Future for Flag {
+///     type Output = ();
+///
+///     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+///         // quick check to avoid registration if already done.
+///         if self.0.set.load(Relaxed) {
+///             return Poll::Ready(());
+///         }
+///
+///         self.0.waker.register(cx.waker());
+///
+///         // Need to check condition **after** `register` to avoid a race
+///         // condition that would result in lost notifications.
+///         if self.0.set.load(Relaxed) {
+///             Poll::Ready(())
+///         } else {
+///             Poll::Pending
+///         }
+///     }
+/// }
+/// ```
+pub struct AtomicWaker {
+    waker: Mutex<RefCell<Option<Waker>>>,
+}
+
+impl AtomicWaker {
+    /// Create an `AtomicWaker`.
+    pub const fn new() -> Self {
+        Self { waker: Mutex::new(RefCell::new(None)) }
+    }
+
+    /// Registers the waker to be notified on calls to `wake`.
+    ///
+    /// The new task will take place of any previous tasks that were registered
+    /// by previous calls to `register`. Any calls to `wake` that happen after
+    /// a call to `register` will notify the `register` caller's task and
+    /// deregister the waker from future notifications.
+    ///
+    /// If the currently registered waker [`will_wake`](Waker::will_wake) the
+    /// same task as `waker`, the stored waker is kept as is and `waker` is not
+    /// cloned.
+    pub fn register(&self, waker: &Waker) {
+        let will_wake = critical_section::with(|cs| match &*self.waker.borrow(cs).borrow() {
+            Some(old_waker) => old_waker.will_wake(waker),
+            None => false,
+        });
+        if !will_wake {
+            // The old waker (if any) is dropped outside of the critical section.
+            drop(self.swap(waker));
+        }
+    }
+
+    /// Registers the waker to be notified on calls to `wake`, returning the
+    /// waker that was previously registered.
+    ///
+    /// This behaves like [`register`](Self::register), except that the old
+    /// waker is handed back to the caller instead of being dropped, and the
+    /// new waker is always stored.
+    pub fn swap(&self, waker: &Waker) -> Option<Waker> {
+        let waker = waker.clone();
+        critical_section::with(|cs| self.waker.borrow(cs).replace(Some(waker)))
+    }
+
+    /// Calls `wake` on the last `Waker` passed to `register`.
+    ///
+    /// If `register` has not been called yet, then this does nothing.
+    pub fn wake(&self) {
+        self.wake_if_registered();
+    }
+
+    /// Calls `wake` on the last `Waker` passed to `register`, like
+    /// [`wake`](Self::wake), and reports whether there was one.
+    ///
+    /// Returns `true` if a registered waker was woken, and `false` if there was
+    /// nothing to wake. The latter is also the case when the wake is left to a
+    /// concurrent call to `register` or `wake`.
+    pub fn wake_if_registered(&self) -> bool {
+        match self.take() {
+            Some(waker) => {
+                waker.wake();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the last `Waker` passed to `register`, so that the user can wake it.
+    ///
+    /// If a waker has not been registered, this returns `None`.
+    pub fn take(&self) -> Option<Waker> {
+        critical_section::with(|cs| self.waker.borrow(cs).borrow_mut().take())
+    }
+
+    /// Returns whether a waker is currently registered.
+    ///
+    /// The result is only a snapshot and may already be outdated by the time
+    /// it is returned; it must not be used to decide whether calling `wake` is
+    /// necessary.
+    pub fn is_registered(&self) -> bool {
+        critical_section::with(|cs| self.waker.borrow(cs).borrow().is_some())
+    }
+}
+
+impl Default for AtomicWaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for AtomicWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AtomicWaker")
+    }
+}
@@ -0,0 +1,146 @@
+use core::cell::Cell;
+use core::fmt;
+use core::task::Waker;
+
+/// A single-threaded counterpart of `AtomicWaker`.
+///
+/// `LocalAtomicWaker` has the same API and semantics as `AtomicWaker`, but is
+/// backed by a plain [`Cell`] instead of an atomic locking protocol. This
+/// makes it `!Sync`, and cheaper to use for synchronization between tasks that
+/// all run on the same thread, for example on a `LocalPool` or on wasm targets.
+///
+/// Consumers should call `register` before checking the result of a
+/// computation and producers should call `wake` after producing the
+/// computation. It is also permitted for `wake` to be called **before**
+/// `register`. This results in a no-op.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::Future;
+/// use futures::task::{Context, Poll, LocalAtomicWaker};
+/// use std::cell::Cell;
+/// use std::pin::Pin;
+/// use std::rc::Rc;
+///
+/// struct Inner {
+///     waker: LocalAtomicWaker,
+///     set: Cell<bool>,
+/// }
+///
+/// #[derive(Clone)]
+/// struct Flag(Rc<Inner>);
+///
+/// impl Flag {
+///     pub fn new() -> Self {
+///         Self(Rc::new(Inner {
+///             waker: LocalAtomicWaker::new(),
+///             set: Cell::new(false),
+///         }))
+///     }
+///
+///     pub fn signal(&self) {
+///         self.0.set.set(true);
+///         self.0.waker.wake();
+///     }
+/// }
+///
+/// impl Future for Flag {
+///     type Output = ();
+///
+///     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+///         if self.0.set.get() {
+///             Poll::Ready(())
+///         } else {
+///             self.0.waker.register(cx.waker());
+///             Poll::Pending
+///         }
+///     }
+/// }
+/// ```
+pub struct LocalAtomicWaker {
+    waker: Cell<Option<Waker>>,
+}
+
+impl LocalAtomicWaker {
+    /// Create a `LocalAtomicWaker`.
+    pub const fn new() -> Self {
+        Self { waker: Cell::new(None) }
+    }
+
+    /// Registers the waker to be notified on calls to `wake`.
+    ///
+    /// The new task will take place of any previous tasks that were registered
+    /// by previous calls to `register`. Any calls to `wake` that happen after
+    /// a call to `register` will notify the `register` caller's task and
+    /// deregister the waker from future notifications.
+    ///
+    /// If the currently registered waker [`will_wake`](Waker::will_wake) the
+    /// same task as `waker`, the stored waker is kept as is and `waker` is not
+    /// cloned.
+    pub fn register(&self, waker: &Waker) {
+        match self.waker.take() {
+            Some(old_waker) if old_waker.will_wake(waker) => self.waker.set(Some(old_waker)),
+            _ => self.waker.set(Some(waker.clone())),
+        }
+    }
+
+    /// Registers the waker to be notified on calls to `wake`, returning the
+    /// waker that was previously registered.
+    ///
+    /// This behaves like [`register`](Self::register), except that the old
+    /// waker is handed back to the caller instead of being dropped, and the
+    /// new waker is always stored.
+    pub fn swap(&self, waker: &Waker) -> Option<Waker> {
+        self.waker.replace(Some(waker.clone()))
+    }
+
+    /// Calls `wake` on the last `Waker` passed to `register`.
+    ///
+    /// If `register` has not been called yet, then this does nothing.
+    pub fn wake(&self) {
+        self.wake_if_registered();
+    }
+
+    /// Calls `wake` on the last `Waker` passed to `register`, like
+    /// [`wake`](Self::wake), and reports whether there was one.
+    ///
+    /// Returns `true` if a registered waker was woken, and `false` if there was
+    /// nothing to wake.
+    pub fn wake_if_registered(&self) -> bool {
+        match self.take() {
+            Some(waker) => {
+                waker.wake();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the last `Waker` passed to `register`, so that the user can wake it.
+    ///
+    /// If a waker has not been registered, this returns `None`.
+    pub fn take(&self) -> Option<Waker> {
+        self.waker.take()
+    }
+
+    /// Returns whether a waker is currently registered.
+    pub fn is_registered(&self) -> bool {
+        let waker = self.waker.take();
+        let is_registered = waker.is_some();
+        self.waker.set(waker);
+        is_registered
+    }
+}
+
+impl Default for LocalAtomicWaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for LocalAtomicWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LocalAtomicWaker")
+    }
+}
@@ -1,4 +1,17 @@
+#[cfg(not(feature = "critical-section"))]
 #[cfg(not(futures_no_atomic_cas))]
 mod atomic_waker;
+#[cfg(not(feature = "critical-section"))]
 #[cfg(not(futures_no_atomic_cas))]
 pub use self::atomic_waker::AtomicWaker;
+
+#[cfg(feature = "critical-section")]
+mod cs_atomic_waker;
+#[cfg(feature = "critical-section")]
+pub use self::cs_atomic_waker::AtomicWaker;
+
+mod local_atomic_waker;
+pub use self::local_atomic_waker::LocalAtomicWaker;
+
+#[cfg(feature = "std")]
+pub mod coop;
@@ -0,0 +1,95 @@
+#![cfg(loom)]
+
+use futures_core::task::__internal::AtomicWaker;
+use futures_core::task::Poll;
+use loom::future::block_on;
+use loom::sync::atomic::AtomicUsize;
+use loom::sync::atomic::Ordering::Relaxed;
+use loom::sync::Arc;
+use loom::thread;
+use std::future::poll_fn;
+
+#[test]
+fn wake_one_thread() {
+    loom::model(|| {
+        let atomic_waker = Arc::new(AtomicWaker::new());
+        let num = Arc::new(AtomicUsize::new(0));
+
+        let th = {
+            let atomic_waker = atomic_waker.clone();
+            let num = num.clone();
+            thread::spawn(move || {
+                num.fetch_add(1, Relaxed);
+                atomic_waker.wake();
+            })
+        };
+
+        block_on(poll_fn(|cx| {
+            atomic_waker.register(cx.waker());
+            if num.load(Relaxed) == 1 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }));
+
+        th.join().unwrap();
+    });
+}
+
+#[test]
+fn swap_while_waking() {
+    loom::model(|| {
+        let atomic_waker = Arc::new(AtomicWaker::new());
+        let num = Arc::new(AtomicUsize::new(0));
+
+        let th = {
+            let atomic_waker = atomic_waker.clone();
+            let num = num.clone();
+            thread::spawn(move || {
+                num.fetch_add(1, Relaxed);
+                atomic_waker.wake();
+            })
+        };
+
+        block_on(poll_fn(|cx| {
+            drop(atomic_waker.swap(cx.waker()));
+            if num.load(Relaxed) == 1 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }));
+
+        th.join().unwrap();
+    });
+}
+
+struct CountWake(std::sync::atomic::AtomicUsize);
+
+impl futures::task::ArcWake for CountWake {
+    fn wake_by_ref(arc_self: &std::sync::Arc<Self>) {
+        arc_self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn swap_always_installs() {
+    loom::model(|| {
+        let atomic_waker = Arc::new(AtomicWaker::new());
+        atomic_waker.register(&futures::task::noop_waker());
+
+        let th = {
+            let atomic_waker = atomic_waker.clone();
+            thread::spawn(move || atomic_waker.wake())
+        };
+
+        let count = std::sync::Arc::new(CountWake(Default::default()));
+        let _old = atomic_waker.swap(&futures::task::waker(count.clone()));
+        th.join().unwrap();
+
+        // The new waker was either installed, or woken by the concurrent wake.
+        let woken = count.0.load(std::sync::atomic::Ordering::SeqCst);
+        assert!(atomic_waker.is_registered() != (woken == 1));
+    });
+}
@@ -0,0 +1,65 @@
+//! Pinning of threads to CPU cores.
+
+use std::io;
+
+/// Restricts the current thread to run on the given cores only.
+#[cfg(target_os = "linux")]
+pub(crate) fn set_affinity(cores: &[usize]) -> io::Result<()> {
+    use std::mem;
+
+    // SAFETY: `cpu_set_t` is a plain bit set, valid when zeroed.
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    for &core in cores {
+        if core >= libc::CPU_SETSIZE as usize {
+            return Err(invalid_core(core));
+        }
+        // SAFETY: `core` is in bounds of the set.
+        unsafe { libc::CPU_SET(core, &mut set) };
+    }
+    // SAFETY: `pthread_self` always returns a valid id.
+    let ret =
+        unsafe { libc::pthread_setaffinity_np(libc::pthread_self(), mem::size_of_val(&set), &set) };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::from_raw_os_error(ret))
+    }
+}
+
+/// Restricts the current thread to run on the given cores only.
+#[cfg(windows)]
+pub(crate) fn set_affinity(cores: &[usize]) -> io::Result<()> {
+    use std::mem;
+    use std::os::raw::c_void;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetCurrentThread() -> *mut c_void;
+        fn SetThreadAffinityMask(thread: *mut c_void, mask: usize) -> usize;
+    }
+
+    let mut mask = 0usize;
+    for &core in cores {
+        if core >= mem::size_of::<usize>() * 8 {
+            return Err(invalid_core(core));
+        }
+        mask |= 1 << core;
+    }
+    // SAFETY: The pseudo handle of the current thread is always valid.
+    if unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) } == 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Restricts the current thread to run on the given cores only.
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) fn set_affinity(_cores: &[usize]) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other, "thread affinity is not supported on this platform"))
+}
+
+#[cfg(any(target_os = "linux", windows))]
+fn invalid_core(core: usize) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("invalid core index {}", core))
+}
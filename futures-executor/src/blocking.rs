@@ -0,0 +1,118 @@
+use futures_task::SpawnError;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// The threads of a `ThreadPool` running blocking closures.
+///
+/// Threads are started on demand, up to a maximum, and stop after having
+/// been idle for a while.
+pub(crate) struct BlockingPool {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    state: Mutex<State>,
+    available: Condvar,
+    max_threads: usize,
+    keep_alive: Duration,
+    name_prefix: Option<String>,
+    stack_size: usize,
+}
+
+struct State {
+    queue: VecDeque<Job>,
+    threads: usize,
+    idle: usize,
+    // Checked under the same lock as the queue, so that no closure is
+    // accepted once `close` returned.
+    closed: bool,
+}
+
+impl BlockingPool {
+    pub(crate) fn new(
+        max_threads: usize,
+        keep_alive: Duration,
+        name_prefix: Option<String>,
+        stack_size: usize,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    queue: VecDeque::new(),
+                    threads: 0,
+                    idle: 0,
+                    closed: false,
+                }),
+                available: Condvar::new(),
+                max_threads,
+                keep_alive,
+                name_prefix,
+                stack_size,
+            }),
+        }
+    }
+
+    pub(crate) fn spawn(&self, job: Job) -> Result<(), SpawnError> {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.closed {
+            return Err(SpawnError::shutdown());
+        }
+        state.queue.push_back(job);
+        if state.idle >= state.queue.len() {
+            self.inner.available.notify_one();
+        } else if state.threads < self.inner.max_threads {
+            let mut builder = thread::Builder::new();
+            if let Some(name_prefix) = &self.inner.name_prefix {
+                builder = builder.name(format!("{}blocking-{}", name_prefix, state.threads));
+            }
+            if self.inner.stack_size > 0 {
+                builder = builder.stack_size(self.inner.stack_size);
+            }
+            let inner = self.inner.clone();
+            match builder.spawn(move || inner.work()) {
+                Ok(_) => state.threads += 1,
+                // The job can still be run by one of the existing threads.
+                Err(_) if state.threads > 0 => {}
+                Err(_) => {
+                    state.queue.pop_back();
+                    return Err(SpawnError::shutdown());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects the closures spawned from now on. The queued ones still run.
+    pub(crate) fn close(&self) {
+        self.inner.state.lock().unwrap().closed = true;
+    }
+}
+
+impl Inner {
+    fn work(&self) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                // Don't lose the thread to a panicking job.
+                let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state.idle += 1;
+            let (guard, timeout) = self.available.wait_timeout(state, self.keep_alive).unwrap();
+            state = guard;
+            state.idle -= 1;
+            if timeout.timed_out() && state.queue.is_empty() {
+                state.threads -= 1;
+                return;
+            }
+        }
+    }
+}
@@ -64,6 +64,23 @@ pub fn enter() -> Result<Enter, EnterError> {
     })
 }
 
+/// Returns `true` if the current thread is within the dynamic extent of an
+/// executor, i.e. if [`enter`] would return an error.
+///
+/// This allows code which may run both inside and outside of an executor to
+/// avoid blocking inside it, or to fall back to
+/// [`block_on_nested`](crate::block_on_nested).
+///
+/// ```
+/// use futures::executor::{block_on, is_entered};
+///
+/// assert!(!is_entered());
+/// block_on(async { assert!(is_entered()) });
+/// ```
+pub fn is_entered() -> bool {
+    ENTERED.with(Cell::get)
+}
+
 impl fmt::Debug for Enter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Enter").finish()
@@ -0,0 +1,71 @@
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll};
+use futures_task::{poll_with_extensions, Extensions};
+use pin_project_lite::pin_project;
+use std::pin::Pin;
+
+pin_project! {
+    /// Future for the [`with_extensions`] function.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct WithExtensions<Fut> {
+        #[pin]
+        future: Fut,
+        extensions: Extensions,
+    }
+}
+
+/// Attaches `extensions` to `future`.
+///
+/// Every poll of the returned future polls `future` with `extensions` made
+/// available through [`ContextExt`](futures_task::ContextExt). Wrapping the
+/// futures of a task before spawning it on any executor, such as a
+/// [`LocalPool`](crate::LocalPool), attaches the extensions to the task.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::{block_on, with_extensions};
+/// use futures::future::poll_fn;
+/// use futures::task::{ContextExt, Extensions, Poll};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct RequestId(u64);
+///
+/// let mut extensions = Extensions::new();
+/// extensions.insert(RequestId(42));
+///
+/// let id = block_on(with_extensions(poll_fn(|cx| Poll::Ready(cx.get_ext::<RequestId>())), extensions));
+/// assert_eq!(id, Some(RequestId(42)));
+/// ```
+pub fn with_extensions<Fut: Future>(future: Fut, extensions: Extensions) -> WithExtensions<Fut> {
+    WithExtensions { future, extensions }
+}
+
+impl<Fut> WithExtensions<Fut> {
+    /// Returns a reference to the attached extensions.
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    /// Returns a mutable reference to the attached extensions.
+    pub fn extensions_mut(self: Pin<&mut Self>) -> &mut Extensions {
+        self.project().extensions
+    }
+}
+
+impl<Fut: Future> Future for WithExtensions<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Fut::Output> {
+        let this = self.project();
+        let future = this.future;
+        poll_with_extensions(this.extensions, cx, |cx| future.poll(cx))
+    }
+}
+
+impl<Fut: FusedFuture> FusedFuture for WithExtensions<Fut> {
+    fn is_terminated(&self) -> bool {
+        self.future.is_terminated()
+    }
+}
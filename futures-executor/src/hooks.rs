@@ -0,0 +1,162 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_task::{waker_ref, ArcWake, LocalFutureObj};
+use futures_util::task::AtomicWaker;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A unique identifier of a task spawned on an executor of this crate.
+///
+/// Identifiers are unique within the process, across all executors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(usize);
+
+impl TaskId {
+    pub(crate) fn next() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Returns the identifier as a `u64`.
+    pub fn as_u64(self) -> u64 {
+        self.0 as u64
+    }
+}
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Instrumentation hooks invoked by an executor around the polls and wakeups
+/// of its tasks.
+///
+/// Hooks are installed with
+/// [`ThreadPoolBuilder::task_hooks`](crate::ThreadPoolBuilder::task_hooks) or
+/// [`LocalPool::with_task_hooks`](crate::LocalPool::with_task_hooks). All
+/// methods have empty default implementations, so implementors only need to
+/// override the events they are interested in.
+///
+/// The hooks are called synchronously by the executor, possibly from several
+/// threads at once, and should return quickly.
+///
+/// # Examples
+///
+/// Measuring how long tasks are polled for:
+///
+/// ```
+/// use futures::executor::{LocalPool, TaskHooks, TaskId};
+/// use futures::task::LocalSpawnExt;
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+/// use std::time::{Duration, Instant};
+///
+/// #[derive(Default)]
+/// struct PollTimer {
+///     started: Mutex<HashMap<TaskId, Instant>>,
+///     total: Mutex<Duration>,
+/// }
+///
+/// impl TaskHooks for PollTimer {
+///     fn on_poll_start(&self, id: TaskId) {
+///         self.started.lock().unwrap().insert(id, Instant::now());
+///     }
+///
+///     fn on_poll_end(&self, id: TaskId, _completed: bool) {
+///         let started = self.started.lock().unwrap().remove(&id).unwrap();
+///         *self.total.lock().unwrap() += started.elapsed();
+///     }
+/// }
+///
+/// let mut pool = LocalPool::with_task_hooks(PollTimer::default());
+/// pool.spawner().spawn_local(async {}).unwrap();
+/// pool.run();
+/// ```
+pub trait TaskHooks: Send + Sync {
+    /// Called right before the task `id` is polled.
+    fn on_poll_start(&self, id: TaskId) {
+        let _ = id;
+    }
+
+    /// Called right after the task `id` has been polled. `completed` is `true`
+    /// if the task's future has completed.
+    fn on_poll_end(&self, id: TaskId, completed: bool) {
+        let _ = (id, completed);
+    }
+
+    /// Called when the task `id` is woken, from the thread calling
+    /// [`wake`](std::task::Waker::wake).
+    ///
+    /// This is called for every wakeup, including wakeups of a task which is
+    /// already scheduled to be polled.
+    fn on_wake(&self, id: TaskId) {
+        let _ = id;
+    }
+}
+
+/// The hooks installed on an executor.
+#[derive(Clone)]
+pub(crate) struct Hooks(pub(crate) Arc<dyn TaskHooks>);
+
+impl Hooks {
+    pub(crate) fn new<H: TaskHooks + 'static>(hooks: H) -> Self {
+        Self(Arc::new(hooks))
+    }
+
+    /// Calls `poll` surrounded by the poll hooks of the task `id`.
+    pub(crate) fn poll<T>(&self, id: TaskId, poll: impl FnOnce() -> Poll<T>) -> Poll<T> {
+        self.0.on_poll_start(id);
+        let res = poll();
+        self.0.on_poll_end(id, res.is_ready());
+        res
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hooks")
+    }
+}
+
+/// A task of a `LocalPool` with hooks installed.
+pub(crate) struct HookedTask {
+    future: LocalFutureObj<'static, ()>,
+    hooks: Hooks,
+    wake: Arc<HookedWake>,
+}
+
+struct HookedWake {
+    id: TaskId,
+    hooks: Hooks,
+    waker: AtomicWaker,
+}
+
+impl ArcWake for HookedWake {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.hooks.0.on_wake(arc_self.id);
+        arc_self.waker.wake();
+    }
+}
+
+impl HookedTask {
+    pub(crate) fn new(id: TaskId, future: LocalFutureObj<'static, ()>, hooks: Hooks) -> Self {
+        let wake = Arc::new(HookedWake { id, hooks: hooks.clone(), waker: AtomicWaker::new() });
+        Self { future, hooks, wake }
+    }
+}
+
+impl Future for HookedTask {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        this.wake.waker.register(cx.waker());
+        let waker = waker_ref(&this.wake);
+        let mut cx = Context::from_waker(&waker);
+        let future = Pin::new(&mut this.future);
+        this.hooks.poll(this.wake.id, || future.poll(&mut cx))
+    }
+}
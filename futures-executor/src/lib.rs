@@ -53,11 +53,19 @@
 ))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "thread-affinity")]
+mod affinity;
 #[cfg(feature = "std")]
 mod local_pool;
 #[cfg(feature = "std")]
-pub use crate::local_pool::{block_on, block_on_stream, BlockingStream, LocalPool, LocalSpawner};
+pub use crate::local_pool::{
+    block_on, block_on_nested, block_on_stream, block_on_with, BlockOnConfig, BlockingStream,
+    LocalPool, LocalPoolBuilder, LocalSpawner, PendingTask, Scope,
+};
 
+#[cfg(feature = "thread-pool")]
+#[cfg(feature = "std")]
+mod blocking;
 #[cfg(feature = "thread-pool")]
 #[cfg_attr(docsrs, doc(cfg(feature = "thread-pool")))]
 #[cfg(feature = "std")]
@@ -69,8 +77,42 @@ mod unpark_mutex;
 #[cfg_attr(docsrs, doc(cfg(feature = "thread-pool")))]
 #[cfg(feature = "std")]
 pub use crate::thread_pool::{ThreadPool, ThreadPoolBuilder};
+#[cfg(feature = "task-stats")]
+mod task_stats;
+#[cfg(feature = "task-stats")]
+pub use crate::task_stats::TaskStats;
+
+#[cfg(feature = "wasm")]
+mod microtask;
+#[cfg(feature = "wasm")]
+pub use crate::microtask::{run_microtasks, set_microtask_scheduler, spawn_local};
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use crate::metrics::Metrics;
+
+#[cfg(feature = "std")]
+mod run_queue;
+#[cfg(feature = "std")]
+pub use crate::run_queue::SchedulingPolicy;
+
+#[cfg(feature = "std")]
+mod park;
+#[cfg(feature = "std")]
+pub use crate::park::{Park, Unpark};
+
+#[cfg(feature = "std")]
+mod hooks;
+#[cfg(feature = "std")]
+pub use crate::hooks::{TaskHooks, TaskId};
+
+#[cfg(feature = "std")]
+mod extensions;
+#[cfg(feature = "std")]
+pub use crate::extensions::{with_extensions, WithExtensions};
 
 #[cfg(feature = "std")]
 mod enter;
 #[cfg(feature = "std")]
-pub use crate::enter::{enter, Enter, EnterError};
+pub use crate::enter::{enter, is_entered, Enter, EnterError};
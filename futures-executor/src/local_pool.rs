@@ -1,20 +1,32 @@
-use crate::enter;
+use crate::enter::{enter, Enter};
+use crate::hooks::{HookedTask, Hooks, TaskHooks, TaskId};
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use crate::park::{CustomPark, Park};
+use crate::run_queue::{LocalTask, RunQueue, SchedulingPolicy};
 use futures_core::future::Future;
 use futures_core::stream::Stream;
 use futures_core::task::{Context, Poll};
 use futures_task::{waker_ref, ArcWake};
-use futures_task::{FutureObj, LocalFutureObj, LocalSpawn, Spawn, SpawnError};
+use futures_task::{
+    FutureObj, LocalFutureObj, LocalSpawn, Priority, Spawn, SpawnError, SpawnPriority,
+};
 use futures_util::pin_mut;
-use futures_util::stream::FuturesUnordered;
-use futures_util::stream::StreamExt;
-use std::cell::RefCell;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use futures_util::task::coop;
+use std::cell::{Cell, RefCell};
+use std::fmt;
 use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::ptr;
 use std::rc::{Rc, Weak};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
+use std::task::Waker;
 use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
 
 /// A single-threaded task pool for polling futures to completion.
 ///
@@ -29,8 +41,28 @@ use std::thread::{self, Thread};
 /// futures, via [`spawn_local_obj`](futures_task::LocalSpawn::spawn_local_obj).
 #[derive(Debug)]
 pub struct LocalPool {
-    pool: FuturesUnordered<LocalFutureObj<'static, ()>>,
+    pool: RunQueue,
     incoming: Rc<Incoming>,
+    hooks: Option<Hooks>,
+    budget: Budget,
+    parker: Parker,
+}
+
+/// A builder for a [`LocalPool`](LocalPool) with a custom configuration.
+///
+/// ```
+/// use futures::executor::{LocalPool, SchedulingPolicy};
+/// use futures::task::LocalSpawnExt;
+///
+/// let mut pool = LocalPool::builder().scheduling_policy(SchedulingPolicy::LifoSlot).create();
+/// pool.spawner().spawn_local(async {}).unwrap();
+/// pool.run();
+/// ```
+#[derive(Debug, Default)]
+pub struct LocalPoolBuilder {
+    policy: SchedulingPolicy,
+    hooks: Option<Hooks>,
+    parker: Parker,
 }
 
 /// A handle to a [`LocalPool`](LocalPool) that implements
@@ -40,7 +72,66 @@ pub struct LocalSpawner {
     incoming: Weak<Incoming>,
 }
 
-type Incoming = RefCell<Vec<LocalFutureObj<'static, ()>>>;
+type Incoming = RefCell<Vec<LocalTask>>;
+
+/// A task of a [`LocalPool`](LocalPool) which hasn't completed yet, as
+/// returned by [`dump_pending`](LocalPool::dump_pending).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingTask {
+    id: TaskId,
+    name: Option<String>,
+}
+
+impl PendingTask {
+    /// Returns the identifier of the task.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Returns the name the task was spawned with, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// How much work the tasks of a `LocalPool` may still do in the current run.
+#[derive(Debug, Default)]
+struct Budget {
+    limit: Cell<Limit>,
+    exhausted: Cell<bool>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Limit {
+    Unlimited,
+    Polls(usize),
+    Deadline(Instant),
+}
+
+impl Default for Limit {
+    fn default() -> Self {
+        Self::Unlimited
+    }
+}
+
+impl Budget {
+    /// Uses up one poll of the budget, returning `false` if it's exhausted.
+    fn take(&self) -> bool {
+        let allowed = match self.limit.get() {
+            Limit::Unlimited => true,
+            Limit::Polls(0) => false,
+            Limit::Polls(n) => {
+                self.limit.set(Limit::Polls(n - 1));
+                true
+            }
+            Limit::Deadline(deadline) => Instant::now() < deadline,
+        };
+        if !allowed {
+            self.exhausted.set(true);
+        }
+        allowed
+    }
+}
 
 pub(crate) struct ThreadNotify {
     /// The (single) executor thread.
@@ -58,6 +149,19 @@ thread_local! {
         thread: thread::current(),
         unparked: AtomicBool::new(false),
     });
+
+    // The pool polling its tasks on this thread, which `block_on_nested`
+    // keeps running while it waits.
+    static CURRENT_POOL: Cell<*const LocalPool> = Cell::new(ptr::null());
+}
+
+/// Restores the previous `CURRENT_POOL` when dropped.
+struct ResetCurrentPool(*const LocalPool);
+
+impl Drop for ResetCurrentPool {
+    fn drop(&mut self) {
+        CURRENT_POOL.with(|current| current.set(self.0));
+    }
 }
 
 impl ArcWake for ThreadNotify {
@@ -75,42 +179,134 @@ impl ArcWake for ThreadNotify {
     }
 }
 
-// Set up and run a basic single-threaded spawner loop, invoking `f` on each
-// turn.
-fn run_executor<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(mut f: F) -> T {
-    let _enter = enter().expect(
-        "cannot execute `LocalPool` executor from within \
-         another executor",
-    );
+/// How an executor waits for its tasks to be woken.
+#[derive(Clone, Debug)]
+enum Parker {
+    /// Park the current thread.
+    Thread,
+    /// Use a `Park` provided by the user.
+    Custom(Rc<CustomPark>),
+}
 
-    CURRENT_THREAD_NOTIFY.with(|thread_notify| {
-        let waker = waker_ref(thread_notify);
-        let mut cx = Context::from_waker(&waker);
-        loop {
-            if let Poll::Ready(t) = f(&mut cx) {
-                return t;
+impl Default for Parker {
+    fn default() -> Self {
+        Self::Thread
+    }
+}
+
+impl Parker {
+    // Set up and run a basic single-threaded spawner loop, invoking `f` on
+    // each turn.
+    fn run<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(&self, f: F) -> T {
+        match self {
+            Self::Thread => run_on_thread(thread::park, f),
+            Self::Custom(park) => {
+                let _enter = enter_executor();
+                poll_loop(&waker_ref(&park.notify), &park.notify.unparked, || park.park(None), f)
             }
+        }
+    }
 
-            // Wait for a wakeup.
-            while !thread_notify.unparked.swap(false, Ordering::Acquire) {
-                // No wakeup occurred. It may occur now, right before parking,
-                // but in that case the token made available by `unpark()`
-                // is guaranteed to still be available and `park()` is a no-op.
-                thread::park();
+    /// Like `run`, from within a task of the executor using this parker.
+    fn run_nested<T, F: FnMut(&mut Context<'_>) -> Poll<T>>(&self, f: F) -> T {
+        let output = match self {
+            Self::Thread => CURRENT_THREAD_NOTIFY.with(|thread_notify| {
+                poll_loop(&waker_ref(thread_notify), &thread_notify.unparked, thread::park, f)
+            }),
+            Self::Custom(park) => {
+                poll_loop(&waker_ref(&park.notify), &park.notify.unparked, || park.park(None), f)
             }
+        };
+        // We may have consumed a wakeup of the executor.
+        match self {
+            Self::Thread => CURRENT_THREAD_NOTIFY
+                .with(|thread_notify| thread_notify.unparked.store(true, Ordering::Release)),
+            Self::Custom(park) => park.notify.unparked.store(true, Ordering::Release),
+        }
+        output
+    }
+
+    /// Check for a wakeup, but don't consume it.
+    fn woken(&self) -> bool {
+        match self {
+            Self::Thread => CURRENT_THREAD_NOTIFY
+                .with(|thread_notify| thread_notify.unparked.load(Ordering::Acquire)),
+            Self::Custom(park) => park.notify.unparked.load(Ordering::Acquire),
         }
+    }
+}
+
+fn enter_executor() -> Enter {
+    enter().expect(
+        "cannot execute `LocalPool` executor from within \
+         another executor",
+    )
+}
+
+// Run `f` like `Parker::run`, calling `park` to park the current thread.
+fn run_on_thread<T>(park: impl FnMut(), f: impl FnMut(&mut Context<'_>) -> Poll<T>) -> T {
+    let _enter = enter_executor();
+    CURRENT_THREAD_NOTIFY.with(|thread_notify| {
+        poll_loop(&waker_ref(thread_notify), &thread_notify.unparked, park, f)
     })
 }
 
-/// Check for a wakeup, but don't consume it.
-fn woken() -> bool {
-    CURRENT_THREAD_NOTIFY.with(|thread_notify| thread_notify.unparked.load(Ordering::Acquire))
+fn poll_loop<T>(
+    waker: &Waker,
+    unparked: &AtomicBool,
+    mut park: impl FnMut(),
+    mut f: impl FnMut(&mut Context<'_>) -> Poll<T>,
+) -> T {
+    let mut cx = Context::from_waker(waker);
+    loop {
+        if let Poll::Ready(t) = f(&mut cx) {
+            return t;
+        }
+
+        // Wait for a wakeup.
+        while !unparked.swap(false, Ordering::Acquire) {
+            // No wakeup occurred. It may occur now, right before parking,
+            // but in that case the token made available by `unpark()`
+            // is guaranteed to still be available and `park()` is a no-op.
+            park();
+        }
+    }
 }
 
 impl LocalPool {
     /// Create a new, empty pool of tasks.
     pub fn new() -> Self {
-        Self { pool: FuturesUnordered::new(), incoming: Default::default() }
+        Self {
+            pool: RunQueue::new(SchedulingPolicy::default()),
+            incoming: Default::default(),
+            hooks: None,
+            budget: Default::default(),
+            parker: Parker::Thread,
+        }
+    }
+
+    /// Create a default pool configuration, which can then be customized.
+    ///
+    /// See the methods of [`LocalPoolBuilder`] for details on the default
+    /// configuration.
+    pub fn builder() -> LocalPoolBuilder {
+        LocalPoolBuilder::new()
+    }
+
+    /// Create a new, empty pool of tasks, which invokes `hooks` around every
+    /// poll and wakeup of the tasks spawned on it.
+    ///
+    /// See [`TaskHooks`] for details.
+    pub fn with_task_hooks<H: TaskHooks + 'static>(hooks: H) -> Self {
+        LocalPoolBuilder::new().task_hooks(hooks).create()
+    }
+
+    /// Create a new, empty pool of tasks, which blocks on `park` instead of
+    /// parking the current thread when it waits for its tasks to be woken.
+    ///
+    /// See [`Park`] for details.
+    pub fn with_park<P: Park + 'static>(park: P) -> Self {
+        LocalPoolBuilder::new().park(park).create()
     }
 
     /// Get a clonable handle to the pool as a [`Spawn`].
@@ -134,7 +330,7 @@ impl LocalPool {
     /// The function will block the calling thread until *all* tasks in the pool
     /// are complete, including any spawned while running existing tasks.
     pub fn run(&mut self) {
-        run_executor(|cx| self.poll_pool(cx))
+        self.parker.clone().run(|cx| self.poll_pool(cx))
     }
 
     /// Runs all the tasks in the pool until the given future completes.
@@ -157,10 +353,11 @@ impl LocalPool {
     pub fn run_until<F: Future>(&mut self, future: F) -> F::Output {
         pin_mut!(future);
 
-        run_executor(|cx| {
+        self.parker.clone().run(|cx| {
             {
                 // if our main task is done, so are we
-                let result = future.as_mut().poll(cx);
+                let _reset = self.set_current();
+                let result = coop::budget(|| future.as_mut().poll(cx));
                 if let Poll::Ready(output) = result {
                     return Poll::Ready(output);
                 }
@@ -171,6 +368,41 @@ impl LocalPool {
         })
     }
 
+    /// Runs `f` with a [`Scope`] for spawning tasks which borrow data from
+    /// the caller, then runs all the tasks in the pool until the scoped tasks
+    /// complete.
+    ///
+    /// ```
+    /// use futures::executor::LocalPool;
+    ///
+    /// let mut pool = LocalPool::new();
+    /// let mut words = vec!["hello"];
+    /// let len = std::cell::Cell::new(0);
+    ///
+    /// pool.scope(|s| {
+    ///     s.spawn(async { len.set(words.len()) });
+    ///     s.spawn(async { println!("{:?}", words) });
+    /// });
+    /// assert_eq!(len.get(), 1);
+    ///
+    /// // The scoped tasks are done with `words`.
+    /// words.push("world");
+    /// ```
+    ///
+    /// The scoped tasks start running once `f` returns, together with the
+    /// other tasks of the pool, and are all complete when this returns. If
+    /// `f` panics, the scoped tasks are dropped without being run.
+    pub fn scope<'a, F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&Scope<'a>) -> R,
+    {
+        let scope = Scope { tasks: RefCell::new(Vec::new()) };
+        let result = f(&scope);
+        let tasks: FuturesUnordered<_> = scope.tasks.into_inner().into_iter().collect();
+        self.run_until(tasks.for_each(|()| async {}));
+        result
+    }
+
     /// Runs all tasks and returns after completing one future or until no more progress
     /// can be made. Returns `true` if one future was completed, `false` otherwise.
     ///
@@ -200,11 +432,12 @@ impl LocalPool {
     /// further use of one of the pool's run or poll methods.
     /// Though only one task will be completed, progress may be made on multiple tasks.
     pub fn try_run_one(&mut self) -> bool {
-        run_executor(|cx| {
+        let parker = self.parker.clone();
+        parker.run(|cx| {
             loop {
                 self.drain_incoming();
 
-                match self.pool.poll_next_unpin(cx) {
+                match self.poll_tasks(cx) {
                     // Success!
                     Poll::Ready(Some(())) => return Poll::Ready(true),
                     // The pool was empty.
@@ -215,7 +448,7 @@ impl LocalPool {
                 if !self.incoming.borrow().is_empty() {
                     // New tasks were spawned; try again.
                     continue;
-                } else if woken() {
+                } else if parker.woken() {
                     // The pool yielded to us, but there's more progress to be made.
                     return Poll::Pending;
                 } else {
@@ -251,11 +484,12 @@ impl LocalPool {
     /// of the pool's run or poll methods. While the function is running, all tasks
     /// in the pool will try to make progress.
     pub fn run_until_stalled(&mut self) {
-        run_executor(|cx| match self.poll_pool(cx) {
+        let parker = self.parker.clone();
+        parker.run(|cx| match self.poll_pool(cx) {
             // The pool is empty.
             Poll::Ready(()) => Poll::Ready(()),
             Poll::Pending => {
-                if woken() {
+                if parker.woken() {
                     Poll::Pending
                 } else {
                     // We're stalled for now.
@@ -265,6 +499,115 @@ impl LocalPool {
         });
     }
 
+    /// Runs the tasks in the pool like
+    /// [`run_until_stalled`](LocalPool::run_until_stalled), but polls them
+    /// at most `max_polls` times in total.
+    ///
+    /// Returns `true` if the budget ran out while some tasks could still make
+    /// progress, and `false` if the pool stalled first. The remaining tasks
+    /// are polled by the next run.
+    ///
+    /// ```
+    /// use futures::executor::LocalPool;
+    /// use futures::task::LocalSpawnExt;
+    /// use futures::future::ready;
+    ///
+    /// let mut pool = LocalPool::new();
+    /// let spawner = pool.spawner();
+    ///
+    /// for _ in 0..3 {
+    ///     spawner.spawn_local(ready(())).unwrap();
+    /// }
+    ///
+    /// assert!(pool.run_until_stalled_with_budget(2));
+    /// assert!(!pool.run_until_stalled_with_budget(2));
+    /// ```
+    pub fn run_until_stalled_with_budget(&mut self, max_polls: usize) -> bool {
+        self.run_until_stalled_with_limit(Limit::Polls(max_polls))
+    }
+
+    /// Runs the tasks in the pool like
+    /// [`run_until_stalled`](LocalPool::run_until_stalled), but stops
+    /// polling them once `duration` has elapsed.
+    ///
+    /// A task being polled when the time runs out isn't interrupted, so this
+    /// may return somewhat after `duration` has elapsed.
+    ///
+    /// Returns `true` if the time ran out while some tasks could still make
+    /// progress, and `false` if the pool stalled first. The remaining tasks
+    /// are polled by the next run.
+    pub fn run_for(&mut self, duration: Duration) -> bool {
+        self.run_until_stalled_with_limit(Limit::Deadline(Instant::now() + duration))
+    }
+
+    fn run_until_stalled_with_limit(&mut self, limit: Limit) -> bool {
+        self.budget.limit.set(limit);
+        self.budget.exhausted.set(false);
+        let parker = self.parker.clone();
+        let exhausted = parker.run(|cx| match self.poll_pool(cx) {
+            // The pool is empty.
+            Poll::Ready(()) => Poll::Ready(false),
+            Poll::Pending => {
+                if self.budget.exhausted.get() {
+                    Poll::Ready(true)
+                } else if parker.woken() {
+                    Poll::Pending
+                } else {
+                    // We're stalled for now.
+                    Poll::Ready(false)
+                }
+            }
+        });
+        self.budget.limit.set(Limit::Unlimited);
+        exhausted
+    }
+
+    /// Returns a handle to the runtime metrics of the pool.
+    ///
+    /// The metrics count the tasks from when the pool starts running them.
+    ///
+    /// This method is only available when the `metrics` feature of this
+    /// library is activated.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn metrics(&self) -> Metrics {
+        self.pool.metrics().clone()
+    }
+
+    /// Returns the tasks of the pool which haven't completed yet, ordered by
+    /// their identifiers.
+    ///
+    /// This includes tasks which were spawned but haven't been polled yet.
+    /// Naming tasks with
+    /// [`spawn_local_named`](LocalSpawner::spawn_local_named) makes it easier
+    /// to tell which ones a stalled pool is waiting on.
+    ///
+    /// ```
+    /// use futures::executor::LocalPool;
+    /// use futures::future::pending;
+    /// use futures::task::LocalSpawnExt;
+    ///
+    /// let mut pool = LocalPool::new();
+    /// let spawner = pool.spawner();
+    ///
+    /// spawner.spawn_local_named("stuck", pending()).unwrap();
+    /// spawner.spawn_local(async {}).unwrap();
+    /// pool.run_until_stalled();
+    ///
+    /// let pending = pool.dump_pending();
+    /// assert_eq!(pending.len(), 1);
+    /// assert_eq!(pending[0].name(), Some("stuck"));
+    /// ```
+    pub fn dump_pending(&self) -> Vec<PendingTask> {
+        let mut pending = Vec::new();
+        let mut push =
+            |task: &LocalTask| pending.push(PendingTask { id: task.id, name: task.name.clone() });
+        self.pool.for_each(&mut push);
+        self.incoming.borrow().iter().for_each(push);
+        pending.sort_by_key(PendingTask::id);
+        pending
+    }
+
     /// Poll `self.pool`, re-filling it with any newly-spawned tasks.
     /// Repeat until either the pool is empty, or it returns `Pending`.
     ///
@@ -272,11 +615,11 @@ impl LocalPool {
     ///
     /// NOTE: the pool may call `wake`, so `Pending` doesn't necessarily
     /// mean that the pool can't make progress.
-    fn poll_pool(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+    fn poll_pool(&self, cx: &mut Context<'_>) -> Poll<()> {
         loop {
             self.drain_incoming();
 
-            let pool_ret = self.pool.poll_next_unpin(cx);
+            let pool_ret = self.poll_tasks(cx);
 
             // We queued up some new tasks; add them and poll again.
             if !self.incoming.borrow().is_empty() {
@@ -292,12 +635,82 @@ impl LocalPool {
     }
 
     /// Empty the incoming queue of newly-spawned tasks.
-    fn drain_incoming(&mut self) {
+    fn drain_incoming(&self) {
         let mut incoming = self.incoming.borrow_mut();
-        for task in incoming.drain(..) {
-            self.pool.push(task)
+        for mut task in incoming.drain(..) {
+            if let Some(hooks) = &self.hooks {
+                let hooked = HookedTask::new(task.id, task.future, hooks.clone());
+                task.future = LocalFutureObj::new(Box::new(hooked));
+            }
+            self.pool.push(task);
         }
     }
+
+    /// Poll the tasks of `self.pool` while the budget allows it.
+    fn poll_tasks(&self, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        self.poll_tasks_with(cx, || self.budget.take())
+    }
+
+    fn poll_tasks_with(
+        &self,
+        cx: &mut Context<'_>,
+        allow: impl FnMut() -> bool,
+    ) -> Poll<Option<()>> {
+        let _reset = self.set_current();
+        self.pool.poll_next(cx, allow)
+    }
+
+    /// Lets `block_on_nested` run the tasks of this pool, until the returned
+    /// guard is dropped.
+    fn set_current(&self) -> ResetCurrentPool {
+        ResetCurrentPool(CURRENT_POOL.with(|current| current.replace(self)))
+    }
+
+    /// Runs the tasks of the pool until `f` completes, from within one of
+    /// the tasks.
+    ///
+    /// The budget of the pool isn't used, since the task can't return to
+    /// the caller of the pool before `f` completes.
+    fn run_nested<F: Future>(&self, mut f: Pin<&mut F>) -> F::Output {
+        self.parker.run_nested(|cx| {
+            if let Poll::Ready(output) = coop::budget(|| f.as_mut().poll(cx)) {
+                return Poll::Ready(output);
+            }
+            loop {
+                self.drain_incoming();
+                match self.poll_tasks_with(cx, || true) {
+                    Poll::Ready(Some(())) => continue,
+                    _ if !self.incoming.borrow().is_empty() => continue,
+                    _ => return Poll::Pending,
+                }
+            }
+        })
+    }
+}
+
+/// A scope for spawning tasks borrowing data on a [`LocalPool`], created by
+/// [`LocalPool::scope`].
+pub struct Scope<'a> {
+    tasks: RefCell<Vec<LocalFutureObj<'a, ()>>>,
+}
+
+impl<'a> Scope<'a> {
+    /// Spawns a task polling the given future, which may borrow data living
+    /// for `'a`, to completion.
+    ///
+    /// The task is run once the scope's closure returns.
+    pub fn spawn<Fut>(&self, future: Fut)
+    where
+        Fut: Future<Output = ()> + 'a,
+    {
+        self.tasks.borrow_mut().push(LocalFutureObj::new(Box::new(future)));
+    }
+}
+
+impl fmt::Debug for Scope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope").field("tasks", &self.tasks.borrow().len()).finish()
+    }
 }
 
 impl Default for LocalPool {
@@ -306,6 +719,52 @@ impl Default for LocalPool {
     }
 }
 
+impl LocalPoolBuilder {
+    /// Create a default pool configuration.
+    ///
+    /// See the other methods on this type for details on the defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the order in which the pool polls its ready tasks.
+    ///
+    /// By default, this is [`SchedulingPolicy::Fifo`].
+    pub fn scheduling_policy(&mut self, policy: SchedulingPolicy) -> &mut Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Invoke `hooks` around every poll and wakeup of the tasks spawned on
+    /// the pool.
+    ///
+    /// See [`TaskHooks`] for details.
+    pub fn task_hooks<H: TaskHooks + 'static>(&mut self, hooks: H) -> &mut Self {
+        self.hooks = Some(Hooks::new(hooks));
+        self
+    }
+
+    /// Block on `park` instead of parking the current thread when the pool
+    /// waits for its tasks to be woken.
+    ///
+    /// The pools created by this builder share `park`. See [`Park`] for
+    /// details.
+    pub fn park<P: Park + 'static>(&mut self, park: P) -> &mut Self {
+        self.parker = Parker::Custom(Rc::new(CustomPark::new(park)));
+        self
+    }
+
+    /// Create a [`LocalPool`] with the given configuration.
+    pub fn create(&mut self) -> LocalPool {
+        LocalPool {
+            pool: RunQueue::new(self.policy),
+            hooks: self.hooks.clone(),
+            parker: self.parker.clone(),
+            ..LocalPool::new()
+        }
+    }
+}
+
 /// Run a future to completion on the current thread.
 ///
 /// This function will block the caller until the given future has completed.
@@ -314,7 +773,139 @@ impl Default for LocalPool {
 /// spawned tasks.
 pub fn block_on<F: Future>(f: F) -> F::Output {
     pin_mut!(f);
-    run_executor(|cx| f.as_mut().poll(cx))
+    Parker::Thread.run(|cx| coop::budget(|| f.as_mut().poll(cx)))
+}
+
+/// Run a future to completion on the current thread, like [`block_on`], even
+/// if the thread is already running an executor.
+///
+/// Outside of an executor, this is the same as [`block_on`]. Inside of one,
+/// e.g. when called by a task of a [`LocalPool`], the future is polled on the
+/// current thread instead of panicking, which eases the migration of
+/// synchronous code calling `block_on` to async code.
+///
+/// Called by a task of a [`LocalPool`], the other tasks of the pool keep
+/// running while the future is polled, so it may wait for them. The tasks of
+/// other executors, such as the worker threads of a `ThreadPool`, aren't
+/// polled until this returns, so the future must not depend on
+/// them to complete, otherwise it deadlocks. Use
+/// [`is_entered`](crate::is_entered) to find out whether the thread runs an
+/// executor.
+///
+/// ```
+/// use futures::executor::{block_on, block_on_nested};
+///
+/// fn sync_code() -> u32 {
+///     // Called both inside and outside of an executor.
+///     block_on_nested(async { 7 })
+/// }
+///
+/// assert_eq!(sync_code(), 7);
+/// assert_eq!(block_on(async { sync_code() }), 7);
+/// ```
+pub fn block_on_nested<F: Future>(f: F) -> F::Output {
+    if !crate::is_entered() {
+        return block_on(f);
+    }
+    pin_mut!(f);
+    let pool = CURRENT_POOL.with(Cell::get);
+    if !pool.is_null() {
+        // Safety: the pool is borrowed by the caller of `set_current` up the
+        // stack, which is polling the task or future calling this.
+        return unsafe { &*pool }.run_nested(f);
+    }
+    CURRENT_THREAD_NOTIFY.with(|thread_notify| {
+        let output =
+            poll_loop(&waker_ref(thread_notify), &thread_notify.unparked, thread::park, |cx| {
+                coop::budget(|| f.as_mut().poll(cx))
+            });
+        // We may have consumed a wakeup of the executor running the thread.
+        thread_notify.unparked.store(true, Ordering::Release);
+        output
+    })
+}
+
+/// The configuration of [`block_on_with`].
+pub struct BlockOnConfig<'a> {
+    max_park: Option<Duration>,
+    on_tick: Option<Box<dyn FnMut() + 'a>>,
+}
+
+impl<'a> BlockOnConfig<'a> {
+    /// Create a configuration parking the thread indefinitely, like
+    /// [`block_on`].
+    pub fn new() -> Self {
+        Self { max_park: None, on_tick: None }
+    }
+
+    /// Park the thread for at most `duration` at a time, so that the
+    /// [`on_tick`](BlockOnConfig::on_tick) callback is called at least that
+    /// often while the future is blocked.
+    pub fn max_park(mut self, duration: Duration) -> Self {
+        self.max_park = Some(duration);
+        self
+    }
+
+    /// Call `f` on the blocked thread every time before it parks, which
+    /// happens whenever the future is pending and again every
+    /// [`max_park`](BlockOnConfig::max_park) while it stays pending.
+    pub fn on_tick<F: FnMut() + 'a>(mut self, f: F) -> Self {
+        self.on_tick = Some(Box::new(f));
+        self
+    }
+}
+
+impl Default for BlockOnConfig<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for BlockOnConfig<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockOnConfig")
+            .field("max_park", &self.max_park)
+            .field("on_tick", &self.on_tick.is_some())
+            .finish()
+    }
+}
+
+/// Run a future to completion on the current thread, like [`block_on`],
+/// while periodically handing control back to the caller.
+///
+/// This allows checking shutdown flags, reporting progress or feeding a
+/// watchdog from the blocked thread, without a second thread.
+///
+/// ```
+/// use futures::executor::{block_on_with, BlockOnConfig};
+/// use futures::channel::oneshot;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let (tx, rx) = oneshot::channel();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(20));
+///     tx.send(5).unwrap();
+/// });
+///
+/// let mut ticks = 0;
+/// let config = BlockOnConfig::new().max_park(Duration::from_millis(1)).on_tick(|| ticks += 1);
+/// assert_eq!(block_on_with(rx, config), Ok(5));
+/// assert!(ticks > 1);
+/// ```
+pub fn block_on_with<F: Future>(f: F, config: BlockOnConfig<'_>) -> F::Output {
+    let BlockOnConfig { max_park, mut on_tick } = config;
+    pin_mut!(f);
+    let park = || {
+        if let Some(on_tick) = &mut on_tick {
+            on_tick();
+        }
+        match max_park {
+            Some(max_park) => thread::park_timeout(max_park),
+            None => thread::park(),
+        }
+    };
+    run_on_thread(park, |cx| coop::budget(|| f.as_mut().poll(cx)))
 }
 
 /// Turn a stream into a blocking iterator.
@@ -363,15 +954,59 @@ impl<S: Stream + Unpin> Iterator for BlockingStream<S> {
     }
 }
 
-impl Spawn for LocalSpawner {
-    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+impl LocalSpawner {
+    /// Spawns a task named `name` that polls the given future with output `()`
+    /// to completion.
+    ///
+    /// The name is reported by [`LocalPool::dump_pending`] while the task is
+    /// alive.
+    pub fn spawn_local_named<S, Fut>(&self, name: S, future: Fut) -> Result<(), SpawnError>
+    where
+        S: Into<String>,
+        Fut: Future<Output = ()> + 'static,
+    {
+        self.spawn_local_named_obj(name, LocalFutureObj::new(Box::new(future)))
+    }
+
+    /// Spawns a named `Send` future onto the pool, like
+    /// [`spawn_obj`](futures_task::Spawn::spawn_obj).
+    pub fn spawn_named_obj<S: Into<String>>(
+        &self,
+        name: S,
+        future: FutureObj<'static, ()>,
+    ) -> Result<(), SpawnError> {
+        self.spawn_local_named_obj(name, future.into())
+    }
+
+    /// Spawns a named future onto the pool, like
+    /// [`spawn_local_obj`](futures_task::LocalSpawn::spawn_local_obj).
+    pub fn spawn_local_named_obj<S: Into<String>>(
+        &self,
+        name: S,
+        future: LocalFutureObj<'static, ()>,
+    ) -> Result<(), SpawnError> {
+        self.push(Some(name.into()), Priority::Normal, future)
+    }
+
+    fn push(
+        &self,
+        name: Option<String>,
+        priority: Priority,
+        future: LocalFutureObj<'static, ()>,
+    ) -> Result<(), SpawnError> {
         if let Some(incoming) = self.incoming.upgrade() {
-            incoming.borrow_mut().push(future.into());
+            incoming.borrow_mut().push(LocalTask::new(name, priority, future));
             Ok(())
         } else {
             Err(SpawnError::shutdown())
         }
     }
+}
+
+impl Spawn for LocalSpawner {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.push(None, Priority::Normal, future.into())
+    }
 
     fn status(&self) -> Result<(), SpawnError> {
         if self.incoming.upgrade().is_some() {
@@ -382,14 +1017,21 @@ impl Spawn for LocalSpawner {
     }
 }
 
+/// The priority is only taken into account by pools using the
+/// [`Priority`](SchedulingPolicy::Priority) scheduling policy.
+impl SpawnPriority for LocalSpawner {
+    fn spawn_obj_with_priority(
+        &self,
+        future: FutureObj<'static, ()>,
+        priority: Priority,
+    ) -> Result<(), SpawnError> {
+        self.push(None, priority, future.into())
+    }
+}
+
 impl LocalSpawn for LocalSpawner {
     fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
-        if let Some(incoming) = self.incoming.upgrade() {
-            incoming.borrow_mut().push(future);
-            Ok(())
-        } else {
-            Err(SpawnError::shutdown())
-        }
+        self.push(None, Priority::Normal, future)
     }
 
     fn status_local(&self) -> Result<(), SpawnError> {
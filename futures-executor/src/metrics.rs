@@ -0,0 +1,116 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A handle to the runtime metrics of an executor.
+///
+/// Metrics are retrieved with [`LocalPool::metrics`](crate::LocalPool::metrics)
+/// or `ThreadPool::metrics`, and are updated live by the executor: reading
+/// them again returns the current values. Reading them is cheap, so they can
+/// be exported periodically to a monitoring system.
+///
+/// Counts are updated without synchronizing with each other, so values read
+/// while the executor runs may be slightly inconsistent.
+///
+/// This type is only available when the `metrics` feature of this library
+/// is activated.
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+#[derive(Clone)]
+pub struct Metrics {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    spawned: AtomicU64,
+    completed: AtomicU64,
+    queued: AtomicUsize,
+    /// Busy time of each worker, in nanoseconds.
+    busy: Box<[AtomicU64]>,
+}
+
+impl Metrics {
+    pub(crate) fn new(workers: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                spawned: AtomicU64::new(0),
+                completed: AtomicU64::new(0),
+                queued: AtomicUsize::new(0),
+                busy: (0..workers).map(|_| AtomicU64::new(0)).collect(),
+            }),
+        }
+    }
+
+    pub(crate) fn task_spawned(&self) {
+        self.inner.spawned.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn task_completed(&self) {
+        self.inner.completed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn task_queued(&self) {
+        self.inner.queued.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn task_dequeued(&self) {
+        self.inner.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_busy(&self, worker: usize, duration: Duration) {
+        self.inner.busy[worker].fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the number of tasks spawned on the executor.
+    pub fn spawned_tasks(&self) -> u64 {
+        self.inner.spawned.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of tasks which completed, panicked or were
+    /// dropped by the executor.
+    pub fn completed_tasks(&self) -> u64 {
+        self.inner.completed.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of tasks spawned and not yet completed.
+    pub fn alive_tasks(&self) -> u64 {
+        let completed = self.completed_tasks();
+        self.spawned_tasks().saturating_sub(completed)
+    }
+
+    /// Returns the number of tasks waiting in the queues of the executor to
+    /// be polled.
+    pub fn queue_depth(&self) -> usize {
+        self.inner.queued.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of threads polling the tasks of the executor.
+    pub fn num_workers(&self) -> usize {
+        self.inner.busy.len()
+    }
+
+    /// Returns the total time spent by the worker `worker` polling tasks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `worker >= self.num_workers()`.
+    pub fn worker_busy_time(&self, worker: usize) -> Duration {
+        Duration::from_nanos(self.inner.busy[worker].load(Ordering::Relaxed))
+    }
+
+    /// Returns the total time spent by all the workers polling tasks.
+    pub fn busy_time(&self) -> Duration {
+        (0..self.num_workers()).map(|worker| self.worker_busy_time(worker)).sum()
+    }
+}
+
+impl fmt::Debug for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metrics")
+            .field("spawned_tasks", &self.spawned_tasks())
+            .field("completed_tasks", &self.completed_tasks())
+            .field("queue_depth", &self.queue_depth())
+            .field("busy_time", &self.busy_time())
+            .finish()
+    }
+}
@@ -0,0 +1,159 @@
+use crate::run_queue::{LocalTask, RunQueue, SchedulingPolicy};
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_task::{waker_ref, ArcWake, LocalFutureObj, Priority};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    static INCOMING: RefCell<Vec<LocalTask>> = RefCell::new(Vec::new());
+    static TASKS: RefCell<RunQueue> = RefCell::new(RunQueue::new(SchedulingPolicy::Fifo));
+    static NOTIFY: Arc<MicrotaskNotify> = Arc::new(MicrotaskNotify {
+        scheduled: AtomicBool::new(false),
+        schedule: Mutex::new(default_scheduler()),
+    });
+}
+
+/// Schedules a run of the tasks of a thread when one of them is woken.
+struct MicrotaskNotify {
+    scheduled: AtomicBool,
+    schedule: Mutex<Option<fn()>>,
+}
+
+impl ArcWake for MicrotaskNotify {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        if !arc_self.scheduled.swap(true, Ordering::AcqRel) {
+            let schedule = *arc_self.schedule.lock().unwrap();
+            if let Some(schedule) = schedule {
+                schedule();
+            }
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm-host"))]
+mod sys {
+    #[link(wasm_import_module = "futures-executor")]
+    extern "C" {
+        pub(super) fn queue_microtask();
+    }
+
+    #[no_mangle]
+    extern "C" fn futures_executor_run_microtasks() {
+        super::run_microtasks()
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "wasm-host"))]
+fn default_scheduler() -> Option<fn()> {
+    fn queue_microtask() {
+        // Safety: the import takes no arguments and is provided by the host.
+        unsafe { sys::queue_microtask() }
+    }
+    Some(queue_microtask as fn())
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm-host")))]
+fn default_scheduler() -> Option<fn()> {
+    None
+}
+
+/// Spawns a task polling the given future to completion on the current
+/// thread, without ever blocking it.
+///
+/// This executor is meant for environments driven by an event loop which
+/// can't be blocked, such as the browser: instead of a thread waiting for
+/// tasks to be woken, a woken task schedules a microtask, which runs the
+/// ready tasks with [`run_microtasks`] and returns as soon as none of them
+/// can make progress.
+///
+/// Runs are scheduled with the function set with
+/// [`set_microtask_scheduler`], for example one calling `queueMicrotask`
+/// through `wasm-bindgen`.
+///
+/// Alternatively, on `wasm32` targets, the `wasm-host` feature of this
+/// library makes the module import the function `queue_microtask` from the
+/// `futures-executor` module, and export a `futures_executor_run_microtasks`
+/// function, which the host calls back from a microtask:
+///
+/// ```js
+/// const { instance } = await WebAssembly.instantiate(bytes, {
+///     "futures-executor": {
+///         queue_microtask: () => queueMicrotask(
+///             () => instance.exports.futures_executor_run_microtasks()
+///         ),
+///     },
+/// });
+/// ```
+///
+/// This function is only available when the `wasm` feature of this library
+/// is activated.
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub fn spawn_local<Fut>(future: Fut)
+where
+    Fut: Future<Output = ()> + 'static,
+{
+    let future = LocalFutureObj::new(Box::new(future));
+    INCOMING.with(|incoming| {
+        incoming.borrow_mut().push(LocalTask::new(None, Priority::Normal, future));
+    });
+    NOTIFY.with(ArcWake::wake_by_ref);
+}
+
+/// Polls the ready tasks spawned with [`spawn_local`] on the current thread,
+/// returning once none of them can make progress.
+///
+/// This is called by the scheduled microtasks, and never blocks. Once every
+/// ready task has been polled, tasks which are still ready are left for
+/// another scheduled run, so that other microtasks get to run in between.
+///
+/// # Panics
+///
+/// Panics if called from one of the tasks.
+///
+/// This function is only available when the `wasm` feature of this library
+/// is activated.
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub fn run_microtasks() {
+    NOTIFY.with(|notify| {
+        notify.scheduled.store(false, Ordering::Release);
+        let waker = waker_ref(notify);
+        let mut cx = Context::from_waker(&waker);
+        TASKS.with(|tasks| {
+            let tasks = tasks.try_borrow_mut().expect("`run_microtasks` called from within a task");
+            loop {
+                INCOMING.with(|incoming| {
+                    for task in incoming.borrow_mut().drain(..) {
+                        tasks.push(task);
+                    }
+                });
+                match tasks.poll_next(&mut cx, || true) {
+                    Poll::Ready(Some(())) => {}
+                    Poll::Ready(None) | Poll::Pending => {
+                        if INCOMING.with(|incoming| incoming.borrow().is_empty()) {
+                            return;
+                        }
+                    }
+                }
+            }
+        })
+    })
+}
+
+/// Sets the function called to schedule a run of [`run_microtasks`] when a
+/// task spawned on the current thread with [`spawn_local`] is woken.
+///
+/// The function should arrange for `run_microtasks` to be called soon on the
+/// current thread, but not from within the call: it's called by the thread
+/// waking the task, which isn't necessarily the current thread. On `wasm32`
+/// targets with the `wasm-host` feature, the default imports
+/// `queue_microtask` from the host, see [`spawn_local`]. Otherwise, there is
+/// no default and tasks only run when `run_microtasks` is called.
+///
+/// This function is only available when the `wasm` feature of this library
+/// is activated.
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub fn set_microtask_scheduler(schedule: fn()) {
+    NOTIFY.with(|notify| *notify.schedule.lock().unwrap() = Some(schedule));
+}
@@ -0,0 +1,131 @@
+use futures_task::ArcWake;
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::Thread;
+use std::time::Duration;
+
+/// A way of blocking the current thread until it's unparked, used by a
+/// [`LocalPool`](crate::LocalPool) instead of
+/// [`thread::park`](std::thread::park) when it has no more work to do.
+///
+/// This allows driving the pool alongside an event loop, such as an I/O
+/// reactor, on the same thread: parking blocks on the event loop, and waking
+/// one of the pool's tasks unparks it.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::{LocalPool, Park};
+/// use futures::task::LocalSpawnExt;
+/// use std::thread::{self, Thread};
+/// use std::time::Duration;
+///
+/// struct ThreadPark;
+///
+/// impl Park for ThreadPark {
+///     type Unpark = Thread;
+///
+///     fn unpark(&self) -> Thread {
+///         thread::current()
+///     }
+///
+///     fn park(&mut self, timeout: Option<Duration>) {
+///         // Poll the reactor here.
+///         match timeout {
+///             Some(timeout) => thread::park_timeout(timeout),
+///             None => thread::park(),
+///         }
+///     }
+/// }
+///
+/// let mut pool = LocalPool::with_park(ThreadPark);
+/// pool.spawner().spawn_local(async {}).unwrap();
+/// pool.run();
+/// ```
+pub trait Park {
+    /// The handle unparking this `Park`.
+    type Unpark: Unpark;
+
+    /// Returns a handle which unparks this `Park` from any thread.
+    ///
+    /// This is called once, when the executor is created.
+    fn unpark(&self) -> Self::Unpark;
+
+    /// Blocks the current thread until it's unparked or `timeout` has
+    /// elapsed.
+    ///
+    /// If the handle returned by [`unpark`](Park::unpark) was used since the
+    /// last call, this must return without blocking. Spurious returns are
+    /// allowed, the executor parks again if it has no work to do.
+    fn park(&mut self, timeout: Option<Duration>);
+}
+
+/// A handle unparking a [`Park`], called when a task of the executor is
+/// woken.
+pub trait Unpark: Send + Sync + 'static {
+    /// Unparks the associated [`Park`].
+    fn unpark(&self);
+}
+
+impl Unpark for Thread {
+    fn unpark(&self) {
+        Thread::unpark(self)
+    }
+}
+
+impl<U: Unpark + ?Sized> Unpark for Arc<U> {
+    fn unpark(&self) {
+        (**self).unpark()
+    }
+}
+
+impl<U: Unpark + ?Sized> Unpark for Box<U> {
+    fn unpark(&self) {
+        (**self).unpark()
+    }
+}
+
+/// A user-provided `Park`, together with the state needed to wake the
+/// executor through it.
+pub(crate) struct CustomPark {
+    park: RefCell<Box<dyn FnMut(Option<Duration>)>>,
+    pub(crate) notify: Arc<ParkNotify>,
+}
+
+pub(crate) struct ParkNotify {
+    unpark: Box<dyn Unpark>,
+    /// Like `ThreadNotify::unparked`, remembers wakeups until the next park.
+    pub(crate) unparked: AtomicBool,
+}
+
+impl CustomPark {
+    pub(crate) fn new<P: Park + 'static>(mut park: P) -> Self {
+        let unpark = Box::new(park.unpark());
+        Self {
+            park: RefCell::new(Box::new(move |timeout| park.park(timeout))),
+            notify: Arc::new(ParkNotify { unpark, unparked: AtomicBool::new(false) }),
+        }
+    }
+
+    pub(crate) fn park(&self, timeout: Option<Duration>) {
+        (self.park.borrow_mut())(timeout)
+    }
+}
+
+impl ArcWake for ParkNotify {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        if !arc_self.unparked.swap(true, Ordering::Release) {
+            arc_self.unpark.unpark();
+        }
+    }
+}
+
+impl fmt::Debug for CustomPark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomPark")
+            .field("unparked", &self.notify.unparked.load(Ordering::Relaxed))
+            .finish()
+    }
+}
@@ -0,0 +1,357 @@
+use crate::hooks::TaskId;
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+use futures_core::task::{Context, Poll};
+use futures_task::{waker_ref, ArcWake, LocalFutureObj, Priority};
+use futures_util::future::FutureExt;
+use futures_util::task::{coop, AtomicWaker};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::{self, ThreadId};
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+/// How many times in a row a task may be polled from the LIFO slot, before
+/// the tasks in the FIFO queue get their turn.
+const MAX_LIFO_POLLS: usize = 3;
+
+/// The order in which a [`LocalPool`](crate::LocalPool) polls its ready
+/// tasks, set with
+/// [`LocalPoolBuilder::scheduling_policy`](crate::LocalPoolBuilder::scheduling_policy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SchedulingPolicy {
+    /// Poll the tasks in the order they were spawned or woken.
+    ///
+    /// This is the default.
+    Fifo,
+    /// Like [`Fifo`](SchedulingPolicy::Fifo), except that a task woken by
+    /// the task being polled is polled right after it.
+    ///
+    /// This reduces the latency of passing messages between tasks: the
+    /// receiving task runs while the message is still hot in the cache. To
+    /// avoid starving the other tasks, the slot is only used a few times in
+    /// a row.
+    LifoSlot,
+    /// Poll the ready tasks of higher [`Priority`] first, and the tasks of
+    /// the same priority in the order they were spawned or woken.
+    ///
+    /// Tasks are given a priority with
+    /// [`SpawnPriority`](futures_task::SpawnPriority). Low priority tasks
+    /// aren't polled as long as tasks of higher priority are ready.
+    Priority,
+}
+
+impl Default for SchedulingPolicy {
+    fn default() -> Self {
+        Self::Fifo
+    }
+}
+
+/// A task of a `LocalPool`.
+pub(crate) struct LocalTask {
+    pub(crate) id: TaskId,
+    pub(crate) name: Option<String>,
+    pub(crate) priority: Priority,
+    pub(crate) future: LocalFutureObj<'static, ()>,
+}
+
+impl LocalTask {
+    pub(crate) fn new(
+        name: Option<String>,
+        priority: Priority,
+        future: LocalFutureObj<'static, ()>,
+    ) -> Self {
+        Self { id: TaskId::next(), name, priority, future }
+    }
+}
+
+impl fmt::Debug for LocalTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalTask")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("priority", &self.priority)
+            .finish()
+    }
+}
+
+/// The tasks of a `LocalPool`, polled in the order given by its
+/// `SchedulingPolicy`.
+///
+/// Its methods take `&self`, and no borrow is held while a task is polled,
+/// so that a task can poll the other tasks from within its own poll, as
+/// `block_on_nested` does.
+pub(crate) struct RunQueue {
+    policy: SchedulingPolicy,
+    tasks: RefCell<Tasks>,
+    ready: Arc<Ready>,
+    lifo_polls: Cell<usize>,
+}
+
+#[derive(Default)]
+struct Tasks {
+    slots: Vec<Option<Slot>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+struct Slot {
+    /// Taken out of the slot while the task is being polled.
+    task: Option<LocalTask>,
+    wake: Arc<TaskWake>,
+}
+
+/// The tasks ready to be polled, shared with their wakers.
+struct Ready {
+    queues: Mutex<Queues>,
+    /// Wakes the executor when a task becomes ready.
+    waker: AtomicWaker,
+    lifo: bool,
+    /// The thread polling the tasks, which uses the LIFO slot.
+    thread: ThreadId,
+    polling: AtomicBool,
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
+}
+
+#[derive(Default)]
+struct Queues {
+    /// One queue per priority, highest first.
+    fifo: [VecDeque<Arc<TaskWake>>; 3],
+    lifo: Option<Arc<TaskWake>>,
+}
+
+struct TaskWake {
+    index: usize,
+    queue: usize,
+    /// Whether the task is in one of the queues.
+    queued: AtomicBool,
+    ready: Weak<Ready>,
+}
+
+fn queue_index(priority: Priority) -> usize {
+    match priority {
+        Priority::High => 0,
+        Priority::Normal => 1,
+        Priority::Low => 2,
+    }
+}
+
+impl ArcWake for TaskWake {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        if arc_self.queued.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let ready = match arc_self.ready.upgrade() {
+            Some(ready) => ready,
+            // The pool is gone.
+            None => return,
+        };
+        #[cfg(feature = "metrics")]
+        ready.metrics.task_queued();
+        {
+            let mut queues = ready.queues.lock().unwrap();
+            if ready.lifo
+                && ready.polling.load(Ordering::Relaxed)
+                && thread::current().id() == ready.thread
+            {
+                if let Some(prev) = queues.lifo.replace(arc_self.clone()) {
+                    queues.fifo[prev.queue].push_back(prev);
+                }
+            } else {
+                queues.fifo[arc_self.queue].push_back(arc_self.clone());
+            }
+        }
+        ready.waker.wake();
+    }
+}
+
+impl RunQueue {
+    pub(crate) fn new(policy: SchedulingPolicy) -> Self {
+        Self {
+            policy,
+            tasks: RefCell::new(Tasks::default()),
+            ready: Arc::new(Ready {
+                queues: Mutex::new(Queues::default()),
+                waker: AtomicWaker::new(),
+                lifo: policy == SchedulingPolicy::LifoSlot,
+                thread: thread::current().id(),
+                polling: AtomicBool::new(false),
+                #[cfg(feature = "metrics")]
+                metrics: Metrics::new(1),
+            }),
+            lifo_polls: Cell::new(0),
+        }
+    }
+
+    /// Adds a task, ready to be polled.
+    pub(crate) fn push(&self, task: LocalTask) {
+        let tasks = &mut *self.tasks.borrow_mut();
+        let index = tasks.free.pop().unwrap_or_else(|| {
+            tasks.slots.push(None);
+            tasks.slots.len() - 1
+        });
+        let queue = match self.policy {
+            SchedulingPolicy::Priority => queue_index(task.priority),
+            _ => queue_index(Priority::Normal),
+        };
+        let wake = Arc::new(TaskWake {
+            index,
+            queue,
+            queued: AtomicBool::new(true),
+            ready: Arc::downgrade(&self.ready),
+        });
+        #[cfg(feature = "metrics")]
+        {
+            self.ready.metrics.task_spawned();
+            self.ready.metrics.task_queued();
+        }
+        self.ready.queues.lock().unwrap().fifo[queue].push_back(wake.clone());
+        tasks.slots[index] = Some(Slot { task: Some(task), wake });
+        tasks.len += 1;
+    }
+
+    #[cfg(feature = "metrics")]
+    pub(crate) fn metrics(&self) -> &Metrics {
+        &self.ready.metrics
+    }
+
+    /// Calls `f` with each task which isn't being polled.
+    pub(crate) fn for_each(&self, f: impl FnMut(&LocalTask)) {
+        let tasks = self.tasks.borrow();
+        tasks.slots.iter().flatten().filter_map(|slot| slot.task.as_ref()).for_each(f);
+    }
+
+    /// Polls the ready tasks until one of them completes, like
+    /// `FuturesUnordered::poll_next`.
+    ///
+    /// `allow` is called before polling each task, and the tasks are left
+    /// ready for the next call if it returns `false`.
+    pub(crate) fn poll_next(
+        &self,
+        cx: &mut Context<'_>,
+        mut allow: impl FnMut() -> bool,
+    ) -> Poll<Option<()>> {
+        let len = self.tasks.borrow().len;
+        if len == 0 {
+            return Poll::Ready(None);
+        }
+        self.ready.waker.register(cx.waker());
+
+        // The wakeups of the tasks being polled further up the stack, which
+        // are requeued on the way out.
+        let mut busy = Vec::new();
+        // Yield after polling every task once, or after tasks woke
+        // themselves a couple of times, so that the caller gets to run.
+        let mut polled = 0;
+        let mut yielded = 0;
+        let res = loop {
+            let wake = match self.pop() {
+                Some(wake) => wake,
+                None => break Poll::Pending,
+            };
+            let task = match &mut self.tasks.borrow_mut().slots[wake.index] {
+                Some(slot) if Arc::ptr_eq(&slot.wake, &wake) => slot.task.take(),
+                // The task completed.
+                _ => continue,
+            };
+            let mut task = match task {
+                Some(task) => task,
+                None => {
+                    busy.push(wake);
+                    continue;
+                }
+            };
+            if !allow() {
+                self.put_back(&wake, task);
+                #[cfg(feature = "metrics")]
+                self.ready.metrics.task_queued();
+                self.ready.queues.lock().unwrap().fifo[wake.queue].push_front(wake);
+                break Poll::Pending;
+            }
+
+            wake.queued.store(false, Ordering::Release);
+            let waker = waker_ref(&wake);
+            let mut task_cx = Context::from_waker(&waker);
+            let polling = self.ready.polling.swap(true, Ordering::Relaxed);
+            #[cfg(feature = "metrics")]
+            let start = Instant::now();
+            // Each task gets its own coop budget, as on the thread pool.
+            let res = coop::budget(|| task.future.poll_unpin(&mut task_cx));
+            #[cfg(feature = "metrics")]
+            self.ready.metrics.record_busy(0, start.elapsed());
+            self.ready.polling.store(polling, Ordering::Relaxed);
+
+            if res.is_ready() {
+                {
+                    let tasks = &mut *self.tasks.borrow_mut();
+                    tasks.slots[wake.index] = None;
+                    tasks.free.push(wake.index);
+                    tasks.len -= 1;
+                }
+                drop(task);
+                #[cfg(feature = "metrics")]
+                self.ready.metrics.task_completed();
+                break Poll::Ready(Some(()));
+            }
+            self.put_back(&wake, task);
+            if wake.queued.load(Ordering::Acquire) {
+                yielded += 1;
+            }
+            polled += 1;
+            if polled == len || yielded >= 2 {
+                cx.waker().wake_by_ref();
+                break Poll::Pending;
+            }
+        };
+
+        if !busy.is_empty() {
+            let mut queues = self.ready.queues.lock().unwrap();
+            for wake in busy.into_iter().rev() {
+                #[cfg(feature = "metrics")]
+                self.ready.metrics.task_queued();
+                queues.fifo[wake.queue].push_front(wake);
+            }
+        }
+        res
+    }
+
+    fn put_back(&self, wake: &TaskWake, task: LocalTask) {
+        let mut tasks = self.tasks.borrow_mut();
+        tasks.slots[wake.index].as_mut().unwrap().task = Some(task);
+    }
+
+    fn pop(&self) -> Option<Arc<TaskWake>> {
+        let mut queues = self.ready.queues.lock().unwrap();
+        if let Some(wake) = queues.lifo.take() {
+            if self.lifo_polls.get() < MAX_LIFO_POLLS {
+                self.lifo_polls.set(self.lifo_polls.get() + 1);
+                #[cfg(feature = "metrics")]
+                self.ready.metrics.task_dequeued();
+                return Some(wake);
+            }
+            queues.fifo[wake.queue].push_back(wake);
+        }
+        self.lifo_polls.set(0);
+        let wake = queues.fifo.iter_mut().find_map(VecDeque::pop_front);
+        #[cfg(feature = "metrics")]
+        {
+            if wake.is_some() {
+                self.ready.metrics.task_dequeued();
+            }
+        }
+        wake
+    }
+}
+
+impl fmt::Debug for RunQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunQueue")
+            .field("policy", &self.policy)
+            .field("len", &self.tasks.borrow().len)
+            .finish()
+    }
+}
@@ -0,0 +1,55 @@
+use crate::hooks::TaskId;
+use std::time::Duration;
+
+/// A snapshot of the time spent polling a task of a
+/// [`ThreadPool`](crate::ThreadPool).
+///
+/// See [`ThreadPool::task_stats`](crate::ThreadPool::task_stats).
+///
+/// This type is only available when the `task-stats` feature of this
+/// library is activated.
+#[cfg_attr(docsrs, doc(cfg(feature = "task-stats")))]
+#[derive(Clone, Debug)]
+pub struct TaskStats {
+    id: TaskId,
+    polls: u64,
+    busy_time: Duration,
+    longest_poll: Duration,
+}
+
+impl TaskStats {
+    pub(crate) fn new(id: TaskId) -> Self {
+        Self {
+            id,
+            polls: 0,
+            busy_time: Duration::from_secs(0),
+            longest_poll: Duration::from_secs(0),
+        }
+    }
+
+    pub(crate) fn record_poll(&mut self, duration: Duration) {
+        self.polls += 1;
+        self.busy_time += duration;
+        self.longest_poll = self.longest_poll.max(duration);
+    }
+
+    /// Returns the identifier of the task.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Returns how many times the task has been polled.
+    pub fn polls(&self) -> u64 {
+        self.polls
+    }
+
+    /// Returns the total time spent polling the task.
+    pub fn busy_time(&self) -> Duration {
+        self.busy_time
+    }
+
+    /// Returns the duration of the longest poll of the task.
+    pub fn longest_poll(&self) -> Duration {
+        self.longest_poll
+    }
+}
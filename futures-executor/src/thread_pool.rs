@@ -1,17 +1,35 @@
+use crate::blocking::BlockingPool;
 use crate::enter;
+use crate::hooks::{Hooks, TaskHooks, TaskId};
+#[cfg(feature = "metrics")]
+use crate::metrics::Metrics;
+#[cfg(feature = "task-stats")]
+use crate::task_stats::TaskStats;
 use crate::unpark_mutex::UnparkMutex;
+use crossbeam_deque::{Injector, Steal, Stealer, Worker};
 use futures_core::future::Future;
-use futures_core::task::{Context, Poll};
-use futures_task::{waker_ref, ArcWake};
-use futures_task::{FutureObj, Spawn, SpawnError};
+use futures_core::task::{Context, Poll, Waker};
+use futures_task::{joinable, waker_ref, ArcWake, JoinHandle};
+use futures_task::{FutureObj, Priority, Spawn, SpawnBlocking, SpawnError, SpawnPriority};
 use futures_util::future::FutureExt;
+use futures_util::task::coop;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
 use std::cmp;
+#[cfg(feature = "task-stats")]
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::fmt;
 use std::io;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::mpsc;
-use std::sync::{Arc, Mutex};
+use std::iter;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
 /// A general-purpose thread pool for scheduling tasks that poll futures to
 /// completion.
@@ -22,6 +40,30 @@ use std::thread;
 /// This type is a clonable handle to the threadpool itself.
 /// Cloning it will only create a new reference, not a new threadpool.
 ///
+/// By default, dropping the last handle to the pool detaches it: the worker
+/// threads exit once they run out of queued tasks, and are started again
+/// when one of the remaining tasks is woken. Use
+/// [`ThreadPool::shutdown`] to stop the pool and wait for its tasks and
+/// threads instead, or [`ThreadPoolBuilder::shutdown_on_drop`] to do so when
+/// the last handle is dropped.
+///
+/// Each worker thread has its own run queues, where it puts the tasks woken
+/// while it polls a future. Tasks spawned or woken from outside of the pool
+/// go to queues shared by all the workers. A worker looks for tasks in its
+/// own queues first, then in the shared ones, and finally steals tasks from
+/// the queues of the other workers.
+///
+/// Tasks can be given a [`Priority`] with
+/// [`SpawnPriorityExt::spawn_with_priority`](futures_util::task::SpawnPriorityExt::spawn_with_priority):
+/// all the queues above exist once per priority, and the worker threads
+/// always look for the ready tasks of the highest priority first.
+///
+/// Blocking work, such as filesystem calls, can be moved off the worker
+/// threads with
+/// [`SpawnBlockingExt::spawn_blocking`](futures_util::task::SpawnBlockingExt::spawn_blocking),
+/// which runs it on a separate set of threads sized with
+/// [`ThreadPoolBuilder::max_blocking_threads`].
+///
 /// This type is only available when the `thread-pool` feature of this
 /// library is activated.
 #[cfg_attr(docsrs, doc(cfg(feature = "thread-pool")))]
@@ -38,18 +80,62 @@ pub struct ThreadPoolBuilder {
     pool_size: usize,
     stack_size: usize,
     name_prefix: Option<String>,
-    after_start: Option<Arc<dyn Fn(usize) + Send + Sync>>,
-    before_stop: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    after_start: Option<WorkerHook>,
+    before_stop: Option<WorkerHook>,
+    hooks: Option<Hooks>,
+    panic_handler: Option<PanicHandler>,
+    #[cfg(feature = "thread-affinity")]
+    affinity: Option<Arc<dyn Fn(usize) -> Vec<usize> + Send + Sync>>,
+    max_blocking_threads: usize,
+    blocking_keep_alive: Duration,
+    shutdown_on_drop: Option<Option<Duration>>,
+    capacity: usize,
 }
 
+type PanicHandler = Arc<dyn Fn(Box<dyn Any + Send>) + Send + Sync>;
+type WorkerHook = Arc<dyn Fn(usize) + Send + Sync>;
+
 trait AssertSendSync: Send + Sync {}
 impl AssertSendSync for ThreadPool {}
 
 struct PoolState {
-    tx: Mutex<mpsc::Sender<Message>>,
-    rx: Mutex<mpsc::Receiver<Message>>,
+    injectors: [Injector<Task>; PRIORITIES],
+    stealers: Vec<[Stealer<Task>; PRIORITIES]>,
+    // Number of workers waiting on `available` for a task, or about to.
+    sleeping: AtomicUsize,
+    sleep: Mutex<()>,
+    available: Condvar,
+    // Set once the pool doesn't accept new tasks anymore.
+    closed: AtomicBool,
+    // Set once the workers should stop, abandoning the remaining tasks.
+    shutdown: AtomicBool,
+    // Set once the last handle is dropped without `shutdown_on_drop`: the
+    // workers stop once they run out of queued tasks.
+    detached: AtomicBool,
+    // Workers which stopped after the pool was detached, started again when
+    // a task is queued.
+    idle: Mutex<Vec<IdleWorker>>,
+    // Number of tasks spawned and not yet completed or dropped.
+    tasks: AtomicUsize,
+    completed: Condvar,
+    // Bound on `tasks` for the spawns which respect it.
+    capacity: usize,
+    // Tasks waiting in `ThreadPool::spawn_async` for capacity.
+    spawn_waiters: Mutex<SpawnWaiters>,
+    #[cfg(feature = "task-stats")]
+    stats: Mutex<HashMap<TaskId, Arc<Mutex<TaskStats>>>>,
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
+    threads: Mutex<Vec<thread::JoinHandle<()>>>,
+    // Number of `ThreadPool` handles.
     cnt: AtomicUsize,
     size: usize,
+    shutdown_on_drop: Option<Option<Duration>>,
+    hooks: Option<Hooks>,
+    panic_handler: Option<PanicHandler>,
+    blocking: BlockingPool,
+    name_prefix: Option<String>,
+    stack_size: usize,
 }
 
 impl fmt::Debug for ThreadPool {
@@ -67,9 +153,36 @@ impl fmt::Debug for ThreadPoolBuilder {
     }
 }
 
-enum Message {
-    Run(Task),
-    Close,
+/// The number of priorities, each having its own run queues.
+const PRIORITIES: usize = 3;
+
+/// How often a worker looks for tasks in the shared queues before its own,
+/// so that tasks spawned from outside of the pool can't be starved by busy
+/// workers.
+const INJECTOR_INTERVAL: u32 = 61;
+
+/// The run queues of a worker thread, indexed by priority.
+struct LocalQueues {
+    pool: *const PoolState,
+    index: usize,
+    queues: [Worker<Task>; PRIORITIES],
+    ticks: Cell<u32>,
+}
+
+thread_local! {
+    static LOCAL: RefCell<Option<LocalQueues>> = RefCell::new(None);
+}
+
+fn queue_index(priority: Priority) -> usize {
+    match priority {
+        Priority::High => 0,
+        Priority::Normal => 1,
+        Priority::Low => 2,
+    }
+}
+
+fn new_queues() -> [Worker<Task>; PRIORITIES] {
+    [Worker::new_fifo(), Worker::new_fifo(), Worker::new_fifo()]
 }
 
 impl ThreadPool {
@@ -95,13 +208,96 @@ impl ThreadPool {
     ///
     /// > **Note**: This method is similar to `Spawn::spawn_obj`, except that
     /// >           it is guaranteed to always succeed.
+    ///
+    /// If the pool has been shut down, the future is dropped instead. The
+    /// [`capacity`](ThreadPoolBuilder::capacity) of the pool is ignored.
     pub fn spawn_obj_ok(&self, future: FutureObj<'static, ()>) {
+        let registration = Registration::new(&self.state);
+        let _ = self.spawn_registered(future, Priority::Normal, registration);
+    }
+
+    fn spawn_registered(
+        &self,
+        future: FutureObj<'static, ()>,
+        priority: Priority,
+        registration: Registration,
+    ) -> Result<(), SpawnError> {
+        // Checked once the task is counted, so that `shutdown` either waits
+        // for the task or the task sees the pool closed.
+        if self.state.closed.load(Ordering::SeqCst) {
+            return Err(SpawnError::shutdown());
+        }
+        let id = TaskId::next();
+        let mut registration = registration;
+        registration.id = Some(id);
+        #[cfg(feature = "task-stats")]
+        let stats = {
+            let stats = Arc::new(Mutex::new(TaskStats::new(id)));
+            self.state.stats.lock().unwrap().insert(id, stats.clone());
+            stats
+        };
+        #[cfg(feature = "metrics")]
+        self.state.metrics.task_spawned();
         let task = Task {
             future,
-            wake_handle: Arc::new(WakeHandle { exec: self.clone(), mutex: UnparkMutex::new() }),
-            exec: self.clone(),
+            wake_handle: Arc::new(WakeHandle {
+                id,
+                priority,
+                pool: self.state.clone(),
+                mutex: UnparkMutex::new(),
+                #[cfg(feature = "task-stats")]
+                stats,
+            }),
+            registration,
         };
-        self.state.send(Message::Run(task));
+        self.state.schedule(task, priority);
+        Ok(())
+    }
+
+    /// Spawns a task that polls the given future with output `()` to
+    /// completion, unless the pool is at
+    /// [`capacity`](ThreadPoolBuilder::capacity).
+    ///
+    /// # Errors
+    ///
+    /// Fails with an error for which [`SpawnError::is_full`] returns `true`
+    /// if the pool is at capacity or futures returned by
+    /// [`spawn_async`](ThreadPool::spawn_async) are waiting for a slot, or if
+    /// it has been shut down.
+    ///
+    /// > **Note**: This method is similar to `SpawnExt::spawn`, which also
+    /// >           respects the capacity of the pool.
+    pub fn try_spawn<Fut>(&self, future: Fut) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_obj(FutureObj::new(Box::new(future)))
+    }
+
+    /// Spawns a task that polls the given future with output `()` to
+    /// completion, waiting for the pool to be below its
+    /// [`capacity`](ThreadPoolBuilder::capacity) first.
+    ///
+    /// The returned future resolves once the task has been spawned, or to
+    /// an error if the pool is shut down first. Waiting futures get freed
+    /// slots in the order they were first polled, before any new spawn.
+    ///
+    /// ```
+    /// use futures::executor::{block_on, ThreadPool};
+    ///
+    /// let pool = ThreadPool::builder().capacity(16).create().unwrap();
+    ///
+    /// block_on(async {
+    ///     for i in 0..100 {
+    ///         pool.spawn_async(async move { /* ... */ }).await.unwrap();
+    ///     }
+    /// });
+    /// ```
+    pub fn spawn_async<Fut>(&self, future: Fut) -> impl Future<Output = Result<(), SpawnError>>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        SpawnAsync { pool: self.clone(), future: Some(FutureObj::new(Box::new(future))), key: None }
     }
 
     /// Spawns a task that polls the given future with output `()` to
@@ -127,40 +323,556 @@ impl ThreadPool {
     {
         self.spawn_obj_ok(FutureObj::new(Box::new(future)))
     }
+
+    /// Spawns a task that polls the given future to completion, and returns
+    /// a [`JoinHandle`] resolving to its output.
+    ///
+    /// A panic of the task is caught, and the handle resolves to a
+    /// [`JoinError`](futures_task::JoinError) holding the panic payload. The
+    /// worker thread which polled the task is unaffected. The handle also
+    /// resolves to an error if the task is aborted with
+    /// [`JoinHandle::abort`], or if the pool has been shut down.
+    ///
+    /// ```
+    /// use futures::executor::{block_on, ThreadPool};
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    ///
+    /// let handle = pool.spawn_joinable(async { panic!("boom") });
+    /// let err = block_on(handle).unwrap_err();
+    /// assert!(err.is_panic());
+    /// assert_eq!(*err.try_into_panic().unwrap().downcast::<&str>().unwrap(), "boom");
+    /// ```
+    ///
+    /// > **Note**: This method is similar to
+    /// >           `SpawnExt::spawn_with_join_handle`, except that it is
+    /// >           guaranteed to always succeed.
+    pub fn spawn_joinable<Fut>(&self, future: Fut) -> JoinHandle<Fut::Output>
+    where
+        Fut: Future + Send + 'static,
+        Fut::Output: Send,
+    {
+        let (future, handle) = joinable(future);
+        self.spawn_ok(future);
+        handle
+    }
+
+    /// Returns a handle to the runtime metrics of the pool.
+    ///
+    /// ```
+    /// use futures::executor::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    /// let metrics = pool.metrics();
+    /// println!(
+    ///     "{} alive tasks, {} queued, busy for {:?}",
+    ///     metrics.alive_tasks(),
+    ///     metrics.queue_depth(),
+    ///     metrics.busy_time(),
+    /// );
+    /// ```
+    ///
+    /// This method is only available when the `metrics` feature of this
+    /// library is activated.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn metrics(&self) -> Metrics {
+        self.state.metrics.clone()
+    }
+
+    /// Returns the statistics of the tasks currently spawned on the pool,
+    /// in no particular order.
+    ///
+    /// Tasks are listed from when they are spawned until they complete or
+    /// are dropped.
+    ///
+    /// ```
+    /// use futures::channel::oneshot;
+    /// use futures::executor::ThreadPool;
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    /// let (tx, rx) = oneshot::channel::<()>();
+    /// pool.spawn_ok(async move { rx.await.unwrap() });
+    ///
+    /// let stats = pool.task_stats();
+    /// assert_eq!(stats.len(), 1);
+    /// println!("polled {} times for {:?}", stats[0].polls(), stats[0].busy_time());
+    /// # tx.send(()).unwrap();
+    /// ```
+    ///
+    /// This method is only available when the `task-stats` feature of this
+    /// library is activated.
+    #[cfg(feature = "task-stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "task-stats")))]
+    pub fn task_stats(&self) -> Vec<TaskStats> {
+        let stats = self.state.stats.lock().unwrap();
+        stats.values().map(|stats| stats.lock().unwrap().clone()).collect()
+    }
+
+    /// Shuts the pool down.
+    ///
+    /// The pool stops accepting new tasks right away: spawning tasks or
+    /// blocking closures fails from then on. This method then waits for the
+    /// tasks already spawned to complete, for at most `timeout` if one is
+    /// given, stops the worker threads and waits for them to exit.
+    ///
+    /// Returns the number of tasks which didn't complete in time. These are
+    /// never polled again: the queued ones are dropped, the others are
+    /// dropped once woken.
+    ///
+    /// ```
+    /// use futures::executor::ThreadPool;
+    /// use std::time::Duration;
+    ///
+    /// let pool = ThreadPool::new().unwrap();
+    /// pool.spawn_ok(async { /* ... */ });
+    ///
+    /// let abandoned = pool.shutdown(Some(Duration::from_secs(1)));
+    /// assert_eq!(abandoned, 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from one of the worker threads of the pool, which
+    /// would have to wait for itself.
+    pub fn shutdown(&self, timeout: Option<Duration>) -> usize {
+        assert!(
+            !self.state.on_worker_thread(),
+            "`ThreadPool::shutdown` called from a worker thread of the pool"
+        );
+        self.state.close();
+        self.state.wait_completed(timeout.map(|timeout| Instant::now() + timeout));
+
+        self.state.shutdown.store(true, Ordering::SeqCst);
+        self.state.wake_all();
+        let threads = mem::take(&mut *self.state.threads.lock().unwrap());
+        for thread in threads {
+            // The panic hook already reported the panics of the workers.
+            let _ = thread.join();
+        }
+        let abandoned = self.state.tasks.load(Ordering::SeqCst);
+        self.state.drain();
+        abandoned
+    }
 }
 
 impl Spawn for ThreadPool {
     fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
-        self.spawn_obj_ok(future);
-        Ok(())
+        self.spawn_obj_with_priority(future, Priority::Normal)
+    }
+
+    fn status(&self) -> Result<(), SpawnError> {
+        if self.state.closed.load(Ordering::SeqCst) {
+            Err(SpawnError::shutdown())
+        } else if self.state.tasks.load(Ordering::SeqCst) >= self.state.capacity
+            || self.state.capacity != usize::MAX
+                && !self.state.spawn_waiters.lock().unwrap().queue.is_empty()
+        {
+            Err(SpawnError::full())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl SpawnPriority for ThreadPool {
+    fn spawn_obj_with_priority(
+        &self,
+        future: FutureObj<'static, ()>,
+        priority: Priority,
+    ) -> Result<(), SpawnError> {
+        if self.state.closed.load(Ordering::SeqCst) {
+            return Err(SpawnError::shutdown());
+        }
+        let registration = self.state.try_register().ok_or_else(SpawnError::full)?;
+        self.spawn_registered(future, priority, registration)
+    }
+}
+
+impl SpawnBlocking for ThreadPool {
+    fn spawn_blocking_obj(&self, f: Box<dyn FnOnce() + Send + 'static>) -> Result<(), SpawnError> {
+        self.state.blocking.spawn(f)
     }
 }
 
 impl PoolState {
-    fn send(&self, msg: Message) {
-        self.tx.lock().unwrap().send(msg).unwrap();
+    /// Queues a task on the current worker thread if it belongs to this
+    /// pool, on the shared queues otherwise.
+    fn schedule(self: &Arc<Self>, task: Task, priority: Priority) {
+        let idx = queue_index(priority);
+        #[cfg(feature = "metrics")]
+        self.metrics.task_queued();
+        let mut task = Some(task);
+        // The thread local is gone if the task is woken while the thread
+        // exits.
+        let _ = LOCAL.try_with(|local| match &*local.borrow() {
+            Some(local) if ptr::eq(local.pool, &**self) => {
+                local.queues[idx].push(task.take().unwrap())
+            }
+            _ => {}
+        });
+        if let Some(task) = task {
+            self.injectors[idx].push(task);
+            // Pairs with the store in `ThreadPool::shutdown`: either the
+            // task is drained there, or here.
+            atomic::fence(Ordering::SeqCst);
+            if self.shutdown.load(Ordering::SeqCst) {
+                self.drain();
+                return;
+            }
+            if self.detached.load(Ordering::SeqCst) {
+                self.unpark_worker();
+            }
+        }
+        self.notify();
     }
 
-    fn work(
-        &self,
+    /// Keeps the queues of a worker which stopped after the pool was
+    /// detached, for a worker started when a task is queued.
+    fn park_worker(self: &Arc<Self>, worker: IdleWorker) {
+        self.idle.lock().unwrap().push(worker);
+        // Pairs with the fence in `schedule`: either a task queued meanwhile
+        // is seen here, or the worker is started again there.
+        atomic::fence(Ordering::SeqCst);
+        if self.has_tasks() {
+            self.unpark_worker();
+        }
+    }
+
+    /// Starts one of the workers which stopped after the pool was detached,
+    /// if any.
+    fn unpark_worker(self: &Arc<Self>) {
+        let worker = self.idle.lock().unwrap().pop();
+        if let Some(worker) = worker {
+            // Nothing joins the workers of a detached pool.
+            let _ = self.spawn_worker(
+                worker.idx,
+                worker.queues,
+                None,
+                worker.before_stop,
+                #[cfg(feature = "thread-affinity")]
+                worker.cores,
+                #[cfg(feature = "thread-affinity")]
+                None,
+            );
+        }
+    }
+
+    fn on_worker_thread(&self) -> bool {
+        LOCAL
+            .try_with(|local| local.borrow().as_ref().map_or(false, |l| ptr::eq(l.pool, self)))
+            .unwrap_or(false)
+    }
+
+    /// Stops accepting new tasks.
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.blocking.close();
+        // Workers may be waiting for the last tasks to complete.
+        self.wake_all();
+        self.wake_spawn_waiters();
+    }
+
+    fn wake_spawn_waiters(&self) {
+        let waiters = mem::take(&mut self.spawn_waiters.lock().unwrap().queue);
+        for (_, waker) in waiters {
+            waker.wake();
+        }
+    }
+
+    fn wake_spawn_waiter(&self) {
+        let waker = self.spawn_waiters.lock().unwrap().front_if_free(self);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Counts a new task for a spawner which respects the capacity, unless
+    /// the pool is at capacity or futures are waiting in
+    /// `ThreadPool::spawn_async` for a slot.
+    fn try_register(self: &Arc<Self>) -> Option<Registration> {
+        if self.capacity == usize::MAX {
+            return Registration::try_new(self);
+        }
+        self.spawn_waiters.lock().unwrap().try_acquire(None, self)
+    }
+
+    /// Waits for all the tasks to complete, or for the deadline.
+    fn wait_completed(&self, deadline: Option<Instant>) {
+        let mut guard = self.sleep.lock().unwrap();
+        while self.tasks.load(Ordering::SeqCst) > 0 {
+            guard = match deadline {
+                None => self.completed.wait(guard).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return;
+                    }
+                    self.completed.wait_timeout(guard, deadline - now).unwrap().0
+                }
+            };
+        }
+    }
+
+    fn wake_all(&self) {
+        let _guard = self.sleep.lock().unwrap();
+        self.available.notify_all();
+        self.completed.notify_all();
+    }
+
+    /// Passes the payload of a task panic to the panic handler.
+    fn report_panic(&self, payload: Box<dyn Any + Send>) {
+        if let Some(panic_handler) = &self.panic_handler {
+            // The panic hook already reported a panic of the handler.
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| panic_handler(payload)));
+        }
+    }
+
+    /// Drops the tasks left in the shared queues.
+    fn drain(&self) {
+        for injector in &self.injectors {
+            loop {
+                match injector.steal() {
+                    Steal::Empty => break,
+                    Steal::Success(_task) => {
+                        #[cfg(feature = "metrics")]
+                        self.metrics.task_dequeued();
+                    }
+                    Steal::Retry => {}
+                }
+            }
+        }
+    }
+
+    /// Wakes up a sleeping worker, if any, to pick up a new task.
+    fn notify(&self) {
+        // Pairs with the fence in `sleep`: either the worker sees the new
+        // task before waiting, or it is counted here.
+        atomic::fence(Ordering::SeqCst);
+        if self.sleeping.load(Ordering::SeqCst) > 0 {
+            let _guard = self.sleep.lock().unwrap();
+            self.available.notify_one();
+        }
+    }
+
+    fn find_task(&self, local: &LocalQueues) -> Option<Task> {
+        let ticks = local.ticks.get().wrapping_add(1);
+        local.ticks.set(ticks);
+        let injector_first = ticks % INJECTOR_INTERVAL == 0;
+
+        'retry: loop {
+            for idx in 0..PRIORITIES {
+                let queue = &local.queues[idx];
+                if !injector_first {
+                    if let Some(task) = queue.pop() {
+                        return Some(task);
+                    }
+                }
+                let others = self.stealers.iter().enumerate().filter(|(i, _)| *i != local.index);
+                let steal = iter::once(self.injectors[idx].steal_batch_and_pop(queue))
+                    .chain(others.map(|(_, stealers)| stealers[idx].steal_batch_and_pop(queue)))
+                    .find(|steal| !steal.is_empty())
+                    .unwrap_or(Steal::Empty);
+                match steal {
+                    Steal::Success(task) => return Some(task),
+                    // Lost a race with another worker for a task.
+                    Steal::Retry => continue 'retry,
+                    Steal::Empty => {}
+                }
+                if injector_first {
+                    if let Some(task) = queue.pop() {
+                        return Some(task);
+                    }
+                }
+            }
+            return None;
+        }
+    }
+
+    fn has_tasks(&self) -> bool {
+        self.injectors.iter().any(|injector| !injector.is_empty())
+            || self.stealers.iter().flatten().any(|stealer| !stealer.is_empty())
+    }
+
+    /// Waits until there may be new tasks to run, returning `false` if the
+    /// pool has been shut down instead.
+    fn sleep(&self) -> bool {
+        let mut guard = self.sleep.lock().unwrap();
+        self.sleeping.fetch_add(1, Ordering::SeqCst);
+        atomic::fence(Ordering::SeqCst);
+        let running = loop {
+            if self.stopping() {
+                break false;
+            }
+            if self.has_tasks() {
+                break true;
+            }
+            guard = self.available.wait(guard).unwrap();
+        };
+        self.sleeping.fetch_sub(1, Ordering::SeqCst);
+        running
+    }
+
+    /// Whether the workers should stop once they run out of tasks.
+    fn stopping(&self) -> bool {
+        self.shutdown.load(Ordering::SeqCst)
+            || self.closed.load(Ordering::SeqCst)
+                && (self.tasks.load(Ordering::SeqCst) == 0
+                    || self.detached.load(Ordering::SeqCst) && !self.has_tasks())
+    }
+
+    /// Starts the worker thread `idx`, running the tasks of `queues`.
+    ///
+    /// The worker reports whether it could be pinned to `cores` on
+    /// `affinity_tx`, and only runs if it could.
+    fn spawn_worker(
+        self: &Arc<Self>,
         idx: usize,
-        after_start: Option<Arc<dyn Fn(usize) + Send + Sync>>,
-        before_stop: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+        queues: [Worker<Task>; PRIORITIES],
+        after_start: Option<WorkerHook>,
+        before_stop: Option<WorkerHook>,
+        #[cfg(feature = "thread-affinity")] cores: Vec<usize>,
+        #[cfg(feature = "thread-affinity")] affinity_tx: Option<
+            std::sync::mpsc::SyncSender<io::Result<()>>,
+        >,
+    ) -> io::Result<thread::JoinHandle<()>> {
+        let mut thread_builder = thread::Builder::new();
+        if let Some(ref name_prefix) = self.name_prefix {
+            thread_builder = thread_builder.name(format!("{}{}", name_prefix, idx));
+        }
+        if self.stack_size > 0 {
+            thread_builder = thread_builder.stack_size(self.stack_size);
+        }
+        let state = self.clone();
+        thread_builder.spawn(move || {
+            // The worker is pinned before running anything, or doesn't run
+            // at all.
+            #[cfg(feature = "thread-affinity")]
+            {
+                let res =
+                    if cores.is_empty() { Ok(()) } else { crate::affinity::set_affinity(&cores) };
+                if let Some(affinity_tx) = affinity_tx {
+                    let pinned = res.is_ok();
+                    let _ = affinity_tx.send(res);
+                    if !pinned {
+                        return;
+                    }
+                }
+            }
+            let respawn = Respawn {
+                state: &state,
+                idx,
+                before_stop,
+                #[cfg(feature = "thread-affinity")]
+                cores,
+            };
+            state.work(queues, after_start, respawn)
+        })
+    }
+
+    fn work(
+        self: &Arc<Self>,
+        queues: [Worker<Task>; PRIORITIES],
+        after_start: Option<WorkerHook>,
+        mut respawn: Respawn<'_>,
     ) {
+        let idx = respawn.idx;
         let _scope = enter().unwrap();
+        LOCAL.with(|local| {
+            *local.borrow_mut() =
+                Some(LocalQueues { pool: &**self, index: idx, queues, ticks: Cell::new(0) })
+        });
         if let Some(after_start) = after_start {
             after_start(idx);
         }
-        loop {
-            let msg = self.rx.lock().unwrap().recv().unwrap();
-            match msg {
-                Message::Run(task) => task.run(),
-                Message::Close => break,
+        while !self.shutdown.load(Ordering::SeqCst) {
+            let task = LOCAL.with(|local| self.find_task(local.borrow().as_ref().unwrap()));
+            match task {
+                #[cfg(feature = "metrics")]
+                Some(task) => {
+                    self.metrics.task_dequeued();
+                    let start = Instant::now();
+                    task.run();
+                    self.metrics.record_busy(idx, start.elapsed());
+                }
+                #[cfg(not(feature = "metrics"))]
+                Some(task) => task.run(),
+                None if self.sleep() => {}
+                None => break,
             }
         }
-        if let Some(before_stop) = before_stop {
+        if let Some(before_stop) = &respawn.before_stop {
             before_stop(idx);
         }
+        let local = LOCAL.with(|local| local.borrow_mut().take());
+        if self.detached.load(Ordering::SeqCst) && !self.shutdown.load(Ordering::SeqCst) {
+            if let Some(local) = local {
+                self.park_worker(IdleWorker {
+                    idx,
+                    queues: local.queues,
+                    before_stop: respawn.before_stop.take(),
+                    #[cfg(feature = "thread-affinity")]
+                    cores: mem::take(&mut respawn.cores),
+                });
+            }
+        }
+    }
+}
+
+/// A worker which stopped after its pool was detached.
+struct IdleWorker {
+    idx: usize,
+    queues: [Worker<Task>; PRIORITIES],
+    before_stop: Option<WorkerHook>,
+    #[cfg(feature = "thread-affinity")]
+    cores: Vec<usize>,
+}
+
+/// Replaces a worker thread which panics, for example in a task hook or
+/// when dropping a task, while the pool is running.
+///
+/// The replacement takes over the queues of the worker. It doesn't run the
+/// `after_start` hook, which has been dropped once all the workers ran it.
+struct Respawn<'a> {
+    state: &'a Arc<PoolState>,
+    idx: usize,
+    before_stop: Option<WorkerHook>,
+    #[cfg(feature = "thread-affinity")]
+    cores: Vec<usize>,
+}
+
+impl Drop for Respawn<'_> {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            return;
+        }
+        let local = LOCAL.try_with(|local| local.try_borrow_mut().ok()?.take()).ok().flatten();
+        let queues = match local {
+            Some(local) => local.queues,
+            // The worker panicked in `after_start`: a replacement would too.
+            None => return,
+        };
+        // Pairs with `ThreadPool::shutdown`: either the replacement is
+        // joined, or it isn't started.
+        let mut threads = self.state.threads.lock().unwrap();
+        // The replacement of a worker of a detached pool stops on its own
+        // once it runs out of tasks.
+        if self.state.stopping() && !self.state.detached.load(Ordering::SeqCst) {
+            return;
+        }
+        let thread = self.state.spawn_worker(
+            self.idx,
+            queues,
+            None,
+            self.before_stop.take(),
+            #[cfg(feature = "thread-affinity")]
+            mem::take(&mut self.cores),
+            #[cfg(feature = "thread-affinity")]
+            None,
+        );
+        if let Ok(thread) = thread {
+            threads.push(thread);
+        }
     }
 }
 
@@ -174,8 +886,17 @@ impl Clone for ThreadPool {
 impl Drop for ThreadPool {
     fn drop(&mut self) {
         if self.state.cnt.fetch_sub(1, Ordering::Relaxed) == 1 {
-            for _ in 0..self.state.size {
-                self.state.send(Message::Close);
+            match self.state.shutdown_on_drop {
+                Some(timeout) if !self.state.on_worker_thread() => {
+                    self.shutdown(timeout);
+                }
+                // Let the workers stop once the remaining tasks complete.
+                Some(_) => self.state.close(),
+                // Let the workers stop once they run out of queued tasks.
+                None => {
+                    self.state.detached.store(true, Ordering::SeqCst);
+                    self.state.close();
+                }
             }
         }
     }
@@ -192,6 +913,14 @@ impl ThreadPoolBuilder {
             name_prefix: None,
             after_start: None,
             before_stop: None,
+            hooks: None,
+            panic_handler: None,
+            #[cfg(feature = "thread-affinity")]
+            affinity: None,
+            max_blocking_threads: 512,
+            blocking_keep_alive: Duration::from_secs(10),
+            shutdown_on_drop: None,
+            capacity: usize::MAX,
         }
     }
 
@@ -237,6 +966,11 @@ impl ThreadPoolBuilder {
     ///
     /// The closure provided will receive an index corresponding to the worker
     /// thread it's running on.
+    ///
+    /// A worker thread which panics outside of a task poll, for example when
+    /// a task panics while being dropped, is replaced by a new thread with the
+    /// same index. The new thread doesn't run this hook, and neither do the
+    /// worker threads which a detached pool (see [`ThreadPool`]) starts again.
     pub fn after_start<F>(&mut self, f: F) -> &mut Self
     where
         F: Fn(usize) + Send + Sync + 'static,
@@ -253,6 +987,9 @@ impl ThreadPoolBuilder {
     ///
     /// The closure provided will receive an index corresponding to the worker
     /// thread it's running on.
+    ///
+    /// The worker threads of a detached pool (see [`ThreadPool`]) run this
+    /// hook each time they stop.
     pub fn before_stop<F>(&mut self, f: F) -> &mut Self
     where
         F: Fn(usize) + Send + Sync + 'static,
@@ -261,30 +998,223 @@ impl ThreadPoolBuilder {
         self
     }
 
+    /// Invoke `hooks` around every poll and wakeup of the tasks spawned on
+    /// the pool.
+    ///
+    /// See [`TaskHooks`](crate::TaskHooks) for details.
+    pub fn task_hooks<H>(&mut self, hooks: H) -> &mut Self
+    where
+        H: TaskHooks + 'static,
+    {
+        self.hooks = Some(Hooks::new(hooks));
+        self
+    }
+
+    /// Pin the worker threads to CPU cores, worker `i` running on core
+    /// `cores[i % cores.len()]` only.
+    ///
+    /// This is a shorthand for [`affinity`](ThreadPoolBuilder::affinity)
+    /// with one core per worker.
+    ///
+    /// This method is only available when the `thread-affinity` feature of
+    /// this library is activated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cores` is empty.
+    #[cfg(feature = "thread-affinity")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "thread-affinity")))]
+    pub fn pin_to_cores(&mut self, cores: Vec<usize>) -> &mut Self {
+        assert!(!cores.is_empty());
+        self.affinity(move |idx| vec![cores[idx % cores.len()]])
+    }
+
+    /// Restrict each worker thread to the CPU cores returned by `f` for its
+    /// index. An empty list leaves the worker free to run on any core.
+    ///
+    /// The affinity is set by each worker when it starts, before running
+    /// anything else, on Linux and Windows. [`create`](ThreadPoolBuilder::create) fails if it can't be
+    /// set, for instance because a core doesn't exist, or on another
+    /// platform.
+    ///
+    /// This method is only available when the `thread-affinity` feature of
+    /// this library is activated.
+    #[cfg(feature = "thread-affinity")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "thread-affinity")))]
+    pub fn affinity<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(usize) -> Vec<usize> + Send + Sync + 'static,
+    {
+        self.affinity = Some(Arc::new(f));
+        self
+    }
+
+    /// Call `f` with the payload of every panic of a task spawned on the
+    /// pool, from the worker thread which polled the task.
+    ///
+    /// A task which panics is dropped, and the worker thread keeps running
+    /// the other tasks, whether a panic handler is set or not. Panics of the
+    /// handler itself are ignored.
+    ///
+    /// Tasks spawned with [`ThreadPool::spawn_joinable`] report their panics
+    /// through their [`JoinHandle`] instead.
+    ///
+    /// ```
+    /// use futures::executor::ThreadPool;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let panics = Arc::new(AtomicUsize::new(0));
+    /// let pool = {
+    ///     let panics = panics.clone();
+    ///     ThreadPool::builder()
+    ///         .panic_handler(move |_payload| {
+    ///             panics.fetch_add(1, Ordering::Relaxed);
+    ///         })
+    ///         .create()
+    ///         .unwrap()
+    /// };
+    ///
+    /// pool.spawn_ok(async { panic!("boom") });
+    /// pool.shutdown(None);
+    /// assert_eq!(panics.load(Ordering::Relaxed), 1);
+    /// ```
+    pub fn panic_handler<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Box<dyn Any + Send>) + Send + Sync + 'static,
+    {
+        self.panic_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Set the maximum number of threads running the blocking closures
+    /// passed to
+    /// [`SpawnBlockingExt::spawn_blocking`](futures_util::task::SpawnBlockingExt::spawn_blocking).
+    ///
+    /// These threads are separate from the worker threads of the pool. They
+    /// are started when needed, and stopped after having been idle for the
+    /// duration set with
+    /// [`blocking_keep_alive`](ThreadPoolBuilder::blocking_keep_alive). When
+    /// all of them are busy, blocking closures are queued. By default, at most
+    /// 512 threads are started.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max == 0`.
+    pub fn max_blocking_threads(&mut self, max: usize) -> &mut Self {
+        assert!(max > 0);
+        self.max_blocking_threads = max;
+        self
+    }
+
+    /// Set how long a thread for blocking closures waits for more work
+    /// before stopping. By default, this is 10 seconds.
+    pub fn blocking_keep_alive(&mut self, keep_alive: Duration) -> &mut Self {
+        self.blocking_keep_alive = keep_alive;
+        self
+    }
+
+    /// Set the maximum number of tasks the pool holds at once, whether
+    /// queued, being polled, or waiting to be woken.
+    ///
+    /// Once the pool holds this many tasks, [`ThreadPool::try_spawn`] and
+    /// the [`Spawn`] implementation fail with an error for which
+    /// [`SpawnError::is_full`] returns `true`, and [`ThreadPool::spawn_async`]
+    /// waits for some of the tasks to complete. [`ThreadPool::spawn_ok`]
+    /// ignores this limit. By default, the number of tasks is unbounded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity == 0`.
+    pub fn capacity(&mut self, capacity: usize) -> &mut Self {
+        assert!(capacity > 0);
+        self.capacity = capacity;
+        self
+    }
+
+    /// Shut the pool down when the last [`ThreadPool`] handle to it is
+    /// dropped, as with [`ThreadPool::shutdown(timeout)`](ThreadPool::shutdown).
+    ///
+    /// Dropping the last handle then blocks until the spawned tasks complete
+    /// or `timeout` expires, and the worker threads exit. Tasks holding a
+    /// handle to the pool keep it alive. When the last handle is dropped on
+    /// a worker thread of the pool, the pool isn't waited for.
+    ///
+    /// By default, the pool is detached instead: the worker threads exit once
+    /// they run out of queued tasks, and are started again when one of the
+    /// remaining tasks is woken.
+    pub fn shutdown_on_drop(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.shutdown_on_drop = Some(timeout);
+        self
+    }
+
     /// Create a [`ThreadPool`](ThreadPool) with the given configuration.
     pub fn create(&mut self) -> Result<ThreadPool, io::Error> {
-        let (tx, rx) = mpsc::channel();
+        let queues: Vec<_> = (0..self.pool_size).map(|_| new_queues()).collect();
+        let stealers = queues
+            .iter()
+            .map(|queues| [queues[0].stealer(), queues[1].stealer(), queues[2].stealer()])
+            .collect();
         let pool = ThreadPool {
             state: Arc::new(PoolState {
-                tx: Mutex::new(tx),
-                rx: Mutex::new(rx),
+                injectors: [Injector::new(), Injector::new(), Injector::new()],
+                stealers,
+                sleeping: AtomicUsize::new(0),
+                sleep: Mutex::new(()),
+                available: Condvar::new(),
+                closed: AtomicBool::new(false),
+                shutdown: AtomicBool::new(false),
+                detached: AtomicBool::new(false),
+                idle: Mutex::new(Vec::new()),
+                tasks: AtomicUsize::new(0),
+                completed: Condvar::new(),
+                capacity: self.capacity,
+                spawn_waiters: Mutex::new(SpawnWaiters { next_key: 0, queue: VecDeque::new() }),
+                #[cfg(feature = "task-stats")]
+                stats: Mutex::new(HashMap::new()),
+                #[cfg(feature = "metrics")]
+                metrics: Metrics::new(self.pool_size),
+                threads: Mutex::new(Vec::with_capacity(self.pool_size)),
                 cnt: AtomicUsize::new(1),
                 size: self.pool_size,
+                shutdown_on_drop: self.shutdown_on_drop,
+                hooks: self.hooks.clone(),
+                panic_handler: self.panic_handler.clone(),
+                blocking: BlockingPool::new(
+                    self.max_blocking_threads,
+                    self.blocking_keep_alive,
+                    self.name_prefix.clone(),
+                    self.stack_size,
+                ),
+                name_prefix: self.name_prefix.clone(),
+                stack_size: self.stack_size,
             }),
         };
 
-        for counter in 0..self.pool_size {
-            let state = pool.state.clone();
-            let after_start = self.after_start.clone();
-            let before_stop = self.before_stop.clone();
-            let mut thread_builder = thread::Builder::new();
-            if let Some(ref name_prefix) = self.name_prefix {
-                thread_builder = thread_builder.name(format!("{}{}", name_prefix, counter));
-            }
-            if self.stack_size > 0 {
-                thread_builder = thread_builder.stack_size(self.stack_size);
+        for (counter, queues) in queues.into_iter().enumerate() {
+            #[cfg(feature = "thread-affinity")]
+            let cores = self.affinity.as_ref().map_or_else(Vec::new, |affinity| affinity(counter));
+            #[cfg(feature = "thread-affinity")]
+            let (affinity_tx, affinity_rx) = std::sync::mpsc::sync_channel(1);
+            let thread = pool.state.spawn_worker(
+                counter,
+                queues,
+                self.after_start.clone(),
+                self.before_stop.clone(),
+                #[cfg(feature = "thread-affinity")]
+                cores,
+                #[cfg(feature = "thread-affinity")]
+                Some(affinity_tx),
+            )?;
+            pool.state.threads.lock().unwrap().push(thread);
+            #[cfg(feature = "thread-affinity")]
+            {
+                if let Ok(Err(err)) = affinity_rx.recv() {
+                    // Stop the workers started so far.
+                    pool.shutdown(None);
+                    return Err(err);
+                }
             }
-            thread_builder.spawn(move || state.work(counter, after_start, before_stop))?;
         }
         Ok(pool)
     }
@@ -299,20 +1229,195 @@ impl Default for ThreadPoolBuilder {
 /// A task responsible for polling a future to completion.
 struct Task {
     future: FutureObj<'static, ()>,
-    exec: ThreadPool,
     wake_handle: Arc<WakeHandle>,
+    registration: Registration,
+}
+
+/// Counts a task as in flight until it completes or is dropped.
+struct Registration {
+    pool: Arc<PoolState>,
+    // Set once the task is spawned.
+    id: Option<TaskId>,
+}
+
+impl Registration {
+    fn new(pool: &Arc<PoolState>) -> Self {
+        pool.tasks.fetch_add(1, Ordering::SeqCst);
+        Self { pool: pool.clone(), id: None }
+    }
+
+    /// Counts a new task, unless the pool is at capacity.
+    fn try_new(pool: &Arc<PoolState>) -> Option<Self> {
+        pool.tasks
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |tasks| {
+                if tasks < pool.capacity {
+                    Some(tasks + 1)
+                } else {
+                    None
+                }
+            })
+            .ok()?;
+        Some(Self { pool: pool.clone(), id: None })
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Some(_id) = self.id {
+            #[cfg(feature = "task-stats")]
+            self.pool.stats.lock().unwrap().remove(&_id);
+            #[cfg(feature = "metrics")]
+            self.pool.metrics.task_completed();
+        }
+        // Pairs with the store in `PoolState::close`: either the closing
+        // thread sees no tasks left, or it is notified here.
+        if self.pool.tasks.fetch_sub(1, Ordering::SeqCst) == 1
+            && self.pool.closed.load(Ordering::SeqCst)
+        {
+            self.pool.wake_all();
+        }
+        if self.pool.capacity != usize::MAX {
+            self.pool.wake_spawn_waiter();
+        }
+    }
+}
+
+/// The futures waiting in `ThreadPool::spawn_async` for capacity, in order.
+///
+/// A waiter stays queued until it gets a slot, and only the one at the front
+/// of the queue may take a freed slot: it is woken when one is freed, and
+/// wakes the next one once it has taken it. New spawners don't take slots
+/// while futures are waiting.
+struct SpawnWaiters {
+    next_key: usize,
+    queue: VecDeque<(usize, Waker)>,
+}
+
+impl SpawnWaiters {
+    /// Counts a new task for the waiter `key`, or for a new spawner, if it
+    /// is its turn and the pool is below capacity. A waiter which gets a
+    /// slot leaves the queue.
+    fn try_acquire(&mut self, key: Option<usize>, pool: &Arc<PoolState>) -> Option<Registration> {
+        let turn = match key {
+            None => self.queue.is_empty(),
+            Some(key) => self.queue.front().map_or(false, |(k, _)| *k == key),
+        };
+        if !turn {
+            return None;
+        }
+        let registration = Registration::try_new(pool)?;
+        if key.is_some() {
+            self.queue.pop_front();
+        }
+        Some(registration)
+    }
+
+    /// Queues the waiter, or updates its waker if it's already queued, in
+    /// which case it keeps its place.
+    fn register(&mut self, key: &mut Option<usize>, waker: &Waker) {
+        if let Some(key) = *key {
+            if let Some((_, queued)) = self.queue.iter_mut().find(|(k, _)| *k == key) {
+                if !queued.will_wake(waker) {
+                    *queued = waker.clone();
+                }
+                return;
+            }
+        }
+        let key = *key.get_or_insert_with(|| {
+            self.next_key = self.next_key.wrapping_add(1);
+            self.next_key
+        });
+        self.queue.push_back((key, waker.clone()));
+    }
+
+    /// Removes the waiter, if it's still queued.
+    fn remove(&mut self, key: usize) {
+        if let Some(index) = self.queue.iter().position(|(k, _)| *k == key) {
+            self.queue.remove(index);
+        }
+    }
+
+    /// Returns the waker of the waiter at the front of the queue, if the
+    /// pool is below capacity.
+    fn front_if_free(&self, pool: &PoolState) -> Option<Waker> {
+        let (_, waker) = self.queue.front()?;
+        if pool.tasks.load(Ordering::SeqCst) < pool.capacity {
+            Some(waker.clone())
+        } else {
+            None
+        }
+    }
+}
+
+/// Future for [`ThreadPool::spawn_async`].
+struct SpawnAsync {
+    pool: ThreadPool,
+    future: Option<FutureObj<'static, ()>>,
+    // Set once the future waited for capacity.
+    key: Option<usize>,
+}
+
+impl Future for SpawnAsync {
+    type Output = Result<(), SpawnError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let state = &this.pool.state;
+        if state.closed.load(Ordering::SeqCst) {
+            return Poll::Ready(Err(SpawnError::shutdown()));
+        }
+        // Slots are only freed and taken under this lock, so that no wake
+        // is lost between the check and the registration.
+        let mut waiters = state.spawn_waiters.lock().unwrap();
+        let registration = match waiters.try_acquire(this.key, state) {
+            Some(registration) => registration,
+            None => {
+                waiters.register(&mut this.key, cx.waker());
+                return Poll::Pending;
+            }
+        };
+        // The next waiter may take a slot freed meanwhile.
+        let next = if this.key.take().is_some() { waiters.front_if_free(state) } else { None };
+        drop(waiters);
+        if let Some(next) = next {
+            next.wake();
+        }
+        let future = this.future.take().expect("`spawn_async` polled after completion");
+        Poll::Ready(this.pool.spawn_registered(future, Priority::Normal, registration))
+    }
+}
+
+impl Drop for SpawnAsync {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            let state = &self.pool.state;
+            let mut waiters = state.spawn_waiters.lock().unwrap();
+            waiters.remove(key);
+            // This future may have been woken for a freed slot it won't
+            // take: pass it on to the next waiter.
+            let next = waiters.front_if_free(state);
+            drop(waiters);
+            if let Some(next) = next {
+                next.wake();
+            }
+        }
+    }
 }
 
 struct WakeHandle {
+    id: TaskId,
+    priority: Priority,
     mutex: UnparkMutex<Task>,
-    exec: ThreadPool,
+    pool: Arc<PoolState>,
+    #[cfg(feature = "task-stats")]
+    stats: Arc<Mutex<TaskStats>>,
 }
 
 impl Task {
     /// Actually run the task (invoking `poll` on the future) on the current
     /// thread.
     fn run(self) {
-        let Self { mut future, wake_handle, mut exec } = self;
+        let Self { mut future, wake_handle, mut registration } = self;
         let waker = waker_ref(&wake_handle);
         let mut cx = Context::from_waker(&waker);
 
@@ -322,18 +1427,31 @@ impl Task {
             wake_handle.mutex.start_poll();
 
             loop {
-                let res = future.poll_unpin(&mut cx);
+                let mut poll = || coop::budget(|| future.poll_unpin(&mut cx));
+                #[cfg(feature = "task-stats")]
+                let start = Instant::now();
+                let res = panic::catch_unwind(AssertUnwindSafe(|| match &wake_handle.pool.hooks {
+                    Some(hooks) => hooks.poll(wake_handle.id, poll),
+                    None => poll(),
+                }));
+                #[cfg(feature = "task-stats")]
+                wake_handle.stats.lock().unwrap().record_poll(start.elapsed());
                 match res {
-                    Poll::Pending => {}
-                    Poll::Ready(()) => return wake_handle.mutex.complete(),
+                    Ok(Poll::Pending) => {}
+                    Ok(Poll::Ready(())) => return wake_handle.mutex.complete(),
+                    Err(payload) => {
+                        wake_handle.mutex.complete();
+                        drop(future);
+                        return wake_handle.pool.report_panic(payload);
+                    }
                 }
-                let task = Self { future, wake_handle: wake_handle.clone(), exec };
+                let task = Self { future, wake_handle: wake_handle.clone(), registration };
                 match wake_handle.mutex.wait(task) {
                     Ok(()) => return, // we've waited
                     Err(task) => {
                         // someone's notified us
                         future = task.future;
-                        exec = task.exec;
+                        registration = task.registration;
                     }
                 }
             }
@@ -349,8 +1467,11 @@ impl fmt::Debug for Task {
 
 impl ArcWake for WakeHandle {
     fn wake_by_ref(arc_self: &Arc<Self>) {
+        if let Some(hooks) = &arc_self.pool.hooks {
+            hooks.0.on_wake(arc_self.id);
+        }
         if let Ok(task) = arc_self.mutex.notify() {
-            arc_self.exec.state.send(Message::Run(task))
+            arc_self.pool.schedule(task, arc_self.priority)
         }
     }
 }
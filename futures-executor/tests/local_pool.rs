@@ -5,7 +5,7 @@ use futures::task::{Context, LocalSpawn, LocalSpawnExt, Poll, Spawn, SpawnExt, W
 use std::cell::{Cell, RefCell};
 use std::pin::Pin;
 use std::rc::Rc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -494,3 +494,282 @@ fn self_waking_try_run_one() {
     // The self-waking futures are each polled once.
     assert_eq!(*wakeups_remaining.borrow(), 7);
 }
+
+#[test]
+fn run_until_stalled_with_budget_limits_polls() {
+    let mut pool = LocalPool::new();
+    let spawn = pool.spawner();
+    let polls = Rc::new(Cell::new(0));
+
+    let polls_ = polls.clone();
+    spawn
+        .spawn_local(poll_fn(move |cx| {
+            polls_.set(polls_.get() + 1);
+            cx.waker().wake_by_ref();
+            Poll::<()>::Pending
+        }))
+        .unwrap();
+
+    assert!(pool.run_until_stalled_with_budget(5));
+    assert_eq!(polls.get(), 5);
+    assert!(pool.run_until_stalled_with_budget(3));
+    assert_eq!(polls.get(), 8);
+}
+
+#[test]
+fn run_until_stalled_with_budget_reports_stall() {
+    let mut pool = LocalPool::new();
+    let spawn = pool.spawner();
+    let (tx, rx) = oneshot::channel::<()>();
+    spawn.spawn_local(async move { rx.await.unwrap() }).unwrap();
+    spawn.spawn_local(pending()).unwrap();
+
+    assert!(!pool.run_until_stalled_with_budget(10));
+    tx.send(()).unwrap();
+    assert!(!pool.run_until_stalled_with_budget(10));
+    assert!(!pool.try_run_one());
+}
+
+#[test]
+fn run_for_stops_spinning_tasks() {
+    let mut pool = LocalPool::new();
+    let spawn = pool.spawner();
+    spawn
+        .spawn_local(poll_fn(|cx| {
+            cx.waker().wake_by_ref();
+            Poll::<()>::Pending
+        }))
+        .unwrap();
+
+    assert!(pool.run_for(Duration::from_millis(10)));
+    // Regular runs are unlimited again.
+    let done = Rc::new(Cell::new(false));
+    let done_ = done.clone();
+    spawn.spawn_local(async move { done_.set(true) }).unwrap();
+    pool.run_until(future::ready(()));
+    assert!(pool.run_for(Duration::from_millis(1)));
+    assert!(done.get());
+}
+
+#[test]
+fn dump_pending_reports_named_tasks() {
+    let mut pool = LocalPool::new();
+    let spawn = pool.spawner();
+    let (tx, rx) = oneshot::channel::<()>();
+
+    spawn.spawn_local_named("waiting", async move { rx.await.unwrap() }).unwrap();
+    spawn.spawn_local_named("done", future::ready(())).unwrap();
+    spawn.spawn_named_obj("stuck", Box::pin(future::pending()).into()).unwrap();
+    spawn.spawn_local(future::pending()).unwrap();
+
+    // Spawned tasks are reported before they're polled.
+    assert_eq!(pool.dump_pending().len(), 4);
+
+    pool.run_until_stalled();
+    let pending = pool.dump_pending();
+    let names: Vec<_> = pending.iter().map(|task| task.name()).collect();
+    assert_eq!(names, [Some("waiting"), Some("stuck"), None]);
+    assert!(pending[0].id() < pending[1].id());
+
+    tx.send(()).unwrap();
+    pool.run_until_stalled();
+    let names: Vec<_> =
+        pool.dump_pending().iter().map(|task| task.name().map(String::from)).collect();
+    assert_eq!(names, [Some("stuck".to_string()), None]);
+}
+
+#[test]
+fn custom_park() {
+    use futures::executor::{Park, Unpark};
+    use std::sync::{Condvar, Mutex};
+
+    #[derive(Default)]
+    struct Reactor {
+        unparked: Mutex<bool>,
+        cond: Condvar,
+        parks: AtomicUsize,
+    }
+
+    struct ReactorPark(Arc<Reactor>);
+
+    impl Unpark for Reactor {
+        fn unpark(&self) {
+            *self.unparked.lock().unwrap() = true;
+            self.cond.notify_one();
+        }
+    }
+
+    impl Park for ReactorPark {
+        type Unpark = Arc<Reactor>;
+
+        fn unpark(&self) -> Arc<Reactor> {
+            self.0.clone()
+        }
+
+        fn park(&mut self, timeout: Option<Duration>) {
+            assert_eq!(timeout, None);
+            self.0.parks.fetch_add(1, Ordering::SeqCst);
+            let mut unparked = self.0.unparked.lock().unwrap();
+            while !*unparked {
+                unparked = self.0.cond.wait(unparked).unwrap();
+            }
+            *unparked = false;
+        }
+    }
+
+    let reactor = Arc::new(Reactor::default());
+    let mut pool = LocalPool::with_park(ReactorPark(reactor.clone()));
+    let (tx, rx) = oneshot::channel();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx.send(7).unwrap();
+    });
+
+    assert_eq!(pool.run_until(rx), Ok(7));
+    assert!(reactor.parks.load(Ordering::SeqCst) >= 1);
+    handle.join().unwrap();
+
+    // The park can be combined with the other settings of the builder.
+    let reactor = Arc::new(Reactor::default());
+    let mut pool = LocalPool::builder()
+        .scheduling_policy(futures::executor::SchedulingPolicy::LifoSlot)
+        .park(ReactorPark(reactor.clone()))
+        .create();
+    let (tx, rx) = oneshot::channel();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx.send(8).unwrap();
+    });
+
+    assert_eq!(pool.run_until(rx), Ok(8));
+    assert!(reactor.parks.load(Ordering::SeqCst) >= 1);
+    handle.join().unwrap();
+}
+
+#[test]
+fn block_on_with_ticks() {
+    use futures::executor::{block_on_with, BlockOnConfig};
+
+    let (tx, rx) = oneshot::channel();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx.send(3).unwrap();
+    });
+    let mut ticks = 0;
+    let config = BlockOnConfig::new().max_park(Duration::from_millis(5)).on_tick(|| ticks += 1);
+    assert_eq!(block_on_with(rx, config), Ok(3));
+    assert!(ticks >= 2, "ticks: {}", ticks);
+    handle.join().unwrap();
+
+    // Ready futures don't park.
+    let mut ticks = 0;
+    assert_eq!(block_on_with(future::ready(1), BlockOnConfig::new().on_tick(|| ticks += 1)), 1);
+    assert_eq!(ticks, 0);
+}
+
+#[test]
+fn block_on_nested_inside_local_pool() {
+    use futures::executor::{block_on_nested, is_entered};
+
+    assert!(!is_entered());
+    assert_eq!(block_on_nested(async { 1 }), 1);
+
+    let mut pool = LocalPool::new();
+    let spawn = pool.spawner();
+    let (tx, rx) = oneshot::channel();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        tx.send(2).unwrap();
+    });
+    let done = Rc::new(Cell::new(false));
+    let done_ = done.clone();
+    spawn
+        .spawn_local(async move {
+            assert!(is_entered());
+            assert_eq!(block_on_nested(rx), Ok(2));
+            done_.set(true);
+        })
+        .unwrap();
+    let ran = Rc::new(Cell::new(false));
+    let ran_ = ran.clone();
+    spawn.spawn_local(async move { ran_.set(true) }).unwrap();
+
+    pool.run();
+    assert!(done.get());
+    assert!(ran.get());
+    handle.join().unwrap();
+}
+
+#[test]
+fn block_on_nested_runs_the_pool() {
+    use futures::executor::block_on_nested;
+
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let done = Rc::new(Cell::new(false));
+    {
+        let spawner = spawner.clone();
+        let done = done.clone();
+        spawner
+            .clone()
+            .spawn_local(async move {
+                let (tx, rx) = oneshot::channel();
+                spawner
+                    .spawn_local(async move {
+                        // Yield a few times before completing.
+                        for _ in 0..3 {
+                            let mut yielded = false;
+                            future::poll_fn(|cx| {
+                                if yielded {
+                                    return Poll::Ready(());
+                                }
+                                yielded = true;
+                                cx.waker().wake_by_ref();
+                                Poll::Pending
+                            })
+                            .await;
+                        }
+                        tx.send(3).unwrap();
+                    })
+                    .unwrap();
+                assert_eq!(block_on_nested(rx), Ok(3));
+                done.set(true);
+            })
+            .unwrap();
+    }
+    pool.run();
+    assert!(done.get());
+
+    // The same goes for the future passed to `run_until`.
+    let output = pool.run_until(async {
+        let handle = spawner.spawn_local_with_handle(async { 4 }).unwrap();
+        block_on_nested(handle)
+    });
+    assert_eq!(output, 4);
+}
+
+#[test]
+fn scope_runs_borrowing_tasks() {
+    let mut pool = LocalPool::new();
+    let spawn = pool.spawner();
+    let mut data = vec![1, 2, 3];
+    let sum = Cell::new(0);
+    let (tx, rx) = oneshot::channel();
+
+    // The scoped tasks depend on a regular task of the pool.
+    spawn.spawn_local(async move { tx.send(10).unwrap() }).unwrap();
+    let len = pool.scope(|s| {
+        for x in &data {
+            let sum = &sum;
+            s.spawn(async move { sum.set(sum.get() + x) });
+        }
+        let sum = &sum;
+        s.spawn(async move { sum.set(sum.get() + rx.await.unwrap()) });
+        data.len()
+    });
+
+    assert_eq!(len, 3);
+    assert_eq!(sum.get(), 16);
+    data.push(4);
+    assert_eq!(data, [1, 2, 3, 4]);
+}
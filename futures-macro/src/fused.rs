@@ -0,0 +1,138 @@
+//! The `FusedFuture` and `FusedStream` derive macro implementations.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Data, DeriveInput, Fields, Lit, Member, Meta, NestedMeta, Path};
+
+const ATTR: &str = "fused";
+
+pub(crate) fn fused_future(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(input, "future", "Future", "FusedFuture")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+pub(crate) fn fused_stream(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(input, "stream", "Stream", "FusedStream")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(
+    input: DeriveInput,
+    module: &str,
+    super_trait: &str,
+    trait_name: &str,
+) -> syn::Result<TokenStream2> {
+    let krate = crate_path(&input)?;
+    let module = syn::Ident::new(module, Span::call_site());
+    let super_trait = syn::Ident::new(super_trait, Span::call_site());
+    let trait_name = syn::Ident::new(trait_name, Span::call_site());
+    let trait_path: Path = parse_quote!(#krate::#module::#trait_name);
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("`{}` can only be derived for structs", trait_name),
+            ))
+        }
+    };
+    let (member, ty) = delegate_field(fields, &trait_name)?;
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    // The struct has to implement the supertrait, which may need more bounds.
+    let self_bound = parse_quote!(#name #ty_generics: #krate::#module::#super_trait);
+    let mut generics = input.generics.clone();
+    let predicates = &mut generics.make_where_clause().predicates;
+    predicates.push(parse_quote!(#ty: #trait_path));
+    predicates.push(self_bound);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            fn is_terminated(&self) -> bool {
+                #trait_path::is_terminated(&self.#member)
+            }
+        }
+    })
+}
+
+/// Returns the path given with `#[fused(crate = "...")]`, `::futures` by
+/// default.
+fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut krate = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident(ATTR)) {
+        for nested in nested_metas(attr)? {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("crate") => {
+                    if krate.is_some() {
+                        return Err(syn::Error::new_spanned(nv, "duplicate `crate` argument"));
+                    }
+                    match &nv.lit {
+                        Lit::Str(lit) => krate = Some(lit.parse()?),
+                        lit => return Err(syn::Error::new_spanned(lit, "expected a string")),
+                    }
+                }
+                nested => return Err(syn::Error::new_spanned(nested, "unknown argument")),
+            }
+        }
+    }
+    Ok(krate.unwrap_or_else(|| parse_quote!(::futures)))
+}
+
+/// Returns the field marked with `#[fused(delegate)]`, or the only field of
+/// the struct.
+fn delegate_field<'a>(
+    fields: &'a Fields,
+    trait_name: &syn::Ident,
+) -> syn::Result<(Member, &'a syn::Type)> {
+    let mut delegate = None;
+    for (i, field) in fields.iter().enumerate() {
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident(ATTR)) {
+            for nested in nested_metas(attr)? {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("delegate") => {
+                        if delegate.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                path,
+                                "only one field can be marked with `#[fused(delegate)]`",
+                            ));
+                        }
+                        delegate = Some((i, field));
+                    }
+                    nested => return Err(syn::Error::new_spanned(nested, "unknown argument")),
+                }
+            }
+        }
+    }
+
+    let (i, field) = match delegate {
+        Some(delegate) => delegate,
+        None if fields.len() == 1 => (0, fields.iter().next().unwrap()),
+        None => {
+            return Err(syn::Error::new(
+                fields.span(),
+                format!("mark the field to delegate `{}` to with `#[fused(delegate)]`", trait_name),
+            ))
+        }
+    };
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(i.into()),
+    };
+    Ok((member, &field.ty))
+}
+
+fn nested_metas(attr: &syn::Attribute) -> syn::Result<Vec<NestedMeta>> {
+    match attr.parse_meta()? {
+        Meta::List(list) => Ok(list.nested.into_iter().collect()),
+        meta => Err(syn::Error::new_spanned(meta, "expected `#[fused(...)]`")),
+    }
+}
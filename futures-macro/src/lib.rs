@@ -17,6 +17,7 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 
 mod executor;
+mod fused;
 mod join;
 mod select;
 mod stream_select;
@@ -59,3 +60,23 @@ pub fn stream_select_internal(input: TokenStream) -> TokenStream {
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
+
+/// The `FusedFuture` derive macro.
+///
+/// Unlike the function-like macros, a derive can't refer to the crate it is
+/// re-exported from, so the generated code uses `::futures` unless another
+/// path is given with `#[fused(crate = "...")]`.
+#[proc_macro_derive(FusedFuture, attributes(fused))]
+pub fn derive_fused_future(input: TokenStream) -> TokenStream {
+    crate::fused::fused_future(input)
+}
+
+/// The `FusedStream` derive macro.
+///
+/// Unlike the function-like macros, a derive can't refer to the crate it is
+/// re-exported from, so the generated code uses `::futures` unless another
+/// path is given with `#[fused(crate = "...")]`.
+#[proc_macro_derive(FusedStream, attributes(fused))]
+pub fn derive_fused_stream(input: TokenStream) -> TokenStream {
+    crate::fused::fused_stream(input)
+}
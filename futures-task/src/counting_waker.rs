@@ -0,0 +1,145 @@
+use alloc::sync::Arc;
+use core::fmt;
+use core::mem;
+use core::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+use core::task::{RawWaker, RawWakerVTable, Waker};
+
+/// A wrapper around a [`Waker`] which counts how its handles are used.
+///
+/// The wakers returned by [`waker`](CountingWaker::waker) forward wakeups to
+/// the wrapped waker, and record every clone, wake and drop in atomic
+/// counters shared by all of them. A [`WakerStats`] snapshot of the counters
+/// can be taken at any time with [`stats`](CountingWaker::stats), which makes
+/// it possible to track down spurious wakeups or leaked wakers in production.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::{noop_waker, CountingWaker};
+///
+/// let counting = CountingWaker::new(noop_waker());
+/// let waker = counting.waker();
+///
+/// waker.wake_by_ref();
+/// waker.clone().wake();
+/// drop(waker);
+///
+/// let stats = counting.stats();
+/// assert_eq!(stats.wakes, 2);
+/// assert_eq!(stats.clones, 2);
+/// assert_eq!(stats.live(), 0);
+/// ```
+pub struct CountingWaker {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    waker: Waker,
+    clones: AtomicUsize,
+    wakes: AtomicUsize,
+    wakes_by_ref: AtomicUsize,
+    drops: AtomicUsize,
+}
+
+/// A snapshot of the counters of a [`CountingWaker`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WakerStats {
+    /// The number of handles created, either by [`CountingWaker::waker`] or by
+    /// cloning another handle.
+    pub clones: usize,
+    /// The number of wakeups, both by value and by reference.
+    pub wakes: usize,
+    /// The number of wakeups by reference, i.e. through
+    /// [`Waker::wake_by_ref`].
+    pub wakes_by_ref: usize,
+    /// The number of handles released, either by being dropped or by being
+    /// consumed by [`Waker::wake`].
+    pub drops: usize,
+}
+
+impl WakerStats {
+    /// Returns the number of handles which are still alive.
+    pub fn live(&self) -> usize {
+        self.clones.saturating_sub(self.drops)
+    }
+}
+
+impl CountingWaker {
+    /// Creates a `CountingWaker` forwarding wakeups to `waker`.
+    pub fn new(waker: Waker) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                waker,
+                clones: AtomicUsize::new(0),
+                wakes: AtomicUsize::new(0),
+                wakes_by_ref: AtomicUsize::new(0),
+                drops: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Creates a new instrumented handle to the wrapped waker.
+    ///
+    /// The new handle is counted as a clone.
+    pub fn waker(&self) -> Waker {
+        unsafe { Waker::from_raw(clone_raw(Arc::as_ptr(&self.inner).cast())) }
+    }
+
+    /// Returns a snapshot of the counters.
+    ///
+    /// The counters are read one after the other, so a snapshot taken while
+    /// the wakers are in use on other threads may be slightly inconsistent.
+    pub fn stats(&self) -> WakerStats {
+        WakerStats {
+            clones: self.inner.clones.load(Relaxed),
+            wakes: self.inner.wakes.load(Relaxed),
+            wakes_by_ref: self.inner.wakes_by_ref.load(Relaxed),
+            drops: self.inner.drops.load(Relaxed),
+        }
+    }
+
+    /// Resets all counters to zero.
+    pub fn reset(&self) {
+        self.inner.clones.store(0, Relaxed);
+        self.inner.wakes.store(0, Relaxed);
+        self.inner.wakes_by_ref.store(0, Relaxed);
+        self.inner.drops.store(0, Relaxed);
+    }
+}
+
+impl fmt::Debug for CountingWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountingWaker")
+            .field("waker", &self.inner.waker)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone_raw, wake_raw, wake_by_ref_raw, drop_raw);
+
+unsafe fn clone_raw(data: *const ()) -> RawWaker {
+    // Retain Arc, but don't touch refcount by wrapping in ManuallyDrop
+    let inner = mem::ManuallyDrop::new(Arc::<Inner>::from_raw(data.cast()));
+    inner.clones.fetch_add(1, Relaxed);
+    RawWaker::new(Arc::into_raw(Arc::clone(&inner)).cast(), &VTABLE)
+}
+
+unsafe fn wake_raw(data: *const ()) {
+    let inner = Arc::<Inner>::from_raw(data.cast());
+    inner.wakes.fetch_add(1, Relaxed);
+    inner.drops.fetch_add(1, Relaxed);
+    inner.waker.wake_by_ref();
+}
+
+unsafe fn wake_by_ref_raw(data: *const ()) {
+    let inner = &*data.cast::<Inner>();
+    inner.wakes.fetch_add(1, Relaxed);
+    inner.wakes_by_ref.fetch_add(1, Relaxed);
+    inner.waker.wake_by_ref();
+}
+
+unsafe fn drop_raw(data: *const ()) {
+    let inner = Arc::<Inner>::from_raw(data.cast());
+    inner.drops.fetch_add(1, Relaxed);
+}
@@ -0,0 +1,209 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Waker};
+
+/// A set of values attached to a task by its executor, at most one per type.
+///
+/// Executors make the extensions of a task available while polling it with
+/// [`poll_with_extensions`]. The future being polled, and all of the futures it
+/// polls in turn with the same waker, can then access them through
+/// [`ContextExt`], for example to look up a deadline, a tracing span or a
+/// priority assigned to the task.
+///
+/// The extensions are attached to the waker of the [`Context`] rather than to
+/// the thread, so they don't leak into other executors run from the task.
+/// This also means that futures polled with a waker of their own, such as
+/// those in a `FuturesUnordered`, don't see them.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::{noop_waker, poll_with_extensions, Context, ContextExt, Extensions};
+///
+/// struct Priority(u8);
+///
+/// let mut extensions = Extensions::new();
+/// extensions.insert(Priority(3));
+///
+/// let waker = noop_waker();
+/// let mut cx = Context::from_waker(&waker);
+///
+/// let priority =
+///     poll_with_extensions(&extensions, &mut cx, |cx| cx.with_ext(|p: Option<&Priority>| p.unwrap().0));
+/// assert_eq!(priority, 3);
+///
+/// // The extensions are only available while polling with them.
+/// assert!(cx.with_ext(|p: Option<&Priority>| p.is_none()));
+/// ```
+#[derive(Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty set of extensions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value, returning the previous value of the same type, if any.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.map.insert(TypeId::of::<T>(), Box::new(value)).and_then(downcast)
+    }
+
+    /// Returns a reference to the value of type `T`, if any.
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.map.get(&TypeId::of::<T>()).and_then(|value| value.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the value of type `T`, if any.
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.map.get_mut(&TypeId::of::<T>()).and_then(|value| value.downcast_mut())
+    }
+
+    /// Removes the value of type `T`, returning it if it was present.
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        self.map.remove(&TypeId::of::<T>()).and_then(downcast)
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+fn downcast<T: 'static>(value: Box<dyn Any + Send + Sync>) -> Option<T> {
+    (value as Box<dyn Any>).downcast().ok().map(|value| *value)
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions").field("len", &self.len()).finish()
+    }
+}
+
+// An extension set attached to a waker by `poll_with_extensions`, both by
+// address.
+struct Attached {
+    waker: usize,
+    extensions: usize,
+}
+
+// The number of attached extension sets, to skip looking them up when there
+// are none.
+static ATTACHED: AtomicUsize = AtomicUsize::new(0);
+
+const SHARDS: usize = 16;
+
+// The attached extension sets, spread over several locks by waker address so
+// that threads polling different tasks rarely contend.
+fn shard(waker: usize) -> &'static Mutex<Vec<Attached>> {
+    static TABLE: AtomicPtr<Vec<Mutex<Vec<Attached>>>> = AtomicPtr::new(ptr::null_mut());
+
+    let mut table = TABLE.load(Ordering::Acquire);
+    if table.is_null() {
+        let new = Box::into_raw(Box::new((0..SHARDS).map(|_| Mutex::new(Vec::new())).collect()));
+        table =
+            match TABLE.compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => new,
+                Err(existing) => {
+                    // Safety: `new` was never shared.
+                    drop(unsafe { Box::from_raw(new) });
+                    existing
+                }
+            };
+    }
+    // Safety: Once set, the table is never freed.
+    let table = unsafe { &*table };
+    // Wakers are at least pointer-aligned, so the low bits carry no entropy.
+    &table[(waker / std::mem::align_of::<Waker>()) % SHARDS]
+}
+
+/// Calls `f` with `cx`, making `extensions` available through [`ContextExt`]
+/// to the futures polled with the waker of `cx` for its duration.
+///
+/// Executors call this around each poll of a task to attach the task's
+/// extensions to it. Calls can be nested, in which case the innermost
+/// extensions are available.
+pub fn poll_with_extensions<R>(
+    extensions: &Extensions,
+    cx: &mut Context<'_>,
+    f: impl FnOnce(&mut Context<'_>) -> R,
+) -> R {
+    struct Detach {
+        waker: usize,
+        extensions: usize,
+    }
+
+    impl Drop for Detach {
+        fn drop(&mut self) {
+            let mut attached = shard(self.waker).lock().unwrap();
+            let idx = attached
+                .iter()
+                .rposition(|a| a.waker == self.waker && a.extensions == self.extensions)
+                .unwrap();
+            attached.remove(idx);
+            ATTACHED.fetch_sub(1, Ordering::Release);
+        }
+    }
+
+    let waker = cx.waker() as *const Waker as usize;
+    let extensions = extensions as *const Extensions as usize;
+    shard(waker).lock().unwrap().push(Attached { waker, extensions });
+    ATTACHED.fetch_add(1, Ordering::Release);
+    let _detach = Detach { waker, extensions };
+    f(cx)
+}
+
+/// Extension trait for [`Context`], giving access to the [`Extensions`] of the
+/// task being polled.
+///
+/// Looking up an extension is cheap when no task is being polled with
+/// extensions. Otherwise it locks one of a few global mutexes, chosen by the
+/// address of the waker, and scans the extension sets attached under it,
+/// which grow with the number of tasks being polled with extensions at once.
+/// Futures polled often, such as combinators, should look up an extension
+/// once and keep it rather than look it up on every poll.
+pub trait ContextExt {
+    /// Calls `f` with a reference to the extension of type `T` of the task
+    /// being polled, or `None` if there is no such extension.
+    fn with_ext<T: 'static, R>(&self, f: impl FnOnce(Option<&T>) -> R) -> R;
+
+    /// Returns a clone of the extension of type `T` of the task being polled,
+    /// or `None` if there is no such extension.
+    fn get_ext<T: Clone + 'static>(&self) -> Option<T> {
+        self.with_ext(|value: Option<&T>| value.cloned())
+    }
+}
+
+impl ContextExt for Context<'_> {
+    fn with_ext<T: 'static, R>(&self, f: impl FnOnce(Option<&T>) -> R) -> R {
+        if ATTACHED.load(Ordering::Acquire) == 0 {
+            return f(None);
+        }
+        let waker = self.waker() as *const Waker as usize;
+        let current = shard(waker)
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|a| a.waker == waker)
+            .map(|a| a.extensions as *const Extensions);
+        // Safety: The waker borrowed by `self` is the one the extensions were
+        // attached to, as no other waker can live at its address meanwhile,
+        // so the call to `poll_with_extensions` borrowing them encloses this
+        // one.
+        let extensions = current.map(|extensions| unsafe { &*extensions });
+        f(extensions.and_then(Extensions::get))
+    }
+}
@@ -0,0 +1,251 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Wraps `future` to be spawned as a task, returning it along with a
+/// [`JoinHandle`] to its output.
+///
+/// The returned [`Joinable`] future is meant to be handed over to an
+/// executor. It catches panics of `future`, which are reported through the
+/// [`JoinHandle`] instead of unwinding into the executor.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::LocalPool;
+/// use futures::task::{joinable, LocalSpawnExt};
+///
+/// let mut pool = LocalPool::new();
+/// let (task, handle) = joinable(async { 1 + 2 });
+/// pool.spawner().spawn_local(task).unwrap();
+/// assert_eq!(pool.run_until(handle).unwrap(), 3);
+/// ```
+pub fn joinable<Fut: Future>(future: Fut) -> (Joinable<Fut>, JoinHandle<Fut::Output>) {
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            output: None,
+            finished: false,
+            aborted: false,
+            join_waker: None,
+            task_waker: None,
+        }),
+    });
+    (Joinable { future: Some(future), inner: inner.clone() }, JoinHandle { inner })
+}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    output: Option<Result<T, JoinError>>,
+    finished: bool,
+    aborted: bool,
+    join_waker: Option<Waker>,
+    task_waker: Option<Waker>,
+}
+
+impl<T> Inner<T> {
+    fn complete(&self, output: Result<T, JoinError>) {
+        let mut state = self.state.lock().unwrap();
+        if state.finished {
+            return;
+        }
+        state.output = Some(output);
+        state.finished = true;
+        let waker = state.join_waker.take();
+        drop(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+fn register(slot: &mut Option<Waker>, waker: &Waker) {
+    match slot {
+        Some(old) if old.will_wake(waker) => {}
+        _ => *slot = Some(waker.clone()),
+    }
+}
+
+/// Future for the [`joinable`] function, running the task on the executor.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Joinable<Fut: Future> {
+    future: Option<Fut>,
+    inner: Arc<Inner<Fut::Output>>,
+}
+
+impl<Fut: Future> Future for Joinable<Fut> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // SAFETY: `future` is never moved, and only dropped in place.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.future.is_none() {
+            return Poll::Ready(());
+        }
+
+        {
+            let mut state = this.inner.state.lock().unwrap();
+            if state.aborted {
+                drop(state);
+                this.future = None;
+                this.inner.complete(Err(JoinError::cancelled()));
+                return Poll::Ready(());
+            }
+            register(&mut state.task_waker, cx.waker());
+        }
+
+        let future = unsafe { Pin::new_unchecked(this.future.as_mut().unwrap()) };
+        // Unwind Safety: the panic is handed over to the `JoinHandle`.
+        let output = match panic::catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Pending) => return Poll::Pending,
+            Ok(Poll::Ready(output)) => Ok(output),
+            Err(payload) => Err(JoinError::panicked(payload)),
+        };
+        this.future = None;
+        this.inner.complete(output);
+        Poll::Ready(())
+    }
+}
+
+impl<Fut: Future> Drop for Joinable<Fut> {
+    fn drop(&mut self) {
+        // Dropped by the executor before completing.
+        self.inner.complete(Err(JoinError::cancelled()));
+    }
+}
+
+impl<Fut: Future + fmt::Debug> fmt::Debug for Joinable<Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Joinable").field("future", &self.future).finish()
+    }
+}
+
+/// A handle to the output of a task, created by [`joinable`].
+///
+/// Awaiting the handle yields the output of the task, or a [`JoinError`] if
+/// the task panicked or was cancelled. Dropping the handle detaches the task:
+/// it keeps running, but its output is discarded. Use
+/// [`abort`](JoinHandle::abort) to cancel it instead.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct JoinHandle<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Aborts the task.
+    ///
+    /// The task is woken up and dropped the next time it is polled by its
+    /// executor, after which the handle completes with a cancelled
+    /// [`JoinError`]. Aborting a task which has already finished does
+    /// nothing.
+    pub fn abort(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.finished || state.aborted {
+            return;
+        }
+        state.aborted = true;
+        let waker = state.task_waker.take();
+        drop(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` if the task has finished, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.inner.state.lock().unwrap().finished
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.state.lock().unwrap();
+        if let Some(output) = state.output.take() {
+            return Poll::Ready(output);
+        }
+        assert!(!state.finished, "`JoinHandle` polled after completion");
+        register(&mut state.join_waker, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinHandle").field("finished", &self.is_finished()).finish()
+    }
+}
+
+/// Error returned for a task which didn't complete successfully.
+pub struct JoinError {
+    repr: Repr,
+}
+
+enum Repr {
+    Cancelled,
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+impl JoinError {
+    /// Creates an error for a task which was aborted, or dropped by its
+    /// executor before completing.
+    pub fn cancelled() -> Self {
+        Self { repr: Repr::Cancelled }
+    }
+
+    /// Creates an error for a task which panicked with `payload`.
+    pub fn panicked(payload: Box<dyn Any + Send + 'static>) -> Self {
+        Self { repr: Repr::Panic(payload) }
+    }
+
+    /// Returns `true` if the task was aborted, or dropped by its executor
+    /// before completing.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.repr, Repr::Cancelled)
+    }
+
+    /// Returns `true` if the task panicked.
+    pub fn is_panic(&self) -> bool {
+        matches!(self.repr, Repr::Panic(_))
+    }
+
+    /// Consumes the error, returning the payload the task panicked with, or
+    /// the error itself if the task didn't panic.
+    ///
+    /// The payload can be passed to [`std::panic::resume_unwind`] to
+    /// propagate the panic.
+    pub fn try_into_panic(self) -> Result<Box<dyn Any + Send + 'static>, Self> {
+        match self.repr {
+            Repr::Panic(payload) => Ok(payload),
+            repr => Err(Self { repr }),
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repr {
+            Repr::Cancelled => write!(f, "JoinError::Cancelled"),
+            Repr::Panic(_) => write!(f, "JoinError::Panic(..)"),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repr {
+            Repr::Cancelled => write!(f, "task was cancelled"),
+            Repr::Panic(_) => write!(f, "task panicked"),
+        }
+    }
+}
+
+impl Error for JoinError {}
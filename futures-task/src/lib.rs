@@ -16,7 +16,9 @@
 extern crate alloc;
 
 mod spawn;
-pub use crate::spawn::{LocalSpawn, Spawn, SpawnError};
+#[cfg(feature = "alloc")]
+pub use crate::spawn::SpawnBlocking;
+pub use crate::spawn::{LocalSpawn, Priority, Spawn, SpawnError, SpawnPriority};
 
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
@@ -25,6 +27,13 @@ mod arc_wake;
 #[cfg(feature = "alloc")]
 pub use crate::arc_wake::ArcWake;
 
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+mod counting_waker;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+pub use crate::counting_waker::{CountingWaker, WakerStats};
+
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 mod waker;
@@ -32,6 +41,16 @@ mod waker;
 #[cfg(feature = "alloc")]
 pub use crate::waker::waker;
 
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+mod waker_fn;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+pub use crate::waker_fn::waker_fn;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub use crate::waker_fn::waker_fn_on_drop;
+
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 mod waker_ref;
@@ -39,6 +58,39 @@ mod waker_ref;
 #[cfg(feature = "alloc")]
 pub use crate::waker_ref::{waker_ref, WakerRef};
 
+#[cfg(feature = "std")]
+mod extensions;
+#[cfg(feature = "std")]
+pub use crate::extensions::{poll_with_extensions, ContextExt, Extensions};
+
+#[cfg(feature = "std")]
+mod join_handle;
+#[cfg(feature = "std")]
+pub use crate::join_handle::{joinable, JoinError, JoinHandle, Joinable};
+
+#[cfg(feature = "std")]
+mod local_arc_wake;
+#[cfg(feature = "std")]
+pub use crate::local_arc_wake::{local_waker, local_waker_ref, LocalArcWake, LocalWakerRef};
+
+#[cfg(feature = "std")]
+mod slab;
+
+#[cfg(feature = "std")]
+mod timer;
+#[cfg(feature = "std")]
+pub use crate::timer::{Sleep, Timer};
+
+#[cfg(feature = "std")]
+mod waker_set;
+#[cfg(feature = "std")]
+pub use crate::waker_set::WakerSet;
+
+#[cfg(feature = "std")]
+mod waker_slab;
+#[cfg(feature = "std")]
+pub use crate::waker_slab::WakerSlab;
+
 mod future_obj;
 pub use crate::future_obj::{FutureObj, LocalFutureObj, UnsafeFutureObj};
 
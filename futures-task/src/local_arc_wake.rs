@@ -0,0 +1,196 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::task::{RawWaker, RawWakerVTable, Waker};
+use std::thread::{self, ThreadId};
+
+/// A way of waking up a specific task, from the thread it belongs to.
+///
+/// This is the single-threaded counterpart of [`ArcWake`](crate::ArcWake):
+/// by implementing this trait, types that are expected to be wrapped in an
+/// `Rc` can be converted into [`Waker`] objects with [`local_waker`] or
+/// [`local_waker_ref`], without requiring `Send` or `Sync`. This lets
+/// single-threaded executors, such as one running on a `LocalPool`, keep
+/// their tasks in `Rc`s.
+///
+/// `Waker` is `Send` and `Sync`, but the wakers created from a
+/// `LocalArcWake` are only valid on the thread they were created on. Cloning
+/// or waking them on any other thread panics instead of touching the `Rc`,
+/// and dropping them there leaks the `Rc`.
+pub trait LocalArcWake {
+    /// Indicates that the associated task is ready to make progress and should
+    /// be `poll`ed.
+    ///
+    /// This function is only ever called on the thread the waker was created
+    /// on.
+    fn wake(self: Rc<Self>) {
+        Self::wake_by_ref(&self)
+    }
+
+    /// Indicates that the associated task is ready to make progress and should
+    /// be `poll`ed.
+    ///
+    /// This function is similar to [`wake`](LocalArcWake::wake), but must not
+    /// consume the provided data pointer.
+    fn wake_by_ref(rc_self: &Rc<Self>);
+}
+
+// The allocation behind every waker created from a `LocalArcWake`. `wake` is
+// an `Rc<W>` turned into a raw pointer, so that freed nodes can be reused for
+// any `W`.
+struct Node {
+    owner: ThreadId,
+    wake: *const (),
+}
+
+impl Node {
+    fn is_owner(&self) -> bool {
+        thread::current().id() == self.owner
+    }
+
+    fn check_owner(&self) {
+        assert!(self.is_owner(), "a waker created from `LocalArcWake` was used on another thread");
+    }
+}
+
+thread_local! {
+    // A node freed on this thread, reused by the next waker created on it so
+    // that `local_waker_ref` doesn't allocate once a task has been polled.
+    static SPARE_NODE: Cell<Option<Box<Node>>> = Cell::new(None);
+}
+
+fn new_node(owner: ThreadId, wake: *const ()) -> *const () {
+    let node = Node { owner, wake };
+    let node = match SPARE_NODE.try_with(Cell::take).ok().flatten() {
+        Some(mut spare) => {
+            *spare = node;
+            spare
+        }
+        None => Box::new(node),
+    };
+    Box::into_raw(node) as *const ()
+}
+
+unsafe fn free_node(data: *const ()) {
+    let node = Box::from_raw(data as *mut Node);
+    let _ = SPARE_NODE.try_with(move |spare| spare.set(Some(node)));
+}
+
+// Borrows the `Rc` owned by a node without touching its reference count.
+unsafe fn borrow_rc<W>(node: &Node) -> ManuallyDrop<Rc<W>> {
+    ManuallyDrop::new(Rc::from_raw(node.wake.cast::<W>()))
+}
+
+fn vtable<W: LocalArcWake + 'static>() -> &'static RawWakerVTable {
+    &RawWakerVTable::new(clone_raw::<W>, wake_raw::<W>, wake_by_ref_raw::<W>, drop_raw::<W>)
+}
+
+fn raw_waker<W: LocalArcWake + 'static>(owner: ThreadId, wake: Rc<W>) -> RawWaker {
+    RawWaker::new(new_node(owner, Rc::into_raw(wake).cast()), vtable::<W>())
+}
+
+unsafe fn clone_raw<W: LocalArcWake + 'static>(data: *const ()) -> RawWaker {
+    let node = &*data.cast::<Node>();
+    node.check_owner();
+    let wake = Rc::clone(&borrow_rc::<W>(node));
+    raw_waker(node.owner, wake)
+}
+
+unsafe fn wake_raw<W: LocalArcWake + 'static>(data: *const ()) {
+    let node = &*data.cast::<Node>();
+    node.check_owner();
+    let wake = Rc::from_raw(node.wake.cast::<W>());
+    free_node(data);
+    LocalArcWake::wake(wake);
+}
+
+unsafe fn wake_by_ref_raw<W: LocalArcWake + 'static>(data: *const ()) {
+    let node = &*data.cast::<Node>();
+    node.check_owner();
+    LocalArcWake::wake_by_ref(&borrow_rc::<W>(node));
+}
+
+unsafe fn drop_raw<W: LocalArcWake + 'static>(data: *const ()) {
+    let node = &*data.cast::<Node>();
+    // Dropping a waker must not panic, so one dropped on another thread leaks
+    // its node and `Rc` instead.
+    if !node.is_owner() {
+        return;
+    }
+    let wake = Rc::from_raw(node.wake.cast::<W>());
+    free_node(data);
+    drop(wake);
+}
+
+/// Creates a [`Waker`] from an `Rc<impl LocalArcWake>`.
+///
+/// The returned [`Waker`] will call
+/// [`LocalArcWake.wake()`](LocalArcWake::wake) if awoken. It must only be
+/// used on the current thread.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::{local_waker, LocalArcWake};
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+///
+/// struct Task {
+///     woken: Cell<bool>,
+/// }
+///
+/// impl LocalArcWake for Task {
+///     fn wake_by_ref(rc_self: &Rc<Self>) {
+///         rc_self.woken.set(true);
+///     }
+/// }
+///
+/// let task = Rc::new(Task { woken: Cell::new(false) });
+/// let waker = local_waker(task.clone());
+/// waker.wake();
+/// assert!(task.woken.get());
+/// ```
+pub fn local_waker<W>(wake: Rc<W>) -> Waker
+where
+    W: LocalArcWake + 'static,
+{
+    unsafe { Waker::from_raw(raw_waker(thread::current().id(), wake)) }
+}
+
+/// A [`Waker`] created by [`local_waker_ref`], borrowing the `Rc` it was
+/// created from.
+///
+/// Note: this type implements [`Deref<Target = Waker>`](std::ops::Deref),
+/// so it can be used to get a `&Waker`.
+#[derive(Debug)]
+pub struct LocalWakerRef<'a> {
+    waker: Waker,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl Deref for LocalWakerRef<'_> {
+    type Target = Waker;
+
+    #[inline]
+    fn deref(&self) -> &Waker {
+        &self.waker
+    }
+}
+
+/// Creates a reference to a [`Waker`] from a reference to
+/// `Rc<impl LocalArcWake>`.
+///
+/// The resulting [`Waker`] will call
+/// [`LocalArcWake.wake()`](LocalArcWake::wake) if awoken. It must only be
+/// used on the current thread.
+///
+/// The allocation backing the waker is reused from the last waker dropped on
+/// this thread, so calling this once per poll doesn't allocate.
+pub fn local_waker_ref<W>(wake: &Rc<W>) -> LocalWakerRef<'_>
+where
+    W: LocalArcWake + 'static,
+{
+    LocalWakerRef { waker: local_waker(wake.clone()), _marker: PhantomData }
+}
@@ -0,0 +1,69 @@
+use std::mem;
+
+/// A minimal slab allocator, handing out `usize` keys and reusing the keys of
+/// removed values.
+pub(crate) struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    /// Head of the list of vacant entries, or `entries.len()` if there is none.
+    next_vacant: usize,
+    len: usize,
+}
+
+enum Entry<T> {
+    Occupied(T),
+    Vacant(usize),
+}
+
+impl<T> Slab<T> {
+    pub(crate) fn new() -> Self {
+        Self { entries: Vec::new(), next_vacant: 0, len: 0 }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn insert(&mut self, value: T) -> usize {
+        let key = self.next_vacant;
+        if key == self.entries.len() {
+            self.entries.push(Entry::Occupied(value));
+            self.next_vacant += 1;
+        } else {
+            match mem::replace(&mut self.entries[key], Entry::Occupied(value)) {
+                Entry::Vacant(next) => self.next_vacant = next,
+                Entry::Occupied(_) => unreachable!(),
+            }
+        }
+        self.len += 1;
+        key
+    }
+
+    pub(crate) fn get_mut(&mut self, key: usize) -> Option<&mut T> {
+        match self.entries.get_mut(key) {
+            Some(Entry::Occupied(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn remove(&mut self, key: usize) -> Option<T> {
+        let entry = self.entries.get_mut(key)?;
+        if let Entry::Vacant(_) = entry {
+            return None;
+        }
+        match mem::replace(entry, Entry::Vacant(self.next_vacant)) {
+            Entry::Occupied(value) => {
+                self.next_vacant = key;
+                self.len -= 1;
+                Some(value)
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entries.iter_mut().filter_map(|entry| match entry {
+            Entry::Occupied(value) => Some(value),
+            Entry::Vacant(_) => None,
+        })
+    }
+}
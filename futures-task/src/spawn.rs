@@ -51,20 +51,93 @@ pub trait LocalSpawn {
     }
 }
 
+/// The priority of a task, used by executors implementing [`SpawnPriority`]
+/// to decide which of their ready tasks to poll first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    /// Background work, polled only when no other task is ready.
+    Low,
+    /// The priority of tasks spawned without one.
+    Normal,
+    /// Latency-sensitive work, polled ahead of all other tasks.
+    High,
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// The `SpawnPriority` trait extends [`Spawn`] for executors which can
+/// prioritize some of their tasks over the others.
+///
+/// Tasks spawned through [`Spawn::spawn_obj`] get the
+/// [`Normal`](Priority::Normal) priority.
+pub trait SpawnPriority: Spawn {
+    /// Spawns a future that will be run to completion, with the given
+    /// priority.
+    ///
+    /// The priority applies to the task for its whole lifetime: whenever
+    /// it is woken, it is scheduled ahead of the ready tasks of lower
+    /// priority.
+    ///
+    /// # Errors
+    ///
+    /// The executor may be unable to spawn tasks, see [`Spawn::spawn_obj`].
+    fn spawn_obj_with_priority(
+        &self,
+        future: FutureObj<'static, ()>,
+        priority: Priority,
+    ) -> Result<(), SpawnError>;
+}
+
+/// The `SpawnBlocking` trait allows for running blocking closures on threads
+/// set aside for them, so that they don't hold up the threads polling
+/// futures.
+#[cfg(feature = "alloc")]
+pub trait SpawnBlocking {
+    /// Runs a closure that may block on a thread dedicated to blocking work.
+    ///
+    /// # Errors
+    ///
+    /// The executor may be unable to run the closure. Spawn errors should
+    /// represent relatively rare scenarios, such as the executor
+    /// having been shut down so that it is no longer able to accept
+    /// work.
+    fn spawn_blocking_obj(
+        &self,
+        f: alloc::boxed::Box<dyn FnOnce() + Send + 'static>,
+    ) -> Result<(), SpawnError>;
+}
+
 /// An error that occurred during spawning.
 pub struct SpawnError {
-    _priv: (),
+    kind: SpawnErrorKind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SpawnErrorKind {
+    Shutdown,
+    Full,
 }
 
 impl fmt::Debug for SpawnError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("SpawnError").field(&"shutdown").finish()
+        let kind = match self.kind {
+            SpawnErrorKind::Shutdown => "shutdown",
+            SpawnErrorKind::Full => "full",
+        };
+        f.debug_tuple("SpawnError").field(&kind).finish()
     }
 }
 
 impl fmt::Display for SpawnError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Executor is shutdown")
+        match self.kind {
+            SpawnErrorKind::Shutdown => write!(f, "Executor is shutdown"),
+            SpawnErrorKind::Full => write!(f, "Executor is at capacity"),
+        }
     }
 }
 
@@ -74,12 +147,22 @@ impl std::error::Error for SpawnError {}
 impl SpawnError {
     /// Spawning failed because the executor has been shut down.
     pub fn shutdown() -> Self {
-        Self { _priv: () }
+        Self { kind: SpawnErrorKind::Shutdown }
+    }
+
+    /// Spawning failed because the executor can't hold more tasks for now.
+    pub fn full() -> Self {
+        Self { kind: SpawnErrorKind::Full }
     }
 
     /// Check whether spawning failed to the executor being shut down.
     pub fn is_shutdown(&self) -> bool {
-        true
+        self.kind == SpawnErrorKind::Shutdown
+    }
+
+    /// Check whether spawning failed to the executor being at capacity.
+    pub fn is_full(&self) -> bool {
+        self.kind == SpawnErrorKind::Full
     }
 }
 
@@ -103,6 +186,26 @@ impl<Sp: ?Sized + Spawn> Spawn for &mut Sp {
     }
 }
 
+impl<Sp: ?Sized + SpawnPriority> SpawnPriority for &Sp {
+    fn spawn_obj_with_priority(
+        &self,
+        future: FutureObj<'static, ()>,
+        priority: Priority,
+    ) -> Result<(), SpawnError> {
+        Sp::spawn_obj_with_priority(self, future, priority)
+    }
+}
+
+impl<Sp: ?Sized + SpawnPriority> SpawnPriority for &mut Sp {
+    fn spawn_obj_with_priority(
+        &self,
+        future: FutureObj<'static, ()>,
+        priority: Priority,
+    ) -> Result<(), SpawnError> {
+        Sp::spawn_obj_with_priority(self, future, priority)
+    }
+}
+
 impl<Sp: ?Sized + LocalSpawn> LocalSpawn for &Sp {
     fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
         Sp::spawn_local_obj(self, future)
@@ -138,6 +241,43 @@ mod if_alloc {
         }
     }
 
+    impl<Sp: ?Sized + SpawnPriority> SpawnPriority for Box<Sp> {
+        fn spawn_obj_with_priority(
+            &self,
+            future: FutureObj<'static, ()>,
+            priority: Priority,
+        ) -> Result<(), SpawnError> {
+            (**self).spawn_obj_with_priority(future, priority)
+        }
+    }
+
+    impl<Sp: ?Sized + SpawnBlocking> SpawnBlocking for &Sp {
+        fn spawn_blocking_obj(
+            &self,
+            f: Box<dyn FnOnce() + Send + 'static>,
+        ) -> Result<(), SpawnError> {
+            Sp::spawn_blocking_obj(self, f)
+        }
+    }
+
+    impl<Sp: ?Sized + SpawnBlocking> SpawnBlocking for &mut Sp {
+        fn spawn_blocking_obj(
+            &self,
+            f: Box<dyn FnOnce() + Send + 'static>,
+        ) -> Result<(), SpawnError> {
+            Sp::spawn_blocking_obj(self, f)
+        }
+    }
+
+    impl<Sp: ?Sized + SpawnBlocking> SpawnBlocking for Box<Sp> {
+        fn spawn_blocking_obj(
+            &self,
+            f: Box<dyn FnOnce() + Send + 'static>,
+        ) -> Result<(), SpawnError> {
+            (**self).spawn_blocking_obj(f)
+        }
+    }
+
     impl<Sp: ?Sized + LocalSpawn> LocalSpawn for Box<Sp> {
         fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
             (**self).spawn_local_obj(future)
@@ -158,6 +298,25 @@ mod if_alloc {
         }
     }
 
+    impl<Sp: ?Sized + SpawnPriority> SpawnPriority for Rc<Sp> {
+        fn spawn_obj_with_priority(
+            &self,
+            future: FutureObj<'static, ()>,
+            priority: Priority,
+        ) -> Result<(), SpawnError> {
+            (**self).spawn_obj_with_priority(future, priority)
+        }
+    }
+
+    impl<Sp: ?Sized + SpawnBlocking> SpawnBlocking for Rc<Sp> {
+        fn spawn_blocking_obj(
+            &self,
+            f: Box<dyn FnOnce() + Send + 'static>,
+        ) -> Result<(), SpawnError> {
+            (**self).spawn_blocking_obj(f)
+        }
+    }
+
     impl<Sp: ?Sized + LocalSpawn> LocalSpawn for Rc<Sp> {
         fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
             (**self).spawn_local_obj(future)
@@ -179,6 +338,27 @@ mod if_alloc {
         }
     }
 
+    #[cfg(not(futures_no_atomic_cas))]
+    impl<Sp: ?Sized + SpawnPriority> SpawnPriority for alloc::sync::Arc<Sp> {
+        fn spawn_obj_with_priority(
+            &self,
+            future: FutureObj<'static, ()>,
+            priority: Priority,
+        ) -> Result<(), SpawnError> {
+            (**self).spawn_obj_with_priority(future, priority)
+        }
+    }
+
+    #[cfg(not(futures_no_atomic_cas))]
+    impl<Sp: ?Sized + SpawnBlocking> SpawnBlocking for alloc::sync::Arc<Sp> {
+        fn spawn_blocking_obj(
+            &self,
+            f: Box<dyn FnOnce() + Send + 'static>,
+        ) -> Result<(), SpawnError> {
+            (**self).spawn_blocking_obj(f)
+        }
+    }
+
     #[cfg(not(futures_no_atomic_cas))]
     impl<Sp: ?Sized + LocalSpawn> LocalSpawn for alloc::sync::Arc<Sp> {
         fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
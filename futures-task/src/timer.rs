@@ -0,0 +1,99 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A future completing at a deadline, as returned by [`Timer::sleep_until`].
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A provider of time for futures which wait, such as timeouts.
+///
+/// Code taking its time from a `Timer` instead of the system clock can be
+/// tested deterministically, by providing it with a timer whose time is
+/// controlled by the test.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::Timer;
+/// use std::time::Duration;
+///
+/// async fn retry_later<T: Timer>(timer: &T) {
+///     let start = timer.now();
+///     timer.sleep(Duration::from_secs(1)).await;
+///     assert!(timer.now() - start >= Duration::from_secs(1));
+/// }
+/// ```
+pub trait Timer {
+    /// Returns the current time of the timer.
+    fn now(&self) -> Instant;
+
+    /// Returns a future completing once the time of the timer reaches
+    /// `deadline`.
+    ///
+    /// The future completes right away if `deadline` has already passed.
+    fn sleep_until(&self, deadline: Instant) -> Sleep;
+
+    /// Returns a future completing once `duration` has elapsed on the
+    /// timer.
+    fn sleep(&self, duration: Duration) -> Sleep {
+        self.sleep_until(self.now() + duration)
+    }
+}
+
+impl<T: ?Sized + Timer> Timer for &T {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        (**self).sleep_until(deadline)
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        (**self).sleep(duration)
+    }
+}
+
+impl<T: ?Sized + Timer> Timer for Box<T> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        (**self).sleep_until(deadline)
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        (**self).sleep(duration)
+    }
+}
+
+impl<T: ?Sized + Timer> Timer for Rc<T> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        (**self).sleep_until(deadline)
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        (**self).sleep(duration)
+    }
+}
+
+impl<T: ?Sized + Timer> Timer for Arc<T> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        (**self).sleep_until(deadline)
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        (**self).sleep(duration)
+    }
+}
@@ -0,0 +1,101 @@
+use super::arc_wake::ArcWake;
+use super::waker::waker;
+use alloc::sync::Arc;
+use core::task::Waker;
+
+/// Creates a [`Waker`] which calls `f` every time it is woken.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::waker_fn;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// let wakes = Arc::new(AtomicUsize::new(0));
+/// let waker = waker_fn({
+///     let wakes = wakes.clone();
+///     move || {
+///         wakes.fetch_add(1, Ordering::SeqCst);
+///     }
+/// });
+///
+/// waker.wake_by_ref();
+/// waker.wake();
+/// assert_eq!(wakes.load(Ordering::SeqCst), 2);
+/// ```
+pub fn waker_fn<F>(f: F) -> Waker
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    waker(Arc::new(WakerFn(f)))
+}
+
+struct WakerFn<F>(F);
+
+impl<F: Fn() + Send + Sync> ArcWake for WakerFn<F> {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        (arc_self.0)()
+    }
+}
+
+/// Creates a [`Waker`] which calls `f` once, the first time it is woken, or
+/// when its last handle is dropped if it is never woken.
+///
+/// This guarantees that `f` runs exactly once, even if the task holding the
+/// waker is dropped without waking it, which is useful to reschedule or clean
+/// up after tasks that may be abandoned.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::waker_fn_on_drop;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// let called = Arc::new(AtomicBool::new(false));
+/// let waker = waker_fn_on_drop({
+///     let called = called.clone();
+///     move || called.store(true, Ordering::SeqCst)
+/// });
+///
+/// let clone = waker.clone();
+/// drop(waker);
+/// assert!(!called.load(Ordering::SeqCst));
+/// drop(clone);
+/// assert!(called.load(Ordering::SeqCst));
+/// ```
+#[cfg(feature = "std")]
+pub fn waker_fn_on_drop<F>(f: F) -> Waker
+where
+    F: FnOnce() + Send + 'static,
+{
+    waker(Arc::new(WakerFnOnDrop(std::sync::Mutex::new(Some(f)))))
+}
+
+#[cfg(feature = "std")]
+struct WakerFnOnDrop<F: FnOnce()>(std::sync::Mutex<Option<F>>);
+
+#[cfg(feature = "std")]
+impl<F: FnOnce()> WakerFnOnDrop<F> {
+    fn call(&self) {
+        let f = self.0.lock().unwrap().take();
+        if let Some(f) = f {
+            f()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: FnOnce() + Send> ArcWake for WakerFnOnDrop<F> {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.call()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: FnOnce()> Drop for WakerFnOnDrop<F> {
+    fn drop(&mut self) {
+        self.call()
+    }
+}
@@ -0,0 +1,174 @@
+use crate::slab::Slab;
+use std::fmt;
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::task::Waker;
+
+/// A set of wakers, for synchronization primitives with many waiting tasks.
+///
+/// Where an `AtomicWaker` holds a single waker, a `WakerSet` holds one entry
+/// per waiting task. Each call to [`insert`](WakerSet::insert) returns a key
+/// identifying the new entry, which can later be used to
+/// [`update`](WakerSet::update) the waker or [`remove`](WakerSet::remove) the
+/// entry once the task is no longer waiting.
+///
+/// Waking an entry takes its waker out of the set, but the entry itself stays
+/// in place until it is removed, so a task can find out whether it has been
+/// woken. The set keeps a count of entries holding a waker outside of its
+/// lock, so that [`wake_one`](WakerSet::wake_one) and
+/// [`wake_all`](WakerSet::wake_all) don't lock anything when nobody is
+/// waiting.
+///
+/// As with `AtomicWaker`, a task should register its waker **before**
+/// checking the condition it is waiting for, and the condition should be
+/// changed **before** waking the set.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::{noop_waker, WakerSet};
+///
+/// let set = WakerSet::new();
+/// let key = set.insert(&noop_waker());
+///
+/// assert!(set.wake_one());
+/// assert!(!set.wake_one());
+///
+/// // The entry was woken before it was removed.
+/// assert!(set.remove(key));
+/// ```
+pub struct WakerSet {
+    /// The number of entries currently holding a waker.
+    waiting: AtomicUsize,
+    /// Entries are `None` once they have been woken.
+    entries: Mutex<Slab<Option<Waker>>>,
+}
+
+impl WakerSet {
+    /// Creates an empty `WakerSet`.
+    pub fn new() -> Self {
+        Self { waiting: AtomicUsize::new(0), entries: Mutex::new(Slab::new()) }
+    }
+
+    /// Inserts a new entry holding `waker`, returning its key.
+    ///
+    /// Keys of removed entries are reused by later calls to `insert`.
+    pub fn insert(&self, waker: &Waker) -> usize {
+        let key = self.entries.lock().unwrap().insert(Some(waker.clone()));
+        self.inc_waiting();
+        key
+    }
+
+    /// Stores `waker` in the entry for `key`.
+    ///
+    /// This re-arms an entry that has been woken. If the entry still holds a
+    /// waker that [`will_wake`](Waker::will_wake) the same task, it is kept and
+    /// `waker` is not cloned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` doesn't refer to an entry of this set.
+    pub fn update(&self, key: usize, waker: &Waker) {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get_mut(key).expect("invalid key") {
+            Some(old_waker) => {
+                if !old_waker.will_wake(waker) {
+                    *old_waker = waker.clone();
+                }
+            }
+            entry @ None => {
+                *entry = Some(waker.clone());
+                self.inc_waiting();
+            }
+        }
+    }
+
+    /// Removes the entry for `key` from the set.
+    ///
+    /// Returns `true` if the entry had been woken. A task that gives up waiting
+    /// after having been woken by [`wake_one`](WakerSet::wake_one) should use
+    /// this to pass the notification on to another entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` doesn't refer to an entry of this set.
+    pub fn remove(&self, key: usize) -> bool {
+        match self.entries.lock().unwrap().remove(key).expect("invalid key") {
+            Some(_) => {
+                self.waiting.fetch_sub(1, Ordering::Relaxed);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Wakes a single entry that holds a waker.
+    ///
+    /// Returns `true` if an entry was woken, or `false` if no entry was
+    /// waiting.
+    pub fn wake_one(&self) -> bool {
+        if !self.has_waiting() {
+            return false;
+        }
+        let waker = self.entries.lock().unwrap().values_mut().find_map(Option::take);
+        let waker = match waker {
+            Some(waker) => waker,
+            None => return false,
+        };
+        self.waiting.fetch_sub(1, Ordering::Relaxed);
+        // Wake outside of the lock, as the woken task might be polled
+        // immediately and try to access the set.
+        waker.wake();
+        true
+    }
+
+    /// Wakes all entries that hold a waker.
+    ///
+    /// Returns `true` if at least one entry was woken.
+    pub fn wake_all(&self) -> bool {
+        if !self.has_waiting() {
+            return false;
+        }
+        let wakers = {
+            let mut entries = self.entries.lock().unwrap();
+            let wakers: Vec<_> = entries.values_mut().filter_map(Option::take).collect();
+            self.waiting.fetch_sub(wakers.len(), Ordering::Relaxed);
+            wakers
+        };
+        let woken = !wakers.is_empty();
+        for waker in wakers {
+            waker.wake();
+        }
+        woken
+    }
+
+    /// Returns `true` if no entry currently holds a waker.
+    pub fn is_empty(&self) -> bool {
+        !self.has_waiting()
+    }
+
+    fn inc_waiting(&self) {
+        self.waiting.fetch_add(1, Ordering::Relaxed);
+        // Pairs with the fence in `has_waiting`: either the waking thread
+        // observes the new entry, or the caller observes the condition that
+        // was changed before waking.
+        fence(Ordering::SeqCst);
+    }
+
+    fn has_waiting(&self) -> bool {
+        fence(Ordering::SeqCst);
+        self.waiting.load(Ordering::Relaxed) != 0
+    }
+}
+
+impl Default for WakerSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for WakerSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WakerSet").field("waiting", &self.waiting.load(Ordering::Relaxed)).finish()
+    }
+}
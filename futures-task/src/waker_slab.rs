@@ -0,0 +1,102 @@
+use crate::slab::Slab;
+use std::fmt;
+use std::sync::Mutex;
+use std::task::Waker;
+
+/// A slab of wakers, for broadcasting wakeups to many subscribers.
+///
+/// Each call to [`register`](WakerSlab::register) returns a key identifying
+/// the new registration, and the key of an [`unregister`](WakerSlab::unregister)ed
+/// registration is reused by later registrations. Unlike a
+/// [`WakerSet`](crate::WakerSet), waking the slab doesn't consume the
+/// registrations: [`wake_all`](WakerSlab::wake_all) wakes every registered
+/// waker and keeps them in place, until their subscriber unregisters (usually
+/// when it is dropped).
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::{noop_waker, WakerSlab};
+///
+/// let slab = WakerSlab::new();
+/// let key = slab.register(&noop_waker());
+///
+/// assert_eq!(slab.wake_all(), 1);
+/// assert_eq!(slab.wake_all(), 1);
+///
+/// slab.unregister(key);
+/// assert_eq!(slab.wake_all(), 0);
+/// ```
+pub struct WakerSlab {
+    wakers: Mutex<Slab<Waker>>,
+}
+
+impl WakerSlab {
+    /// Creates an empty `WakerSlab`.
+    pub fn new() -> Self {
+        Self { wakers: Mutex::new(Slab::new()) }
+    }
+
+    /// Registers `waker`, returning the key of the new registration.
+    pub fn register(&self, waker: &Waker) -> usize {
+        self.wakers.lock().unwrap().insert(waker.clone())
+    }
+
+    /// Replaces the waker registered for `key` with `waker`.
+    ///
+    /// If the registered waker [`will_wake`](Waker::will_wake) the same task
+    /// as `waker`, it is kept and `waker` is not cloned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` isn't registered in this slab.
+    pub fn update(&self, key: usize, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap();
+        let old_waker = wakers.get_mut(key).expect("invalid key");
+        if !old_waker.will_wake(waker) {
+            *old_waker = waker.clone();
+        }
+    }
+
+    /// Removes the registration for `key`, returning its waker.
+    ///
+    /// Returns `None` if `key` isn't registered in this slab.
+    pub fn unregister(&self, key: usize) -> Option<Waker> {
+        self.wakers.lock().unwrap().remove(key)
+    }
+
+    /// Wakes all registered wakers, returning how many were woken.
+    ///
+    /// The wakers stay registered. They are woken after the internal lock has
+    /// been released, so woken tasks may access the slab right away.
+    pub fn wake_all(&self) -> usize {
+        let wakers: Vec<_> = self.wakers.lock().unwrap().values_mut().map(|w| w.clone()).collect();
+        let len = wakers.len();
+        for waker in wakers {
+            waker.wake();
+        }
+        len
+    }
+
+    /// Returns the number of registered wakers.
+    pub fn len(&self) -> usize {
+        self.wakers.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no waker is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for WakerSlab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for WakerSlab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WakerSlab").field("len", &self.len()).finish()
+    }
+}
@@ -3,6 +3,28 @@ use futures_core::stream::Stream;
 #[doc(hidden)]
 pub fn assert_is_unpin_stream<S: Stream + Unpin>(_: &mut S) {}
 
+#[doc(hidden)]
+pub fn collect_stream<S: Stream + Unpin>(stream: &mut S) -> Vec<S::Item> {
+    futures_executor::block_on(futures_util::stream::StreamExt::collect(stream))
+}
+
+#[doc(hidden)]
+pub fn wait_woken(count: &crate::task::AwokenCount) -> bool {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while count.get() == 0 {
+        if std::time::Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    true
+}
+
+#[doc(hidden)]
+pub fn next_item<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+    futures_executor::block_on(futures_util::stream::StreamExt::next(stream))
+}
+
 /// Assert that the next poll to the provided stream will return
 /// [`Poll::Pending`](futures_core::task::Poll::Pending).
 ///
@@ -122,3 +144,108 @@ macro_rules! assert_stream_done {
         }
     }};
 }
+
+/// Assert that the provided stream yields exactly the provided items, in
+/// order, before completing.
+///
+/// The stream is run to completion on the current thread, blocking it while
+/// the stream is pending.
+///
+/// # Examples
+///
+/// ```
+/// use futures::stream;
+/// use futures_test::assert_stream_eq;
+/// use futures_test::stream::StreamTestExt;
+///
+/// let mut stream = stream::iter(vec![1, 2, 3]).interleave_pending();
+///
+/// assert_stream_eq!(stream, [1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! assert_stream_eq {
+    ($stream:expr, $items:expr) => {{
+        let stream = &mut $stream;
+        let items = $crate::__private::assert::collect_stream(stream);
+        assert_eq!(items, $items, "assertion failed: stream yielded unexpected items");
+    }};
+}
+
+/// Assert that the next poll to the provided stream will return
+/// [`Poll::Pending`](futures_core::task::Poll::Pending), and that the stream
+/// then wakes its task and yields the provided item.
+///
+/// The current thread is blocked until the stream wakes its task, for up to
+/// 5 seconds, and then until it yields its next item.
+///
+/// # Examples
+///
+/// ```
+/// use futures::stream;
+/// use futures_test::assert_stream_pending_then;
+/// use futures_test::stream::StreamTestExt;
+///
+/// let mut stream = stream::iter(vec![1, 2]).interleave_pending();
+///
+/// assert_stream_pending_then!(stream, 1);
+/// assert_stream_pending_then!(stream, 2);
+/// ```
+#[macro_export]
+macro_rules! assert_stream_pending_then {
+    ($stream:expr, $item:expr) => {{
+        let mut stream = &mut $stream;
+        $crate::__private::assert::assert_is_unpin_stream(stream);
+        let (waker, count) = $crate::task::new_count_waker();
+        let mut cx = $crate::__private::task::Context::from_waker(&waker);
+        let poll = $crate::__private::stream::Stream::poll_next(
+            $crate::__private::Pin::new(&mut *stream),
+            &mut cx,
+        );
+        if poll.is_ready() {
+            panic!("assertion failed: stream is not pending");
+        }
+        if !$crate::__private::assert::wait_woken(&count) {
+            panic!("assertion failed: stream was pending and didn't wake its task");
+        }
+        match $crate::__private::assert::next_item(stream) {
+            $crate::__private::Some(x) => {
+                assert_eq!(x, $item);
+            }
+            $crate::__private::None => {
+                panic!(
+                    "assertion failed: expected stream to provide item but stream is at its end"
+                );
+            }
+        }
+    }};
+}
+
+/// Assert that the provided closure doesn't leak clones of the waker of the
+/// [`Context`](futures_core::task::Context) it's given.
+///
+/// The closure is called with a context holding a waker tracked by a
+/// [`WakerTracker`](crate::task::WakerTracker), and the assertion fails if
+/// clones of the waker are still alive once it returns. The failure
+/// describes how the leaked clones were created, with backtraces if the
+/// `backtrace` feature of this library is activated.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::oneshot;
+/// use futures::future::FutureExt;
+/// use futures_test::assert_no_waker_leaks;
+///
+/// assert_no_waker_leaks!(|cx| {
+///     let (tx, mut rx) = oneshot::channel::<()>();
+///     assert!(rx.poll_unpin(cx).is_pending());
+///     drop(tx);
+///     assert!(rx.poll_unpin(cx).is_ready());
+/// });
+/// ```
+#[macro_export]
+macro_rules! assert_no_waker_leaks {
+    ($f:expr) => {
+        $crate::task::WakerTracker::new().check($f)
+    };
+}
@@ -0,0 +1,167 @@
+use crate::task::MockClock;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_executor::{LocalPool, LocalSpawner};
+use futures_task::{waker, ArcWake, Sleep, Timer};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// An executor running tasks on the current thread against a virtual clock.
+///
+/// The clock of the pool starts at the time the pool is created and only
+/// advances when every task is stalled: it then jumps straight to the next
+/// deadline of a sleep started through the pool's [`Timer`], waking the
+/// tasks waiting for it. Code taking its time from a `Timer` thus runs
+/// deterministically and without actually waiting, however long its
+/// timeouts are.
+///
+/// # Examples
+///
+/// ```
+/// use futures::task::{LocalSpawnExt, Timer};
+/// use futures_test::executor::DeterministicPool;
+/// use std::time::Duration;
+///
+/// let mut pool = DeterministicPool::new();
+/// let timer = pool.timer();
+/// pool.spawner()
+///     .spawn_local(async move {
+///         timer.sleep(Duration::from_secs(60)).await;
+///     })
+///     .unwrap();
+///
+/// pool.run();
+/// assert_eq!(pool.elapsed(), Duration::from_secs(60));
+/// ```
+pub struct DeterministicPool {
+    pool: LocalPool,
+    clock: MockClock,
+}
+
+impl DeterministicPool {
+    /// Creates a new pool, with its clock set to the current time.
+    pub fn new() -> Self {
+        Self { pool: LocalPool::new(), clock: MockClock::new() }
+    }
+
+    /// Returns a spawner spawning tasks on this pool.
+    pub fn spawner(&self) -> LocalSpawner {
+        self.pool.spawner()
+    }
+
+    /// Returns a handle to the virtual clock of this pool, to be given to
+    /// the tasks.
+    ///
+    /// The clock shouldn't be advanced through the handle while the pool
+    /// runs, but its pending sleeps can be inspected.
+    pub fn timer(&self) -> MockClock {
+        self.clock.clone()
+    }
+
+    /// Returns the time elapsed on the virtual clock since the pool was
+    /// created.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    /// Runs all tasks in the pool until none of them can make progress,
+    /// without advancing the clock.
+    pub fn run_until_stalled(&mut self) {
+        self.pool.run_until_stalled();
+    }
+
+    /// Runs all tasks in the pool to completion, advancing the clock
+    /// whenever they are stalled.
+    ///
+    /// Returns once every task has completed, or once the remaining tasks
+    /// are stalled without any pending sleep to wake them.
+    pub fn run(&mut self) {
+        loop {
+            self.pool.run_until_stalled();
+            if !self.clock.fire_next(None) {
+                return;
+            }
+        }
+    }
+
+    /// Runs all tasks in the pool until the given future completes,
+    /// advancing the clock whenever they are stalled, and returns its
+    /// output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future and the tasks are stalled without any pending
+    /// sleep to wake them, as the future would then never complete.
+    pub fn run_until<F: Future>(&mut self, future: F) -> F::Output {
+        futures_util::pin_mut!(future);
+        let flag = Arc::new(Flag(AtomicBool::new(true)));
+        let waker = waker(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if flag.0.swap(false, Ordering::AcqRel) {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return output;
+                }
+            }
+            self.pool.run_until_stalled();
+            if flag.0.load(Ordering::Acquire) {
+                continue;
+            }
+            if !self.clock.fire_next(None) {
+                panic!("`DeterministicPool::run_until` stalled with no pending sleep");
+            }
+        }
+    }
+
+    /// Advances the clock by `duration`, running the tasks woken along the
+    /// way.
+    ///
+    /// Sleeps ending before the new time are completed in the order of their
+    /// deadlines, and the tasks are run until stalled after each of them, so
+    /// that tasks observe the time of the deadline which woke them.
+    pub fn advance(&mut self, duration: Duration) {
+        let target = self.clock.now() + duration;
+        loop {
+            self.pool.run_until_stalled();
+            if !self.clock.fire_next(Some(target)) {
+                break;
+            }
+        }
+        self.clock.advance_to(target);
+    }
+}
+
+impl Default for DeterministicPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timer for DeterministicPool {
+    fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        self.clock.sleep_until(deadline)
+    }
+}
+
+impl fmt::Debug for DeterministicPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeterministicPool")
+            .field("pool", &self.pool)
+            .field("elapsed", &self.elapsed())
+            .finish()
+    }
+}
+
+struct Flag(AtomicBool);
+
+impl ArcWake for Flag {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.store(true, Ordering::Release);
+    }
+}
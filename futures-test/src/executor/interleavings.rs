@@ -0,0 +1,223 @@
+use futures_core::task::{Context, Poll};
+use futures_task::{waker, ArcWake, FutureObj, LocalFutureObj, LocalSpawn, Spawn, SpawnError};
+use futures_util::future::FutureExt;
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Runs a set of tasks under many different poll orders, to find the
+/// interleavings of wakes and polls under which they fail.
+///
+/// Each run of the tasks is driven by a seed: whenever several tasks are
+/// ready, the task polled next is picked at random among them. Runs use
+/// consecutive seeds, so a failure found by a run can be replayed by
+/// running only its seed, which is reported on the standard error before
+/// the panic is propagated.
+///
+/// A run fails if one of its tasks panics, or if the tasks are stalled
+/// without any of them being woken: as they can only be woken by each
+/// other, they would never complete.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpsc;
+/// use futures::task::LocalSpawnExt;
+/// use futures::{SinkExt, StreamExt};
+/// use futures_test::executor::InterleavingExplorer;
+///
+/// InterleavingExplorer::new().iterations(100).explore(|spawner| {
+///     let (mut tx, mut rx) = mpsc::channel(1);
+///     spawner
+///         .spawn_local(async move {
+///             for i in 0..3 {
+///                 tx.send(i).await.unwrap();
+///             }
+///         })
+///         .unwrap();
+///     spawner
+///         .spawn_local(async move {
+///             for i in 0..3 {
+///                 assert_eq!(rx.next().await, Some(i));
+///             }
+///         })
+///         .unwrap();
+/// });
+/// ```
+///
+/// Replaying the seed 42 reported by a failure:
+///
+/// ```no_run
+/// # use futures_test::executor::InterleavingExplorer;
+/// InterleavingExplorer::new().seed(42).iterations(1).explore(|spawner| {
+///     // Spawn the same tasks.
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct InterleavingExplorer {
+    seed: u64,
+    iterations: usize,
+    max_polls: usize,
+}
+
+impl InterleavingExplorer {
+    /// Creates an explorer running 1000 runs, starting at the seed 0.
+    pub fn new() -> Self {
+        Self { seed: 0, iterations: 1000, max_polls: 100_000 }
+    }
+
+    /// Sets the seed of the first run.
+    ///
+    /// The following runs use the following seeds.
+    pub fn seed(&mut self, seed: u64) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of runs.
+    pub fn iterations(&mut self, iterations: usize) -> &mut Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the number of polls after which a run fails, to catch tasks
+    /// waking each other forever.
+    ///
+    /// The default is 100000.
+    pub fn max_polls(&mut self, max_polls: usize) -> &mut Self {
+        self.max_polls = max_polls;
+        self
+    }
+
+    /// Runs the tasks spawned by `setup` once per seed.
+    ///
+    /// `setup` is called before each run, and should spawn the same tasks
+    /// each time. Tasks can also be spawned by the tasks themselves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the runs fails, after reporting its seed.
+    pub fn explore<F>(&self, mut setup: F)
+    where
+        F: FnMut(&InterleavingSpawner),
+    {
+        for i in 0..self.iterations {
+            let seed = self.seed.wrapping_add(i as u64);
+            let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                let spawner = InterleavingSpawner::default();
+                setup(&spawner);
+                self.run(seed, &spawner);
+            }));
+            if let Err(payload) = res {
+                eprintln!(
+                    "interleaving failed with seed {}, replay it with \
+                     `InterleavingExplorer::new().seed({}).iterations(1)`",
+                    seed, seed
+                );
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+
+    fn run(&self, seed: u64, spawner: &InterleavingSpawner) {
+        let mut rng = Rng::new(seed);
+        let mut tasks: Vec<(LocalFutureObj<'static, ()>, Arc<Woken>)> = Vec::new();
+        let mut ready = Vec::new();
+        for _ in 0..self.max_polls {
+            tasks.extend(
+                spawner
+                    .tasks
+                    .borrow_mut()
+                    .drain(..)
+                    .map(|future| (future, Arc::new(Woken(AtomicBool::new(true))))),
+            );
+            if tasks.is_empty() {
+                return;
+            }
+
+            ready.clear();
+            ready.extend((0..tasks.len()).filter(|&i| tasks[i].1 .0.load(Ordering::Acquire)));
+            if ready.is_empty() {
+                panic!("{} tasks are stalled without being woken", tasks.len());
+            }
+
+            let index = ready[rng.below(ready.len())];
+            let (future, woken) = &mut tasks[index];
+            woken.0.store(false, Ordering::Release);
+            let waker = waker(woken.clone());
+            let mut cx = Context::from_waker(&waker);
+            if let Poll::Ready(()) = future.poll_unpin(&mut cx) {
+                tasks.swap_remove(index);
+            }
+        }
+        panic!("tasks did not complete after {} polls", self.max_polls);
+    }
+}
+
+impl Default for InterleavingExplorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A spawner for the tasks of a run of an [`InterleavingExplorer`].
+///
+/// Futures spawned by `setup` are polled once the run starts, and futures
+/// spawned by a task once that task returns from its poll.
+#[derive(Clone, Default)]
+pub struct InterleavingSpawner {
+    tasks: Rc<RefCell<Vec<LocalFutureObj<'static, ()>>>>,
+}
+
+impl LocalSpawn for InterleavingSpawner {
+    fn spawn_local_obj(&self, future: LocalFutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.tasks.borrow_mut().push(future);
+        Ok(())
+    }
+}
+
+impl Spawn for InterleavingSpawner {
+    fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+        self.spawn_local_obj(future.into())
+    }
+}
+
+impl fmt::Debug for InterleavingSpawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterleavingSpawner").field("pending", &self.tasks.borrow().len()).finish()
+    }
+}
+
+struct Woken(AtomicBool);
+
+impl ArcWake for Woken {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.store(true, Ordering::Release);
+    }
+}
+
+/// A xorshift64* generator, seeded with splitmix64 so that consecutive seeds
+/// give unrelated sequences.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        // The state of xorshift must not be zero.
+        Self(if z == 0 { 1 } else { z })
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let x = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        (((x >> 32) * n as u64) >> 32) as usize
+    }
+}
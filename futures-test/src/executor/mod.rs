@@ -0,0 +1,14 @@
+//! Executors for testing.
+//!
+//! - [`DeterministicPool`](crate::executor::DeterministicPool) runs tasks on
+//!   the current thread against a virtual clock, which only advances once
+//!   every task is stalled.
+//! - [`InterleavingExplorer`](crate::executor::InterleavingExplorer) runs a
+//!   set of tasks under many seeded poll orders, to find the interleavings
+//!   under which they fail.
+
+mod deterministic_pool;
+pub use self::deterministic_pool::DeterministicPool;
+
+mod interleavings;
+pub use self::interleavings::{InterleavingExplorer, InterleavingSpawner};
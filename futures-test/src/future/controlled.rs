@@ -0,0 +1,179 @@
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll, Waker};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// Creates a future completed by its [`Controller`], without a channel.
+///
+/// The controller can resolve or fail the future, hold it pending even
+/// once resolved, and check how many times it was polled. The future stays
+/// pending until it's resolved, even if the controller is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::FutureExt;
+/// use futures_test::future::controlled;
+/// use futures_test::task::noop_context;
+///
+/// let (controller, mut future) = controlled::<i32>();
+/// let mut cx = noop_context();
+///
+/// assert!(future.poll_unpin(&mut cx).is_pending());
+/// controller.resolve(3);
+/// assert_eq!(controller.poll_count(), 1);
+/// assert_eq!(future.poll_unpin(&mut cx), std::task::Poll::Ready(3));
+/// controller.assert_polled(2);
+/// ```
+pub fn controlled<T>() -> (Controller<T>, ControlledFuture<T>) {
+    let inner = Arc::new(Mutex::new(State {
+        value: None,
+        held: false,
+        done: false,
+        polls: 0,
+        waker: None,
+    }));
+    (Controller { inner: inner.clone() }, ControlledFuture { inner })
+}
+
+struct State<T> {
+    value: Option<T>,
+    held: bool,
+    done: bool,
+    polls: usize,
+    waker: Option<Waker>,
+}
+
+/// Controls the completion of a [`ControlledFuture`], created by
+/// [`controlled`].
+pub struct Controller<T> {
+    inner: Arc<Mutex<State<T>>>,
+}
+
+impl<T> Controller<T> {
+    /// Completes the future with `value`, waking its task.
+    ///
+    /// If the future is held pending, it completes once released.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future was already resolved.
+    pub fn resolve(&self, value: T) {
+        let waker = {
+            let mut state = self.inner.lock().unwrap();
+            assert!(state.value.is_none() && !state.done, "controlled future already resolved");
+            state.value = Some(value);
+            if state.held {
+                None
+            } else {
+                state.waker.take()
+            }
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Sets whether the future is held pending.
+    ///
+    /// While held, polling the future returns [`Poll::Pending`], even if
+    /// it's resolved. Releasing a resolved future wakes its task.
+    pub fn hold_pending(&self, held: bool) {
+        let waker = {
+            let mut state = self.inner.lock().unwrap();
+            state.held = held;
+            if !held && state.value.is_some() {
+                state.waker.take()
+            } else {
+                None
+            }
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Returns whether the future was resolved.
+    pub fn is_resolved(&self) -> bool {
+        let state = self.inner.lock().unwrap();
+        state.value.is_some() || state.done
+    }
+
+    /// Returns the number of times the future was polled.
+    pub fn poll_count(&self) -> usize {
+        self.inner.lock().unwrap().polls
+    }
+
+    /// Asserts that the future was polled `count` times.
+    pub fn assert_polled(&self, count: usize) {
+        let polls = self.poll_count();
+        assert_eq!(
+            polls, count,
+            "controlled future was polled {} times, expected {}",
+            polls, count
+        );
+    }
+}
+
+impl<T, E> Controller<Result<T, E>> {
+    /// Completes the future with the error `error`, waking its task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the future was already resolved.
+    pub fn fail(&self, error: E) {
+        self.resolve(Err(error))
+    }
+}
+
+impl<T> fmt::Debug for Controller<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.lock().unwrap();
+        f.debug_struct("Controller")
+            .field("resolved", &(state.value.is_some() || state.done))
+            .field("held", &state.held)
+            .field("polls", &state.polls)
+            .finish()
+    }
+}
+
+/// Future for the [`controlled`] function.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ControlledFuture<T> {
+    inner: Arc<Mutex<State<T>>>,
+}
+
+impl<T> Future for ControlledFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.inner.lock().unwrap();
+        assert!(!state.done, "controlled future polled after completion");
+        state.polls += 1;
+        if !state.held {
+            if let Some(value) = state.value.take() {
+                state.done = true;
+                state.waker = None;
+                return Poll::Ready(value);
+            }
+        }
+        match &state.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            _ => state.waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> FusedFuture for ControlledFuture<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.lock().unwrap().done
+    }
+}
+
+impl<T> fmt::Debug for ControlledFuture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ControlledFuture").finish()
+    }
+}
@@ -1,8 +1,16 @@
 //! Additional combinators for testing futures.
 
+mod controlled;
+pub use self::controlled::{controlled, ControlledFuture, Controller};
+
 mod pending_once;
 pub use self::pending_once::PendingOnce;
 
+mod poll_trace;
+pub use self::poll_trace::{
+    record_poll_trace, PollTrace, RecordPollTrace, TraceEvent, TraceEventKind,
+};
+
 use futures_core::future::Future;
 use std::thread;
 
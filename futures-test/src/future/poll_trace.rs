@@ -0,0 +1,243 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll, Waker};
+use futures_task::{waker, ArcWake};
+use pin_project::pin_project;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// What happened to a future recorded by [`record_poll_trace`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TraceEventKind {
+    /// The future started being polled.
+    Polled,
+    /// The poll returned [`Poll::Pending`].
+    Pending,
+    /// The poll returned [`Poll::Ready`].
+    Ready,
+    /// The future woke the waker of one of its polls.
+    Woken,
+}
+
+/// An event of a [`PollTrace`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceEvent {
+    kind: TraceEventKind,
+    poll: usize,
+    elapsed: Duration,
+}
+
+impl TraceEvent {
+    /// Returns what happened.
+    pub fn kind(&self) -> TraceEventKind {
+        self.kind
+    }
+
+    /// Returns the number of the poll the event relates to, starting at 0.
+    ///
+    /// For [`Woken`](TraceEventKind::Woken) events, this is the poll whose
+    /// waker was woken.
+    pub fn poll(&self) -> usize {
+        self.poll
+    }
+
+    /// Returns the time of the event, relative to the creation of the trace.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// The trace of the polls of a future and of its wakeups, recorded by
+/// [`record_poll_trace`].
+///
+/// Clones of a `PollTrace` share the same events.
+#[derive(Clone)]
+pub struct PollTrace {
+    inner: Arc<TraceInner>,
+}
+
+struct TraceInner {
+    start: Instant,
+    events: Mutex<Vec<TraceEvent>>,
+}
+
+impl PollTrace {
+    fn new() -> Self {
+        Self {
+            inner: Arc::new(TraceInner { start: Instant::now(), events: Mutex::new(Vec::new()) }),
+        }
+    }
+
+    fn record(&self, kind: TraceEventKind, poll: usize) {
+        let elapsed = self.inner.start.elapsed();
+        self.inner.events.lock().unwrap().push(TraceEvent { kind, poll, elapsed });
+    }
+
+    /// Returns the events recorded so far, in order.
+    pub fn events(&self) -> Vec<TraceEvent> {
+        self.inner.events.lock().unwrap().clone()
+    }
+
+    /// Returns the number of times the future was polled.
+    pub fn poll_count(&self) -> usize {
+        self.count(TraceEventKind::Polled)
+    }
+
+    /// Returns the number of times the future woke its task.
+    pub fn wake_count(&self) -> usize {
+        self.count(TraceEventKind::Woken)
+    }
+
+    fn count(&self, kind: TraceEventKind) -> usize {
+        self.inner.events.lock().unwrap().iter().filter(|event| event.kind == kind).count()
+    }
+
+    /// Asserts that the future was polled `count` times.
+    pub fn assert_poll_count(&self, count: usize) {
+        let polls = self.poll_count();
+        assert_eq!(polls, count, "future was polled {} times, expected {}", polls, count);
+    }
+
+    /// Asserts that the future returned [`Poll::Ready`], and wasn't polled
+    /// again afterwards.
+    pub fn assert_ready(&self) {
+        let events = self.events();
+        match events.iter().rev().find(|event| event.kind != TraceEventKind::Woken) {
+            Some(TraceEvent { kind: TraceEventKind::Ready, .. }) => {}
+            _ => panic!("future didn't end with `Poll::Ready`, trace: {:?}", events),
+        }
+        self.assert_no_poll_after_ready();
+    }
+
+    /// Asserts that the future wasn't polled after returning
+    /// [`Poll::Ready`].
+    pub fn assert_no_poll_after_ready(&self) {
+        let events = self.events();
+        if let Some(ready) = events.iter().position(|event| event.kind == TraceEventKind::Ready) {
+            if let Some(event) =
+                events[ready..].iter().find(|event| event.kind == TraceEventKind::Polled)
+            {
+                panic!(
+                    "future was polled after returning `Poll::Ready` (poll {}), trace: {:?}",
+                    event.poll, events
+                );
+            }
+        }
+    }
+
+    /// Asserts that the future woke its task after each poll returning
+    /// [`Poll::Pending`], before being polled again.
+    ///
+    /// This catches futures which rely on being polled again without
+    /// arranging for it, as well as executors polling without being woken.
+    pub fn assert_woken_before_repoll(&self) {
+        let events = self.events();
+        let mut pending = None;
+        let mut woken = None;
+        for event in &events {
+            match event.kind {
+                TraceEventKind::Polled if woken < pending => panic!(
+                    "future was polled again (poll {}) without being woken, trace: {:?}",
+                    event.poll, events
+                ),
+                TraceEventKind::Pending => pending = Some(event.poll),
+                // Wakers may be woken during the poll, before it returns.
+                TraceEventKind::Woken => woken = woken.max(Some(event.poll)),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl fmt::Debug for PollTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollTrace").field("events", &*self.inner.events.lock().unwrap()).finish()
+    }
+}
+
+/// Future for the [`record_poll_trace`] function.
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecordPollTrace<Fut> {
+    #[pin]
+    future: Fut,
+    trace: PollTrace,
+    polls: usize,
+}
+
+impl<Fut> RecordPollTrace<Fut> {
+    /// Returns the trace recorded by this future.
+    pub fn trace(&self) -> PollTrace {
+        self.trace.clone()
+    }
+}
+
+/// Wraps a future to record a trace of its polls, of their results and of
+/// its wakeups, with their timestamps.
+///
+/// The trace is obtained with [`RecordPollTrace::trace`], and can be
+/// inspected while the future runs and after it's dropped.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures_test::future::{record_poll_trace, FutureTestExt, TraceEventKind};
+///
+/// let future = record_poll_trace(async { 5 }.pending_once());
+/// let trace = future.trace();
+/// assert_eq!(block_on(future), 5);
+///
+/// trace.assert_poll_count(2);
+/// trace.assert_ready();
+/// trace.assert_woken_before_repoll();
+/// let kinds: Vec<_> = trace.events().iter().map(|event| event.kind()).collect();
+/// assert_eq!(
+///     kinds,
+///     [
+///         TraceEventKind::Polled,
+///         TraceEventKind::Woken,
+///         TraceEventKind::Pending,
+///         TraceEventKind::Polled,
+///         TraceEventKind::Ready,
+///     ]
+/// );
+/// ```
+pub fn record_poll_trace<Fut: Future>(future: Fut) -> RecordPollTrace<Fut> {
+    RecordPollTrace { future, trace: PollTrace::new(), polls: 0 }
+}
+
+impl<Fut: Future> Future for RecordPollTrace<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let poll = *this.polls;
+        *this.polls += 1;
+        this.trace.record(TraceEventKind::Polled, poll);
+
+        let recorder =
+            Arc::new(WakeRecorder { trace: this.trace.clone(), poll, waker: cx.waker().clone() });
+        let waker = waker(recorder);
+        let res = this.future.poll(&mut Context::from_waker(&waker));
+
+        let kind = if res.is_ready() { TraceEventKind::Ready } else { TraceEventKind::Pending };
+        this.trace.record(kind, poll);
+        res
+    }
+}
+
+struct WakeRecorder {
+    trace: PollTrace,
+    poll: usize,
+    waker: Waker,
+}
+
+impl ArcWake for WakeRecorder {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.trace.record(TraceEventKind::Woken, arc_self.poll);
+        arc_self.waker.wake_by_ref();
+    }
+}
@@ -0,0 +1,246 @@
+use futures_io::{self as io, AsyncRead, AsyncWrite};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::{cmp, mem, thread};
+
+/// An operation expected by a [`MockIo`].
+#[derive(Debug)]
+enum Op {
+    Read(Vec<u8>),
+    ReadError(io::Error),
+    Eof,
+    Write(Vec<u8>),
+    WriteError(io::Error),
+    Flush,
+    Close,
+    Pending,
+}
+
+impl Op {
+    fn name(&self) -> &'static str {
+        match self {
+            Op::Read(_) | Op::ReadError(_) | Op::Eof => "a read",
+            Op::Write(_) | Op::WriteError(_) => "a write",
+            Op::Flush => "a flush",
+            Op::Close => "a close",
+            Op::Pending => "any operation",
+        }
+    }
+}
+
+/// Builds a [`MockIo`] from the sequence of operations it expects.
+///
+/// See [`MockIo`] for an example.
+#[derive(Debug, Default)]
+pub struct MockIoBuilder {
+    ops: VecDeque<Op>,
+}
+
+impl MockIoBuilder {
+    /// Creates a builder expecting no operation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects a read, which returns `data`.
+    ///
+    /// If the buffer of the read is too small, the rest of `data` is
+    /// returned by the following reads.
+    pub fn read(&mut self, data: &[u8]) -> &mut Self {
+        self.ops.push_back(Op::Read(data.to_vec()));
+        self
+    }
+
+    /// Expects a read, which fails with `error`.
+    pub fn read_error(&mut self, error: io::Error) -> &mut Self {
+        self.ops.push_back(Op::ReadError(error));
+        self
+    }
+
+    /// Expects a read, which returns 0 to signal the end of the stream.
+    pub fn read_eof(&mut self) -> &mut Self {
+        self.ops.push_back(Op::Eof);
+        self
+    }
+
+    /// Expects a write of `data`.
+    ///
+    /// The data may be written by several writes, and a write may only
+    /// write part of its buffer, up to the end of `data`.
+    pub fn write(&mut self, data: &[u8]) -> &mut Self {
+        self.ops.push_back(Op::Write(data.to_vec()));
+        self
+    }
+
+    /// Expects a write, which fails with `error`.
+    pub fn write_error(&mut self, error: io::Error) -> &mut Self {
+        self.ops.push_back(Op::WriteError(error));
+        self
+    }
+
+    /// Expects a flush.
+    pub fn flush(&mut self) -> &mut Self {
+        self.ops.push_back(Op::Flush);
+        self
+    }
+
+    /// Expects a close.
+    pub fn close(&mut self) -> &mut Self {
+        self.ops.push_back(Op::Close);
+        self
+    }
+
+    /// Makes the next operation, whichever it is, return
+    /// [`Poll::Pending`] once, like an operation which would block.
+    ///
+    /// The task is woken right away, so that the operation is retried.
+    pub fn pending(&mut self) -> &mut Self {
+        self.ops.push_back(Op::Pending);
+        self
+    }
+
+    /// Creates the mock, leaving this builder empty.
+    pub fn build(&mut self) -> MockIo {
+        MockIo { ops: mem::take(&mut self.ops) }
+    }
+}
+
+/// A mock I/O object, asserting that it's read from and written to exactly
+/// as scripted by its [`MockIoBuilder`].
+///
+/// Each read, write, flush and close performs the next expected operation,
+/// and panics if it's an operation of another kind or if a write doesn't
+/// match the expected data. Dropping the mock panics if some expected
+/// operations weren't performed.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::io::{AsyncReadExt, AsyncWriteExt};
+/// use futures_test::io::MockIo;
+///
+/// let mut io = MockIo::builder()
+///     .write(b"ping")
+///     .pending()
+///     .read(b"pong")
+///     .read_eof()
+///     .build();
+///
+/// block_on(async {
+///     io.write_all(b"ping").await?;
+///     let mut reply = Vec::new();
+///     io.read_to_end(&mut reply).await?;
+///     assert_eq!(reply, b"pong");
+///     Ok::<(), std::io::Error>(())
+/// })
+/// .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MockIo {
+    ops: VecDeque<Op>,
+}
+
+impl MockIo {
+    /// Returns a builder for a mock.
+    pub fn builder() -> MockIoBuilder {
+        MockIoBuilder::new()
+    }
+
+    /// Returns whether every expected operation was performed.
+    pub fn is_done(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Takes the next operation, returning `Poll::Pending` if it's a pending
+    /// point.
+    fn next_op(&mut self, cx: &mut Context<'_>, performed: &str) -> Poll<Op> {
+        match self.ops.pop_front() {
+            Some(Op::Pending) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Some(op) => Poll::Ready(op),
+            None => panic!("unexpected {} on a mock expecting no more operations", performed),
+        }
+    }
+}
+
+fn unexpected(op: &Op, performed: &str) -> ! {
+    panic!("unexpected {} on a mock expecting {}: {:?}", performed, op.name(), op)
+}
+
+impl AsyncRead for MockIo {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        match futures_core::ready!(this.next_op(cx, "read")) {
+            Op::Read(mut data) => {
+                let n = cmp::min(buf.len(), data.len());
+                buf[..n].copy_from_slice(&data[..n]);
+                if n < data.len() {
+                    data.drain(..n);
+                    this.ops.push_front(Op::Read(data));
+                }
+                Poll::Ready(Ok(n))
+            }
+            Op::ReadError(error) => Poll::Ready(Err(error)),
+            Op::Eof => Poll::Ready(Ok(0)),
+            op => unexpected(&op, "read"),
+        }
+    }
+}
+
+impl AsyncWrite for MockIo {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        match futures_core::ready!(this.next_op(cx, "write")) {
+            Op::Write(mut data) => {
+                let n = cmp::min(buf.len(), data.len());
+                assert_eq!(
+                    &buf[..n],
+                    &data[..n],
+                    "write of unexpected data on a mock, expecting {:?}",
+                    data
+                );
+                if n < data.len() {
+                    data.drain(..n);
+                    this.ops.push_front(Op::Write(data));
+                }
+                Poll::Ready(Ok(n))
+            }
+            Op::WriteError(error) => Poll::Ready(Err(error)),
+            op => unexpected(&op, "write"),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match futures_core::ready!(self.get_mut().next_op(cx, "flush")) {
+            Op::Flush => Poll::Ready(Ok(())),
+            op => unexpected(&op, "flush"),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match futures_core::ready!(self.get_mut().next_op(cx, "close")) {
+            Op::Close => Poll::Ready(Ok(())),
+            op => unexpected(&op, "close"),
+        }
+    }
+}
+
+impl Drop for MockIo {
+    fn drop(&mut self) {
+        if !thread::panicking() && !self.ops.is_empty() {
+            panic!("mock dropped before performing the expected operations: {:?}", self.ops);
+        }
+    }
+}
@@ -2,6 +2,9 @@
 
 mod limited;
 
+mod mock;
+pub use mock::{MockIo, MockIoBuilder};
+
 pub mod read;
 pub use read::AsyncReadTestExt;
 
@@ -56,6 +56,9 @@ pub mod sink;
 #[cfg(feature = "std")]
 pub mod io;
 
+#[cfg(feature = "std")]
+pub mod executor;
+
 mod assert_unmoved;
 mod interleave_pending;
 mod track_closed;
@@ -5,6 +5,9 @@ use futures_core::stream::Stream;
 pub use crate::assert_unmoved::AssertUnmoved;
 pub use crate::interleave_pending::InterleavePending;
 
+mod tester;
+pub use self::tester::StreamTester;
+
 /// Additional combinators for testing streams.
 pub trait StreamTestExt: Stream {
     /// Asserts that the given is not moved after being polled.
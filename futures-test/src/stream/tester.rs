@@ -0,0 +1,159 @@
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+use futures_task::{waker, ArcWake};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+/// A harness running a stream under test to completion, while checking that
+/// it behaves as the [`Stream`] contract requires.
+///
+/// The tester checks that:
+///
+/// - the stream wakes its task after returning
+///   [`Poll::Pending`](futures_core::task::Poll::Pending), within a timeout,
+///   so that no wakeup is lost;
+/// - every [`size_hint`](Stream::size_hint) returned along the way bounds
+///   the number of items the stream actually yielded afterwards;
+/// - with [`run_fused`](StreamTester::run_fused), the stream keeps returning
+///   `None` once it's done, and reports being terminated.
+///
+/// Items are yielded to the tester's own thread, so the stream must be
+/// driven by wakes from other tasks or threads, or by itself.
+///
+/// # Examples
+///
+/// ```
+/// use futures::stream;
+/// use futures_test::stream::{StreamTestExt, StreamTester};
+///
+/// let items = StreamTester::new().run(stream::iter(1..=3).interleave_pending());
+/// assert_eq!(items, [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamTester {
+    wake_timeout: Duration,
+    polls_after_end: usize,
+}
+
+impl StreamTester {
+    /// Creates a tester with a wake timeout of 5 seconds.
+    pub fn new() -> Self {
+        Self { wake_timeout: Duration::from_secs(5), polls_after_end: 3 }
+    }
+
+    /// Sets how long to wait for the stream to wake the task after returning
+    /// `Poll::Pending`, before reporting a lost wakeup.
+    pub fn wake_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.wake_timeout = timeout;
+        self
+    }
+
+    /// Sets how many times [`run_fused`](StreamTester::run_fused) polls the
+    /// stream once it's done.
+    ///
+    /// The default is 3.
+    pub fn polls_after_end(&mut self, polls: usize) -> &mut Self {
+        self.polls_after_end = polls;
+        self
+    }
+
+    /// Runs the stream to completion and returns its items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream loses a wakeup, or returns a size hint which
+    /// doesn't match the number of items it yielded.
+    pub fn run<S: Stream + Unpin>(&self, mut stream: S) -> Vec<S::Item> {
+        self.run_inner(&mut stream)
+    }
+
+    /// Runs the stream to completion like [`run`](StreamTester::run), then
+    /// checks that it behaves as a fused stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`run`](StreamTester::run) would, if the stream doesn't
+    /// report being terminated once done, or if it returns anything but
+    /// `Poll::Ready(None)` when polled again.
+    pub fn run_fused<S: FusedStream + Unpin>(&self, mut stream: S) -> Vec<S::Item> {
+        let items = self.run_inner(&mut stream);
+        assert!(stream.is_terminated(), "stream is not terminated after returning `None`");
+
+        let mut cx = Context::from_waker(futures_task::noop_waker_ref());
+        for _ in 0..self.polls_after_end {
+            match Pin::new(&mut stream).poll_next(&mut cx) {
+                Poll::Ready(None) => {}
+                Poll::Ready(Some(_)) => panic!("fused stream yielded an item after `None`"),
+                Poll::Pending => panic!("fused stream returned `Poll::Pending` after `None`"),
+            }
+            assert!(stream.is_terminated(), "fused stream is no longer terminated");
+        }
+        items
+    }
+
+    fn run_inner<S: Stream + Unpin>(&self, stream: &mut S) -> Vec<S::Item> {
+        let notify = Arc::new(Notify { woken: AtomicBool::new(false), thread: thread::current() });
+        let waker = waker(notify.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut items = Vec::new();
+        let mut hints = Vec::new();
+        loop {
+            hints.push((items.len(), stream.size_hint()));
+            notify.woken.store(false, Ordering::SeqCst);
+            match Pin::new(&mut *stream).poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => break,
+                Poll::Pending => self.wait(&notify),
+            }
+        }
+
+        let len = items.len();
+        for (yielded, (lower, upper)) in hints {
+            let remaining = len - yielded;
+            assert!(
+                lower <= remaining && upper.map_or(true, |upper| remaining <= upper),
+                "size hint ({}, {:?}) returned after {} items, while {} more items were yielded",
+                lower,
+                upper,
+                yielded,
+                remaining
+            );
+        }
+        items
+    }
+
+    fn wait(&self, notify: &Notify) {
+        let deadline = Instant::now() + self.wake_timeout;
+        while !notify.woken.load(Ordering::SeqCst) {
+            let now = Instant::now();
+            if now >= deadline {
+                panic!(
+                    "lost wakeup: stream returned `Poll::Pending` and wasn't woken within {:?}",
+                    self.wake_timeout
+                );
+            }
+            thread::park_timeout(deadline - now);
+        }
+    }
+}
+
+impl Default for StreamTester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Notify {
+    woken: AtomicBool,
+    thread: Thread,
+}
+
+impl ArcWake for Notify {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.store(true, Ordering::SeqCst);
+        arc_self.thread.unpark();
+    }
+}
@@ -0,0 +1,194 @@
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll, Waker};
+use futures_task::{Sleep, Timer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A [`Timer`] whose time only advances when told to, for testing code
+/// which waits.
+///
+/// Clones of a `MockClock` share the same time. Sleeps started through it
+/// complete once the clock is advanced past their deadline, without
+/// actually waiting.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::FutureExt;
+/// use futures::task::Timer;
+/// use futures_test::task::{noop_context, MockClock};
+/// use std::time::Duration;
+///
+/// let clock = MockClock::new();
+/// let mut sleep = clock.sleep(Duration::from_secs(5));
+/// let mut cx = noop_context();
+///
+/// clock.advance(Duration::from_millis(4999));
+/// assert!(sleep.poll_unpin(&mut cx).is_pending());
+/// assert_eq!(clock.pending_sleeps(), 1);
+///
+/// clock.advance(Duration::from_millis(1));
+/// assert!(sleep.poll_unpin(&mut cx).is_ready());
+/// assert_eq!(clock.pending_sleeps(), 0);
+/// ```
+#[derive(Clone)]
+pub struct MockClock {
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    start: Instant,
+    now: Instant,
+    next_id: u64,
+    /// The pending sleeps, ordered by deadline and then by creation.
+    sleepers: BTreeMap<(Instant, u64), Option<Waker>>,
+}
+
+impl MockClock {
+    /// Creates a clock set to the current time.
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                start: now,
+                now,
+                next_id: 0,
+                sleepers: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Returns the time elapsed on the clock since it was created.
+    pub fn elapsed(&self) -> Duration {
+        let inner = self.inner.lock().unwrap();
+        inner.now - inner.start
+    }
+
+    /// Advances the clock by `duration`, waking the sleeps which ended.
+    pub fn advance(&self, duration: Duration) {
+        let time = self.now() + duration;
+        self.advance_to(time);
+    }
+
+    /// Advances the clock to `time`, waking the sleeps which ended.
+    ///
+    /// Does nothing if the clock is already past `time`.
+    pub fn advance_to(&self, time: Instant) {
+        let wakers = {
+            let mut inner = self.inner.lock().unwrap();
+            if time > inner.now {
+                inner.now = time;
+            }
+            inner.expire()
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Returns the number of sleeps which haven't completed yet.
+    ///
+    /// Sleeps stop being counted once the clock reaches their deadline, or
+    /// once they are dropped.
+    pub fn pending_sleeps(&self) -> usize {
+        self.inner.lock().unwrap().sleepers.len()
+    }
+
+    /// Returns the earliest deadline of the pending sleeps.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.inner.lock().unwrap().sleepers.keys().next().map(|&(deadline, _)| deadline)
+    }
+
+    /// Advances the clock to the earliest pending deadline, if there is one
+    /// no later than `limit`, and wakes the sleeps which ended.
+    pub(crate) fn fire_next(&self, limit: Option<Instant>) -> bool {
+        match self.next_deadline() {
+            Some(deadline) if limit.map_or(true, |limit| deadline <= limit) => {
+                self.advance_to(deadline);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Inner {
+    /// Removes the sleeps which ended, returning their wakers.
+    fn expire(&mut self) -> Vec<Waker> {
+        let mut wakers = Vec::new();
+        while let Some(&key) = self.sleepers.keys().next() {
+            if key.0 > self.now {
+                break;
+            }
+            wakers.extend(self.sleepers.remove(&key).unwrap());
+        }
+        wakers
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timer for MockClock {
+    fn now(&self) -> Instant {
+        self.inner.lock().unwrap().now
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        if deadline > inner.now {
+            inner.sleepers.insert((deadline, id), None);
+        }
+        Box::pin(MockSleep { inner: self.inner.clone(), key: (deadline, id) })
+    }
+}
+
+impl fmt::Debug for MockClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock().unwrap();
+        f.debug_struct("MockClock")
+            .field("elapsed", &(inner.now - inner.start))
+            .field("pending_sleeps", &inner.sleepers.len())
+            .finish()
+    }
+}
+
+struct MockSleep {
+    inner: Arc<Mutex<Inner>>,
+    key: (Instant, u64),
+}
+
+impl Future for MockSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.now >= self.key.0 {
+            inner.sleepers.remove(&self.key);
+            return Poll::Ready(());
+        }
+        match inner.sleepers.get_mut(&self.key) {
+            Some(Some(waker)) if waker.will_wake(cx.waker()) => {}
+            Some(slot) => *slot = Some(cx.waker().clone()),
+            // The clock reached the deadline, which was checked above.
+            None => unreachable!(),
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for MockSleep {
+    fn drop(&mut self) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.sleepers.remove(&self.key);
+        }
+    }
+}
@@ -27,6 +27,8 @@
 //!   [`wake`](futures_core::task::Waker) is called.
 //! - [`new_count_waker`](crate::task::new_count_waker) creates a waker that increments a counter whenever
 //!   [`wake`](futures_core::task::Waker) is called.
+//! - [`WakerTracker`](crate::task::WakerTracker) creates wakers recording
+//!   their clones, wakes and drops, to find leaked wakers.
 //!
 //! Test spawners:
 //! - [`NoopSpawner`](crate::task::NoopSpawner) ignores calls to
@@ -35,6 +37,10 @@
 //!   called.
 //! - [`RecordSpawner`](crate::task::RecordSpawner) records the spawned futures.
 //!
+//! Test timers:
+//! - [`MockClock`](crate::task::MockClock) is a [`Timer`](futures_task::Timer)
+//!   whose time only advances when told to.
+//!
 //! For convenience there additionally exist various functions that directly
 //! return waker/spawner references: [`noop_waker_ref`](crate::task::noop_waker_ref),
 //! [`panic_waker_ref`](crate::task::panic_waker_ref), [`noop_spawner_mut`](crate::task::noop_spawner_mut) and [`panic_spawner_mut`](crate::task::panic_spawner_mut).
@@ -42,6 +48,9 @@
 mod context;
 pub use self::context::{noop_context, panic_context};
 
+mod mock_clock;
+pub use self::mock_clock::MockClock;
+
 mod noop_spawner;
 pub use self::noop_spawner::{noop_spawner_mut, NoopSpawner};
 
@@ -58,3 +67,6 @@ pub use self::record_spawner::RecordSpawner;
 
 mod wake_counter;
 pub use self::wake_counter::{new_count_waker, AwokenCount};
+
+mod waker_tracker;
+pub use self::waker_tracker::{WakerEvent, WakerEventKind, WakerTracker};
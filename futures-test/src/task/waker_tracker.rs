@@ -0,0 +1,255 @@
+use futures_core::task::{Context, RawWaker, RawWakerVTable, Waker};
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::sync::{Arc, Mutex};
+
+/// What happened to a waker tracked by a [`WakerTracker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WakerEventKind {
+    /// The waker was created by [`WakerTracker::waker`].
+    Created,
+    /// The waker was created by cloning the waker `from`.
+    Cloned {
+        /// The id of the cloned waker.
+        from: usize,
+    },
+    /// The waker was woken by reference, and is still alive.
+    WokenByRef,
+    /// The waker was woken by value, which dropped it.
+    Woken,
+    /// The waker was dropped.
+    Dropped,
+}
+
+/// An event recorded by a [`WakerTracker`].
+#[derive(Clone)]
+pub struct WakerEvent {
+    id: usize,
+    kind: WakerEventKind,
+    #[cfg(feature = "backtrace")]
+    backtrace: Arc<Backtrace>,
+}
+
+impl WakerEvent {
+    /// Returns the id of the waker, unique among the wakers of its tracker.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns what happened to the waker.
+    pub fn kind(&self) -> WakerEventKind {
+        self.kind
+    }
+
+    /// Returns the backtrace of the event.
+    ///
+    /// This method is only available when the `backtrace` feature of this
+    /// library is activated, which requires Rust 1.65.
+    #[cfg(feature = "backtrace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "backtrace")))]
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+}
+
+impl fmt::Debug for WakerEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WakerEvent").field("id", &self.id).field("kind", &self.kind).finish()
+    }
+}
+
+/// Creates wakers recording every clone, wake and drop, to find the wakers
+/// which are never dropped.
+///
+/// Each waker created by the tracker, directly or by cloning, is given its
+/// own id, so that a leaked waker can be traced back to the clone which
+/// created it. With the `backtrace` feature of this library, every event
+/// also records a backtrace.
+///
+/// See also [`assert_no_waker_leaks!`](crate::assert_no_waker_leaks).
+///
+/// # Examples
+///
+/// ```
+/// use futures_test::task::{WakerEventKind, WakerTracker};
+///
+/// let tracker = WakerTracker::new();
+/// let waker = tracker.waker();
+/// let clone = waker.clone();
+/// assert_eq!(tracker.live_wakers(), 2);
+///
+/// clone.wake();
+/// drop(waker);
+/// assert_eq!(tracker.live_wakers(), 0);
+/// assert_eq!(tracker.events()[2].kind(), WakerEventKind::Woken);
+/// tracker.assert_no_leaks();
+/// ```
+#[derive(Clone)]
+pub struct WakerTracker {
+    inner: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: usize,
+    /// The live wakers, with the index of the event which created them.
+    live: BTreeMap<usize, usize>,
+    events: Vec<WakerEvent>,
+}
+
+impl State {
+    fn record(&mut self, id: usize, kind: WakerEventKind) {
+        self.events.push(WakerEvent {
+            id,
+            kind,
+            #[cfg(feature = "backtrace")]
+            backtrace: Arc::new(Backtrace::force_capture()),
+        });
+        match kind {
+            WakerEventKind::Created | WakerEventKind::Cloned { .. } => {
+                self.live.insert(id, self.events.len() - 1);
+            }
+            WakerEventKind::Woken | WakerEventKind::Dropped => {
+                self.live.remove(&id);
+            }
+            WakerEventKind::WokenByRef => {}
+        }
+    }
+}
+
+/// The data of a tracked waker.
+struct Tracked {
+    id: usize,
+    tracker: Arc<Mutex<State>>,
+}
+
+impl WakerTracker {
+    /// Creates a tracker with no waker.
+    pub fn new() -> Self {
+        Self { inner: Arc::new(Mutex::new(State::default())) }
+    }
+
+    /// Creates a new tracked waker.
+    pub fn waker(&self) -> Waker {
+        let raw = new_raw(&self.inner, WakerEventKind::Created);
+        // Safety: the vtable functions uphold the `RawWaker` contract.
+        unsafe { Waker::from_raw(raw) }
+    }
+
+    /// Returns the events recorded so far, in order.
+    pub fn events(&self) -> Vec<WakerEvent> {
+        self.inner.lock().unwrap().events.clone()
+    }
+
+    /// Returns the number of tracked wakers which are still alive.
+    pub fn live_wakers(&self) -> usize {
+        self.inner.lock().unwrap().live.len()
+    }
+
+    /// Returns the number of times the tracked wakers were woken.
+    pub fn wake_count(&self) -> usize {
+        let state = self.inner.lock().unwrap();
+        state
+            .events
+            .iter()
+            .filter(|event| {
+                matches!(event.kind, WakerEventKind::Woken | WakerEventKind::WokenByRef)
+            })
+            .count()
+    }
+
+    /// Asserts that every tracked waker was dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some wakers are still alive, describing how they were
+    /// created.
+    pub fn assert_no_leaks(&self) {
+        let state = self.inner.lock().unwrap();
+        if state.live.is_empty() {
+            return;
+        }
+        let mut msg = format!("{} waker(s) leaked:", state.live.len());
+        for (&id, &event) in &state.live {
+            let event = &state.events[event];
+            match event.kind {
+                WakerEventKind::Cloned { from } => {
+                    write!(msg, "\n  waker #{} cloned from waker #{}", id, from).unwrap();
+                }
+                _ => write!(msg, "\n  waker #{} created by the tracker", id).unwrap(),
+            }
+            #[cfg(feature = "backtrace")]
+            write!(msg, " at:\n{}", event.backtrace).unwrap();
+        }
+        drop(state);
+        panic!("{}", msg);
+    }
+
+    /// Runs `f` with a context holding a tracked waker, then asserts that
+    /// every tracked waker was dropped.
+    ///
+    /// This is what [`assert_no_waker_leaks!`](crate::assert_no_waker_leaks)
+    /// calls.
+    pub fn check<R>(&self, f: impl FnOnce(&mut Context<'_>) -> R) -> R {
+        let waker = self.waker();
+        let res = f(&mut Context::from_waker(&waker));
+        drop(waker);
+        self.assert_no_leaks();
+        res
+    }
+}
+
+impl Default for WakerTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for WakerTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.lock().unwrap();
+        f.debug_struct("WakerTracker")
+            .field("live_wakers", &state.live.len())
+            .field("events", &state.events.len())
+            .finish()
+    }
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone_raw, wake_raw, wake_by_ref_raw, drop_raw);
+
+fn new_raw(tracker: &Arc<Mutex<State>>, kind: WakerEventKind) -> RawWaker {
+    let id = {
+        let mut state = tracker.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.record(id, kind);
+        id
+    };
+    let data = Box::into_raw(Box::new(Tracked { id, tracker: tracker.clone() }));
+    RawWaker::new(data as *const (), &VTABLE)
+}
+
+unsafe fn clone_raw(data: *const ()) -> RawWaker {
+    let tracked = &*(data as *const Tracked);
+    new_raw(&tracked.tracker, WakerEventKind::Cloned { from: tracked.id })
+}
+
+unsafe fn wake_raw(data: *const ()) {
+    let tracked = Box::from_raw(data as *mut Tracked);
+    tracked.tracker.lock().unwrap().record(tracked.id, WakerEventKind::Woken);
+}
+
+unsafe fn wake_by_ref_raw(data: *const ()) {
+    let tracked = &*(data as *const Tracked);
+    tracked.tracker.lock().unwrap().record(tracked.id, WakerEventKind::WokenByRef);
+}
+
+unsafe fn drop_raw(data: *const ()) {
+    let tracked = Box::from_raw(data as *mut Tracked);
+    // Don't panic again while unwinding from a poisoning panic.
+    if let Ok(mut state) = tracked.tracker.lock() {
+        state.record(tracked.id, WakerEventKind::Dropped);
+    };
+}
@@ -0,0 +1,185 @@
+use core::fmt;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+use futures_task::WakerSet;
+use std::error::Error;
+use std::sync::{Arc, Mutex, Weak};
+
+/// A shared, cloneable signal for cancelling many futures at once.
+///
+/// All clones of a token share its state: once [`cancel`](Self::cancel) has
+/// been called on any of them, [`is_cancelled`](Self::is_cancelled) returns
+/// `true` and every [`cancelled`](Self::cancelled) future completes.
+///
+/// Tokens form a hierarchy: cancelling a token also cancels all of the tokens
+/// created from it with [`child_token`](Self::child_token), and their
+/// children in turn, but cancelling a child token doesn't affect its parent.
+///
+/// Futures and streams can be tied to a token with
+/// [`FutureExt::with_cancellation`](crate::future::FutureExt::with_cancellation)
+/// and
+/// [`StreamExt::take_until_cancelled`](crate::stream::StreamExt::take_until_cancelled).
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::future::{self, CancellationToken, Cancelled, FutureExt};
+///
+/// let token = CancellationToken::new();
+/// let child = token.child_token();
+///
+/// token.cancel();
+/// assert!(child.is_cancelled());
+///
+/// let result = future::pending::<()>().with_cancellation(child).await;
+/// assert_eq!(result, Err(Cancelled));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    cancelled: AtomicBool,
+    wakers: WakerSet,
+    children: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl Inner {
+    fn new(cancelled: bool) -> Self {
+        Self {
+            cancelled: AtomicBool::new(cancelled),
+            wakers: WakerSet::new(),
+            children: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.wakers.wake_all();
+
+        // Children can't be added anymore, see `child_token`.
+        let children = std::mem::take(&mut *self.children.lock().unwrap());
+        for child in children {
+            if let Some(child) = child.upgrade() {
+                child.cancel();
+            }
+        }
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new token, which isn't cancelled.
+    pub fn new() -> Self {
+        Self { inner: Arc::new(Inner::new(false)) }
+    }
+
+    /// Creates a child token, which is cancelled along with this token.
+    ///
+    /// If this token is already cancelled, the child token is cancelled as
+    /// well.
+    pub fn child_token(&self) -> Self {
+        let mut children = self.inner.children.lock().unwrap();
+        // Checked under the lock, which `cancel` takes after setting the flag.
+        let child = Arc::new(Inner::new(self.inner.cancelled.load(Ordering::SeqCst)));
+        if !child.cancelled.load(Ordering::Relaxed) {
+            children.retain(|child| child.strong_count() > 0);
+            children.push(Arc::downgrade(&child));
+        }
+        Self { inner: child }
+    }
+
+    /// Cancels this token and all of its children.
+    ///
+    /// Calling this on a token which is already cancelled does nothing.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns a future which completes once this token is cancelled.
+    pub fn cancelled(&self) -> WaitForCancellation {
+        WaitForCancellation { token: self.clone(), key: None }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken").field("is_cancelled", &self.is_cancelled()).finish()
+    }
+}
+
+/// Future for the [`cancelled`](CancellationToken::cancelled) method.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForCancellation {
+    token: CancellationToken,
+    key: Option<usize>,
+}
+
+impl Future for WaitForCancellation {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let inner = &self.token.inner;
+        if !inner.cancelled.load(Ordering::SeqCst) {
+            match self.key {
+                Some(key) => inner.wakers.update(key, cx.waker()),
+                None => {
+                    let key = inner.wakers.insert(cx.waker());
+                    self.key = Some(key);
+                }
+            }
+            // Check again after registering, to not miss a concurrent `cancel`.
+            if !self.token.is_cancelled() {
+                return Poll::Pending;
+            }
+        }
+
+        if let Some(key) = self.key.take() {
+            self.token.inner.wakers.remove(key);
+        }
+        Poll::Ready(())
+    }
+}
+
+impl Drop for WaitForCancellation {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            self.token.inner.wakers.remove(key);
+        }
+    }
+}
+
+impl fmt::Debug for WaitForCancellation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitForCancellation").field("token", &self.token).finish()
+    }
+}
+
+/// Indicator that a future was cancelled by a [`CancellationToken`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`CancellationToken` was cancelled")
+    }
+}
+
+impl Error for Cancelled {}
@@ -0,0 +1,412 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::future::{FusedFuture, Future};
+use futures_core::stream::{FusedStream, Stream};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+
+// Expands to `$t`, to repeat it once per branch.
+macro_rules! replace_ty {
+    ($_branch:ident, $t:ty) => {
+        $t
+    };
+}
+
+macro_rules! either_n {
+    (
+        $(#[$attr:meta])*
+        $name:ident { $first:ident: $first_ctor:ident $(, $rest:ident: $rest_ctor:ident)* }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        pub enum $name<$first $(, $rest)*> {
+            /// First branch of the type
+            $first(/* #[pin] */ $first),
+            $(
+                /// Another branch of the type
+                $rest(/* #[pin] */ $rest),
+            )*
+        }
+
+        impl<$first $(, $rest)*> $name<$first $(, $rest)*> {
+            /// Wraps `inner` in the branch of the same name, the types of the
+            /// other branches being inferred.
+            pub fn $first_ctor(inner: $first) -> Self {
+                $name::$first(inner)
+            }
+
+            $(
+                /// Wraps `inner` in the branch of the same name, the types of
+                /// the other branches being inferred.
+                pub fn $rest_ctor(inner: $rest) -> Self {
+                    $name::$rest(inner)
+                }
+            )*
+
+            /// Convert a pinned reference to the enum into the enum of pinned
+            /// references to the inner variants.
+            pub fn as_pin_ref(self: Pin<&Self>) -> $name<Pin<&$first> $(, Pin<&$rest>)*> {
+                // SAFETY: See `Either::as_pin_ref`.
+                unsafe {
+                    match *Pin::get_ref(self) {
+                        $name::$first(ref inner) => $name::$first(Pin::new_unchecked(inner)),
+                        $($name::$rest(ref inner) => $name::$rest(Pin::new_unchecked(inner)),)*
+                    }
+                }
+            }
+
+            /// Convert a pinned mutable reference to the enum into the enum
+            /// of pinned mutable references to the inner variants.
+            pub fn as_pin_mut(
+                self: Pin<&mut Self>,
+            ) -> $name<Pin<&mut $first> $(, Pin<&mut $rest>)*> {
+                // SAFETY: See `Either::as_pin_mut`.
+                unsafe {
+                    match *Pin::get_unchecked_mut(self) {
+                        $name::$first(ref mut inner) => $name::$first(Pin::new_unchecked(inner)),
+                        $($name::$rest(ref mut inner) => $name::$rest(Pin::new_unchecked(inner)),)*
+                    }
+                }
+            }
+        }
+
+        impl<T> $name<T $(, replace_ty!($rest, T))*> {
+            /// Extract the value of an enum over equivalent types.
+            pub fn into_inner(self) -> T {
+                match self {
+                    $name::$first(x) => x,
+                    $($name::$rest(x) => x,)*
+                }
+            }
+        }
+
+        impl<$first $(, $rest)*> Future for $name<$first $(, $rest)*>
+        where
+            $first: Future,
+            $($rest: Future<Output = $first::Output>,)*
+        {
+            type Output = $first::Output;
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll(cx),
+                    $($name::$rest(x) => x.poll(cx),)*
+                }
+            }
+        }
+
+        impl<$first $(, $rest)*> FusedFuture for $name<$first $(, $rest)*>
+        where
+            $first: FusedFuture,
+            $($rest: FusedFuture<Output = $first::Output>,)*
+        {
+            fn is_terminated(&self) -> bool {
+                match self {
+                    $name::$first(x) => x.is_terminated(),
+                    $($name::$rest(x) => x.is_terminated(),)*
+                }
+            }
+        }
+
+        impl<$first $(, $rest)*> Stream for $name<$first $(, $rest)*>
+        where
+            $first: Stream,
+            $($rest: Stream<Item = $first::Item>,)*
+        {
+            type Item = $first::Item;
+
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_next(cx),
+                    $($name::$rest(x) => x.poll_next(cx),)*
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match self {
+                    $name::$first(x) => x.size_hint(),
+                    $($name::$rest(x) => x.size_hint(),)*
+                }
+            }
+        }
+
+        impl<$first $(, $rest)*> FusedStream for $name<$first $(, $rest)*>
+        where
+            $first: FusedStream,
+            $($rest: FusedStream<Item = $first::Item>,)*
+        {
+            fn is_terminated(&self) -> bool {
+                match self {
+                    $name::$first(x) => x.is_terminated(),
+                    $($name::$rest(x) => x.is_terminated(),)*
+                }
+            }
+        }
+
+        #[cfg(feature = "sink")]
+        impl<$first $(, $rest)*, Item> Sink<Item> for $name<$first $(, $rest)*>
+        where
+            $first: Sink<Item>,
+            $($rest: Sink<Item, Error = $first::Error>,)*
+        {
+            type Error = $first::Error;
+
+            fn poll_ready(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Result<(), Self::Error>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_ready(cx),
+                    $($name::$rest(x) => x.poll_ready(cx),)*
+                }
+            }
+
+            fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.start_send(item),
+                    $($name::$rest(x) => x.start_send(item),)*
+                }
+            }
+
+            fn poll_flush(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Result<(), Self::Error>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_flush(cx),
+                    $($name::$rest(x) => x.poll_flush(cx),)*
+                }
+            }
+
+            fn poll_close(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Result<(), Self::Error>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_close(cx),
+                    $($name::$rest(x) => x.poll_close(cx),)*
+                }
+            }
+        }
+
+        #[cfg(feature = "io")]
+        #[cfg(feature = "std")]
+        impl<$first $(, $rest)*> if_std::AsyncRead for $name<$first $(, $rest)*>
+        where
+            $first: if_std::AsyncRead,
+            $($rest: if_std::AsyncRead,)*
+        {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<if_std::Result<usize>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_read(cx, buf),
+                    $($name::$rest(x) => x.poll_read(cx, buf),)*
+                }
+            }
+
+            fn poll_read_vectored(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                bufs: &mut [if_std::IoSliceMut<'_>],
+            ) -> Poll<if_std::Result<usize>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_read_vectored(cx, bufs),
+                    $($name::$rest(x) => x.poll_read_vectored(cx, bufs),)*
+                }
+            }
+        }
+
+        #[cfg(feature = "io")]
+        #[cfg(feature = "std")]
+        impl<$first $(, $rest)*> if_std::AsyncWrite for $name<$first $(, $rest)*>
+        where
+            $first: if_std::AsyncWrite,
+            $($rest: if_std::AsyncWrite,)*
+        {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<if_std::Result<usize>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_write(cx, buf),
+                    $($name::$rest(x) => x.poll_write(cx, buf),)*
+                }
+            }
+
+            fn poll_write_vectored(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                bufs: &[if_std::IoSlice<'_>],
+            ) -> Poll<if_std::Result<usize>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_write_vectored(cx, bufs),
+                    $($name::$rest(x) => x.poll_write_vectored(cx, bufs),)*
+                }
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<if_std::Result<()>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_flush(cx),
+                    $($name::$rest(x) => x.poll_flush(cx),)*
+                }
+            }
+
+            fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<if_std::Result<()>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_close(cx),
+                    $($name::$rest(x) => x.poll_close(cx),)*
+                }
+            }
+        }
+
+        #[cfg(feature = "io")]
+        #[cfg(feature = "std")]
+        impl<$first $(, $rest)*> if_std::AsyncSeek for $name<$first $(, $rest)*>
+        where
+            $first: if_std::AsyncSeek,
+            $($rest: if_std::AsyncSeek,)*
+        {
+            fn poll_seek(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                pos: if_std::SeekFrom,
+            ) -> Poll<if_std::Result<u64>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_seek(cx, pos),
+                    $($name::$rest(x) => x.poll_seek(cx, pos),)*
+                }
+            }
+        }
+
+        #[cfg(feature = "io")]
+        #[cfg(feature = "std")]
+        impl<$first $(, $rest)*> if_std::AsyncBufRead for $name<$first $(, $rest)*>
+        where
+            $first: if_std::AsyncBufRead,
+            $($rest: if_std::AsyncBufRead,)*
+        {
+            fn poll_fill_buf(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<if_std::Result<&[u8]>> {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.poll_fill_buf(cx),
+                    $($name::$rest(x) => x.poll_fill_buf(cx),)*
+                }
+            }
+
+            fn consume(self: Pin<&mut Self>, amt: usize) {
+                match self.as_pin_mut() {
+                    $name::$first(x) => x.consume(amt),
+                    $($name::$rest(x) => x.consume(amt),)*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "io")]
+#[cfg(feature = "std")]
+mod if_std {
+    pub(super) use futures_io::{
+        AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite, IoSlice, IoSliceMut, Result, SeekFrom,
+    };
+}
+
+either_n! {
+    /// Combines three different futures, streams, or sinks having the same
+    /// associated types into a single type.
+    ///
+    /// This is the three-branch version of [`Either`](super::Either), useful
+    /// when conditionally choosing between three distinct future types:
+    ///
+    /// ```rust
+    /// use futures::future::Either3;
+    ///
+    /// # futures::executor::block_on(async {
+    /// let n = 1;
+    ///
+    /// let fut = match n {
+    ///     0 => Either3::A(async move { 12 }),
+    ///     1 => Either3::B(async move { 44 }),
+    ///     _ => Either3::C(async move { 0 }),
+    /// };
+    ///
+    /// assert_eq!(fut.await, 44);
+    /// # })
+    /// ```
+    ///
+    /// Each branch also has a constructor named after it, playing the part of
+    /// [`left_future`](super::FutureExt::left_future) and
+    /// [`right_future`](super::FutureExt::right_future) for `Either`. It can
+    /// be passed where a function is expected, for example to merge streams
+    /// of different item types:
+    ///
+    /// ```rust
+    /// use futures::future::Either3;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// # futures::executor::block_on(async {
+    /// let numbers = stream::iter(vec![1, 2]).map(Either3::a);
+    /// let letters = stream::iter(vec!['x']).map(Either3::b);
+    /// let flags = stream::iter(vec![true]).map(Either3::c);
+    ///
+    /// let items: Vec<String> = numbers
+    ///     .chain(letters)
+    ///     .chain(flags)
+    ///     .map(|item| match item {
+    ///         Either3::A(n) => n.to_string(),
+    ///         Either3::B(c) => c.to_string(),
+    ///         Either3::C(b) => b.to_string(),
+    ///     })
+    ///     .collect()
+    ///     .await;
+    /// assert_eq!(items, ["1", "2", "x", "true"]);
+    /// # })
+    /// ```
+    Either3 { A: a, B: b, C: c }
+}
+
+either_n! {
+    /// Combines four different futures, streams, or sinks having the same
+    /// associated types into a single type.
+    ///
+    /// See [`Either3`] for details.
+    Either4 { A: a, B: b, C: c, D: d }
+}
+
+either_n! {
+    /// Combines five different futures, streams, or sinks having the same
+    /// associated types into a single type.
+    ///
+    /// See [`Either3`] for details.
+    Either5 { A: a, B: b, C: c, D: d, E: e }
+}
+
+either_n! {
+    /// Combines six different futures, streams, or sinks having the same
+    /// associated types into a single type.
+    ///
+    /// See [`Either3`] for details.
+    Either6 { A: a, B: b, C: c, D: d, E: e, F: f }
+}
+
+either_n! {
+    /// Combines seven different futures, streams, or sinks having the same
+    /// associated types into a single type.
+    ///
+    /// See [`Either3`] for details.
+    Either7 { A: a, B: b, C: c, D: d, E: e, F: f, G: g }
+}
+
+either_n! {
+    /// Combines eight different futures, streams, or sinks having the same
+    /// associated types into a single type.
+    ///
+    /// See [`Either3`] for details.
+    Either8 { A: a, B: b, C: c, D: d, E: e, F: f, G: g, H: h }
+}
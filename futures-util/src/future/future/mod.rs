@@ -116,6 +116,12 @@ mod shared;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::shared::{Shared, WeakShared};
 
+#[cfg(feature = "std")]
+mod with_cancellation;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::with_cancellation::WithCancellation;
+
 impl<T: ?Sized> FutureExt for T where T: Future {}
 
 /// An extension trait for `Future`s that provides a variety of convenient
@@ -491,6 +497,39 @@ pub trait FutureExt: Future {
         assert_future::<Self::Output, _>(Shared::new(self))
     }
 
+    /// Ties this future to a [`CancellationToken`](crate::future::CancellationToken).
+    ///
+    /// The returned future resolves to `Ok` with the output of this future if
+    /// it completes first, or to `Err(Cancelled)` as soon as `token` is
+    /// cancelled, in which case this future is not polled anymore.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::future::{self, CancellationToken, Cancelled, FutureExt};
+    ///
+    /// let token = CancellationToken::new();
+    /// assert_eq!(future::ready(1).with_cancellation(token.clone()).await, Ok(1));
+    ///
+    /// token.cancel();
+    /// assert_eq!(future::ready(1).with_cancellation(token).await, Err(Cancelled));
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    fn with_cancellation(self, token: crate::future::CancellationToken) -> WithCancellation<Self>
+    where
+        Self: Sized,
+    {
+        assert_future::<Result<Self::Output, crate::future::Cancelled>, _>(WithCancellation::new(
+            self,
+            token.cancelled(),
+        ))
+    }
+
     /// Turn this future into a future that yields `()` on completion and sends
     /// its output to another future on a separate task.
     ///
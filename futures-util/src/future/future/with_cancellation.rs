@@ -0,0 +1,49 @@
+use crate::cancellation_token::{Cancelled, WaitForCancellation};
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for the [`with_cancellation`](super::FutureExt::with_cancellation) method.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct WithCancellation<Fut> {
+        #[pin]
+        future: Fut,
+        cancelled: Option<WaitForCancellation>,
+    }
+}
+
+impl<Fut: Future> WithCancellation<Fut> {
+    pub(super) fn new(future: Fut, cancelled: WaitForCancellation) -> Self {
+        Self { future, cancelled: Some(cancelled) }
+    }
+}
+
+impl<Fut: Future> Future for WithCancellation<Fut> {
+    type Output = Result<Fut::Output, Cancelled>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let cancelled = this.cancelled.as_mut().expect("WithCancellation polled after completion");
+
+        // Check for cancellation first, so that a cancelled future doesn't
+        // make any further progress.
+        if Pin::new(cancelled).poll(cx).is_ready() {
+            *this.cancelled = None;
+            return Poll::Ready(Err(Cancelled));
+        }
+
+        let output = ready!(this.future.poll(cx));
+        *this.cancelled = None;
+        Poll::Ready(Ok(output))
+    }
+}
+
+impl<Fut: Future> FusedFuture for WithCancellation<Fut> {
+    fn is_terminated(&self) -> bool {
+        self.cancelled.is_none()
+    }
+}
@@ -15,6 +15,52 @@ pub use core::future::Future;
 #[cfg(feature = "alloc")]
 pub use futures_core::future::{BoxFuture, LocalBoxFuture};
 pub use futures_core::future::{FusedFuture, TryFuture};
+
+/// Derives [`FusedFuture`](trait@FusedFuture) for a struct wrapping a future,
+/// by delegating [`is_terminated`](FusedFuture::is_terminated) to one of its
+/// fields.
+///
+/// The field is the one marked with `#[fused(delegate)]`, which can be
+/// omitted if the struct has a single field. The generated implementation
+/// requires the type of this field to implement `FusedFuture`, and the
+/// struct itself to implement [`Future`].
+///
+/// The generated code refers to the `futures` crate; crates depending on
+/// `futures-util` instead can set the path to use with
+/// `#[fused(crate = "futures_util")]` on the struct.
+///
+/// # Examples
+///
+/// ```
+/// use futures::future::{self, FusedFuture, Future, FutureExt};
+/// use futures::task::{Context, Poll};
+/// use std::pin::Pin;
+///
+/// #[derive(FusedFuture)]
+/// struct Counted<Fut> {
+///     #[fused(delegate)]
+///     future: Fut,
+///     polls: usize,
+/// }
+///
+/// impl<Fut: Future + Unpin> Future for Counted<Fut> {
+///     type Output = Fut::Output;
+///
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+///         self.polls += 1;
+///         Pin::new(&mut self.future).poll(cx)
+///     }
+/// }
+///
+/// # futures::executor::block_on(async {
+/// let mut counted = Counted { future: future::ready(1).fuse(), polls: 0 };
+/// assert!(!counted.is_terminated());
+/// assert_eq!((&mut counted).await, 1);
+/// assert!(counted.is_terminated());
+/// # });
+/// ```
+#[cfg(feature = "async-await-macro")]
+pub use futures_macro::FusedFuture;
 pub use futures_task::{FutureObj, LocalFutureObj, UnsafeFutureObj};
 
 // Extension traits and combinators
@@ -38,6 +84,9 @@ pub use self::future::{Remote, RemoteHandle};
 #[cfg(feature = "std")]
 pub use self::future::{Shared, WeakShared};
 
+#[cfg(feature = "std")]
+pub use self::future::WithCancellation;
+
 mod try_future;
 pub use self::try_future::{
     AndThen, ErrInto, InspectErr, InspectOk, IntoFuture, MapErr, MapOk, MapOkOrElse, OkInto,
@@ -109,6 +158,9 @@ pub use self::select_ok::{select_ok, SelectOk};
 mod either;
 pub use self::either::Either;
 
+mod either_n;
+pub use self::either_n::{Either3, Either4, Either5, Either6, Either7, Either8};
+
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 mod abortable;
@@ -119,6 +171,9 @@ pub use crate::abortable::{AbortHandle, AbortRegistration, Abortable, Aborted};
 #[cfg(feature = "alloc")]
 pub use abortable::abortable;
 
+#[cfg(feature = "std")]
+pub use crate::cancellation_token::{CancellationToken, Cancelled, WaitForCancellation};
+
 // Just a helper function to ensure the futures we're returning all have the
 // right implementations.
 pub(crate) fn assert_future<T, F>(future: F) -> F
@@ -332,5 +332,8 @@ pub mod lock;
 #[cfg(feature = "alloc")]
 mod abortable;
 
+#[cfg(feature = "std")]
+mod cancellation_token;
+
 mod fns;
 mod unfold_state;
@@ -15,7 +15,10 @@ use core::ptr;
 use core::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release, SeqCst};
 use core::sync::atomic::{AtomicBool, AtomicPtr};
 use futures_core::future::Future;
+use futures_core::ready;
 use futures_core::stream::{FusedStream, Stream};
+#[cfg(feature = "std")]
+use futures_core::task::__internal::coop;
 use futures_core::task::{Context, Poll};
 use futures_task::{FutureObj, LocalFutureObj, LocalSpawn, Spawn, SpawnError};
 
@@ -389,6 +392,9 @@ impl<Fut: Future> Stream for FuturesUnordered<Fut> {
     type Item = Fut::Output;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        #[cfg(feature = "std")]
+        let coop = ready!(coop::poll_proceed(cx));
+
         let len = self.len();
 
         // Keep track of how many child futures we have polled,
@@ -538,7 +544,11 @@ impl<Fut: Future> Stream for FuturesUnordered<Fut> {
                     }
                     continue;
                 }
-                Poll::Ready(output) => return Poll::Ready(Some(output)),
+                Poll::Ready(output) => {
+                    #[cfg(feature = "std")]
+                    coop.made_progress();
+                    return Poll::Ready(Some(output));
+                }
             }
         }
     }
@@ -0,0 +1,268 @@
+//! Merging of sorted streams
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+
+use super::assert_stream;
+use crate::stream::StreamExt;
+
+/// Stream for the [`kmerge_by`] function.
+#[must_use = "streams do nothing unless polled"]
+pub struct KMergeBy<St: Stream, F> {
+    // Streams are dropped once they end.
+    streams: Vec<Option<St>>,
+    // The indices of the streams whose next item is needed before the
+    // smallest item is known.
+    needed: Vec<usize>,
+    // A binary heap of the next item of each stream, with the index of the
+    // stream, ordered by `first`.
+    heap: Vec<(St::Item, usize)>,
+    first: F,
+}
+
+impl<St: Stream, F> Unpin for KMergeBy<St, F> {}
+
+impl<St, F> fmt::Debug for KMergeBy<St, F>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KMergeBy")
+            .field("streams", &self.streams)
+            .field("needed", &self.needed)
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
+impl<St, F> KMergeBy<St, F>
+where
+    St: Stream + Unpin,
+    F: FnMut(&St::Item, &St::Item) -> bool,
+{
+    fn new(streams: Vec<Option<St>>, first: F) -> Self {
+        let needed = (0..streams.len()).collect();
+        let heap = Vec::with_capacity(streams.len());
+        Self { streams, needed, heap, first }
+    }
+
+    // Items which are equal by `first` are ordered by the index of their
+    // stream, so the merge is stable.
+    fn is_before(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (&self.heap[a], &self.heap[b]);
+        if (self.first)(&a.0, &b.0) {
+            true
+        } else if (self.first)(&b.0, &a.0) {
+            false
+        } else {
+            a.1 < b.1
+        }
+    }
+
+    fn push(&mut self, item: St::Item, index: usize) {
+        self.heap.push((item, index));
+        let mut child = self.heap.len() - 1;
+        while child > 0 {
+            let parent = (child - 1) / 2;
+            if !self.is_before(child, parent) {
+                break;
+            }
+            self.heap.swap(child, parent);
+            child = parent;
+        }
+    }
+
+    fn pop(&mut self) -> Option<(St::Item, usize)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let top = self.heap.swap_remove(0);
+        let mut parent = 0;
+        loop {
+            let mut smallest = parent;
+            for child in [2 * parent + 1, 2 * parent + 2].iter().copied() {
+                if child < self.heap.len() && self.is_before(child, smallest) {
+                    smallest = child;
+                }
+            }
+            if smallest == parent {
+                break;
+            }
+            self.heap.swap(parent, smallest);
+            parent = smallest;
+        }
+        Some(top)
+    }
+}
+
+impl<St, F> Stream for KMergeBy<St, F>
+where
+    St: Stream + Unpin,
+    F: FnMut(&St::Item, &St::Item) -> bool,
+{
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        // Only the streams without an item in the heap are polled. The
+        // smallest item isn't known until all of them yielded one.
+        let mut i = 0;
+        while i < this.needed.len() {
+            let index = this.needed[i];
+            let stream = this.streams[index].as_mut().unwrap();
+            match stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.needed.swap_remove(i);
+                    this.push(item, index);
+                }
+                Poll::Ready(None) => {
+                    this.needed.swap_remove(i);
+                    this.streams[index] = None;
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+        if !this.needed.is_empty() {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(this.pop().map(|(item, index)| {
+            this.needed.push(index);
+            item
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut lower = self.heap.len();
+        let mut upper = Some(self.heap.len());
+        for stream in self.streams.iter().flatten() {
+            let (l, u) = stream.size_hint();
+            lower = lower.saturating_add(l);
+            upper = match (upper, u) {
+                (Some(x), Some(y)) => x.checked_add(y),
+                _ => None,
+            };
+        }
+        (lower, upper)
+    }
+}
+
+impl<St, F> FusedStream for KMergeBy<St, F>
+where
+    St: Stream + Unpin,
+    F: FnMut(&St::Item, &St::Item) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.needed.is_empty() && self.heap.is_empty()
+    }
+}
+
+type Lt<T> = fn(&T, &T) -> bool;
+
+/// Stream for the [`kmerge`] function.
+#[must_use = "streams do nothing unless polled"]
+pub struct KMerge<St: Stream> {
+    inner: KMergeBy<St, Lt<St::Item>>,
+}
+
+impl<St> fmt::Debug for KMerge<St>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KMerge").field("inner", &self.inner).finish()
+    }
+}
+
+impl<St: Stream + Unpin> Stream for KMerge<St> {
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<St: Stream + Unpin> FusedStream for KMerge<St> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
+
+/// Merges streams which are sorted in ascending order into a single stream
+/// sorted in ascending order.
+///
+/// The next item is yielded once the next item of every stream which hasn't
+/// ended is known, so only the stream which yielded the last item is pulled
+/// from. Items which are equal are yielded in the order of their streams.
+///
+/// This function is only available when the `std` or `alloc` feature of this
+/// library is activated, and it is activated by default.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::stream::{self, StreamExt};
+///
+/// let merged = stream::kmerge(vec![
+///     stream::iter(vec![1, 4, 7]),
+///     stream::iter(vec![2, 5]),
+///     stream::iter(vec![3, 6, 9]),
+/// ]);
+///
+/// assert_eq!(merged.collect::<Vec<_>>().await, vec![1, 2, 3, 4, 5, 6, 7, 9]);
+/// # });
+/// ```
+pub fn kmerge<I>(streams: I) -> KMerge<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Stream + Unpin,
+    <I::Item as Stream>::Item: PartialOrd,
+{
+    let streams = streams.into_iter().map(Some).collect();
+    let first: Lt<_> = PartialOrd::lt;
+    assert_stream::<<I::Item as Stream>::Item, _>(KMerge { inner: KMergeBy::new(streams, first) })
+}
+
+/// Merges streams which are sorted by `first` into a single stream sorted by
+/// `first`.
+///
+/// This is like [`kmerge`], except that `first(a, b)` tells whether item `a`
+/// should be yielded before item `b`.
+///
+/// This function is only available when the `std` or `alloc` feature of this
+/// library is activated, and it is activated by default.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::stream::{self, StreamExt};
+///
+/// let merged = stream::kmerge_by(
+///     vec![stream::iter(vec![7, 4, 1]), stream::iter(vec![6, 5])],
+///     |a: &i32, b: &i32| a > b,
+/// );
+///
+/// assert_eq!(merged.collect::<Vec<_>>().await, vec![7, 6, 5, 4, 1]);
+/// # });
+/// ```
+pub fn kmerge_by<I, F>(streams: I, first: F) -> KMergeBy<I::Item, F>
+where
+    I: IntoIterator,
+    I::Item: Stream + Unpin,
+    F: FnMut(&<I::Item as Stream>::Item, &<I::Item as Stream>::Item) -> bool,
+{
+    let streams = streams.into_iter().map(Some).collect();
+    assert_stream::<<I::Item as Stream>::Item, _>(KMergeBy::new(streams, first))
+}
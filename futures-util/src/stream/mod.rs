@@ -13,25 +13,71 @@
 pub use futures_core::stream::{BoxStream, LocalBoxStream};
 pub use futures_core::stream::{FusedStream, Stream, TryStream};
 
+/// Derives [`FusedStream`](trait@FusedStream) for a struct wrapping a stream,
+/// by delegating [`is_terminated`](FusedStream::is_terminated) to one of its
+/// fields.
+///
+/// The field is the one marked with `#[fused(delegate)]`, which can be
+/// omitted if the struct has a single field. The generated implementation
+/// requires the type of this field to implement `FusedStream`, and the
+/// struct itself to implement [`Stream`].
+///
+/// The generated code refers to the `futures` crate; crates depending on
+/// `futures-util` instead can set the path to use with
+/// `#[fused(crate = "futures_util")]` on the struct.
+///
+/// # Examples
+///
+/// ```
+/// use futures::stream::{self, FusedStream, Stream, StreamExt};
+/// use futures::task::{Context, Poll};
+/// use std::pin::Pin;
+///
+/// #[derive(FusedStream)]
+/// struct Doubled<St>(St);
+///
+/// impl<St: Stream<Item = u32> + Unpin> Stream for Doubled<St> {
+///     type Item = u32;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+///         self.0.poll_next_unpin(cx).map(|item| item.map(|x| x * 2))
+///     }
+/// }
+///
+/// # futures::executor::block_on(async {
+/// let mut doubled = Doubled(stream::iter(vec![1, 2]).fuse());
+/// assert_eq!(doubled.next().await, Some(2));
+/// assert_eq!(doubled.next().await, Some(4));
+/// assert_eq!(doubled.next().await, None);
+/// assert!(doubled.is_terminated());
+/// # });
+/// ```
+#[cfg(feature = "async-await-macro")]
+pub use futures_macro::FusedStream;
+
 // Extension traits and combinators
 
 #[allow(clippy::module_inception)]
 mod stream;
 pub use self::stream::{
-    All, Any, Chain, Collect, Concat, Count, Cycle, Enumerate, Filter, FilterMap, FlatMap, Flatten,
-    Fold, ForEach, Fuse, Inspect, Map, Next, NextIf, NextIfEq, Peek, PeekMut, Peekable, Scan,
-    SelectNextSome, Skip, SkipWhile, StreamExt, StreamFuture, Take, TakeUntil, TakeWhile, Then,
-    TryFold, TryForEach, Unzip, Zip,
+    All, Any, Chain, Collect, CollectInto, Concat, Count, Cycle, Dedup, DedupByKey, EitherOrBoth,
+    Enumerate, Filter, FilterMap, FlatMap, Flatten, Fold, ForEach, Fuse, Inspect, Map, MapWhile,
+    Next, NextIf, NextIfEq, Peek, PeekMut, Peekable, Scan, SelectNextSome, Skip, SkipWhile,
+    StreamExt, StreamFuture, SwitchMap, Take, TakeUntil, TakeWhile, Then, TryFold, TryForEach,
+    Unzip, Zip, ZipLongest,
 };
 
 #[cfg(feature = "std")]
-pub use self::stream::CatchUnwind;
+pub use self::stream::{
+    CatchUnwind, ChunksTimeout, Debounce, Elapsed, FlatMapUnorderedKeyed, Group, GroupBy,
+    LagPolicy, Partition, RateLimit, Share, TakeUntilCancelled, Tee, Throttle, Timeout,
+};
 
 #[cfg(feature = "alloc")]
-pub use self::stream::Chunks;
+pub use self::stream::{ChunkBy, Chunks, NextNIf, PeekN};
 
 #[cfg(feature = "alloc")]
-pub use self::stream::ReadyChunks;
+pub use self::stream::{ReadyChunks, Windows};
 
 #[cfg(feature = "sink")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sink")))]
@@ -40,8 +86,8 @@ pub use self::stream::Forward;
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 pub use self::stream::{
-    BufferUnordered, Buffered, FlatMapUnordered, FlattenUnordered, ForEachConcurrent,
-    TryForEachConcurrent,
+    BufferUnordered, BufferUnorderedIndexed, Buffered, ConcurrencyLimit, FlatMapUnordered,
+    FlattenUnordered, ForEachConcurrent, TryForEachConcurrent,
 };
 
 #[cfg(not(futures_no_atomic_cas))]
@@ -53,8 +99,8 @@ pub use self::stream::{ReuniteError, SplitSink, SplitStream};
 mod try_stream;
 pub use self::try_stream::{
     try_unfold, AndThen, ErrInto, InspectErr, InspectOk, IntoStream, MapErr, MapOk, OrElse,
-    TryCollect, TryConcat, TryFilter, TryFilterMap, TryFlatten, TryNext, TrySkipWhile,
-    TryStreamExt, TryTakeWhile, TryUnfold,
+    TryCollect, TryCollectInto, TryConcat, TryFilter, TryFilterMap, TryFlatten, TryNext,
+    TrySkipWhile, TryStreamExt, TryTakeWhile, TryUnfold,
 };
 
 #[cfg(feature = "io")]
@@ -131,6 +177,11 @@ pub mod select_all;
 #[doc(inline)]
 pub use self::select_all::{select_all, SelectAll};
 
+#[cfg(feature = "alloc")]
+mod kmerge;
+#[cfg(feature = "alloc")]
+pub use self::kmerge::{kmerge, kmerge_by, KMerge, KMergeBy};
+
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 mod abortable;
@@ -141,6 +192,9 @@ pub use crate::abortable::{AbortHandle, AbortRegistration, Abortable, Aborted};
 #[cfg(feature = "alloc")]
 pub use abortable::abortable;
 
+#[cfg(feature = "std")]
+pub use crate::cancellation_token::CancellationToken;
+
 // Just a helper function to ensure the streams we're returning all have the
 // right implementations.
 pub(crate) fn assert_stream<T, S>(stream: S) -> S
@@ -1,6 +1,5 @@
-use crate::stream::{Fuse, FuturesUnordered, StreamExt};
+use crate::stream::{ConcurrencyLimit, Fuse, FuturesUnordered, StreamExt};
 use core::fmt;
-use core::num::NonZeroUsize;
 use core::pin::Pin;
 use futures_core::future::Future;
 use futures_core::stream::{FusedStream, Stream};
@@ -20,7 +19,7 @@ pin_project! {
         #[pin]
         stream: Fuse<St>,
         in_progress_queue: FuturesUnordered<St::Item>,
-        max: Option<NonZeroUsize>,
+        limit: ConcurrencyLimit,
     }
 }
 
@@ -32,7 +31,7 @@ where
         f.debug_struct("BufferUnordered")
             .field("stream", &self.stream)
             .field("in_progress_queue", &self.in_progress_queue)
-            .field("max", &self.max)
+            .field("limit", &self.limit)
             .finish()
     }
 }
@@ -46,10 +45,16 @@ where
         Self {
             stream: super::Fuse::new(stream),
             in_progress_queue: FuturesUnordered::new(),
-            max: n.and_then(NonZeroUsize::new),
+            limit: ConcurrencyLimit::new(n),
         }
     }
 
+    /// Returns a handle to the concurrency limit of this stream, which
+    /// changes it while this stream runs.
+    pub fn concurrency_limit(&self) -> ConcurrencyLimit {
+        self.limit.clone()
+    }
+
     delegate_access_inner!(stream, St, (.));
 }
 
@@ -64,8 +69,10 @@ where
         let mut this = self.project();
 
         // First up, try to spawn off as many futures as possible by filling up
-        // our queue of futures.
-        while this.max.map(|max| this.in_progress_queue.len() < max.get()).unwrap_or(true) {
+        // our queue of futures. The limit may be raised while we wait.
+        this.limit.register(cx.waker());
+        let max = this.limit.max();
+        while max.map(|max| this.in_progress_queue.len() < max.get()).unwrap_or(true) {
             match this.stream.as_mut().poll_next(cx) {
                 Poll::Ready(Some(fut)) => this.in_progress_queue.push(fut),
                 Poll::Ready(None) | Poll::Pending => break,
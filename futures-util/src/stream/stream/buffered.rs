@@ -1,6 +1,5 @@
-use crate::stream::{Fuse, FusedStream, FuturesOrdered, StreamExt};
+use crate::stream::{ConcurrencyLimit, Fuse, FusedStream, FuturesOrdered, StreamExt};
 use core::fmt;
-use core::num::NonZeroUsize;
 use core::pin::Pin;
 use futures_core::future::Future;
 use futures_core::ready;
@@ -21,7 +20,7 @@ pin_project! {
         #[pin]
         stream: Fuse<St>,
         in_progress_queue: FuturesOrdered<St::Item>,
-        max: Option<NonZeroUsize>,
+        limit: ConcurrencyLimit,
     }
 }
 
@@ -34,7 +33,7 @@ where
         f.debug_struct("Buffered")
             .field("stream", &self.stream)
             .field("in_progress_queue", &self.in_progress_queue)
-            .field("max", &self.max)
+            .field("limit", &self.limit)
             .finish()
     }
 }
@@ -48,10 +47,16 @@ where
         Self {
             stream: super::Fuse::new(stream),
             in_progress_queue: FuturesOrdered::new(),
-            max: n.and_then(NonZeroUsize::new),
+            limit: ConcurrencyLimit::new(n),
         }
     }
 
+    /// Returns a handle to the concurrency limit of this stream, which
+    /// changes it while this stream runs.
+    pub fn concurrency_limit(&self) -> ConcurrencyLimit {
+        self.limit.clone()
+    }
+
     delegate_access_inner!(stream, St, (.));
 }
 
@@ -66,8 +71,10 @@ where
         let mut this = self.project();
 
         // First up, try to spawn off as many futures as possible by filling up
-        // our queue of futures.
-        while this.max.map(|max| this.in_progress_queue.len() < max.get()).unwrap_or(true) {
+        // our queue of futures. The limit may be raised while we wait.
+        this.limit.register(cx.waker());
+        let max = this.limit.max();
+        while max.map(|max| this.in_progress_queue.len() < max.get()).unwrap_or(true) {
             match this.stream.as_mut().poll_next(cx) {
                 Poll::Ready(Some(fut)) => this.in_progress_queue.push_back(fut),
                 Poll::Ready(None) | Poll::Pending => break,
@@ -0,0 +1,122 @@
+use crate::stream::Fuse;
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for the [`chunk_by`](super::StreamExt::chunk_by) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct ChunkBy<St: Stream, F, K> {
+        #[pin]
+        stream: Fuse<St>,
+        f: F,
+        // The key of the run being collected into `items`.
+        key: Option<K>,
+        items: Vec<St::Item>,
+    }
+}
+
+impl<St, F, K> fmt::Debug for ChunkBy<St, F, K>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkBy")
+            .field("stream", &self.stream)
+            .field("key", &self.key)
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+impl<St, F, K> ChunkBy<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq,
+{
+    pub(super) fn new(stream: St, f: F) -> Self {
+        Self { stream: super::Fuse::new(stream), f, key: None, items: Vec::new() }
+    }
+
+    delegate_access_inner!(stream, St, (.));
+}
+
+impl<St, F, K> Stream for ChunkBy<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq,
+{
+    type Item = (K, Vec<St::Item>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let key = (this.f)(&item);
+                    if this.key.as_ref().map_or(true, |k| *k == key) {
+                        *this.key = Some(key);
+                        this.items.push(item);
+                        continue;
+                    }
+
+                    // The item starts a new run, so it is kept as the first
+                    // item of the next chunk while the finished one is
+                    // returned.
+                    let items = mem::take(this.items);
+                    this.items.push(item);
+                    let key = this.key.replace(key).unwrap();
+                    return Poll::Ready(Some((key, items)));
+                }
+
+                None => {
+                    return Poll::Ready(this.key.take().map(|key| (key, mem::take(this.items))));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let chunk_len = usize::from(self.key.is_some());
+        let (lower, upper) = self.stream.size_hint();
+        let lower = usize::from(lower > 0 || chunk_len > 0);
+        let upper = match upper {
+            Some(x) => x.checked_add(chunk_len),
+            None => None,
+        };
+        (lower, upper)
+    }
+}
+
+impl<St, F, K> FusedStream for ChunkBy<St, F, K>
+where
+    St: FusedStream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.key.is_none()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, F, K, Item> Sink<Item> for ChunkBy<S, F, K>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
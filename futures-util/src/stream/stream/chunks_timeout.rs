@@ -0,0 +1,136 @@
+use crate::stream::Fuse;
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use futures_task::{Sleep, Timer};
+use pin_project_lite::pin_project;
+use std::time::Duration;
+
+pin_project! {
+    /// Stream for the [`chunks_timeout`](super::StreamExt::chunks_timeout) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct ChunksTimeout<St: Stream, T> {
+        #[pin]
+        stream: Fuse<St>,
+        timer: T,
+        duration: Duration,
+        items: Vec<St::Item>,
+        cap: usize,
+        // Started when the first item of a chunk is buffered.
+        sleep: Option<Sleep>,
+    }
+}
+
+impl<St, T> fmt::Debug for ChunksTimeout<St, T>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunksTimeout")
+            .field("stream", &self.stream)
+            .field("duration", &self.duration)
+            .field("items", &self.items)
+            .field("cap", &self.cap)
+            .finish()
+    }
+}
+
+impl<St: Stream, T: Timer> ChunksTimeout<St, T> {
+    pub(super) fn new(stream: St, capacity: usize, duration: Duration, timer: T) -> Self {
+        assert!(capacity > 0);
+
+        Self {
+            stream: super::Fuse::new(stream),
+            timer,
+            duration,
+            items: Vec::with_capacity(capacity),
+            cap: capacity,
+            sleep: None,
+        }
+    }
+
+    delegate_access_inner!(stream, St, (.));
+}
+
+impl<St: Stream, T: Timer> Stream for ChunksTimeout<St, T> {
+    type Item = Vec<St::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if this.items.is_empty() {
+                        *this.sleep = Some(this.timer.sleep(*this.duration));
+                    }
+                    this.items.push(item);
+                    if this.items.len() >= *this.cap {
+                        *this.sleep = None;
+                        let cap = *this.cap;
+                        return Poll::Ready(Some(mem::replace(
+                            this.items,
+                            Vec::with_capacity(cap),
+                        )));
+                    }
+                }
+
+                // Since the underlying stream ran out of values, return what we
+                // have buffered, if we have anything.
+                Poll::Ready(None) => {
+                    *this.sleep = None;
+                    let last =
+                        if this.items.is_empty() { None } else { Some(mem::take(this.items)) };
+                    return Poll::Ready(last);
+                }
+
+                Poll::Pending => break,
+            }
+        }
+
+        // Return a partial chunk once the timeout since its first item expires.
+        match this.sleep.as_mut() {
+            Some(sleep) => {
+                ready!(sleep.as_mut().poll(cx));
+                *this.sleep = None;
+                let cap = *this.cap;
+                Poll::Ready(Some(mem::replace(this.items, Vec::with_capacity(cap))))
+            }
+            None => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let chunk_len = usize::from(!self.items.is_empty());
+        let (lower, upper) = self.stream.size_hint();
+        let lower = usize::from(lower > 0 || chunk_len > 0);
+        let upper = match upper {
+            Some(x) => x.checked_add(chunk_len),
+            None => None,
+        };
+        (lower, upper)
+    }
+}
+
+impl<St: Stream, T: Timer> FusedStream for ChunksTimeout<St, T> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.items.is_empty()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, T, Item> Sink<Item> for ChunksTimeout<S, T>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
@@ -0,0 +1,52 @@
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::stream::Stream;
+use futures_core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for the [`collect_into`](super::StreamExt::collect_into) method.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct CollectInto<'a, St, C> {
+        #[pin]
+        stream: St,
+        collection: Option<&'a mut C>,
+    }
+}
+
+impl<'a, St: Stream, C> CollectInto<'a, St, C> {
+    pub(super) fn new(stream: St, collection: &'a mut C) -> Self {
+        Self { stream, collection: Some(collection) }
+    }
+}
+
+impl<St, C> FusedFuture for CollectInto<'_, St, C>
+where
+    St: Stream,
+    C: Extend<St::Item>,
+{
+    fn is_terminated(&self) -> bool {
+        self.collection.is_none()
+    }
+}
+
+impl<'a, St, C> Future for CollectInto<'a, St, C>
+where
+    St: Stream,
+    C: Extend<St::Item>,
+{
+    type Output = &'a mut C;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let collection = this.collection.as_mut().expect("CollectInto polled after completion");
+        loop {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(e) => collection.extend(Some(e)),
+                None => return Poll::Ready(this.collection.take().unwrap()),
+            }
+        }
+    }
+}
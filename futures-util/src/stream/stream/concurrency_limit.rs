@@ -0,0 +1,67 @@
+use crate::task::AtomicWaker;
+use alloc::sync::Arc;
+use core::fmt;
+use core::num::NonZeroUsize;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use futures_core::task::Waker;
+
+/// A handle to the concurrency limit of a [`Buffered`](super::Buffered) or
+/// [`BufferUnordered`](super::BufferUnordered) stream, which changes it while
+/// the stream runs.
+///
+/// A handle is returned by the `concurrency_limit` method of these streams,
+/// and can be cloned.
+#[derive(Clone)]
+pub struct ConcurrencyLimit {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    // Zero for no limit.
+    max: AtomicUsize,
+    waker: AtomicWaker,
+}
+
+impl fmt::Debug for ConcurrencyLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrencyLimit").field("max", &self.get()).finish()
+    }
+}
+
+impl ConcurrencyLimit {
+    pub(super) fn new(n: Option<usize>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                max: AtomicUsize::new(n.unwrap_or(0)),
+                waker: AtomicWaker::new(),
+            }),
+        }
+    }
+
+    /// Returns the maximum number of futures run at once, or `None` if there
+    /// is no limit.
+    pub fn get(&self) -> Option<usize> {
+        self.max().map(NonZeroUsize::get)
+    }
+
+    /// Sets the maximum number of futures run at once.
+    ///
+    /// As with the limit given when creating the stream, a limit of zero is
+    /// interpreted as no limit at all, like `None`.
+    ///
+    /// Raising the limit wakes the stream so that it starts more futures.
+    /// Lowering it doesn't cancel any running future: new futures are only
+    /// started once enough of them have completed.
+    pub fn set(&self, n: impl Into<Option<usize>>) {
+        self.inner.max.store(n.into().unwrap_or(0), Ordering::Release);
+        self.inner.waker.wake();
+    }
+
+    pub(super) fn max(&self) -> Option<NonZeroUsize> {
+        NonZeroUsize::new(self.inner.max.load(Ordering::Acquire))
+    }
+
+    pub(super) fn register(&self, waker: &Waker) {
+        self.inner.waker.register(waker);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::stream::Fuse;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use futures_task::{Sleep, Timer};
+use pin_project_lite::pin_project;
+use std::time::{Duration, Instant};
+
+pin_project! {
+    /// Stream for the [`debounce`](super::StreamExt::debounce) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct Debounce<St: Stream, T> {
+        #[pin]
+        stream: Fuse<St>,
+        timer: T,
+        duration: Duration,
+        // The latest item, to be yielded once nothing was received for
+        // `duration` after `deadline`.
+        item: Option<St::Item>,
+        deadline: Option<Instant>,
+        // Reused for as long as the deadline keeps moving, so it may complete
+        // before the deadline.
+        sleep: Option<Sleep>,
+    }
+}
+
+impl<St, T> fmt::Debug for Debounce<St, T>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debounce")
+            .field("stream", &self.stream)
+            .field("duration", &self.duration)
+            .field("item", &self.item)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
+
+impl<St: Stream, T: Timer> Debounce<St, T> {
+    pub(super) fn new(stream: St, duration: Duration, timer: T) -> Self {
+        Self {
+            stream: super::Fuse::new(stream),
+            timer,
+            duration,
+            item: None,
+            deadline: None,
+            sleep: None,
+        }
+    }
+
+    delegate_access_inner!(stream, St, (.));
+}
+
+impl<St: Stream, T: Timer> Stream for Debounce<St, T> {
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let deadline = this.timer.now() + *this.duration;
+                    *this.item = Some(item);
+                    *this.deadline = Some(deadline);
+                    if this.sleep.is_none() {
+                        *this.sleep = Some(this.timer.sleep_until(deadline));
+                    }
+                }
+                // The latest item is yielded right away once the underlying
+                // stream ends.
+                Poll::Ready(None) => {
+                    *this.sleep = None;
+                    *this.deadline = None;
+                    return Poll::Ready(this.item.take());
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        // Both the underlying stream and the sleep must have registered the
+        // task before returning `Poll::Pending`.
+        while let Some(sleep) = this.sleep.as_mut() {
+            ready!(sleep.as_mut().poll(cx));
+            let deadline = this.deadline.unwrap();
+            if this.timer.now() < deadline {
+                *this.sleep = Some(this.timer.sleep_until(deadline));
+            } else {
+                *this.sleep = None;
+                *this.deadline = None;
+                return Poll::Ready(this.item.take());
+            }
+        }
+        Poll::Pending
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Any number of the remaining items may be superseded.
+        let item_len = usize::from(self.item.is_some());
+        let (lower, upper) = self.stream.size_hint();
+        let lower = usize::from(lower > 0 || item_len > 0);
+        let upper = match upper {
+            Some(x) => x.checked_add(item_len),
+            None => None,
+        };
+        (lower, upper)
+    }
+}
+
+impl<St: Stream, T: Timer> FusedStream for Debounce<St, T> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.item.is_none()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, T, Item> Sink<Item> for Debounce<S, T>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
@@ -0,0 +1,90 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for the [`dedup`](super::StreamExt::dedup) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct Dedup<St: Stream> {
+        #[pin]
+        stream: St,
+        last: Option<St::Item>,
+    }
+}
+
+impl<St> fmt::Debug for Dedup<St>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dedup").field("stream", &self.stream).field("last", &self.last).finish()
+    }
+}
+
+impl<St> Dedup<St>
+where
+    St: Stream,
+    St::Item: PartialEq + Clone,
+{
+    pub(super) fn new(stream: St) -> Self {
+        Self { stream, last: None }
+    }
+
+    delegate_access_inner!(stream, St, ());
+}
+
+impl<St> Stream for Dedup<St>
+where
+    St: Stream,
+    St::Item: PartialEq + Clone,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        Poll::Ready(loop {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) if this.last.as_ref() == Some(&item) => {}
+                Some(item) => {
+                    *this.last = Some(item.clone());
+                    break Some(item);
+                }
+                None => break None,
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Only the first item is sure to be yielded.
+        let (lower, upper) = self.stream.size_hint();
+        let lower = if self.last.is_some() { 0 } else { usize::from(lower > 0) };
+        (lower, upper)
+    }
+}
+
+impl<St> FusedStream for Dedup<St>
+where
+    St: FusedStream,
+    St::Item: PartialEq + Clone,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, Item> Sink<Item> for Dedup<S>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
@@ -0,0 +1,99 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for the [`dedup_by_key`](super::StreamExt::dedup_by_key) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct DedupByKey<St, F, K> {
+        #[pin]
+        stream: St,
+        f: F,
+        // Only the key of the last item is kept, so items aren't cloned.
+        last: Option<K>,
+    }
+}
+
+impl<St, F, K> fmt::Debug for DedupByKey<St, F, K>
+where
+    St: fmt::Debug,
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedupByKey")
+            .field("stream", &self.stream)
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+impl<St, F, K> DedupByKey<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq,
+{
+    pub(super) fn new(stream: St, f: F) -> Self {
+        Self { stream, f, last: None }
+    }
+
+    delegate_access_inner!(stream, St, ());
+}
+
+impl<St, F, K> Stream for DedupByKey<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        Poll::Ready(loop {
+            let item = match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => item,
+                None => break None,
+            };
+            let key = (this.f)(&item);
+            if this.last.as_ref() != Some(&key) {
+                *this.last = Some(key);
+                break Some(item);
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Only the first item is sure to be yielded.
+        let (lower, upper) = self.stream.size_hint();
+        let lower = if self.last.is_some() { 0 } else { usize::from(lower > 0) };
+        (lower, upper)
+    }
+}
+
+impl<St, F, K> FusedStream for DedupByKey<St, F, K>
+where
+    St: FusedStream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, F, K, Item> Sink<Item> for DedupByKey<S, F, K>
+where
+    S: Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
@@ -0,0 +1,195 @@
+use crate::stream::{Fuse, FuturesUnordered, StreamExt};
+use core::fmt;
+use core::hash::Hash;
+use core::num::NonZeroUsize;
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+use std::collections::{HashMap, VecDeque};
+
+// Resolves to the next item of a substream, along with the substream and its
+// key.
+struct KeyedNext<K, U> {
+    entry: Option<(K, U)>,
+}
+
+// The key is never pinned.
+impl<K, U> Unpin for KeyedNext<K, U> {}
+
+impl<K, U: Stream + Unpin> Future for KeyedNext<K, U> {
+    type Output = (K, U, Option<U::Item>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let item = {
+            let (_, stream) = self.entry.as_mut().expect("polled KeyedNext after completion");
+            ready!(stream.poll_next_unpin(cx))
+        };
+        let (key, stream) = self.entry.take().unwrap();
+        Poll::Ready((key, stream, item))
+    }
+}
+
+pin_project! {
+    /// Stream for the
+    /// [`flat_map_unordered_keyed`](super::StreamExt::flat_map_unordered_keyed)
+    /// method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct FlatMapUnorderedKeyed<St, K, U, G, F> {
+        #[pin]
+        stream: Fuse<St>,
+        key: G,
+        f: F,
+        limit: Option<NonZeroUsize>,
+        per_key_limit: usize,
+        active: FuturesUnordered<KeyedNext<K, U>>,
+        // The number of active substreams of each key, keys without any
+        // being removed.
+        active_per_key: HashMap<K, usize>,
+        // The substreams waiting to be admitted, in the order they were read.
+        queue: VecDeque<(K, U)>,
+    }
+}
+
+impl<St, K, U, G, F> fmt::Debug for FlatMapUnorderedKeyed<St, K, U, G, F>
+where
+    St: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlatMapUnorderedKeyed")
+            .field("stream", &self.stream)
+            .field("limit", &self.limit)
+            .field("per_key_limit", &self.per_key_limit)
+            .field("active", &self.active.len())
+            .field("queued", &self.queue.len())
+            .finish()
+    }
+}
+
+impl<St, K, U, G, F> FlatMapUnorderedKeyed<St, K, U, G, F>
+where
+    St: Stream,
+    K: Hash + Eq + Clone,
+    U: Stream + Unpin,
+    G: FnMut(&St::Item) -> K,
+    F: FnMut(St::Item) -> U,
+{
+    pub(super) fn new(
+        stream: St,
+        limit: Option<usize>,
+        per_key_limit: usize,
+        key: G,
+        f: F,
+    ) -> Self {
+        assert!(per_key_limit > 0);
+
+        Self {
+            stream: super::Fuse::new(stream),
+            key,
+            f,
+            limit: limit.and_then(NonZeroUsize::new),
+            per_key_limit,
+            active: FuturesUnordered::new(),
+            active_per_key: HashMap::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    delegate_access_inner!(stream, St, (.));
+}
+
+impl<St, K, U, G, F> Stream for FlatMapUnorderedKeyed<St, K, U, G, F>
+where
+    St: Stream,
+    K: Hash + Eq + Clone,
+    U: Stream + Unpin,
+    G: FnMut(&St::Item) -> K,
+    F: FnMut(St::Item) -> U,
+{
+    type Item = U::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            // Read substreams from the underlying stream for as long as
+            // fewer than `limit` of them are waiting.
+            while this.limit.map_or(true, |limit| this.queue.len() < limit.get()) {
+                match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(item)) => {
+                        let key = (this.key)(&item);
+                        this.queue.push_back((key, (this.f)(item)));
+                    }
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+
+            // Admit the waiting substreams in order, skipping those whose
+            // key is at its limit.
+            let mut i = 0;
+            while i < this.queue.len()
+                && this.limit.map_or(true, |limit| this.active.len() < limit.get())
+            {
+                let count = this.active_per_key.get(&this.queue[i].0).copied().unwrap_or(0);
+                if count < *this.per_key_limit {
+                    let (key, stream) = this.queue.remove(i).unwrap();
+                    *this.active_per_key.entry(key.clone()).or_insert(0) += 1;
+                    this.active.push(KeyedNext { entry: Some((key, stream)) });
+                } else {
+                    i += 1;
+                }
+            }
+
+            match this.active.poll_next_unpin(cx) {
+                Poll::Ready(Some((key, stream, Some(item)))) => {
+                    this.active.push(KeyedNext { entry: Some((key, stream)) });
+                    return Poll::Ready(Some(item));
+                }
+                // A substream ended, which may let a waiting one in.
+                Poll::Ready(Some((key, _, None))) => {
+                    let count = this.active_per_key.get_mut(&key).unwrap();
+                    *count -= 1;
+                    if *count == 0 {
+                        this.active_per_key.remove(&key);
+                    }
+                }
+                Poll::Ready(None) | Poll::Pending => {
+                    // Waiting substreams only remain while others are active.
+                    return if this.stream.is_done() && this.active.is_empty() {
+                        Poll::Ready(None)
+                    } else {
+                        Poll::Pending
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<St, K, U, G, F> FusedStream for FlatMapUnorderedKeyed<St, K, U, G, F>
+where
+    St: Stream,
+    K: Hash + Eq + Clone,
+    U: Stream + Unpin,
+    G: FnMut(&St::Item) -> K,
+    F: FnMut(St::Item) -> U,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_done() && self.active.is_empty() && self.queue.is_empty()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, K, U, G, F, Item> Sink<Item> for FlatMapUnorderedKeyed<S, K, U, G, F>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
@@ -0,0 +1,186 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::boxed::Box;
+use std::sync::{Arc, Mutex};
+
+const OUTER: usize = 0;
+const GROUP: usize = 1;
+
+struct Inner<St: Stream, F, K> {
+    stream: Pin<Box<St>>,
+    f: F,
+    done: bool,
+    // The key of the current run, and the generation of the group yielding
+    // it. Groups of older generations are finished.
+    key: Option<K>,
+    generation: usize,
+    // An item read by a group that starts the next run.
+    boundary: Option<(K, St::Item)>,
+    // The tasks of the outer stream and the current group waiting on the
+    // underlying stream. Only the task that polled it last is woken by it,
+    // so the other one is woken once it yields an item.
+    wakers: [Option<Waker>; 2],
+}
+
+impl<St: Stream, F, K> Inner<St, F, K> {
+    fn poll_item(&mut self, who: usize, cx: &mut Context<'_>) -> Poll<Option<St::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        match self.stream.as_mut().poll_next(cx) {
+            Poll::Ready(item) => {
+                self.done = item.is_none();
+                if let Some(waker) = self.wakers[1 - who].take() {
+                    waker.wake();
+                }
+                Poll::Ready(item)
+            }
+            Poll::Pending => {
+                self.wakers[who] = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Stream for the [`group_by`](super::StreamExt::group_by) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct GroupBy<St: Stream, F, K> {
+    inner: Option<Arc<Mutex<Inner<St, F, K>>>>,
+}
+
+impl<St: Stream, F, K> fmt::Debug for GroupBy<St, F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupBy").finish()
+    }
+}
+
+impl<St, F, K> GroupBy<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq + Clone,
+{
+    pub(super) fn new(stream: St, f: F) -> Self {
+        let inner = Inner {
+            stream: Box::pin(stream),
+            f,
+            done: false,
+            key: None,
+            generation: 0,
+            boundary: None,
+            wakers: [None, None],
+        };
+        Self { inner: Some(Arc::new(Mutex::new(inner))) }
+    }
+}
+
+impl<St, F, K> Stream for GroupBy<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq + Clone,
+{
+    type Item = (K, Group<St, F, K>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let arc = match &self.inner {
+            Some(arc) => arc.clone(),
+            None => return Poll::Ready(None),
+        };
+        let mut inner = arc.lock().unwrap();
+        loop {
+            let (key, item) = match inner.boundary.take() {
+                Some(boundary) => boundary,
+                None => match ready!(inner.poll_item(OUTER, cx)) {
+                    Some(item) => ((inner.f)(&item), item),
+                    None => {
+                        drop(inner);
+                        self.inner = None;
+                        return Poll::Ready(None);
+                    }
+                },
+            };
+
+            // Skip the rest of the current run if its group wasn't consumed.
+            if inner.key.as_ref() == Some(&key) {
+                continue;
+            }
+
+            inner.key = Some(key.clone());
+            inner.generation = inner.generation.wrapping_add(1);
+            if let Some(waker) = inner.wakers[GROUP].take() {
+                waker.wake();
+            }
+            let group =
+                Group { inner: arc.clone(), generation: inner.generation, first: Some(item) };
+            return Poll::Ready(Some((key, group)));
+        }
+    }
+}
+
+impl<St, F, K> FusedStream for GroupBy<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq + Clone,
+{
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+/// A run of items sharing a key, yielded by [`GroupBy`].
+///
+/// The items are read from the underlying stream as the group is polled.
+/// Once the [`GroupBy`] stream yields the next group, this one ends, skipping
+/// any of its remaining items.
+#[must_use = "streams do nothing unless polled"]
+pub struct Group<St: Stream, F, K> {
+    inner: Arc<Mutex<Inner<St, F, K>>>,
+    generation: usize,
+    first: Option<St::Item>,
+}
+
+// The items are never pinned.
+impl<St: Stream, F, K> Unpin for Group<St, F, K> {}
+
+impl<St: Stream, F, K> fmt::Debug for Group<St, F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Group").field("generation", &self.generation).finish()
+    }
+}
+
+impl<St, F, K> Stream for Group<St, F, K>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> K,
+    K: PartialEq,
+{
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(item) = self.first.take() {
+            return Poll::Ready(Some(item));
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generation != self.generation || inner.boundary.is_some() {
+            return Poll::Ready(None);
+        }
+        let item = match ready!(inner.poll_item(GROUP, cx)) {
+            Some(item) => item,
+            None => return Poll::Ready(None),
+        };
+        let key = (inner.f)(&item);
+        if inner.key.as_ref() == Some(&key) {
+            Poll::Ready(Some(item))
+        } else {
+            inner.boundary = Some((key, item));
+            Poll::Ready(None)
+        }
+    }
+}
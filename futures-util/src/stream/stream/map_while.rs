@@ -0,0 +1,90 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for the [`map_while`](super::StreamExt::map_while) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct MapWhile<St, F> {
+        #[pin]
+        stream: St,
+        f: F,
+        done_taking: bool,
+    }
+}
+
+impl<St, F> fmt::Debug for MapWhile<St, F>
+where
+    St: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapWhile")
+            .field("stream", &self.stream)
+            .field("done_taking", &self.done_taking)
+            .finish()
+    }
+}
+
+impl<St, F> MapWhile<St, F> {
+    pub(super) fn new(stream: St, f: F) -> Self {
+        Self { stream, f, done_taking: false }
+    }
+
+    delegate_access_inner!(stream, St, ());
+}
+
+impl<B, St, F> Stream for MapWhile<St, F>
+where
+    St: Stream,
+    F: FnMut(St::Item) -> Option<B>,
+{
+    type Item = B;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<B>> {
+        if self.done_taking {
+            return Poll::Ready(None);
+        }
+
+        let this = self.project();
+        let item = ready!(this.stream.poll_next(cx)).and_then(this.f);
+        *this.done_taking = item.is_none();
+        Poll::Ready(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done_taking {
+            return (0, Some(0));
+        }
+
+        let (_, upper) = self.stream.size_hint();
+        (0, upper) // can't know a lower bound, due to the closure
+    }
+}
+
+// The underlying stream isn't polled once it ended, so this stream is fused
+// whether or not it is.
+impl<B, St, F> FusedStream for MapWhile<St, F>
+where
+    St: Stream,
+    F: FnMut(St::Item) -> Option<B>,
+{
+    fn is_terminated(&self) -> bool {
+        self.done_taking
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, F, Item> Sink<Item> for MapWhile<S, F>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
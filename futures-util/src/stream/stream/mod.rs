@@ -19,6 +19,12 @@ use futures_core::{
 };
 #[cfg(feature = "sink")]
 use futures_sink::Sink;
+#[cfg(feature = "std")]
+use futures_task::Timer;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+#[cfg(feature = "std")]
+use std::time::Duration;
 
 use crate::fns::{inspect_fn, InspectFn};
 
@@ -30,6 +36,10 @@ mod collect;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::collect::Collect;
 
+mod collect_into;
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::collect_into::CollectInto;
+
 mod unzip;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::unzip::Unzip;
@@ -54,6 +64,14 @@ mod filter;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::filter::Filter;
 
+mod dedup;
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::dedup::Dedup;
+
+mod dedup_by_key;
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::dedup_by_key::DedupByKey;
+
 mod filter_map;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::filter_map::FilterMap;
@@ -134,6 +152,9 @@ pub use self::select_next_some::SelectNextSome;
 mod peek;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::peek::{NextIf, NextIfEq, Peek, PeekMut, Peekable};
+#[cfg(feature = "alloc")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::peek::{NextNIf, PeekN};
 
 mod skip;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
@@ -151,6 +172,10 @@ mod take_while;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::take_while::TakeWhile;
 
+mod map_while;
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::map_while::MapWhile;
+
 mod take_until;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::take_until::TakeUntil;
@@ -159,6 +184,10 @@ mod then;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::then::Then;
 
+mod switch_map;
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::switch_map::SwitchMap;
+
 mod try_for_each;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::try_for_each::TryForEach;
@@ -171,12 +200,28 @@ mod zip;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::zip::Zip;
 
+mod zip_longest;
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::zip_longest::{EitherOrBoth, ZipLongest};
+
 #[cfg(feature = "alloc")]
 mod chunks;
 #[cfg(feature = "alloc")]
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::chunks::Chunks;
 
+#[cfg(feature = "alloc")]
+mod chunk_by;
+#[cfg(feature = "alloc")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::chunk_by::ChunkBy;
+
+#[cfg(feature = "alloc")]
+mod windows;
+#[cfg(feature = "alloc")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::windows::Windows;
+
 #[cfg(feature = "alloc")]
 mod ready_chunks;
 #[cfg(feature = "alloc")]
@@ -187,6 +232,14 @@ mod scan;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::scan::Scan;
 
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+mod concurrency_limit;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::concurrency_limit::ConcurrencyLimit;
+
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 mod buffer_unordered;
@@ -195,6 +248,14 @@ mod buffer_unordered;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::buffer_unordered::BufferUnordered;
 
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+mod buffer_unordered_indexed;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::buffer_unordered_indexed::BufferUnorderedIndexed;
+
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
 mod buffered;
@@ -256,6 +317,75 @@ mod catch_unwind;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::catch_unwind::CatchUnwind;
 
+#[cfg(feature = "std")]
+mod chunks_timeout;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::chunks_timeout::ChunksTimeout;
+
+#[cfg(feature = "std")]
+mod debounce;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::debounce::Debounce;
+
+#[cfg(feature = "std")]
+mod throttle;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::throttle::Throttle;
+
+#[cfg(feature = "std")]
+mod rate_limit;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::rate_limit::RateLimit;
+
+#[cfg(feature = "std")]
+mod timeout;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::timeout::{Elapsed, Timeout};
+
+#[cfg(feature = "std")]
+mod partition;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::partition::Partition;
+
+#[cfg(feature = "std")]
+mod share;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::share::{LagPolicy, Share};
+
+#[cfg(feature = "std")]
+mod tee;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::tee::Tee;
+
+#[cfg(feature = "std")]
+mod flat_map_unordered_keyed;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::flat_map_unordered_keyed::FlatMapUnorderedKeyed;
+
+#[cfg(feature = "std")]
+mod group_by;
+#[cfg(feature = "std")]
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::group_by::{Group, GroupBy};
+
+#[cfg(feature = "std")]
+delegate_all!(
+    /// Stream for the [`take_until_cancelled`](StreamExt::take_until_cancelled) method.
+    TakeUntilCancelled<St>(
+        TakeUntil<St, crate::future::WaitForCancellation>
+    ): Debug + Sink + Stream + FusedStream + AccessInner[St, (.)] + New[|x: St, token: crate::stream::CancellationToken| TakeUntil::new(x, token.cancelled())]
+    where St: Stream
+);
+
 impl<T: ?Sized> StreamExt for T where T: Stream {}
 
 /// An extension trait for `Stream`s that provides a variety of convenient
@@ -437,6 +567,57 @@ pub trait StreamExt: Stream {
         assert_stream::<Self::Item, _>(Filter::new(self, f))
     }
 
+    /// Skips items which are equal to the previous item of the stream.
+    ///
+    /// Like [`Vec::dedup`], only consecutive repeated items are removed. The
+    /// last yielded item is cloned to compare the following ones with it, see
+    /// [`dedup_by_key`](StreamExt::dedup_by_key) to compare by a key instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![1, 1, 2, 2, 2, 1, 3]).dedup();
+    ///
+    /// assert_eq!(vec![1, 2, 1, 3], stream.collect::<Vec<_>>().await);
+    /// # });
+    /// ```
+    fn dedup(self) -> Dedup<Self>
+    where
+        Self::Item: PartialEq + Clone,
+        Self: Sized,
+    {
+        assert_stream::<Self::Item, _>(Dedup::new(self))
+    }
+
+    /// Skips items whose key is equal to the key of the previous item of the
+    /// stream.
+    ///
+    /// The key of each item is computed with `f`. Only the key of the last
+    /// yielded item is kept, so the items don't need to implement `Clone`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![10, 11, 20, 30, 31, 12]).dedup_by_key(|x| x / 10);
+    ///
+    /// assert_eq!(vec![10, 20, 30, 12], stream.collect::<Vec<_>>().await);
+    /// # });
+    /// ```
+    fn dedup_by_key<K, F>(self, f: F) -> DedupByKey<Self, F, K>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+        Self: Sized,
+    {
+        assert_stream::<Self::Item, _>(DedupByKey::new(self, f))
+    }
+
     /// Filters the values produced by this stream while simultaneously mapping
     /// them to a different type according to the provided asynchronous closure.
     ///
@@ -502,6 +683,41 @@ pub trait StreamExt: Stream {
         assert_stream::<Fut::Output, _>(Then::new(self, f))
     }
 
+    /// Computes from this stream's items new items of a different type using
+    /// an asynchronous closure, only running the future of the latest item.
+    ///
+    /// This is like [`then`](StreamExt::then), except that items are pulled
+    /// from the underlying stream while a future is in flight. When a newer
+    /// item arrives, the future of the previous one is dropped before it
+    /// completes, and the future returned by `f` for the new item is run
+    /// instead. This is sometimes called `then_latest`, and is useful for
+    /// search-as-you-type and similar workloads where only the response to
+    /// the most recent request matters.
+    ///
+    /// Note that this function consumes the stream passed into it and returns a
+    /// wrapped version of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(1..=3).switch_map(|x| async move { x * 10 });
+    ///
+    /// // The futures of `1` and `2` are superseded before they are polled.
+    /// assert_eq!(vec![30], stream.collect::<Vec<_>>().await);
+    /// # });
+    /// ```
+    fn switch_map<Fut, F>(self, f: F) -> SwitchMap<Self, Fut, F>
+    where
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future,
+        Self: Sized,
+    {
+        assert_stream::<Fut::Output, _>(SwitchMap::new(self, f))
+    }
+
     /// Transforms a stream into a collection, returning a
     /// future representing the result of that computation.
     ///
@@ -534,6 +750,43 @@ pub trait StreamExt: Stream {
         assert_future::<C, _>(Collect::new(self))
     }
 
+    /// Transforms a stream into a collection by extending an existing one,
+    /// returning a future representing the result of that computation.
+    ///
+    /// Unlike [`collect`](StreamExt::collect), this doesn't create a new
+    /// collection, so that a buffer can be reused, for instance to handle
+    /// several batches of items. The items already in the collection are
+    /// kept.
+    ///
+    /// The returned future will be resolved to the collection when the stream
+    /// terminates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let mut buffer = Vec::with_capacity(3);
+    ///
+    /// for batch in vec![vec![1, 2, 3], vec![4, 5]] {
+    ///     stream::iter(batch).collect_into(&mut buffer).await;
+    ///     assert!(buffer.len() <= 3);
+    ///     buffer.clear();
+    /// }
+    ///
+    /// buffer.push(1);
+    /// let output = stream::iter(2..=3).collect_into(&mut buffer).await;
+    /// assert_eq!(output, &[1, 2, 3]);
+    /// # });
+    /// ```
+    fn collect_into<C: Extend<Self::Item>>(self, collection: &mut C) -> CollectInto<'_, Self, C>
+    where
+        Self: Sized,
+    {
+        assert_future::<&mut C, _>(CollectInto::new(self, collection))
+    }
+
     /// Converts a stream of pairs into a future, which
     /// resolves to pair of containers.
     ///
@@ -573,6 +826,133 @@ pub trait StreamExt: Stream {
         assert_future::<(FromA, FromB), _>(Unzip::new(self))
     }
 
+    /// Splits this stream into two streams, the first one yielding the items
+    /// for which `f` returns `true` and the second one yielding the rest.
+    ///
+    /// Both halves pull items from this stream. An item pulled by one half
+    /// which belongs to the other one is buffered until the other half is
+    /// polled. At most `capacity` items are buffered for each half: once the
+    /// buffer of a half is full, the other half waits for it to be polled,
+    /// so a consumer that ignores one half applies backpressure instead of
+    /// making the buffer grow. Items aren't buffered for a half that was
+    /// dropped.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::future;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let (even, odd) = stream::iter(1..=6).partition(4, |x| x % 2 == 0);
+    ///
+    /// let (even, odd) = future::join(even.collect::<Vec<_>>(), odd.collect::<Vec<_>>()).await;
+    /// assert_eq!(even, vec![2, 4, 6]);
+    /// assert_eq!(odd, vec![1, 3, 5]);
+    /// # });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    #[cfg(feature = "std")]
+    fn partition<F>(self, capacity: usize, f: F) -> (Partition<Self, F>, Partition<Self, F>)
+    where
+        F: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        let (matching, rest) = Partition::new(self, capacity, f);
+        (assert_stream::<Self::Item, _>(matching), assert_stream::<Self::Item, _>(rest))
+    }
+
+    /// Creates a cloneable handle to this stream, where every clone yields
+    /// every item of this stream.
+    ///
+    /// This is the stream counterpart of [`FutureExt::shared`]. A clone
+    /// yields the items read after it was made, starting with the items not
+    /// yet yielded by the handle it was cloned from. The handles read items
+    /// from this stream as they're polled and buffer them for the other
+    /// handles, up to `capacity` items. What happens once a handle falls
+    /// behind the others by `capacity` items is decided by its
+    /// [`LagPolicy`]: by default, the other handles wait for it, but with
+    /// [`LagPolicy::DropOldest`] the lagging handle skips the oldest items
+    /// instead.
+    ///
+    /// [`FutureExt::shared`]: crate::future::FutureExt::shared
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::future;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let first = stream::iter(1..=3).share(2);
+    /// let second = first.clone();
+    ///
+    /// let (first, second) =
+    ///     future::join(first.collect::<Vec<_>>(), second.collect::<Vec<_>>()).await;
+    /// assert_eq!(first, vec![1, 2, 3]);
+    /// assert_eq!(second, vec![1, 2, 3]);
+    /// # });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    #[cfg(feature = "std")]
+    fn share(self, capacity: usize) -> Share<Self>
+    where
+        Self::Item: Clone,
+        Self: Sized,
+    {
+        assert_stream::<Self::Item, _>(Share::new(self, capacity))
+    }
+
+    /// Splits this stream into two streams which both yield a clone of
+    /// every item of this stream.
+    ///
+    /// Both halves pull items from this stream, and an item pulled by one
+    /// half is kept for the other one until it's polled. Only a single item
+    /// is kept, so this stream is pulled at the pace of the slower half: the
+    /// faster one waits for the other one to catch up before reading on.
+    /// Items aren't kept for a half that was dropped. See
+    /// [`share`](StreamExt::share) for a variant with more handles and a
+    /// larger buffer.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::future;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let (left, right) = stream::iter(1..=3).tee();
+    ///
+    /// let (left, right) = future::join(left.collect::<Vec<_>>(), right.collect::<Vec<_>>()).await;
+    /// assert_eq!(left, vec![1, 2, 3]);
+    /// assert_eq!(right, vec![1, 2, 3]);
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    fn tee(self) -> (Tee<Self>, Tee<Self>)
+    where
+        Self::Item: Clone,
+        Self: Sized,
+    {
+        let (left, right) = Tee::new(self);
+        (assert_stream::<Self::Item, _>(left), assert_stream::<Self::Item, _>(right))
+    }
+
     /// Concatenate all items of a stream into a single extendable
     /// destination, returning a future representing the end result.
     ///
@@ -925,16 +1305,21 @@ pub trait StreamExt: Stream {
         assert_stream::<U::Item, _>(FlatMapUnordered::new(self, limit.into(), f))
     }
 
-    /// Combinator similar to [`StreamExt::fold`] that holds internal state
-    /// and produces a new stream.
+    /// Maps a stream like [`StreamExt::flat_map_unordered`], while also
+    /// limiting the number of concurrently polled streams sharing a key.
     ///
-    /// Accepts initial state and closure which will be applied to each element
-    /// of the stream until provided closure returns `None`. Once `None` is
-    /// returned, stream will be terminated.
+    /// The key of each item is computed by `key` before the item is mapped
+    /// to a stream by `f`. No more than `per_key_limit` streams with the same
+    /// key are polled at the same time, in addition to the `limit` on the
+    /// number of streams polled at the same time, which is interpreted as
+    /// by [`StreamExt::flat_map_unordered`].
     ///
-    /// Unlike [`Iterator::scan`], the closure takes the state by value instead of
-    /// mutable reference to avoid [the limitation of the async
-    /// block](https://github.com/rust-lang/futures-rs/issues/2171).
+    /// Streams which can't be polled yet wait in a queue, and are started
+    /// in the order they were produced as earlier streams end. This stream
+    /// isn't polled while `limit` streams are waiting.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
     ///
     /// # Examples
     ///
@@ -942,24 +1327,87 @@ pub trait StreamExt: Stream {
     /// # futures::executor::block_on(async {
     /// use futures::stream::{self, StreamExt};
     ///
-    /// let stream = stream::iter(1..=10);
-    ///
-    /// let stream = stream.scan(0, |mut state, x| async move {
-    ///     state += x;
-    ///     if state < 10 {
-    ///         Some((state, x))
-    ///     } else {
-    ///         None
-    ///     }
-    /// });
+    /// // At most two requests per tenant are handled at once.
+    /// let requests = stream::iter(vec![("a", 1), ("a", 2), ("b", 3), ("a", 4)]);
+    /// let responses = requests.flat_map_unordered_keyed(
+    ///     10,
+    ///     2,
+    ///     |(tenant, _)| *tenant,
+    ///     |(_, id)| stream::iter(vec![id * 10, id * 10 + 1]),
+    /// );
+    /// let mut values = responses.collect::<Vec<_>>().await;
+    /// values.sort();
     ///
-    /// assert_eq!(vec![1, 2, 3], stream.collect::<Vec<_>>().await);
+    /// assert_eq!(vec![10, 11, 20, 21, 30, 31, 40, 41], values);
     /// # });
     /// ```
-    fn scan<S, B, Fut, F>(self, initial_state: S, f: F) -> Scan<Self, S, Fut, F>
-    where
-        F: FnMut(S, Self::Item) -> Fut,
-        Fut: Future<Output = Option<(S, B)>>,
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `per_key_limit` is zero.
+    #[cfg(feature = "std")]
+    fn flat_map_unordered_keyed<K, U, G, F>(
+        self,
+        limit: impl Into<Option<usize>>,
+        per_key_limit: usize,
+        key: G,
+        f: F,
+    ) -> FlatMapUnorderedKeyed<Self, K, U, G, F>
+    where
+        K: Hash + Eq + Clone,
+        U: Stream + Unpin,
+        G: FnMut(&Self::Item) -> K,
+        F: FnMut(Self::Item) -> U,
+        Self: Sized,
+    {
+        assert_stream::<U::Item, _>(FlatMapUnorderedKeyed::new(
+            self,
+            limit.into(),
+            per_key_limit,
+            key,
+            f,
+        ))
+    }
+
+    /// Combinator similar to [`StreamExt::fold`] that holds internal state
+    /// and produces a new stream.
+    ///
+    /// Accepts initial state and closure which will be applied to each element
+    /// of the stream until provided closure returns `None`. Once `None` is
+    /// returned, stream will be terminated.
+    ///
+    /// Unlike [`Iterator::scan`], the closure takes the state by value instead of
+    /// mutable reference to avoid [the limitation of the async
+    /// block](https://github.com/rust-lang/futures-rs/issues/2171).
+    ///
+    /// The closure returns a future resolving to the new state and the item,
+    /// so the state can be carried across awaits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(1..=10);
+    ///
+    /// let stream = stream.scan(0, |mut state, x| async move {
+    ///     state += x;
+    ///     if state < 10 {
+    ///         Some((state, x))
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(vec![1, 2, 3], stream.collect::<Vec<_>>().await);
+    /// # });
+    /// ```
+    #[cfg_attr(docsrs, doc(alias = "scan_async"))]
+    fn scan<S, B, Fut, F>(self, initial_state: S, f: F) -> Scan<Self, S, Fut, F>
+    where
+        F: FnMut(S, Self::Item) -> Fut,
+        Fut: Future<Output = Option<(S, B)>>,
         Self: Sized,
     {
         assert_stream::<B, _>(Scan::new(self, initial_state, f))
@@ -1026,6 +1474,35 @@ pub trait StreamExt: Stream {
         assert_stream::<Self::Item, _>(TakeWhile::new(self, f))
     }
 
+    /// Maps elements of this stream while the provided closure returns
+    /// `Some`.
+    ///
+    /// This function, like `Iterator::map_while`, will yield the values
+    /// returned by `f` until it returns `None`. Once `f` returns `None`, or
+    /// this stream ends, the returned stream will always return that it is
+    /// done, without polling this stream again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec!["1", "2", "three", "4"]);
+    ///
+    /// let stream = stream.map_while(|s| s.parse::<i32>().ok());
+    ///
+    /// assert_eq!(vec![1, 2], stream.collect::<Vec<_>>().await);
+    /// # });
+    /// ```
+    fn map_while<B, F>(self, f: F) -> MapWhile<Self, F>
+    where
+        F: FnMut(Self::Item) -> Option<B>,
+        Self: Sized,
+    {
+        assert_stream::<B, _>(MapWhile::new(self, f))
+    }
+
     /// Take elements from this stream until the provided future resolves.
     ///
     /// This function will take elements from the stream until the provided
@@ -1070,6 +1547,199 @@ pub trait StreamExt: Stream {
         assert_stream::<Self::Item, _>(TakeUntil::new(self, fut))
     }
 
+    /// Yields an item only once no newer item was received for `duration`.
+    ///
+    /// Every item received from this stream restarts the `duration`, and
+    /// replaces the item that was waiting for it to elapse, which is dropped.
+    /// When this stream ends, the last item is yielded right away.
+    ///
+    /// The time is taken from `timer`, so this works with the timers of any
+    /// runtime.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{Stream, StreamExt};
+    /// use futures::task::Timer;
+    /// use std::time::Duration;
+    ///
+    /// // Only search once the user stopped typing.
+    /// fn queries<T: Timer>(
+    ///     input: impl Stream<Item = String>,
+    ///     timer: T,
+    /// ) -> impl Stream<Item = String> {
+    ///     input.debounce(Duration::from_millis(300), timer)
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    fn debounce<T>(self, duration: Duration, timer: T) -> Debounce<Self, T>
+    where
+        T: Timer,
+        Self: Sized,
+    {
+        assert_stream::<Self::Item, _>(Debounce::new(self, duration, timer))
+    }
+
+    /// Yields at most one item per `duration`.
+    ///
+    /// An item is yielded right away if `duration` has elapsed since the
+    /// previous item was yielded. Otherwise it is dropped, so items are never
+    /// delayed.
+    ///
+    /// The time is taken from `timer`, so this works with the timers of any
+    /// runtime.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{Stream, StreamExt};
+    /// use futures::task::Timer;
+    /// use std::time::Duration;
+    ///
+    /// // Handle at most one click per second.
+    /// fn clicks<T: Timer>(
+    ///     input: impl Stream<Item = (i32, i32)>,
+    ///     timer: T,
+    /// ) -> impl Stream<Item = (i32, i32)> {
+    ///     input.throttle(Duration::from_secs(1), timer)
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    fn throttle<T>(self, duration: Duration, timer: T) -> Throttle<Self, T>
+    where
+        T: Timer,
+        Self: Sized,
+    {
+        assert_stream::<Self::Item, _>(Throttle::new(self, duration, timer))
+    }
+
+    /// Limits the rate of this stream to one item per `interval` on
+    /// average, allowing bursts of up to `burst` items.
+    ///
+    /// This is a token bucket holding up to `burst` tokens, which is full at
+    /// first and gains a token every `interval`. Each yielded item takes a
+    /// token, and once the bucket is empty this stream isn't polled until
+    /// the next token is gained, so items are delayed rather than dropped.
+    /// The end of this stream is only noticed once a token is available as
+    /// well.
+    ///
+    /// The time is taken from `timer`, so this works with the timers of any
+    /// runtime.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{Stream, StreamExt};
+    /// use futures::task::Timer;
+    /// use std::time::Duration;
+    ///
+    /// // Send at most 10 requests per second, and at most 5 at once.
+    /// fn requests<T: Timer>(
+    ///     input: impl Stream<Item = String>,
+    ///     timer: T,
+    /// ) -> impl Stream<Item = String> {
+    ///     input.rate_limit(Duration::from_millis(100), 5, timer)
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `burst` is zero.
+    #[cfg(feature = "std")]
+    fn rate_limit<T>(self, interval: Duration, burst: u32, timer: T) -> RateLimit<Self, T>
+    where
+        T: Timer,
+        Self: Sized,
+    {
+        assert_stream::<Self::Item, _>(RateLimit::new(self, interval, burst, timer))
+    }
+
+    /// Yields an error whenever no item was received for `duration`.
+    ///
+    /// Each wait for an item of this stream is given a timeout of
+    /// `duration`, which is reset whenever an item arrives. If it elapses,
+    /// [`Elapsed`] is yielded and the next wait starts with a new timeout, so
+    /// consumers can detect a stalled stream and decide whether to keep
+    /// waiting for it.
+    ///
+    /// The time is taken from `timer`, so this works with the timers of any
+    /// runtime.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{Stream, StreamExt};
+    /// use futures::task::Timer;
+    /// use std::time::Duration;
+    ///
+    /// // Give up on a connection once it stalls.
+    /// async fn read_all<T: Timer>(
+    ///     packets: impl Stream<Item = Vec<u8>>,
+    ///     timer: T,
+    /// ) -> Vec<u8> {
+    ///     let mut packets = Box::pin(packets.timeout(Duration::from_secs(30), timer));
+    ///     let mut data = Vec::new();
+    ///     while let Some(Ok(packet)) = packets.next().await {
+    ///         data.extend(packet);
+    ///     }
+    ///     data
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    fn timeout<T>(self, duration: Duration, timer: T) -> Timeout<Self, T>
+    where
+        T: Timer,
+        Self: Sized,
+    {
+        assert_stream::<Result<Self::Item, Elapsed>, _>(Timeout::new(self, duration, timer))
+    }
+
+    /// Take elements from this stream until `token` is cancelled.
+    ///
+    /// Once the [`CancellationToken`](crate::stream::CancellationToken) is
+    /// cancelled, the returned stream ends and this stream is not polled
+    /// anymore.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, CancellationToken, StreamExt};
+    ///
+    /// let token = CancellationToken::new();
+    /// let mut stream = stream::iter(1..=3).take_until_cancelled(token.clone());
+    ///
+    /// assert_eq!(stream.next().await, Some(1));
+    /// token.cancel();
+    /// assert_eq!(stream.next().await, None);
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    fn take_until_cancelled(
+        self,
+        token: crate::stream::CancellationToken,
+    ) -> TakeUntilCancelled<Self>
+    where
+        Self: Sized,
+    {
+        assert_stream::<Self::Item, _>(TakeUntilCancelled::new(self, token))
+    }
+
     /// Runs this stream to completion, executing the provided asynchronous
     /// closure for each element on the stream.
     ///
@@ -1512,6 +2182,8 @@ pub trait StreamExt: Stream {
     /// The limit argument is of type `Into<Option<usize>>`, and so can be
     /// provided as either `None`, `Some(10)`, or just `10`. Note: a limit of zero is
     /// interpreted as no limit at all, and will have the same result as passing in `None`.
+    /// The limit can be changed while the stream runs, through the handle
+    /// returned by [`Buffered::concurrency_limit`].
     ///
     /// The returned stream will be a stream of each future's output.
     ///
@@ -1538,6 +2210,8 @@ pub trait StreamExt: Stream {
     /// The limit argument is of type `Into<Option<usize>>`, and so can be
     /// provided as either `None`, `Some(10)`, or just `10`. Note: a limit of zero is
     /// interpreted as no limit at all, and will have the same result as passing in `None`.
+    /// The limit can be changed while the stream runs, through the handle
+    /// returned by [`BufferUnordered::concurrency_limit`].
     ///
     /// The returned stream will be a stream of each future's output.
     ///
@@ -1576,6 +2250,52 @@ pub trait StreamExt: Stream {
         assert_stream::<<Self::Item as Future>::Output, _>(BufferUnordered::new(self, n.into()))
     }
 
+    /// An adaptor for creating a buffered list of pending futures (unordered),
+    /// which yields the index of each future with its output.
+    ///
+    /// This is like [`buffer_unordered`](StreamExt::buffer_unordered), except
+    /// that the outputs are paired with the index of their future in this
+    /// stream, starting at zero, so they can be matched with their input
+    /// when they complete out of order.
+    ///
+    /// This method is only available when the `std` or `alloc` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::channel::oneshot;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let (send_one, recv_one) = oneshot::channel();
+    /// let (send_two, recv_two) = oneshot::channel();
+    ///
+    /// let stream_of_futures = stream::iter(vec![recv_one, recv_two]);
+    /// let mut buffered = stream_of_futures.buffer_unordered_indexed(10);
+    ///
+    /// send_two.send(2i32)?;
+    /// assert_eq!(buffered.next().await, Some((1, Ok(2i32))));
+    ///
+    /// send_one.send(1i32)?;
+    /// assert_eq!(buffered.next().await, Some((0, Ok(1i32))));
+    ///
+    /// assert_eq!(buffered.next().await, None);
+    /// # Ok::<(), i32>(()) }).unwrap();
+    /// ```
+    #[cfg(not(futures_no_atomic_cas))]
+    #[cfg(feature = "alloc")]
+    fn buffer_unordered_indexed(self, n: impl Into<Option<usize>>) -> BufferUnorderedIndexed<Self>
+    where
+        Self::Item: Future,
+        Self: Sized,
+    {
+        assert_stream::<(usize, <Self::Item as Future>::Output), _>(BufferUnorderedIndexed::new(
+            self,
+            n.into(),
+        ))
+    }
+
     /// An adapter for zipping two streams together.
     ///
     /// The zipped stream waits for both streams to produce an item, and then
@@ -1606,6 +2326,36 @@ pub trait StreamExt: Stream {
         assert_stream::<(Self::Item, St::Item), _>(Zip::new(self, other))
     }
 
+    /// An adapter for zipping two streams together, until both of them end.
+    ///
+    /// This is like [`zip`](StreamExt::zip), except that once either stream
+    /// ends, the items of the other one are still yielded, alone. Each item
+    /// is an [`EitherOrBoth`] telling which streams it holds an item of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, EitherOrBoth, StreamExt};
+    ///
+    /// let stream1 = stream::iter(1..=3);
+    /// let stream2 = stream::iter(5..=6);
+    ///
+    /// let vec = stream1.zip_longest(stream2).collect::<Vec<_>>().await;
+    /// assert_eq!(
+    ///     vec![EitherOrBoth::Both(1, 5), EitherOrBoth::Both(2, 6), EitherOrBoth::Left(3)],
+    ///     vec
+    /// );
+    /// # });
+    /// ```
+    fn zip_longest<St>(self, other: St) -> ZipLongest<Self, St>
+    where
+        St: Stream,
+        Self: Sized,
+    {
+        assert_stream::<EitherOrBoth<Self::Item, St::Item>, _>(ZipLongest::new(self, other))
+    }
+
     /// Adapter for chaining two streams.
     ///
     /// The resulting stream emits elements from the first stream, and when
@@ -1698,6 +2448,176 @@ pub trait StreamExt: Stream {
         assert_stream::<Vec<Self::Item>, _>(ReadyChunks::new(self, capacity))
     }
 
+    /// An adaptor for yielding overlapping windows of the last `size` items
+    /// of the stream.
+    ///
+    /// Like [`slice::windows`], a window is yielded for every item once `size`
+    /// items were received, containing clones of the last `size` items with
+    /// the oldest one first. If the underlying stream ends before `size` items
+    /// were received, no window is yielded.
+    ///
+    /// This method is only available when the `std` or `alloc` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![1, 2, 3, 4, 5]).windows(3);
+    /// let averages = stream.map(|window| window.iter().sum::<i32>() / 3);
+    ///
+    /// assert_eq!(averages.collect::<Vec<_>>().await, vec![2, 3, 4]);
+    /// # });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `size` is zero.
+    #[cfg(feature = "alloc")]
+    fn windows(self, size: usize) -> Windows<Self>
+    where
+        Self::Item: Clone,
+        Self: Sized,
+    {
+        assert_stream::<Vec<Self::Item>, _>(Windows::new(self, size))
+    }
+
+    /// An adaptor for chunking up items of the stream inside a vector, which
+    /// doesn't hold them back for longer than `duration`.
+    ///
+    /// This is like [`chunks`](StreamExt::chunks), except that a partial
+    /// chunk is yielded once `duration` has passed since its first item was
+    /// buffered, so items aren't held back while this stream is quiet. This is
+    /// useful for batching writes to a database or a socket.
+    ///
+    /// The time is taken from `timer`, so this works with the timers of any
+    /// runtime.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::stream::{Stream, StreamExt};
+    /// use futures::task::Timer;
+    /// use std::time::Duration;
+    ///
+    /// // Write rows in batches of up to 100, at most a second late.
+    /// fn batches<T: Timer>(
+    ///     rows: impl Stream<Item = String>,
+    ///     timer: T,
+    /// ) -> impl Stream<Item = Vec<String>> {
+    ///     rows.chunks_timeout(100, Duration::from_secs(1), timer)
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    #[cfg(feature = "std")]
+    fn chunks_timeout<T>(
+        self,
+        capacity: usize,
+        duration: Duration,
+        timer: T,
+    ) -> ChunksTimeout<Self, T>
+    where
+        T: Timer,
+        Self: Sized,
+    {
+        assert_stream::<Vec<Self::Item>, _>(ChunksTimeout::new(self, capacity, duration, timer))
+    }
+
+    /// An adaptor for grouping runs of consecutive items that share a key.
+    ///
+    /// The key of each item is computed with `f`. Items are buffered into a
+    /// vector for as long as their key is equal to the key of the previous
+    /// item, and the vector is yielded together with that key once an item
+    /// with a different key arrives or the underlying stream ends. Like
+    /// [`Itertools::chunk_by`](https://docs.rs/itertools/latest/itertools/trait.Itertools.html#method.chunk_by),
+    /// only adjacent items are grouped, so the stream should be sorted by the
+    /// key to get a single chunk per key.
+    ///
+    /// See [`group_by`](StreamExt::group_by) for a variant that doesn't buffer
+    /// the runs.
+    ///
+    /// This method is only available when the `std` or `alloc` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![1, 3, 2, 4, 6, 5]).chunk_by(|x| x % 2);
+    ///
+    /// assert_eq!(
+    ///     stream.collect::<Vec<_>>().await,
+    ///     vec![(1, vec![1, 3]), (0, vec![2, 4, 6]), (1, vec![5])],
+    /// );
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn chunk_by<K, F>(self, f: F) -> ChunkBy<Self, F, K>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+        Self: Sized,
+    {
+        assert_stream::<(K, Vec<Self::Item>), _>(ChunkBy::new(self, f))
+    }
+
+    /// An adaptor for streaming runs of consecutive items that share a key.
+    ///
+    /// This is like [`chunk_by`](StreamExt::chunk_by), except that each run is
+    /// yielded as soon as its first item arrives, as a [`Group`] stream
+    /// together with its key. The rest of the run is read from the
+    /// underlying stream as the group is polled, so runs are never buffered.
+    ///
+    /// Only the most recent group yields items: once the next group is
+    /// requested, the previous one ends, and any of its items that weren't
+    /// consumed yet are skipped.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let mut groups = stream::iter(vec![1, 3, 2, 4, 6, 5]).group_by(|x| x % 2);
+    ///
+    /// let (key, group) = groups.next().await.unwrap();
+    /// assert_eq!(key, 1);
+    /// assert_eq!(group.collect::<Vec<_>>().await, vec![1, 3]);
+    ///
+    /// let (key, mut group) = groups.next().await.unwrap();
+    /// assert_eq!(key, 0);
+    /// assert_eq!(group.next().await, Some(2));
+    ///
+    /// // The rest of the group is skipped.
+    /// let (key, group) = groups.next().await.unwrap();
+    /// assert_eq!(key, 1);
+    /// assert_eq!(group.collect::<Vec<_>>().await, vec![5]);
+    /// assert!(groups.next().await.is_none());
+    /// # });
+    /// ```
+    #[cfg(feature = "std")]
+    fn group_by<K, F>(self, f: F) -> GroupBy<Self, F, K>
+    where
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq + Clone,
+        Self: Sized,
+    {
+        assert_stream::<(K, Group<Self, F, K>), _>(GroupBy::new(self, f))
+    }
+
     /// A future that completes after the given stream has been fully processed
     /// into the sink and the sink has been flushed and closed.
     ///
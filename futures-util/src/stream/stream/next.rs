@@ -1,7 +1,10 @@
 use crate::stream::StreamExt;
 use core::pin::Pin;
 use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
 use futures_core::stream::{FusedStream, Stream};
+#[cfg(feature = "std")]
+use futures_core::task::__internal::coop;
 use futures_core::task::{Context, Poll};
 
 /// Future for the [`next`](super::StreamExt::next) method.
@@ -29,6 +32,11 @@ impl<St: ?Sized + Stream + Unpin> Future for Next<'_, St> {
     type Output = Option<St::Item>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        self.stream.poll_next_unpin(cx)
+        #[cfg(feature = "std")]
+        let coop = ready!(coop::poll_proceed(cx));
+        let item = ready!(self.stream.poll_next_unpin(cx));
+        #[cfg(feature = "std")]
+        coop.made_progress();
+        Poll::Ready(item)
     }
 }
@@ -0,0 +1,143 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::boxed::Box;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+struct Inner<St: Stream, F> {
+    stream: Pin<Box<St>>,
+    f: F,
+    done: bool,
+    capacity: usize,
+    // The items read for each half but not yielded yet, the matching ones
+    // first. Items aren't buffered for a dropped half.
+    buffers: [VecDeque<St::Item>; 2],
+    dropped: [bool; 2],
+    // A half waits on the underlying stream, or for the other half to make
+    // room in its buffer. Either way, it's woken once the other half yields
+    // an item, as only the task that polled the stream last is woken by it.
+    wakers: [Option<Waker>; 2],
+}
+
+impl<St: Stream, F> Inner<St, F> {
+    fn wake(&mut self, side: usize) {
+        if let Some(waker) = self.wakers[side].take() {
+            waker.wake();
+        }
+    }
+}
+
+/// One half of the stream returned by the
+/// [`partition`](super::StreamExt::partition) method.
+#[must_use = "streams do nothing unless polled"]
+pub struct Partition<St: Stream, F> {
+    inner: Arc<Mutex<Inner<St, F>>>,
+    // 0 for the matching half, 1 for the other one.
+    side: usize,
+}
+
+impl<St: Stream, F> Unpin for Partition<St, F> {}
+
+impl<St: Stream, F> fmt::Debug for Partition<St, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Partition").field("matching", &(self.side == 0)).finish()
+    }
+}
+
+impl<St, F> Partition<St, F>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> bool,
+{
+    pub(super) fn new(stream: St, capacity: usize, f: F) -> (Self, Self) {
+        assert!(capacity > 0);
+
+        let inner = Arc::new(Mutex::new(Inner {
+            stream: Box::pin(stream),
+            f,
+            done: false,
+            capacity,
+            buffers: [VecDeque::new(), VecDeque::new()],
+            dropped: [false, false],
+            wakers: [None, None],
+        }));
+        (Self { inner: inner.clone(), side: 0 }, Self { inner, side: 1 })
+    }
+}
+
+impl<St, F> Stream for Partition<St, F>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> bool,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (side, other) = (self.side, 1 - self.side);
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(item) = inner.buffers[side].pop_front() {
+            inner.wake(other);
+            return Poll::Ready(Some(item));
+        }
+
+        loop {
+            if inner.done {
+                return Poll::Ready(None);
+            }
+
+            // Reading on would buffer an unbounded number of items for the
+            // other half, so wait for it to catch up.
+            if inner.buffers[other].len() >= inner.capacity {
+                inner.wakers[side] = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+
+            let item = match inner.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => item,
+                Poll::Ready(None) => {
+                    inner.done = true;
+                    inner.wake(other);
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => {
+                    inner.wakers[side] = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            };
+
+            let target = if (inner.f)(&item) { 0 } else { 1 };
+            if target == side {
+                inner.wake(other);
+                return Poll::Ready(Some(item));
+            } else if !inner.dropped[other] {
+                inner.buffers[other].push_back(item);
+                inner.wake(other);
+            }
+        }
+    }
+}
+
+impl<St, F> FusedStream for Partition<St, F>
+where
+    St: Stream,
+    F: FnMut(&St::Item) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.done && inner.buffers[self.side].is_empty()
+    }
+}
+
+impl<St: Stream, F> Drop for Partition<St, F> {
+    fn drop(&mut self) {
+        // The other half may be waiting for room in this half's buffer.
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.dropped[self.side] = true;
+            inner.buffers[self.side].clear();
+            inner.wake(1 - self.side);
+        }
+    }
+}
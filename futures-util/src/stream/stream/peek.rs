@@ -1,8 +1,14 @@
 use crate::fns::FnOnce1;
 use crate::stream::{Fuse, StreamExt};
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::fmt;
 use core::marker::PhantomData;
 use core::pin::Pin;
+#[cfg(feature = "alloc")]
+use core::{cmp, mem, slice};
 use futures_core::future::{FusedFuture, Future};
 use futures_core::ready;
 use futures_core::stream::{FusedStream, Stream};
@@ -22,13 +28,13 @@ pin_project! {
     pub struct Peekable<St: Stream> {
         #[pin]
         stream: Fuse<St>,
-        peeked: Option<St::Item>,
+        peeked: Peeked<St::Item>,
     }
 }
 
 impl<St: Stream> Peekable<St> {
     pub(super) fn new(stream: St) -> Self {
-        Self { stream: stream.fuse(), peeked: None }
+        Self { stream: stream.fuse(), peeked: Peeked::new() }
     }
 
     delegate_access_inner!(stream, St, (.));
@@ -47,10 +53,10 @@ impl<St: Stream> Peekable<St> {
         let mut this = self.project();
 
         Poll::Ready(loop {
-            if this.peeked.is_some() {
-                break this.peeked.as_ref();
+            if !this.peeked.is_empty() {
+                break this.peeked.first();
             } else if let Some(item) = ready!(this.stream.as_mut().poll_next(cx)) {
-                *this.peeked = Some(item);
+                this.peeked.push_back(item);
             } else {
                 break None;
             }
@@ -95,10 +101,10 @@ impl<St: Stream> Peekable<St> {
         let mut this = self.project();
 
         Poll::Ready(loop {
-            if this.peeked.is_some() {
-                break this.peeked.as_mut();
+            if !this.peeked.is_empty() {
+                break this.peeked.first_mut();
             } else if let Some(item) = ready!(this.stream.as_mut().poll_next(cx)) {
-                *this.peeked = Some(item);
+                this.peeked.push_back(item);
             } else {
                 break None;
             }
@@ -184,11 +190,111 @@ impl<St: Stream> Peekable<St> {
             inner: NextIf { inner: Some((self, NextIfEqFn { expected, _next: PhantomData })) },
         }
     }
+
+    /// Produces a future which retrieves a slice of the next `n` items in the
+    /// stream, or of fewer of them if the underlying stream terminates first.
+    ///
+    /// The items are kept until they are returned by the stream, so looking
+    /// further ahead only polls the underlying stream for the missing ones.
+    ///
+    /// This method is only available when the `alloc` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    /// use futures::pin_mut;
+    ///
+    /// let stream = stream::iter(vec![1, 2, 3]).peekable();
+    /// pin_mut!(stream);
+    ///
+    /// assert_eq!(stream.as_mut().peek_n(2).await, &[1, 2]);
+    /// assert_eq!(stream.as_mut().next().await, Some(1));
+    /// // Only two items remain.
+    /// assert_eq!(stream.as_mut().peek_n(5).await, &[2, 3]);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn peek_n(self: Pin<&mut Self>, n: usize) -> PeekN<'_, St> {
+        PeekN { inner: Some(self), n }
+    }
+
+    /// Retrieves a slice of the next `n` items in the stream.
+    ///
+    /// This method polls the underlying stream until it holds `n` items, or
+    /// until the underlying stream terminates, in which case the slice holds
+    /// fewer of them.
+    ///
+    /// This method is only available when the `alloc` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "alloc")]
+    pub fn poll_peek_n(self: Pin<&mut Self>, cx: &mut Context<'_>, n: usize) -> Poll<&[St::Item]> {
+        let mut this = self.project();
+
+        while this.peeked.len() < n {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => this.peeked.push_back(item),
+                None => break,
+            }
+        }
+        let items = this.peeked.as_slice();
+        Poll::Ready(&items[..cmp::min(n, items.len())])
+    }
+
+    /// Returns the items which were already peeked, in the order the stream
+    /// returns them, without polling the underlying stream.
+    ///
+    /// This method is only available when the `alloc` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "alloc")]
+    pub fn peek_slice(&self) -> &[St::Item] {
+        self.peeked.as_slice()
+    }
+
+    /// Creates a future which will consume and return the next `n` values of
+    /// this stream if a condition is true.
+    ///
+    /// If `func` returns `true` for the slice of the next `n` values of this
+    /// stream, consume and return them. Otherwise, return `None` and keep
+    /// them peeked. If the stream terminates before `n` values, `func` isn't
+    /// called and `None` is returned.
+    ///
+    /// This method is only available when the `alloc` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// Consume two tokens if they form an arrow.
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, StreamExt};
+    /// use futures::pin_mut;
+    ///
+    /// let stream = stream::iter(vec!['-', '>', '-', '-']).peekable();
+    /// pin_mut!(stream);
+    /// let is_arrow = |tokens: &[char]| tokens == ['-', '>'];
+    ///
+    /// assert_eq!(stream.as_mut().next_n_if(2, is_arrow).await, Some(vec!['-', '>']));
+    /// // The next tokens aren't an arrow, so they are kept.
+    /// assert_eq!(stream.as_mut().next_n_if(2, is_arrow).await, None);
+    /// assert_eq!(stream.peek_slice(), &['-', '-']);
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn next_n_if<F>(self: Pin<&mut Self>, n: usize, func: F) -> NextNIf<'_, St, F>
+    where
+        F: FnOnce(&[St::Item]) -> bool,
+    {
+        NextNIf { inner: Some((self, func)), n }
+    }
 }
 
 impl<St: Stream> FusedStream for Peekable<St> {
     fn is_terminated(&self) -> bool {
-        self.peeked.is_none() && self.stream.is_terminated()
+        self.peeked.is_empty() && self.stream.is_terminated()
     }
 }
 
@@ -197,14 +303,14 @@ impl<S: Stream> Stream for Peekable<S> {
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.project();
-        if let Some(item) = this.peeked.take() {
+        if let Some(item) = this.peeked.pop_front() {
             return Poll::Ready(Some(item));
         }
         this.stream.poll_next(cx)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let peek_len = usize::from(self.peeked.is_some());
+        let peek_len = self.peeked.len();
         let (lower, upper) = self.stream.size_hint();
         let lower = lower.saturating_add(peek_len);
         let upper = match upper {
@@ -355,13 +461,12 @@ where
             let (peekable, func) = inner.take().unwrap();
             match res {
                 Some(ref matched) if func.call_once(matched) => Poll::Ready(res),
-                other => {
-                    let peekable = peekable.project();
-                    // Since we called `self.next()`, we consumed `self.peeked`.
-                    assert!(peekable.peeked.is_none());
-                    *peekable.peeked = other;
+                Some(item) => {
+                    // Put the item back in front of any other peeked item.
+                    peekable.project().peeked.push_front(item);
                     Poll::Ready(None)
                 }
+                None => Poll::Ready(None),
             }
         } else {
             panic!("NextIf polled after completion")
@@ -369,6 +474,115 @@ where
     }
 }
 
+pin_project! {
+    /// Future for the [`Peekable::peek_n`](self::Peekable::peek_n) method.
+    #[cfg(feature = "alloc")]
+    #[must_use = "futures do nothing unless polled"]
+    pub struct PeekN<'a, St: Stream> {
+        inner: Option<Pin<&'a mut Peekable<St>>>,
+        n: usize,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<St> fmt::Debug for PeekN<'_, St>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PeekN").field("inner", &self.inner).field("n", &self.n).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<St: Stream> FusedFuture for PeekN<'_, St> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, St> Future for PeekN<'a, St>
+where
+    St: Stream,
+{
+    type Output = &'a [St::Item];
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(peekable) = this.inner {
+            ready!(peekable.as_mut().poll_peek_n(cx, *this.n));
+
+            this.inner.take().unwrap().poll_peek_n(cx, *this.n)
+        } else {
+            panic!("PeekN polled after completion")
+        }
+    }
+}
+
+pin_project! {
+    /// Future for the [`Peekable::next_n_if`](self::Peekable::next_n_if) method.
+    #[cfg(feature = "alloc")]
+    #[must_use = "futures do nothing unless polled"]
+    pub struct NextNIf<'a, St: Stream, F> {
+        inner: Option<(Pin<&'a mut Peekable<St>>, F)>,
+        n: usize,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<St, F> fmt::Debug for NextNIf<'_, St, F>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NextNIf")
+            .field("inner", &self.inner.as_ref().map(|(s, _f)| s))
+            .field("n", &self.n)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<St, F> FusedFuture for NextNIf<'_, St, F>
+where
+    St: Stream,
+    F: FnOnce(&[St::Item]) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<St, F> Future for NextNIf<'_, St, F>
+where
+    St: Stream,
+    F: FnOnce(&[St::Item]) -> bool,
+{
+    type Output = Option<Vec<St::Item>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let n = *this.n;
+        if let Some((peekable, _)) = this.inner {
+            let len = ready!(peekable.as_mut().poll_peek_n(cx, n)).len();
+
+            let (peekable, func) = this.inner.take().unwrap();
+            let peeked = peekable.project().peeked;
+            if len == n && func(&peeked.as_slice()[..n]) {
+                Poll::Ready(Some(peeked.drain_front(n)))
+            } else {
+                Poll::Ready(None)
+            }
+        } else {
+            panic!("NextNIf polled after completion")
+        }
+    }
+}
+
 pin_project! {
     /// Future for the [`Peekable::next_if_eq`](self::Peekable::next_if_eq) method.
     #[must_use = "futures do nothing unless polled"]
@@ -431,3 +645,159 @@ where
         next == self.expected
     }
 }
+
+// The items peeked from the underlying stream, in the order they were
+// returned. A single item, which is the common case, is kept inline; more
+// items spill into a `VecDeque`, which is kept contiguous so that they can be
+// borrowed as a slice.
+//
+// Without the `alloc` feature, at most one item can be peeked.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+enum Peeked<T> {
+    One(Option<T>),
+    Many(VecDeque<T>),
+}
+
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug)]
+struct Peeked<T> {
+    item: Option<T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Peeked<T> {
+    fn new() -> Self {
+        Self::One(None)
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::One(item) => usize::from(item.is_some()),
+            Self::Many(items) => items.len(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn first(&self) -> Option<&T> {
+        match self {
+            Self::One(item) => item.as_ref(),
+            Self::Many(items) => items.front(),
+        }
+    }
+
+    fn first_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Self::One(item) => item.as_mut(),
+            Self::Many(items) => items.front_mut(),
+        }
+    }
+
+    fn push_back(&mut self, item: T) {
+        match self {
+            Self::One(slot) => match slot.take() {
+                None => *slot = Some(item),
+                Some(first) => *self = Self::spill(first, item),
+            },
+            Self::Many(items) => {
+                items.push_back(item);
+                make_contiguous(items);
+            }
+        }
+    }
+
+    fn push_front(&mut self, item: T) {
+        match self {
+            Self::One(slot) => match slot.take() {
+                None => *slot = Some(item),
+                Some(second) => *self = Self::spill(item, second),
+            },
+            Self::Many(items) => {
+                items.push_front(item);
+                make_contiguous(items);
+            }
+        }
+    }
+
+    fn spill(first: T, second: T) -> Self {
+        let mut items = VecDeque::with_capacity(2);
+        items.push_back(first);
+        items.push_back(second);
+        Self::Many(items)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        match self {
+            Self::One(item) => item.take(),
+            // Removing the front item leaves the rest contiguous.
+            Self::Many(items) => items.pop_front(),
+        }
+    }
+
+    fn as_slice(&self) -> &[T] {
+        match self {
+            Self::One(Some(item)) => slice::from_ref(item),
+            Self::One(None) => &[],
+            Self::Many(items) => {
+                let (front, back) = items.as_slices();
+                debug_assert!(back.is_empty());
+                front
+            }
+        }
+    }
+
+    fn drain_front(&mut self, n: usize) -> Vec<T> {
+        match self {
+            Self::One(item) => item.take().into_iter().take(n).collect(),
+            Self::Many(items) => items.drain(..n).collect(),
+        }
+    }
+}
+
+// Moves the items of a `VecDeque` which wrapped around its buffer back into a
+// single slice. `VecDeque::make_contiguous` requires Rust 1.48.
+#[cfg(feature = "alloc")]
+fn make_contiguous<T>(items: &mut VecDeque<T>) {
+    if !items.as_slices().1.is_empty() {
+        *items = VecDeque::from(Vec::from(mem::take(items)));
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<T> Peeked<T> {
+    fn new() -> Self {
+        Self { item: None }
+    }
+
+    fn len(&self) -> usize {
+        usize::from(self.item.is_some())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.item.is_none()
+    }
+
+    fn first(&self) -> Option<&T> {
+        self.item.as_ref()
+    }
+
+    fn first_mut(&mut self) -> Option<&mut T> {
+        self.item.as_mut()
+    }
+
+    fn push_back(&mut self, item: T) {
+        assert!(self.item.is_none());
+        self.item = Some(item);
+    }
+
+    fn push_front(&mut self, item: T) {
+        self.push_back(item);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.item.take()
+    }
+}
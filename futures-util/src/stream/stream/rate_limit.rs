@@ -0,0 +1,110 @@
+use crate::stream::Fuse;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use futures_task::{Sleep, Timer};
+use pin_project_lite::pin_project;
+use std::time::{Duration, Instant};
+
+pin_project! {
+    /// Stream for the [`rate_limit`](super::StreamExt::rate_limit) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct RateLimit<St, T> {
+        #[pin]
+        stream: Fuse<St>,
+        timer: T,
+        interval: Duration,
+        // How far ahead of the current time `tat` may be before the bucket
+        // is empty, which is `interval` for each token but the last.
+        tolerance: Duration,
+        // The time at which the bucket is full again, if it isn't yet. Each
+        // yielded item moves it `interval` further.
+        tat: Option<Instant>,
+        sleep: Option<Sleep>,
+    }
+}
+
+impl<St: fmt::Debug, T> fmt::Debug for RateLimit<St, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimit")
+            .field("stream", &self.stream)
+            .field("interval", &self.interval)
+            .field("tolerance", &self.tolerance)
+            .field("tat", &self.tat)
+            .finish()
+    }
+}
+
+impl<St: Stream, T: Timer> RateLimit<St, T> {
+    pub(super) fn new(stream: St, interval: Duration, burst: u32, timer: T) -> Self {
+        assert!(burst > 0);
+
+        Self {
+            stream: super::Fuse::new(stream),
+            timer,
+            interval,
+            tolerance: interval * (burst - 1),
+            tat: None,
+            sleep: None,
+        }
+    }
+
+    delegate_access_inner!(stream, St, (.));
+}
+
+impl<St: Stream, T: Timer> Stream for RateLimit<St, T> {
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if this.stream.is_terminated() {
+            return Poll::Ready(None);
+        }
+
+        // The underlying stream isn't polled until a token is available, so
+        // items aren't read ahead of the limit.
+        loop {
+            if let Some(sleep) = this.sleep.as_mut() {
+                ready!(sleep.as_mut().poll(cx));
+                *this.sleep = None;
+            }
+
+            let now = this.timer.now();
+            let tat = this.tat.map_or(now, |tat| tat.max(now));
+            if tat <= now + *this.tolerance {
+                let item = ready!(this.stream.as_mut().poll_next(cx));
+                if item.is_some() {
+                    *this.tat = Some(tat + *this.interval);
+                }
+                return Poll::Ready(item);
+            }
+            *this.sleep = Some(this.timer.sleep_until(tat - *this.tolerance));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<St: Stream, T: Timer> FusedStream for RateLimit<St, T> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, T, Item> Sink<Item> for RateLimit<S, T>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
@@ -0,0 +1,248 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use slab::Slab;
+use std::boxed::Box;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// What happens to the items of a [`Share`] handle that falls behind the
+/// others by the whole buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LagPolicy {
+    /// The other handles wait for the lagging one before reading on, so no
+    /// item is missed. This is the default.
+    Block,
+    /// The oldest buffered items are dropped for the lagging handle, which
+    /// skips them.
+    DropOldest,
+}
+
+impl Default for LagPolicy {
+    fn default() -> Self {
+        Self::Block
+    }
+}
+
+struct Subscriber {
+    // The sequence number of the next item yielded to this handle.
+    next: u64,
+    policy: LagPolicy,
+    waker: Option<Waker>,
+}
+
+struct Inner<St: Stream> {
+    stream: Pin<Box<St>>,
+    done: bool,
+    capacity: usize,
+    // The items not yielded to every handle yet, the first one having the
+    // sequence number `start`.
+    buffer: VecDeque<St::Item>,
+    start: u64,
+    subscribers: Slab<Subscriber>,
+}
+
+impl<St: Stream> Inner<St> {
+    fn end(&self) -> u64 {
+        self.start + self.buffer.len() as u64
+    }
+
+    // Handles wait on the underlying stream or for a lagging handle, and only
+    // the task that polled the stream last is woken by it, so all of them are
+    // woken whenever an item is read or room is made in the buffer.
+    fn wake_all(&mut self) {
+        for (_, subscriber) in self.subscribers.iter_mut() {
+            if let Some(waker) = subscriber.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn is_blocked(&self) -> bool {
+        self.subscribers.iter().any(|(_, s)| s.next <= self.start && s.policy == LagPolicy::Block)
+    }
+
+    // Drops the items which were yielded to every handle.
+    fn trim(&mut self) {
+        let min = self.subscribers.iter().map(|(_, s)| s.next).min().unwrap_or_else(|| self.end());
+        let mut trimmed = false;
+        while self.start < min && !self.buffer.is_empty() {
+            self.buffer.pop_front();
+            self.start += 1;
+            trimmed = true;
+        }
+        if trimmed {
+            self.wake_all();
+        }
+    }
+}
+
+/// Stream for the [`share`](super::StreamExt::share) method.
+///
+/// Every clone of this handle yields every item of the underlying stream
+/// read after the clone was made.
+#[must_use = "streams do nothing unless polled"]
+pub struct Share<St: Stream> {
+    inner: Arc<Mutex<Inner<St>>>,
+    key: usize,
+}
+
+// The underlying stream is pinned behind the `Arc`, and the items are never
+// pinned.
+impl<St: Stream> Unpin for Share<St> {}
+
+impl<St: Stream> fmt::Debug for Share<St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share").field("key", &self.key).finish()
+    }
+}
+
+impl<St> Share<St>
+where
+    St: Stream,
+    St::Item: Clone,
+{
+    pub(super) fn new(stream: St, capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        let mut subscribers = Slab::new();
+        let key = subscribers.insert(Subscriber { next: 0, policy: LagPolicy::Block, waker: None });
+        let inner = Inner {
+            stream: Box::pin(stream),
+            done: false,
+            capacity,
+            buffer: VecDeque::new(),
+            start: 0,
+            subscribers,
+        };
+        Self { inner: Arc::new(Mutex::new(inner)), key }
+    }
+}
+
+impl<St: Stream> Share<St> {
+    /// Returns the lag policy of this handle.
+    pub fn lag_policy(&self) -> LagPolicy {
+        self.inner.lock().unwrap().subscribers[self.key].policy
+    }
+
+    /// Sets the lag policy of this handle. Clones of this handle made
+    /// afterwards share it.
+    pub fn set_lag_policy(&mut self, policy: LagPolicy) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.subscribers[self.key].policy = policy;
+        // Handles blocked on this one may now drop its items.
+        inner.wake_all();
+    }
+}
+
+impl<St: Stream> Clone for Share<St> {
+    fn clone(&self) -> Self {
+        let mut inner = self.inner.lock().unwrap();
+        let subscriber = &inner.subscribers[self.key];
+        let subscriber =
+            Subscriber { next: subscriber.next, policy: subscriber.policy, waker: None };
+        let key = inner.subscribers.insert(subscriber);
+        Self { inner: self.inner.clone(), key }
+    }
+}
+
+impl<St> Stream for Share<St>
+where
+    St: Stream,
+    St::Item: Clone,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let key = self.key;
+        let mut inner = self.inner.lock().unwrap();
+        let inner = &mut *inner;
+
+        // Items dropped while this handle was lagging are skipped.
+        let next = inner.subscribers[key].next.max(inner.start);
+
+        if next == inner.end() {
+            if inner.done {
+                return Poll::Ready(None);
+            }
+
+            // Reading on with a full buffer drops its oldest item, unless a
+            // handle which hasn't yielded it yet blocks.
+            let full = inner.buffer.len() >= inner.capacity;
+            if full && inner.is_blocked() {
+                inner.subscribers[key].waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+
+            match inner.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if full {
+                        inner.buffer.pop_front();
+                        inner.start += 1;
+                    }
+                    inner.buffer.push_back(item);
+                }
+                Poll::Ready(None) => {
+                    inner.done = true;
+                    inner.wake_all();
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => {
+                    inner.subscribers[key].waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+            inner.wake_all();
+        }
+
+        let item = inner.buffer[(next - inner.start) as usize].clone();
+        inner.subscribers[key].next = next + 1;
+        inner.trim();
+        Poll::Ready(Some(item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let inner = self.inner.lock().unwrap();
+        let subscriber = &inner.subscribers[self.key];
+        let len = (inner.end() - subscriber.next.max(inner.start)) as usize;
+        if inner.done {
+            return (len, Some(len));
+        }
+        let (lower, upper) = inner.stream.size_hint();
+        let lower = match subscriber.policy {
+            LagPolicy::Block => lower.saturating_add(len),
+            // Any number of the items may be dropped for a lagging handle,
+            // but not the last one.
+            LagPolicy::DropOldest => usize::from(lower > 0 || len > 0),
+        };
+        let upper = match upper {
+            Some(x) => x.checked_add(len),
+            None => None,
+        };
+        (lower, upper)
+    }
+}
+
+impl<St> FusedStream for Share<St>
+where
+    St: Stream,
+    St::Item: Clone,
+{
+    fn is_terminated(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.done && inner.subscribers[self.key].next >= inner.end()
+    }
+}
+
+impl<St: Stream> Drop for Share<St> {
+    fn drop(&mut self) {
+        // Other handles may be waiting for this one, or on the underlying
+        // stream if this handle polled it last.
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.subscribers.remove(self.key);
+            inner.trim();
+            inner.wake_all();
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use core::fmt;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll, Waker};
+use std::boxed::Box;
+use std::sync::{Arc, Mutex};
+
+struct Inner<St: Stream> {
+    stream: Pin<Box<St>>,
+    done: bool,
+    // The item read by the other half but not yielded by each half yet.
+    // Items aren't kept for a dropped half.
+    buffered: [Option<St::Item>; 2],
+    dropped: [bool; 2],
+    // A half waits on the underlying stream, or for the other half to yield
+    // its buffered item. Either way, it's woken once the other half yields
+    // an item, as only the task that polled the stream last is woken by it.
+    wakers: [Option<Waker>; 2],
+}
+
+impl<St: Stream> Inner<St> {
+    fn wake(&mut self, side: usize) {
+        if let Some(waker) = self.wakers[side].take() {
+            waker.wake();
+        }
+    }
+}
+
+/// One half of the stream returned by the [`tee`](super::StreamExt::tee)
+/// method.
+#[must_use = "streams do nothing unless polled"]
+pub struct Tee<St: Stream> {
+    inner: Arc<Mutex<Inner<St>>>,
+    side: usize,
+}
+
+impl<St: Stream> Unpin for Tee<St> {}
+
+impl<St: Stream> fmt::Debug for Tee<St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tee").field("side", &self.side).finish()
+    }
+}
+
+impl<St> Tee<St>
+where
+    St: Stream,
+    St::Item: Clone,
+{
+    pub(super) fn new(stream: St) -> (Self, Self) {
+        let inner = Arc::new(Mutex::new(Inner {
+            stream: Box::pin(stream),
+            done: false,
+            buffered: [None, None],
+            dropped: [false, false],
+            wakers: [None, None],
+        }));
+        (Self { inner: inner.clone(), side: 0 }, Self { inner, side: 1 })
+    }
+}
+
+impl<St> Stream for Tee<St>
+where
+    St: Stream,
+    St::Item: Clone,
+{
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (side, other) = (self.side, 1 - self.side);
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(item) = inner.buffered[side].take() {
+            inner.wake(other);
+            return Poll::Ready(Some(item));
+        }
+
+        if inner.done {
+            return Poll::Ready(None);
+        }
+
+        // The other half hasn't yielded the last item yet, so reading on
+        // would have to buffer another one for it.
+        if inner.buffered[other].is_some() {
+            inner.wakers[side] = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        match inner.stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                if !inner.dropped[other] {
+                    inner.buffered[other] = Some(item.clone());
+                    inner.wake(other);
+                }
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                inner.done = true;
+                inner.wake(other);
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                inner.wakers[side] = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let inner = self.inner.lock().unwrap();
+        let buffered_len = usize::from(inner.buffered[self.side].is_some());
+        if inner.done {
+            return (buffered_len, Some(buffered_len));
+        }
+        let (lower, upper) = inner.stream.size_hint();
+        let lower = lower.saturating_add(buffered_len);
+        let upper = match upper {
+            Some(x) => x.checked_add(buffered_len),
+            None => None,
+        };
+        (lower, upper)
+    }
+}
+
+impl<St> FusedStream for Tee<St>
+where
+    St: Stream,
+    St::Item: Clone,
+{
+    fn is_terminated(&self) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.done && inner.buffered[self.side].is_none()
+    }
+}
+
+impl<St: Stream> Drop for Tee<St> {
+    fn drop(&mut self) {
+        // The other half may be waiting for this half to yield its item.
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.dropped[self.side] = true;
+            inner.buffered[self.side] = None;
+            inner.wake(1 - self.side);
+        }
+    }
+}
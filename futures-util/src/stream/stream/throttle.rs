@@ -0,0 +1,86 @@
+use crate::stream::Fuse;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use futures_task::Timer;
+use pin_project_lite::pin_project;
+use std::time::{Duration, Instant};
+
+pin_project! {
+    /// Stream for the [`throttle`](super::StreamExt::throttle) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct Throttle<St, T> {
+        #[pin]
+        stream: Fuse<St>,
+        timer: T,
+        duration: Duration,
+        // The end of the interval started by the last yielded item.
+        until: Option<Instant>,
+    }
+}
+
+impl<St: fmt::Debug, T> fmt::Debug for Throttle<St, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Throttle")
+            .field("stream", &self.stream)
+            .field("duration", &self.duration)
+            .field("until", &self.until)
+            .finish()
+    }
+}
+
+impl<St: Stream, T: Timer> Throttle<St, T> {
+    pub(super) fn new(stream: St, duration: Duration, timer: T) -> Self {
+        Self { stream: super::Fuse::new(stream), timer, duration, until: None }
+    }
+
+    delegate_access_inner!(stream, St, (.));
+}
+
+impl<St: Stream, T: Timer> Stream for Throttle<St, T> {
+    type Item = St::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        Poll::Ready(loop {
+            let item = ready!(this.stream.as_mut().poll_next(cx));
+            if item.is_none() {
+                break None;
+            }
+
+            // Items received before the end of the interval are dropped.
+            let now = this.timer.now();
+            if this.until.map_or(true, |until| now >= until) {
+                *this.until = Some(now + *this.duration);
+                break item;
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        (usize::from(lower > 0), upper)
+    }
+}
+
+impl<St: Stream, T: Timer> FusedStream for Throttle<St, T> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, T, Item> Sink<Item> for Throttle<S, T>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
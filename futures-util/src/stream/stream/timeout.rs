@@ -0,0 +1,107 @@
+use crate::stream::Fuse;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use futures_task::{Sleep, Timer};
+use pin_project_lite::pin_project;
+use std::time::Duration;
+
+/// The error yielded by [`Timeout`] when no item was received before the
+/// timeout elapsed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed {
+    _priv: (),
+}
+
+impl fmt::Debug for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Elapsed").finish()
+    }
+}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stream timed out waiting for an item")
+    }
+}
+
+impl std::error::Error for Elapsed {}
+
+pin_project! {
+    /// Stream for the [`timeout`](super::StreamExt::timeout) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct Timeout<St, T> {
+        #[pin]
+        stream: Fuse<St>,
+        timer: T,
+        duration: Duration,
+        // Started once the underlying stream is pending, and dropped once it
+        // yields an item or the timeout elapses.
+        sleep: Option<Sleep>,
+    }
+}
+
+impl<St: fmt::Debug, T> fmt::Debug for Timeout<St, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeout")
+            .field("stream", &self.stream)
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+impl<St: Stream, T: Timer> Timeout<St, T> {
+    pub(super) fn new(stream: St, duration: Duration, timer: T) -> Self {
+        Self { stream: super::Fuse::new(stream), timer, duration, sleep: None }
+    }
+
+    delegate_access_inner!(stream, St, (.));
+}
+
+impl<St: Stream, T: Timer> Stream for Timeout<St, T> {
+    type Item = Result<St::Item, Elapsed>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if let Poll::Ready(item) = this.stream.as_mut().poll_next(cx) {
+            *this.sleep = None;
+            return Poll::Ready(item.map(Ok));
+        }
+
+        let timer = &*this.timer;
+        let duration = *this.duration;
+        let sleep = this.sleep.get_or_insert_with(|| timer.sleep(duration));
+        ready!(sleep.as_mut().poll(cx));
+
+        // The next wait gets a timeout of its own.
+        *this.sleep = None;
+        Poll::Ready(Some(Err(Elapsed { _priv: () })))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, _) = self.stream.size_hint();
+        (lower, None)
+    }
+}
+
+impl<St: Stream, T: Timer> FusedStream for Timeout<St, T> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, T, Item> Sink<Item> for Timeout<S, T>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
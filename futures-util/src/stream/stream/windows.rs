@@ -0,0 +1,101 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Stream for the [`windows`](super::StreamExt::windows) method.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct Windows<St: Stream> {
+        #[pin]
+        stream: St,
+        // The last `size` items, oldest first.
+        items: VecDeque<St::Item>,
+        size: usize,
+    }
+}
+
+impl<St> fmt::Debug for Windows<St>
+where
+    St: Stream + fmt::Debug,
+    St::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Windows")
+            .field("stream", &self.stream)
+            .field("items", &self.items)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<St: Stream> Windows<St>
+where
+    St::Item: Clone,
+{
+    pub(super) fn new(stream: St, size: usize) -> Self {
+        assert!(size > 0);
+
+        Self { stream, items: VecDeque::with_capacity(size), size }
+    }
+
+    delegate_access_inner!(stream, St, ());
+}
+
+impl<St: Stream> Stream for Windows<St>
+where
+    St::Item: Clone,
+{
+    type Item = Vec<St::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let item = match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(item) => item,
+                None => return Poll::Ready(None),
+            };
+
+            if this.items.len() == *this.size {
+                this.items.pop_front();
+            }
+            this.items.push_back(item);
+            if this.items.len() == *this.size {
+                return Poll::Ready(Some(this.items.iter().cloned().collect()));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every item yields a window once the first one is full.
+        let missing = self.size.saturating_sub(self.items.len() + 1);
+        let (lower, upper) = self.stream.size_hint();
+        (lower.saturating_sub(missing), upper.map(|x| x.saturating_sub(missing)))
+    }
+}
+
+impl<St: FusedStream> FusedStream for Windows<St>
+where
+    St::Item: Clone,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+#[cfg(feature = "sink")]
+impl<S, Item> Sink<Item> for Windows<S>
+where
+    S: Stream + Sink<Item>,
+{
+    type Error = S::Error;
+
+    delegate_sink!(stream, Item);
+}
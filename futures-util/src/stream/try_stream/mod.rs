@@ -111,6 +111,10 @@ mod try_collect;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::try_collect::TryCollect;
 
+mod try_collect_into;
+#[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
+pub use self::try_collect_into::TryCollectInto;
+
 mod try_concat;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
 pub use self::try_concat::TryConcat;
@@ -510,6 +514,41 @@ pub trait TryStreamExt: TryStream {
         assert_future::<Result<C, Self::Error>, _>(TryCollect::new(self))
     }
 
+    /// Attempt to transform a stream into a collection by extending an
+    /// existing one, returning a future representing the result of that
+    /// computation.
+    ///
+    /// Unlike [`try_collect`](TryStreamExt::try_collect), this doesn't create
+    /// a new collection, so that a buffer can be reused. If an error happens
+    /// then it will be returned, and the collection keeps the successful
+    /// results read until then.
+    ///
+    /// The returned future will be resolved to the collection when the stream
+    /// terminates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures::executor::block_on(async {
+    /// use futures::stream::{self, TryStreamExt};
+    ///
+    /// let mut buffer = vec![1];
+    ///
+    /// let stream = stream::iter(vec![Ok::<i32, i32>(2), Ok(3)]);
+    /// assert_eq!(stream.try_collect_into(&mut buffer).await, Ok(&mut vec![1, 2, 3]));
+    ///
+    /// let stream = stream::iter(vec![Ok(4), Err(5), Ok(6)]);
+    /// assert_eq!(stream.try_collect_into(&mut buffer).await, Err(5));
+    /// assert_eq!(buffer, vec![1, 2, 3, 4]);
+    /// # })
+    /// ```
+    fn try_collect_into<C: Extend<Self::Ok>>(self, items: &mut C) -> TryCollectInto<'_, Self, C>
+    where
+        Self: Sized,
+    {
+        assert_future::<Result<&mut C, Self::Error>, _>(TryCollectInto::new(self, items))
+    }
+
     /// An adaptor for chunking up successful items of the stream inside a vector.
     ///
     /// This combinator will attempt to pull successful items from this stream and buffer
@@ -0,0 +1,56 @@
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::ready;
+use futures_core::stream::TryStream;
+use futures_core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Future for the [`try_collect_into`](super::TryStreamExt::try_collect_into) method.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct TryCollectInto<'a, St, C> {
+        #[pin]
+        stream: St,
+        items: Option<&'a mut C>,
+    }
+}
+
+impl<'a, St: TryStream, C> TryCollectInto<'a, St, C> {
+    pub(super) fn new(s: St, items: &'a mut C) -> Self {
+        Self { stream: s, items: Some(items) }
+    }
+}
+
+impl<St, C> FusedFuture for TryCollectInto<'_, St, C>
+where
+    St: TryStream,
+    C: Extend<St::Ok>,
+{
+    fn is_terminated(&self) -> bool {
+        self.items.is_none()
+    }
+}
+
+impl<'a, St, C> Future for TryCollectInto<'a, St, C>
+where
+    St: TryStream,
+    C: Extend<St::Ok>,
+{
+    type Output = Result<&'a mut C, St::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let items = this.items.as_mut().expect("TryCollectInto polled after completion");
+        Poll::Ready(loop {
+            match ready!(this.stream.as_mut().try_poll_next(cx)) {
+                Some(Ok(x)) => items.extend(Some(x)),
+                Some(Err(e)) => {
+                    this.items.take();
+                    break Err(e);
+                }
+                None => break Ok(this.items.take().unwrap()),
+            }
+        })
+    }
+}
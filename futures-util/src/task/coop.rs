@@ -0,0 +1,104 @@
+//! Cooperative scheduling.
+//!
+//! A task whose futures are always ready, for example one draining a stream
+//! that always has an item available, never returns `Poll::Pending` on its
+//! own and starves the other tasks of its executor. To prevent that, the
+//! executors of this library give each poll of a task a *budget* with
+//! [`budget`]. Leaf futures and streams, such as
+//! [`StreamExt::next`](crate::stream::StreamExt::next), the receivers of
+//! `futures-channel` and [`FuturesUnordered`](crate::stream::FuturesUnordered),
+//! consume one unit of it each time they make progress. Once the budget is
+//! exhausted they return `Poll::Pending` after waking the task, forcing it to
+//! yield back to the executor.
+//!
+//! Futures can opt out of the budget by being wrapped in [`unconstrained`].
+//! Outside of a call to `budget`, for example on executors which don't set
+//! one, the budget is unlimited.
+//!
+//! Custom leaf futures can take part in cooperative scheduling by calling
+//! [`poll_proceed`] before making progress.
+//!
+//! # Examples
+//!
+//! ```
+//! use futures::executor::block_on;
+//! use futures::stream::{self, StreamExt};
+//! use futures::task::coop;
+//!
+//! block_on(async {
+//!     let mut stream = stream::repeat(1);
+//!     for _ in 0..coop::INITIAL_BUDGET {
+//!         stream.next().await;
+//!     }
+//!     // The budget of this poll is exhausted, so the task yields here.
+//!     assert!(!coop::has_budget_remaining());
+//!     stream.next().await;
+//!     assert!(coop::has_budget_remaining());
+//! });
+//! ```
+
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+
+pub use futures_core::task::__internal::coop::{
+    budget, has_budget_remaining, poll_proceed, Proceed, INITIAL_BUDGET,
+};
+
+pin_project! {
+    /// Future for the [`unconstrained`] function.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Unconstrained<Fut> {
+        #[pin]
+        future: Fut,
+    }
+}
+
+/// Turns off cooperative scheduling for a future.
+///
+/// The returned future polls `future` with an unlimited budget, so that the
+/// leaf futures it awaits never force it to yield. The budget of the enclosing
+/// task is left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+/// use futures::task::coop;
+///
+/// block_on(coop::unconstrained(async {
+///     let mut stream = stream::repeat(1);
+///     for _ in 0..1000 {
+///         stream.next().await;
+///     }
+///     assert!(coop::has_budget_remaining());
+/// }));
+/// ```
+pub fn unconstrained<Fut: Future>(future: Fut) -> Unconstrained<Fut> {
+    Unconstrained { future }
+}
+
+impl<Fut> Unconstrained<Fut> {
+    /// Consumes this combinator, returning the underlying future.
+    pub fn into_inner(self) -> Fut {
+        self.future
+    }
+}
+
+impl<Fut: Future> Future for Unconstrained<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Fut::Output> {
+        let future = self.project().future;
+        futures_core::task::__internal::coop::with_unconstrained(|| future.poll(cx))
+    }
+}
+
+impl<Fut: FusedFuture> FusedFuture for Unconstrained<Fut> {
+    fn is_terminated(&self) -> bool {
+        self.future.is_terminated()
+    }
+}
@@ -13,7 +13,13 @@
 #[doc(no_inline)]
 pub use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 
-pub use futures_task::{FutureObj, LocalFutureObj, LocalSpawn, Spawn, SpawnError, UnsafeFutureObj};
+pub use futures_task::{
+    FutureObj, LocalFutureObj, LocalSpawn, Priority, Spawn, SpawnError, SpawnPriority,
+    UnsafeFutureObj,
+};
+
+#[cfg(feature = "alloc")]
+pub use futures_task::SpawnBlocking;
 
 pub use futures_task::noop_waker;
 pub use futures_task::noop_waker_ref;
@@ -28,10 +34,70 @@ pub use futures_task::waker;
 
 #[cfg(not(futures_no_atomic_cas))]
 #[cfg(feature = "alloc")]
-pub use futures_task::{waker_ref, WakerRef};
+pub use futures_task::{CountingWaker, WakerStats};
+
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+pub use futures_task::waker_fn;
+
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub use futures_task::waker_fn_on_drop;
 
 #[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "alloc")]
+pub use futures_task::{waker_ref, WakerRef};
+
+#[cfg(feature = "std")]
+pub use futures_task::{joinable, JoinError, JoinHandle, Joinable};
+
+#[cfg(feature = "std")]
+pub use futures_task::{local_waker, local_waker_ref, LocalArcWake, LocalWakerRef};
+
+#[cfg(feature = "std")]
+pub use futures_task::{poll_with_extensions, ContextExt, Extensions};
+
+#[cfg(feature = "std")]
+pub use futures_task::{Sleep, Timer};
+
+#[cfg(feature = "std")]
+pub use futures_task::{WakerSet, WakerSlab};
+
+#[cfg(any(not(futures_no_atomic_cas), feature = "critical-section"))]
 pub use futures_core::task::__internal::AtomicWaker;
 
+pub use futures_core::task::__internal::LocalAtomicWaker;
+
+#[cfg(feature = "std")]
+pub mod coop;
+#[cfg(feature = "std")]
+pub use self::coop::{unconstrained, Unconstrained};
+
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+mod scope;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "std")]
+pub use self::scope::{scope, Scope, Scoped};
+
 mod spawn;
-pub use self::spawn::{LocalSpawnExt, SpawnExt};
+#[cfg(feature = "alloc")]
+pub use self::spawn::SpawnBlockingExt;
+pub use self::spawn::{LocalSpawnExt, SpawnExt, SpawnPriorityExt};
+
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "channel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "channel")))]
+mod task_group;
+#[cfg(not(futures_no_atomic_cas))]
+#[cfg(feature = "channel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "channel")))]
+pub use self::task_group::TaskGroup;
+
+#[cfg(feature = "std")]
+mod task_local;
+#[cfg(feature = "std")]
+pub use self::task_local::{AccessError, LocalKey, TaskLocalFuture};
+
+mod yield_now;
+pub use self::yield_now::{yield_now, YieldNow};
@@ -0,0 +1,158 @@
+use crate::future::{maybe_done, MaybeDone};
+use crate::stream::{FuturesUnordered, StreamExt};
+use crate::task::AtomicWaker;
+use core::fmt;
+use core::pin::Pin;
+use futures_core::future::{BoxFuture, FusedFuture, Future};
+use futures_core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+use std::sync::{Arc, Mutex};
+
+/// Runs the future returned by `f` alongside the child futures it spawns on
+/// the given [`Scope`].
+///
+/// Unlike futures spawned on an executor, the children may borrow from the
+/// stack frame enclosing the call to `scope`, as they never outlive the
+/// returned [`Scoped`] future: it is the one polling them, it only completes
+/// once the body and all of the children have completed, and dropping it
+/// drops, and so cancels, any children still running.
+///
+/// The children run concurrently with the body and with each other, but on
+/// the task polling the [`Scoped`] future rather than as separate tasks.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::task::scope;
+/// use std::sync::Mutex;
+///
+/// let mut results = Mutex::new(Vec::new());
+/// let inputs = vec![1, 2, 3];
+///
+/// let (results_ref, inputs_ref) = (&results, &inputs);
+/// scope(|s| async move {
+///     for input in inputs_ref {
+///         s.spawn(async move { results_ref.lock().unwrap().push(input * 2) });
+///     }
+/// })
+/// .await;
+///
+/// let results = results.get_mut().unwrap();
+/// results.sort();
+/// assert_eq!(*results, vec![2, 4, 6]);
+/// # });
+/// ```
+pub fn scope<'env, F, Fut>(f: F) -> Scoped<'env, Fut>
+where
+    F: FnOnce(Scope<'env>) -> Fut,
+    Fut: Future,
+{
+    let shared = Arc::new(Shared { incoming: Mutex::new(Vec::new()), waker: AtomicWaker::new() });
+    let body = f(Scope { shared: shared.clone() });
+    Scoped { body: maybe_done(body), children: FuturesUnordered::new(), shared }
+}
+
+struct Shared<'env> {
+    incoming: Mutex<Vec<BoxFuture<'env, ()>>>,
+    waker: AtomicWaker,
+}
+
+/// A handle for spawning futures on a scope, created by [`scope`].
+///
+/// The handle is cheaply cloneable and can be moved into the children, so
+/// that they can spawn further children.
+#[derive(Clone)]
+pub struct Scope<'env> {
+    shared: Arc<Shared<'env>>,
+}
+
+impl<'env> Scope<'env> {
+    /// Spawns a child future on the scope.
+    ///
+    /// The future runs until it completes or the [`Scoped`] future is
+    /// dropped. Futures spawned after the [`Scoped`] future has completed are
+    /// dropped without being polled.
+    pub fn spawn<Fut>(&self, future: Fut)
+    where
+        Fut: Future<Output = ()> + Send + 'env,
+    {
+        self.shared.incoming.lock().unwrap().push(Box::pin(future));
+        self.shared.waker.wake();
+    }
+}
+
+impl fmt::Debug for Scope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope").finish()
+    }
+}
+
+pin_project! {
+    /// Future for the [`scope`] function.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct Scoped<'env, Fut: Future> {
+        #[pin]
+        body: MaybeDone<Fut>,
+        children: FuturesUnordered<BoxFuture<'env, ()>>,
+        shared: Arc<Shared<'env>>,
+    }
+}
+
+impl<Fut: Future> Scoped<'_, Fut> {
+    /// Returns the number of children spawned on the scope which haven't
+    /// completed yet.
+    pub fn len(&self) -> usize {
+        self.children.len() + self.shared.incoming.lock().unwrap().len()
+    }
+
+    /// Returns `true` if there are no children running on the scope.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<Fut: Future> Future for Scoped<'_, Fut> {
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        this.shared.waker.register(cx.waker());
+
+        loop {
+            let body_done = this.body.as_mut().poll(cx).is_ready();
+
+            for child in this.shared.incoming.lock().unwrap().drain(..) {
+                this.children.push(child);
+            }
+            while let Poll::Ready(Some(())) = this.children.poll_next_unpin(cx) {}
+
+            // Children may have spawned more children while being polled.
+            if !this.shared.incoming.lock().unwrap().is_empty() {
+                continue;
+            }
+            if body_done && this.children.is_empty() {
+                return Poll::Ready(
+                    this.body.take_output().expect("Scoped polled after completion"),
+                );
+            }
+            return Poll::Pending;
+        }
+    }
+}
+
+impl<Fut: Future> FusedFuture for Scoped<'_, Fut> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.body, MaybeDone::Gone)
+    }
+}
+
+impl<Fut> fmt::Debug for Scoped<'_, Fut>
+where
+    Fut: Future + fmt::Debug,
+    Fut::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scoped").field("body", &self.body).field("children", &self.len()).finish()
+    }
+}
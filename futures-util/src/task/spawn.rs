@@ -1,4 +1,4 @@
-use futures_task::{LocalSpawn, Spawn};
+use futures_task::{LocalSpawn, Spawn, SpawnPriority};
 
 #[cfg(feature = "compat")]
 use crate::compat::Compat;
@@ -11,10 +11,17 @@ use alloc::boxed::Box;
 #[cfg(feature = "alloc")]
 use futures_core::future::Future;
 #[cfg(feature = "alloc")]
-use futures_task::{FutureObj, LocalFutureObj, SpawnError};
+use futures_task::SpawnBlocking;
+#[cfg(feature = "std")]
+use futures_task::{joinable, JoinHandle};
+#[cfg(feature = "alloc")]
+use futures_task::{FutureObj, LocalFutureObj, Priority, SpawnError};
 
 impl<Sp: ?Sized> SpawnExt for Sp where Sp: Spawn {}
 impl<Sp: ?Sized> LocalSpawnExt for Sp where Sp: LocalSpawn {}
+impl<Sp: ?Sized> SpawnPriorityExt for Sp where Sp: SpawnPriority {}
+#[cfg(feature = "alloc")]
+impl<Sp: ?Sized> SpawnBlockingExt for Sp where Sp: SpawnBlocking {}
 
 /// Extension trait for `Spawn`.
 pub trait SpawnExt: Spawn {
@@ -87,6 +94,40 @@ pub trait SpawnExt: Spawn {
         Ok(handle)
     }
 
+    /// Spawns a task that polls the given future to completion and returns a
+    /// [`JoinHandle`] to its output.
+    ///
+    /// Unlike [`spawn_with_handle`](SpawnExt::spawn_with_handle), the task
+    /// keeps running if the handle is dropped, can be cancelled with
+    /// [`JoinHandle::abort`], and a panic of the task is reported as a
+    /// [`JoinError`](futures_task::JoinError) instead of being propagated.
+    ///
+    /// ```
+    /// # {
+    /// use futures::executor::{block_on, ThreadPool};
+    /// use futures::task::SpawnExt;
+    ///
+    /// let executor = ThreadPool::new().unwrap();
+    ///
+    /// let handle = executor.spawn_with_join_handle(async { 1 }).unwrap();
+    /// assert_eq!(block_on(handle).unwrap(), 1);
+    /// # }
+    /// # std::thread::sleep(std::time::Duration::from_millis(500)); // wait for background threads closed: https://github.com/rust-lang/miri/issues/1371
+    /// ```
+    #[cfg(feature = "std")]
+    fn spawn_with_join_handle<Fut>(
+        &self,
+        future: Fut,
+    ) -> Result<JoinHandle<Fut::Output>, SpawnError>
+    where
+        Fut: Future + Send + 'static,
+        Fut::Output: Send,
+    {
+        let (future, handle) = joinable(future);
+        self.spawn(future)?;
+        Ok(handle)
+    }
+
     /// Wraps a [`Spawn`] and makes it usable as a futures 0.1 `Executor`.
     /// Requires the `compat` feature to enable.
     #[cfg(feature = "compat")]
@@ -166,4 +207,104 @@ pub trait LocalSpawnExt: LocalSpawn {
         self.spawn_local(future)?;
         Ok(handle)
     }
+
+    /// Spawns a task that polls the given future to completion and returns a
+    /// [`JoinHandle`] to its output.
+    ///
+    /// Unlike [`spawn_local_with_handle`](LocalSpawnExt::spawn_local_with_handle),
+    /// the task keeps running if the handle is dropped, can be cancelled with
+    /// [`JoinHandle::abort`], and a panic of the task is reported as a
+    /// [`JoinError`](futures_task::JoinError) instead of being propagated.
+    ///
+    /// ```
+    /// use futures::executor::LocalPool;
+    /// use futures::task::LocalSpawnExt;
+    ///
+    /// let mut executor = LocalPool::new();
+    /// let spawner = executor.spawner();
+    ///
+    /// let handle = spawner.spawn_local_with_join_handle(async { 1 }).unwrap();
+    /// assert_eq!(executor.run_until(handle).unwrap(), 1);
+    /// ```
+    #[cfg(feature = "std")]
+    fn spawn_local_with_join_handle<Fut>(
+        &self,
+        future: Fut,
+    ) -> Result<JoinHandle<Fut::Output>, SpawnError>
+    where
+        Fut: Future + 'static,
+    {
+        let (future, handle) = joinable(future);
+        self.spawn_local(future)?;
+        Ok(handle)
+    }
+}
+
+/// Extension trait for `SpawnPriority`.
+pub trait SpawnPriorityExt: SpawnPriority {
+    /// Spawns a task that polls the given future with output `()` to
+    /// completion, with the given priority.
+    ///
+    /// This method returns a [`Result`] that contains a [`SpawnError`] if
+    /// spawning fails.
+    ///
+    /// ```
+    /// # {
+    /// use futures::executor::ThreadPool;
+    /// use futures::task::{Priority, SpawnPriorityExt};
+    ///
+    /// let executor = ThreadPool::new().unwrap();
+    ///
+    /// let future = async { /* ... */ };
+    /// executor.spawn_with_priority(future, Priority::High).unwrap();
+    /// # }
+    /// # std::thread::sleep(std::time::Duration::from_millis(500)); // wait for background threads closed: https://github.com/rust-lang/miri/issues/1371
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn spawn_with_priority<Fut>(&self, future: Fut, priority: Priority) -> Result<(), SpawnError>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn_obj_with_priority(FutureObj::new(Box::new(future)), priority)
+    }
+}
+
+/// Extension trait for `SpawnBlocking`.
+#[cfg(feature = "alloc")]
+pub trait SpawnBlockingExt: SpawnBlocking {
+    /// Runs a closure that may block on a thread dedicated to blocking work,
+    /// returning a [`JoinHandle`] to its result.
+    ///
+    /// A panic of the closure is reported as a
+    /// [`JoinError`](futures_task::JoinError). Aborting the handle only
+    /// prevents the closure from running if it hasn't started yet.
+    ///
+    /// This method returns a [`Result`] that contains a [`SpawnError`] if
+    /// spawning fails.
+    ///
+    /// ```
+    /// # {
+    /// use futures::executor::{block_on, ThreadPool};
+    /// use futures::task::SpawnBlockingExt;
+    ///
+    /// let executor = ThreadPool::new().unwrap();
+    ///
+    /// let handle = executor.spawn_blocking(|| std::fs::metadata(".").is_ok()).unwrap();
+    /// assert!(block_on(handle).unwrap());
+    /// # }
+    /// # std::thread::sleep(std::time::Duration::from_millis(500)); // wait for background threads closed: https://github.com/rust-lang/miri/issues/1371
+    /// ```
+    #[cfg(feature = "std")]
+    fn spawn_blocking<F, R>(&self, f: F) -> Result<JoinHandle<R>, SpawnError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (task, handle) = joinable(crate::future::lazy(move |_| f()));
+        self.spawn_blocking_obj(Box::new(move || {
+            // The task completes when first polled.
+            let _ = crate::future::FutureExt::now_or_never(task);
+        }))?;
+        Ok(handle)
+    }
 }
@@ -0,0 +1,188 @@
+use crate::future::{AbortHandle, Abortable, Aborted, FutureExt};
+use crate::stream::{FuturesUnordered, StreamExt};
+use futures_channel::oneshot::{self, Receiver};
+use futures_core::future::Future;
+use futures_core::ready;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{Context, Poll};
+use futures_task::{JoinError, LocalSpawn, Spawn, SpawnError};
+use std::fmt;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::thread;
+
+/// A group of tasks spawned on executors, whose results are yielded as a
+/// stream in the order the tasks complete.
+///
+/// Unlike [`FuturesUnordered`], which polls the futures it holds itself and
+/// so requires them to be of the same type, the tasks of a group run on the
+/// executors they were spawned on, boxed as [`FutureObj`](crate::task::FutureObj)s.
+/// Only their outputs need to be of the same type.
+///
+/// A task which panics reports the panic as a [`JoinError`] instead of
+/// unwinding into the task polling the group. Tasks can be aborted
+/// individually with the [`AbortHandle`] returned when spawning them, or all
+/// at once with [`abort_all`](TaskGroup::abort_all); aborted tasks are
+/// reported as cancelled. Dropping the group aborts all of its tasks.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::{block_on, ThreadPool};
+/// use futures::stream::StreamExt;
+/// use futures::task::TaskGroup;
+///
+/// let pool = ThreadPool::new().unwrap();
+/// let mut group = TaskGroup::new();
+/// for i in 0..3 {
+///     group.spawn(&pool, async move { i * 2 }).unwrap();
+/// }
+///
+/// let mut results: Vec<_> = block_on(group.map(Result::unwrap).collect());
+/// results.sort();
+/// assert_eq!(results, vec![0, 2, 4]);
+/// ```
+#[must_use = "streams do nothing unless polled"]
+pub struct TaskGroup<T> {
+    tasks: FuturesUnordered<JoinEntry<T>>,
+}
+
+impl<T> TaskGroup<T> {
+    /// Creates a new, empty group.
+    pub fn new() -> Self {
+        Self { tasks: FuturesUnordered::new() }
+    }
+
+    /// Returns the number of tasks in the group whose result hasn't been
+    /// yielded yet.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if the group contains no tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Aborts all of the tasks in the group.
+    ///
+    /// The tasks stay in the group until their results are yielded: those
+    /// which hadn't completed yet are reported as cancelled.
+    pub fn abort_all(&mut self) {
+        for task in self.tasks.iter() {
+            task.abort.abort();
+        }
+    }
+}
+
+impl<T: Send + 'static> TaskGroup<T> {
+    /// Spawns `future` on `spawner` as a task of the group.
+    ///
+    /// Returns a handle to abort this task only, or the error returned by
+    /// `spawner` if the task couldn't be spawned.
+    pub fn spawn<Sp, Fut>(&mut self, spawner: &Sp, future: Fut) -> Result<AbortHandle, SpawnError>
+    where
+        Sp: Spawn + ?Sized,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let (task, entry) = join_task(future);
+        spawner.spawn_obj(Box::pin(task).into())?;
+        Ok(self.push(entry))
+    }
+}
+
+impl<T: 'static> TaskGroup<T> {
+    /// Spawns the `!Send` `future` on `spawner` as a task of the group.
+    ///
+    /// Returns a handle to abort this task only, or the error returned by
+    /// `spawner` if the task couldn't be spawned.
+    pub fn spawn_local<Sp, Fut>(
+        &mut self,
+        spawner: &Sp,
+        future: Fut,
+    ) -> Result<AbortHandle, SpawnError>
+    where
+        Sp: LocalSpawn + ?Sized,
+        Fut: Future<Output = T> + 'static,
+    {
+        let (task, entry) = join_task(future);
+        spawner.spawn_local_obj(Box::pin(task).into())?;
+        Ok(self.push(entry))
+    }
+}
+
+impl<T> TaskGroup<T> {
+    fn push(&mut self, entry: JoinEntry<T>) -> AbortHandle {
+        let abort = entry.abort.clone();
+        self.tasks.push(entry);
+        abort
+    }
+}
+
+impl<T> Default for TaskGroup<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Stream for TaskGroup<T> {
+    type Item = Result<T, JoinError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.tasks.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tasks.size_hint()
+    }
+}
+
+impl<T> FusedStream for TaskGroup<T> {
+    fn is_terminated(&self) -> bool {
+        self.tasks.is_terminated()
+    }
+}
+
+impl<T> fmt::Debug for TaskGroup<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskGroup").field("len", &self.len()).finish()
+    }
+}
+
+type JoinResult<T> = Result<thread::Result<T>, Aborted>;
+
+fn join_task<Fut: Future>(future: Fut) -> (impl Future<Output = ()>, JoinEntry<Fut::Output>) {
+    let (abort, reg) = AbortHandle::new_pair();
+    let (tx, rx) = oneshot::channel();
+    // Unwind Safety: the panic is handed over to the group, like for `RemoteHandle`.
+    let task = Abortable::new(AssertUnwindSafe(future).catch_unwind(), reg).map(move |res| {
+        // The group may have been dropped.
+        let _ = tx.send(res);
+    });
+    (task, JoinEntry { rx, abort })
+}
+
+/// The group's side of a task.
+struct JoinEntry<T> {
+    rx: Receiver<JoinResult<T>>,
+    abort: AbortHandle,
+}
+
+impl<T> Future for JoinEntry<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Poll::Ready(match ready!(self.rx.poll_unpin(cx)) {
+            Ok(Ok(Ok(output))) => Ok(output),
+            Ok(Ok(Err(panic))) => Err(JoinError::panicked(panic)),
+            // Aborted, or dropped by the executor before completing.
+            Ok(Err(Aborted)) | Err(_) => Err(JoinError::cancelled()),
+        })
+    }
+}
+
+impl<T> Drop for JoinEntry<T> {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
@@ -0,0 +1,198 @@
+use core::fmt;
+use core::mem;
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{Context, Poll};
+use pin_project_lite::pin_project;
+use std::cell::RefCell;
+use std::error::Error;
+use std::thread;
+
+/// Declares new task-local keys of type [`LocalKey`].
+///
+/// The syntax is the same as for [`thread_local!`](std::thread_local), except
+/// that the keys have no initializer: a task-local key has no value until one
+/// is given to it with [`LocalKey::scope`] or [`LocalKey::sync_scope`].
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+///
+/// futures::task_local! {
+///     static REQUEST_ID: u64;
+///     pub static USER: String;
+/// }
+///
+/// block_on(REQUEST_ID.scope(42, async {
+///     assert_eq!(REQUEST_ID.get(), 42);
+///     assert!(USER.try_with(|_| ()).is_err());
+/// }));
+/// ```
+#[macro_export]
+macro_rules! task_local {
+    () => {};
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty; $($rest:tt)*) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+        $crate::task_local!($($rest)*);
+    };
+
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty) => {
+        $crate::__task_local_inner!($(#[$attr])* $vis $name, $t);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __task_local_inner {
+    ($(#[$attr:meta])* $vis:vis $name:ident, $t:ty) => {
+        $(#[$attr])*
+        $vis static $name: $crate::task::LocalKey<$t> = {
+            ::std::thread_local! {
+                static __KEY: ::std::cell::RefCell<::std::option::Option<$t>> =
+                    ::std::cell::RefCell::new(::std::option::Option::None);
+            }
+
+            $crate::task::LocalKey { inner: __KEY }
+        };
+    };
+}
+
+/// A key for task-local data, declared with [`task_local!`](crate::task_local).
+///
+/// A value is given to the key for the duration of a future with
+/// [`scope`](LocalKey::scope). The value is set every time the future is
+/// polled and removed again afterwards, so it is available to any code polled
+/// inside that future, on any executor and on whichever thread the future
+/// happens to be polled on, but never to other tasks.
+pub struct LocalKey<T: 'static> {
+    #[doc(hidden)]
+    pub inner: thread::LocalKey<RefCell<Option<T>>>,
+}
+
+impl<T: 'static> LocalKey<T> {
+    /// Sets the value of this key to `value` while `future` is being polled.
+    ///
+    /// Scopes can be nested, in which case the innermost value is visible.
+    pub fn scope<Fut: Future>(&'static self, value: T, future: Fut) -> TaskLocalFuture<T, Fut> {
+        TaskLocalFuture { key: self, slot: Some(value), future }
+    }
+
+    /// Sets the value of this key to `value` while `f` is being called.
+    pub fn sync_scope<F: FnOnce() -> R, R>(&'static self, value: T, f: F) -> R {
+        let mut slot = Some(value);
+        self.enter(&mut slot, f)
+    }
+
+    /// Swaps `slot` into this key for the duration of the call to `f`.
+    fn enter<F: FnOnce() -> R, R>(&'static self, slot: &mut Option<T>, f: F) -> R {
+        struct Reset<'a, T: 'static> {
+            key: &'static LocalKey<T>,
+            slot: &'a mut Option<T>,
+        }
+
+        impl<T: 'static> Drop for Reset<'_, T> {
+            fn drop(&mut self) {
+                self.key.inner.with(|value| mem::swap(self.slot, &mut *value.borrow_mut()));
+            }
+        }
+
+        self.inner.with(|value| {
+            let mut value = value
+                .try_borrow_mut()
+                .expect("cannot enter a task-local scope while the value is borrowed");
+            mem::swap(slot, &mut *value);
+        });
+        let _reset = Reset { key: self, slot };
+        f()
+    }
+
+    /// Calls `f` with a reference to the value of this key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key has no value, i.e. if this isn't called from inside
+    /// a [`scope`](LocalKey::scope) of this key.
+    pub fn with<F: FnOnce(&T) -> R, R>(&'static self, f: F) -> R {
+        self.try_with(f).expect("cannot access a task-local value outside of its scope")
+    }
+
+    /// Calls `f` with a reference to the value of this key, or returns an
+    /// [`AccessError`] if the key has no value.
+    pub fn try_with<F: FnOnce(&T) -> R, R>(&'static self, f: F) -> Result<R, AccessError> {
+        self.inner.with(|value| match &*value.borrow() {
+            Some(value) => Ok(f(value)),
+            None => Err(AccessError { _priv: () }),
+        })
+    }
+}
+
+impl<T: Copy + 'static> LocalKey<T> {
+    /// Returns a copy of the value of this key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key has no value, i.e. if this isn't called from inside
+    /// a [`scope`](LocalKey::scope) of this key.
+    pub fn get(&'static self) -> T {
+        self.with(|value| *value)
+    }
+}
+
+impl<T: 'static> fmt::Debug for LocalKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("LocalKey { .. }")
+    }
+}
+
+/// The error returned by [`LocalKey::try_with`] when the key has no value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccessError {
+    _priv: (),
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task-local value not set")
+    }
+}
+
+impl Error for AccessError {}
+
+pin_project! {
+    /// Future for the [`LocalKey::scope`] method.
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct TaskLocalFuture<T: 'static, Fut> {
+        key: &'static LocalKey<T>,
+        slot: Option<T>,
+        #[pin]
+        future: Fut,
+    }
+}
+
+impl<T: 'static, Fut: Future> Future for TaskLocalFuture<T, Fut> {
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Fut::Output> {
+        let this = self.project();
+        let future = this.future;
+        this.key.enter(this.slot, || future.poll(cx))
+    }
+}
+
+impl<T: 'static, Fut: FusedFuture> FusedFuture for TaskLocalFuture<T, Fut> {
+    fn is_terminated(&self) -> bool {
+        self.future.is_terminated()
+    }
+}
+
+impl<T: fmt::Debug + 'static, Fut: fmt::Debug> fmt::Debug for TaskLocalFuture<T, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskLocalFuture")
+            .field("key", self.key)
+            .field("value", &self.slot)
+            .field("future", &self.future)
+            .finish()
+    }
+}
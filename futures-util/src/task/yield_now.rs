@@ -0,0 +1,51 @@
+use core::pin::Pin;
+use futures_core::future::Future;
+use futures_core::task::{Context, Poll};
+
+/// Future for the [`yield_now()`] function.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct YieldNow {
+    yielded: bool,
+}
+
+/// Creates a future which yields back to the executor once before completing.
+///
+/// The first time it is polled, the returned future wakes its own task and
+/// returns [`Poll::Pending`], giving the executor a chance to run other tasks.
+/// It completes the next time it is polled.
+///
+/// # Interaction with `FuturesUnordered`
+///
+/// [`FuturesUnordered`](crate::stream::FuturesUnordered) treats a future that
+/// wakes itself while being polled as wanting to yield. When two of its
+/// futures have yielded during a single call to `poll_next`, it stops polling
+/// its futures, wakes its own task and returns `Poll::Pending`, so that
+/// `yield_now` is propagated to the executor instead of only moving on to the
+/// next future in the set.
+///
+/// # Examples
+///
+/// ```
+/// # futures::executor::block_on(async {
+/// use futures::task;
+///
+/// task::yield_now().await;
+/// # });
+/// ```
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
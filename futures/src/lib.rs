@@ -140,6 +140,9 @@ pub use futures_util::{future, sink, stream, task};
 #[cfg(feature = "async-await")]
 pub use futures_util::stream_select;
 
+#[cfg(feature = "std")]
+pub use futures_util::task_local;
+
 #[cfg(feature = "alloc")]
 #[doc(inline)]
 pub use futures_channel as channel;
@@ -194,13 +197,23 @@ pub mod executor {
     //! [`spawn_local_obj`]: https://docs.rs/futures/0.3/futures/task/trait.LocalSpawn.html#tymethod.spawn_local_obj
 
     pub use futures_executor::{
-        block_on, block_on_stream, enter, BlockingStream, Enter, EnterError, LocalPool,
-        LocalSpawner,
+        block_on, block_on_nested, block_on_stream, block_on_with, enter, is_entered,
+        with_extensions, BlockOnConfig, BlockingStream, Enter, EnterError, LocalPool,
+        LocalPoolBuilder, LocalSpawner, Park, PendingTask, SchedulingPolicy, Scope, TaskHooks,
+        TaskId, Unpark, WithExtensions,
     };
 
     #[cfg(feature = "thread-pool")]
     #[cfg_attr(docsrs, doc(cfg(feature = "thread-pool")))]
     pub use futures_executor::{ThreadPool, ThreadPoolBuilder};
+
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub use futures_executor::Metrics;
+
+    #[cfg(feature = "wasm")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    pub use futures_executor::{run_microtasks, set_microtask_scheduler, spawn_local};
 }
 
 #[cfg(feature = "compat")]
@@ -6,8 +6,13 @@
     feature = "io-compat",
     feature = "executor",
     feature = "thread-pool",
+    feature = "thread-affinity",
+    feature = "task-stats",
+    feature = "metrics",
+    feature = "wasm",
 )))]
 compile_error!(
     "`futures` tests must have all stable features activated: \
-    use `--all-features` or `--features default,thread-pool,io-compat`"
+    use `--all-features` or \
+    `--features default,thread-pool,io-compat,thread-affinity,task-stats,metrics,wasm`"
 );
@@ -72,22 +72,177 @@ pub mod channel {
     use super::*;
     use futures::channel::*;
 
+    assert_impl!(broadcast::Receiver<()>: Send);
+    assert_not_impl!(broadcast::Receiver<*const ()>: Send);
+    assert_impl!(broadcast::Receiver<()>: Sync);
+    assert_not_impl!(broadcast::Receiver<*const ()>: Sync);
+    assert_impl!(broadcast::Receiver<PhantomPinned>: Unpin);
+
+    assert_impl!(broadcast::Recv<'_, ()>: Send);
+    assert_not_impl!(broadcast::Recv<'_, *const ()>: Send);
+    assert_impl!(broadcast::Recv<'_, ()>: Sync);
+    assert_not_impl!(broadcast::Recv<'_, *const ()>: Sync);
+    assert_impl!(broadcast::Recv<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(broadcast::SendError<()>: Send);
+    assert_not_impl!(broadcast::SendError<*const ()>: Send);
+    assert_impl!(broadcast::SendError<()>: Sync);
+    assert_not_impl!(broadcast::SendError<*const ()>: Sync);
+    assert_impl!(broadcast::SendError<()>: Unpin);
+    assert_not_impl!(broadcast::SendError<PhantomPinned>: Unpin);
+
+    assert_impl!(broadcast::Sender<()>: Send);
+    assert_not_impl!(broadcast::Sender<*const ()>: Send);
+    assert_impl!(broadcast::Sender<()>: Sync);
+    assert_not_impl!(broadcast::Sender<*const ()>: Sync);
+    assert_impl!(broadcast::Sender<PhantomPinned>: Unpin);
+
+    assert_impl!(broadcast::RecvError: Send);
+    assert_impl!(broadcast::RecvError: Sync);
+    assert_impl!(broadcast::RecvError: Unpin);
+
+    assert_impl!(broadcast::TryRecvError: Send);
+    assert_impl!(broadcast::TryRecvError: Sync);
+    assert_impl!(broadcast::TryRecvError: Unpin);
+
+    assert_impl!(mpmc::Receiver<()>: Send);
+    assert_not_impl!(mpmc::Receiver<*const ()>: Send);
+    assert_impl!(mpmc::Receiver<()>: Sync);
+    assert_not_impl!(mpmc::Receiver<*const ()>: Sync);
+    assert_impl!(mpmc::Receiver<PhantomPinned>: Unpin);
+
+    assert_impl!(mpmc::Send<'_, ()>: Send);
+    assert_not_impl!(mpmc::Send<'_, *const ()>: Send);
+    assert_impl!(mpmc::Send<'_, ()>: Sync);
+    assert_not_impl!(mpmc::Send<'_, *const ()>: Sync);
+    assert_impl!(mpmc::Send<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpmc::Sender<()>: Send);
+    assert_not_impl!(mpmc::Sender<*const ()>: Send);
+    assert_impl!(mpmc::Sender<()>: Sync);
+    assert_not_impl!(mpmc::Sender<*const ()>: Sync);
+    assert_impl!(mpmc::Sender<PhantomPinned>: Unpin);
+
+    assert_impl!(mpmc::UnboundedReceiver<()>: Send);
+    assert_not_impl!(mpmc::UnboundedReceiver<*const ()>: Send);
+    assert_impl!(mpmc::UnboundedReceiver<()>: Sync);
+    assert_not_impl!(mpmc::UnboundedReceiver<*const ()>: Sync);
+    assert_impl!(mpmc::UnboundedReceiver<PhantomPinned>: Unpin);
+
+    assert_impl!(mpmc::UnboundedSender<()>: Send);
+    assert_not_impl!(mpmc::UnboundedSender<*const ()>: Send);
+    assert_impl!(mpmc::UnboundedSender<()>: Sync);
+    assert_not_impl!(mpmc::UnboundedSender<*const ()>: Sync);
+    assert_impl!(mpmc::UnboundedSender<PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::CloseReason: Send);
+    assert_impl!(mpsc::CloseReason: Sync);
+    assert_impl!(mpsc::CloseReason: Unpin);
+
+    assert_impl!(mpsc::Closed<'_, ()>: Send);
+    assert_not_impl!(mpsc::Closed<'_, *const ()>: Send);
+    assert_impl!(mpsc::Closed<'_, ()>: Sync);
+    assert_not_impl!(mpsc::Closed<'_, *const ()>: Sync);
+    assert_impl!(mpsc::Closed<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::Elapsed: Send);
+    assert_impl!(mpsc::Elapsed: Sync);
+    assert_impl!(mpsc::Elapsed: Unpin);
+
+    assert_impl!(mpsc::OverflowPolicy: Send);
+    assert_impl!(mpsc::OverflowPolicy: Sync);
+    assert_impl!(mpsc::OverflowPolicy: Unpin);
+
+    assert_impl!(mpsc::Peek<'_, ()>: Send);
+    assert_not_impl!(mpsc::Peek<'_, *const ()>: Send);
+    assert_impl!(mpsc::Peek<'_, ()>: Sync);
+    assert_not_impl!(mpsc::Peek<'_, *const ()>: Sync);
+    assert_impl!(mpsc::Peek<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::Permit<'_, ()>: Send);
+    assert_not_impl!(mpsc::Permit<'_, *const ()>: Send);
+    assert_impl!(mpsc::Permit<'_, ()>: Sync);
+    assert_not_impl!(mpsc::Permit<'_, *const ()>: Sync);
+    assert_impl!(mpsc::Permit<'_, PhantomPinned>: Unpin);
+
     assert_impl!(mpsc::Receiver<()>: Send);
     assert_not_impl!(mpsc::Receiver<*const ()>: Send);
     assert_impl!(mpsc::Receiver<()>: Sync);
     assert_not_impl!(mpsc::Receiver<*const ()>: Sync);
     assert_impl!(mpsc::Receiver<PhantomPinned>: Unpin);
 
+    assert_impl!(mpsc::ReceiverSet<()>: Send);
+    assert_not_impl!(mpsc::ReceiverSet<*const ()>: Send);
+    assert_impl!(mpsc::ReceiverSet<()>: Sync);
+    assert_not_impl!(mpsc::ReceiverSet<*const ()>: Sync);
+    assert_impl!(mpsc::ReceiverSet<PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::RecvMany<'_, ()>: Send);
+    assert_not_impl!(mpsc::RecvMany<'_, *const ()>: Send);
+    assert_impl!(mpsc::RecvMany<'_, ()>: Sync);
+    assert_not_impl!(mpsc::RecvMany<'_, *const ()>: Sync);
+    assert_impl!(mpsc::RecvMany<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::RecvTimeout<'_, ()>: Send);
+    assert_not_impl!(mpsc::RecvTimeout<'_, *const ()>: Send);
+    assert_not_impl!(mpsc::RecvTimeout<'_, ()>: Sync);
+    assert_impl!(mpsc::RecvTimeout<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::Reserve<'_, ()>: Send);
+    assert_not_impl!(mpsc::Reserve<'_, *const ()>: Send);
+    assert_impl!(mpsc::Reserve<'_, ()>: Sync);
+    assert_not_impl!(mpsc::Reserve<'_, *const ()>: Sync);
+    assert_impl!(mpsc::Reserve<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::SendAll<'_, (), SendTryStream<()>>: Send);
+    assert_not_impl!(mpsc::SendAll<'_, (), LocalTryStream>: Send);
+    assert_not_impl!(mpsc::SendAll<'_, *const (), SendTryStream<()>>: Send);
+    assert_impl!(mpsc::SendAll<'_, (), SyncTryStream<()>>: Sync);
+    assert_not_impl!(mpsc::SendAll<'_, (), LocalTryStream>: Sync);
+    assert_not_impl!(mpsc::SendAll<'_, *const (), SyncTryStream<()>>: Sync);
+    assert_impl!(mpsc::SendAll<'_, PhantomPinned, UnpinTryStream>: Unpin);
+    assert_not_impl!(mpsc::SendAll<'_, (), PinnedTryStream>: Unpin);
+
+    assert_impl!(mpsc::SendBatch<'_, (), ()>: Send);
+    assert_not_impl!(mpsc::SendBatch<'_, (), *const ()>: Send);
+    assert_not_impl!(mpsc::SendBatch<'_, *const (), ()>: Send);
+    assert_impl!(mpsc::SendBatch<'_, (), ()>: Sync);
+    assert_not_impl!(mpsc::SendBatch<'_, (), *const ()>: Sync);
+    assert_not_impl!(mpsc::SendBatch<'_, *const (), ()>: Sync);
+    assert_impl!(mpsc::SendBatch<'_, PhantomPinned, PhantomPinned>: Unpin);
+
     assert_impl!(mpsc::SendError: Send);
     assert_impl!(mpsc::SendError: Sync);
     assert_impl!(mpsc::SendError: Unpin);
 
+    assert_impl!(mpsc::SendTimeout<'_, ()>: Send);
+    assert_not_impl!(mpsc::SendTimeout<'_, *const ()>: Send);
+    assert_not_impl!(mpsc::SendTimeout<'_, ()>: Sync);
+    assert_impl!(mpsc::SendTimeout<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::SendTimeoutError<()>: Send);
+    assert_not_impl!(mpsc::SendTimeoutError<*const ()>: Send);
+    assert_impl!(mpsc::SendTimeoutError<()>: Sync);
+    assert_not_impl!(mpsc::SendTimeoutError<*const ()>: Sync);
+    assert_impl!(mpsc::SendTimeoutError<()>: Unpin);
+    assert_not_impl!(mpsc::SendTimeoutError<PhantomPinned>: Unpin);
+
     assert_impl!(mpsc::Sender<()>: Send);
     assert_not_impl!(mpsc::Sender<*const ()>: Send);
     assert_impl!(mpsc::Sender<()>: Sync);
     assert_not_impl!(mpsc::Sender<*const ()>: Sync);
     assert_impl!(mpsc::Sender<PhantomPinned>: Unpin);
 
+    assert_impl!(mpsc::SenderEvent: Send);
+    assert_impl!(mpsc::SenderEvent: Sync);
+    assert_impl!(mpsc::SenderEvent: Unpin);
+
+    assert_impl!(mpsc::SenderEvents<()>: Send);
+    assert_not_impl!(mpsc::SenderEvents<*const ()>: Send);
+    assert_impl!(mpsc::SenderEvents<()>: Sync);
+    assert_not_impl!(mpsc::SenderEvents<*const ()>: Sync);
+    assert_impl!(mpsc::SenderEvents<PhantomPinned>: Unpin);
+
     assert_impl!(mpsc::TryRecvError: Send);
     assert_impl!(mpsc::TryRecvError: Sync);
     assert_impl!(mpsc::TryRecvError: Unpin);
@@ -99,6 +254,18 @@ pub mod channel {
     assert_impl!(mpsc::TrySendError<()>: Unpin);
     assert_not_impl!(mpsc::TrySendError<PhantomPinned>: Unpin);
 
+    assert_impl!(mpsc::UnboundedClosed<'_, ()>: Send);
+    assert_not_impl!(mpsc::UnboundedClosed<'_, *const ()>: Send);
+    assert_impl!(mpsc::UnboundedClosed<'_, ()>: Sync);
+    assert_not_impl!(mpsc::UnboundedClosed<'_, *const ()>: Sync);
+    assert_impl!(mpsc::UnboundedClosed<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::UnboundedPeek<'_, ()>: Send);
+    assert_not_impl!(mpsc::UnboundedPeek<'_, *const ()>: Send);
+    assert_impl!(mpsc::UnboundedPeek<'_, ()>: Sync);
+    assert_not_impl!(mpsc::UnboundedPeek<'_, *const ()>: Sync);
+    assert_impl!(mpsc::UnboundedPeek<'_, PhantomPinned>: Unpin);
+
     assert_impl!(mpsc::UnboundedReceiver<()>: Send);
     assert_not_impl!(mpsc::UnboundedReceiver<*const ()>: Send);
     assert_impl!(mpsc::UnboundedReceiver<()>: Sync);
@@ -111,6 +278,35 @@ pub mod channel {
     assert_not_impl!(mpsc::UnboundedReceiver<*const ()>: Sync);
     assert_impl!(mpsc::UnboundedReceiver<PhantomPinned>: Unpin);
 
+    assert_impl!(mpsc::UnboundedRecvMany<'_, ()>: Send);
+    assert_not_impl!(mpsc::UnboundedRecvMany<'_, *const ()>: Send);
+    assert_impl!(mpsc::UnboundedRecvMany<'_, ()>: Sync);
+    assert_not_impl!(mpsc::UnboundedRecvMany<'_, *const ()>: Sync);
+    assert_impl!(mpsc::UnboundedRecvMany<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::UnboundedRecvTimeout<'_, ()>: Send);
+    assert_not_impl!(mpsc::UnboundedRecvTimeout<'_, *const ()>: Send);
+    assert_not_impl!(mpsc::UnboundedRecvTimeout<'_, ()>: Sync);
+    assert_impl!(mpsc::UnboundedRecvTimeout<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::UnboundedSenderEvents<()>: Send);
+    assert_not_impl!(mpsc::UnboundedSenderEvents<*const ()>: Send);
+    assert_impl!(mpsc::UnboundedSenderEvents<()>: Sync);
+    assert_not_impl!(mpsc::UnboundedSenderEvents<*const ()>: Sync);
+    assert_impl!(mpsc::UnboundedSenderEvents<PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::WeakSender<()>: Send);
+    assert_not_impl!(mpsc::WeakSender<*const ()>: Send);
+    assert_impl!(mpsc::WeakSender<()>: Sync);
+    assert_not_impl!(mpsc::WeakSender<*const ()>: Sync);
+    assert_impl!(mpsc::WeakSender<PhantomPinned>: Unpin);
+
+    assert_impl!(mpsc::WeakUnboundedSender<()>: Send);
+    assert_not_impl!(mpsc::WeakUnboundedSender<*const ()>: Send);
+    assert_impl!(mpsc::WeakUnboundedSender<()>: Sync);
+    assert_not_impl!(mpsc::WeakUnboundedSender<*const ()>: Sync);
+    assert_impl!(mpsc::WeakUnboundedSender<PhantomPinned>: Unpin);
+
     assert_impl!(oneshot::Canceled: Send);
     assert_impl!(oneshot::Canceled: Sync);
     assert_impl!(oneshot::Canceled: Unpin);
@@ -132,6 +328,117 @@ pub mod channel {
     assert_impl!(oneshot::Sender<()>: Sync);
     assert_not_impl!(oneshot::Sender<*const ()>: Sync);
     assert_impl!(oneshot::Sender<PhantomPinned>: Unpin);
+
+    assert_impl!(priority::Receiver<()>: Send);
+    assert_not_impl!(priority::Receiver<*const ()>: Send);
+    assert_impl!(priority::Receiver<()>: Sync);
+    assert_not_impl!(priority::Receiver<*const ()>: Sync);
+    assert_impl!(priority::Receiver<PhantomPinned>: Unpin);
+
+    assert_impl!(priority::SendError<()>: Send);
+    assert_not_impl!(priority::SendError<*const ()>: Send);
+    assert_impl!(priority::SendError<()>: Sync);
+    assert_not_impl!(priority::SendError<*const ()>: Sync);
+    assert_impl!(priority::SendError<()>: Unpin);
+    assert_not_impl!(priority::SendError<PhantomPinned>: Unpin);
+
+    assert_impl!(priority::Sender<()>: Send);
+    assert_not_impl!(priority::Sender<*const ()>: Send);
+    assert_impl!(priority::Sender<()>: Sync);
+    assert_not_impl!(priority::Sender<*const ()>: Sync);
+    assert_impl!(priority::Sender<PhantomPinned>: Unpin);
+
+    assert_impl!(priority::TryRecvError: Send);
+    assert_impl!(priority::TryRecvError: Sync);
+    assert_impl!(priority::TryRecvError: Unpin);
+
+    assert_impl!(reusable::Receiver<()>: Send);
+    assert_not_impl!(reusable::Receiver<*const ()>: Send);
+    assert_impl!(reusable::Receiver<()>: Sync);
+    assert_not_impl!(reusable::Receiver<*const ()>: Sync);
+    assert_impl!(reusable::Receiver<PhantomPinned>: Unpin);
+
+    assert_impl!(reusable::Recv<'_, ()>: Send);
+    assert_not_impl!(reusable::Recv<'_, *const ()>: Send);
+    assert_impl!(reusable::Recv<'_, ()>: Sync);
+    assert_not_impl!(reusable::Recv<'_, *const ()>: Sync);
+    assert_impl!(reusable::Recv<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(reusable::Sender<()>: Send);
+    assert_not_impl!(reusable::Sender<*const ()>: Send);
+    assert_impl!(reusable::Sender<()>: Sync);
+    assert_not_impl!(reusable::Sender<*const ()>: Sync);
+    assert_impl!(reusable::Sender<PhantomPinned>: Unpin);
+
+    assert_impl!(spsc::Receiver<()>: Send);
+    assert_not_impl!(spsc::Receiver<*const ()>: Send);
+    assert_impl!(spsc::Receiver<()>: Sync);
+    assert_not_impl!(spsc::Receiver<*const ()>: Sync);
+    assert_impl!(spsc::Receiver<PhantomPinned>: Unpin);
+
+    assert_impl!(spsc::Send<'_, ()>: Send);
+    assert_not_impl!(spsc::Send<'_, *const ()>: Send);
+    assert_impl!(spsc::Send<'_, ()>: Sync);
+    assert_not_impl!(spsc::Send<'_, *const ()>: Sync);
+    assert_impl!(spsc::Send<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(spsc::Sender<()>: Send);
+    assert_not_impl!(spsc::Sender<*const ()>: Send);
+    assert_impl!(spsc::Sender<()>: Sync);
+    assert_not_impl!(spsc::Sender<*const ()>: Sync);
+    assert_impl!(spsc::Sender<PhantomPinned>: Unpin);
+
+    assert_impl!(watch::Changed<'_, ()>: Send);
+    assert_not_impl!(watch::Changed<'_, *const ()>: Send);
+    assert_impl!(watch::Changed<'_, ()>: Sync);
+    assert_not_impl!(watch::Changed<'_, *const ()>: Sync);
+    assert_impl!(watch::Changed<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(watch::Receiver<()>: Send);
+    assert_not_impl!(watch::Receiver<*const ()>: Send);
+    assert_impl!(watch::Receiver<()>: Sync);
+    assert_not_impl!(watch::Receiver<*const ()>: Sync);
+    assert_impl!(watch::Receiver<PhantomPinned>: Unpin);
+
+    assert_not_impl!(watch::Ref<'_, ()>: Send);
+    assert_impl!(watch::Ref<'_, ()>: Sync);
+    assert_not_impl!(watch::Ref<'_, *const ()>: Sync);
+    assert_impl!(watch::Ref<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(watch::SendError<()>: Send);
+    assert_not_impl!(watch::SendError<*const ()>: Send);
+    assert_impl!(watch::SendError<()>: Sync);
+    assert_not_impl!(watch::SendError<*const ()>: Sync);
+    assert_impl!(watch::SendError<()>: Unpin);
+    assert_not_impl!(watch::SendError<PhantomPinned>: Unpin);
+
+    assert_impl!(watch::Sender<()>: Send);
+    assert_not_impl!(watch::Sender<*const ()>: Send);
+    assert_impl!(watch::Sender<()>: Sync);
+    assert_not_impl!(watch::Sender<*const ()>: Sync);
+    assert_impl!(watch::Sender<PhantomPinned>: Unpin);
+
+    assert_impl!(watch::RecvError: Send);
+    assert_impl!(watch::RecvError: Sync);
+    assert_impl!(watch::RecvError: Unpin);
+
+    assert_impl!(weighted::Receiver<()>: Send);
+    assert_not_impl!(weighted::Receiver<*const ()>: Send);
+    assert_impl!(weighted::Receiver<()>: Sync);
+    assert_not_impl!(weighted::Receiver<*const ()>: Sync);
+    assert_impl!(weighted::Receiver<PhantomPinned>: Unpin);
+
+    assert_impl!(weighted::Send<'_, ()>: Send);
+    assert_not_impl!(weighted::Send<'_, *const ()>: Send);
+    assert_impl!(weighted::Send<'_, ()>: Sync);
+    assert_not_impl!(weighted::Send<'_, *const ()>: Sync);
+    assert_impl!(weighted::Send<'_, PhantomPinned>: Unpin);
+
+    assert_impl!(weighted::Sender<()>: Send);
+    assert_not_impl!(weighted::Sender<*const ()>: Send);
+    assert_impl!(weighted::Sender<()>: Sync);
+    assert_not_impl!(weighted::Sender<*const ()>: Sync);
+    assert_impl!(weighted::Sender<PhantomPinned>: Unpin);
 }
 
 /// Assert Send/Sync/Unpin for all public types in `futures::compat`.
@@ -205,6 +512,10 @@ pub mod executor {
     assert_not_impl!(LocalSpawner: Sync);
     assert_impl!(LocalSpawner: Unpin);
 
+    assert_impl!(TaskId: Send);
+    assert_impl!(TaskId: Sync);
+    assert_impl!(TaskId: Unpin);
+
     assert_impl!(ThreadPool: Send);
     assert_impl!(ThreadPool: Sync);
     assert_impl!(ThreadPool: Unpin);
@@ -212,6 +523,13 @@ pub mod executor {
     assert_impl!(ThreadPoolBuilder: Send);
     assert_impl!(ThreadPoolBuilder: Sync);
     assert_impl!(ThreadPoolBuilder: Unpin);
+
+    assert_impl!(WithExtensions<SendFuture>: Send);
+    assert_not_impl!(WithExtensions<LocalFuture>: Send);
+    assert_impl!(WithExtensions<SyncFuture>: Sync);
+    assert_not_impl!(WithExtensions<LocalFuture>: Sync);
+    assert_impl!(WithExtensions<UnpinFuture>: Unpin);
+    assert_not_impl!(WithExtensions<PinnedFuture>: Unpin);
 }
 
 /// Assert Send/Sync/Unpin for all public types in `futures::future`.
@@ -250,6 +568,14 @@ pub mod future {
     assert_not_impl!(AndThen<PinnedFuture, UnpinFuture, PhantomPinned>: Unpin);
     assert_not_impl!(AndThen<UnpinFuture, PinnedFuture, PhantomPinned>: Unpin);
 
+    assert_impl!(CancellationToken: Send);
+    assert_impl!(CancellationToken: Sync);
+    assert_impl!(CancellationToken: Unpin);
+
+    assert_impl!(Cancelled: Send);
+    assert_impl!(Cancelled: Sync);
+    assert_impl!(Cancelled: Unpin);
+
     assert_impl!(CatchUnwind<SendFuture>: Send);
     assert_not_impl!(CatchUnwind<LocalFuture>: Send);
     assert_impl!(CatchUnwind<SyncFuture>: Sync);
@@ -610,12 +936,23 @@ pub mod future {
     assert_impl!(UnwrapOrElse<UnpinFuture, PhantomPinned>: Unpin);
     assert_not_impl!(UnwrapOrElse<PhantomPinned, ()>: Unpin);
 
+    assert_impl!(WaitForCancellation: Send);
+    assert_impl!(WaitForCancellation: Sync);
+    assert_impl!(WaitForCancellation: Unpin);
+
     assert_impl!(WeakShared<SendFuture<()>>: Send);
     assert_not_impl!(WeakShared<SendFuture>: Send);
     assert_not_impl!(WeakShared<LocalFuture>: Send);
     assert_not_impl!(WeakShared<SyncFuture<()>>: Sync);
     assert_impl!(WeakShared<PinnedFuture>: Unpin);
 
+    assert_impl!(WithCancellation<SendFuture>: Send);
+    assert_not_impl!(WithCancellation<LocalFuture>: Send);
+    assert_impl!(WithCancellation<SyncFuture>: Sync);
+    assert_not_impl!(WithCancellation<LocalFuture>: Sync);
+    assert_impl!(WithCancellation<UnpinFuture>: Unpin);
+    assert_not_impl!(WithCancellation<PinnedFuture>: Unpin);
+
     assert_impl!(Either<SendFuture, SendFuture>: Send);
     assert_not_impl!(Either<SendFuture, LocalFuture>: Send);
     assert_not_impl!(Either<LocalFuture, SendFuture>: Send);
@@ -626,6 +963,18 @@ pub mod future {
     assert_not_impl!(Either<UnpinFuture, PinnedFuture>: Unpin);
     assert_not_impl!(Either<PinnedFuture, UnpinFuture>: Unpin);
 
+    assert_impl!(Either3<SendFuture, SendFuture, SendFuture>: Send);
+    assert_not_impl!(Either3<SendFuture, SendFuture, LocalFuture>: Send);
+    assert_impl!(Either3<SyncFuture, SyncFuture, SyncFuture>: Sync);
+    assert_not_impl!(Either3<SyncFuture, SyncFuture, LocalFuture>: Sync);
+    assert_impl!(Either3<UnpinFuture, UnpinFuture, UnpinFuture>: Unpin);
+    assert_not_impl!(Either3<UnpinFuture, UnpinFuture, PinnedFuture>: Unpin);
+
+    assert_impl!(Either8<SendFuture, SendFuture, SendFuture, SendFuture, SendFuture, SendFuture, SendFuture, SendFuture>: Send);
+    assert_not_impl!(Either8<SendFuture, SendFuture, SendFuture, SendFuture, SendFuture, SendFuture, SendFuture, LocalFuture>: Send);
+    assert_impl!(Either8<UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture>: Unpin);
+    assert_not_impl!(Either8<UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, UnpinFuture, PinnedFuture>: Unpin);
+
     assert_impl!(MaybeDone<SendFuture<()>>: Send);
     assert_not_impl!(MaybeDone<SendFuture>: Send);
     assert_not_impl!(MaybeDone<LocalFuture>: Send);
@@ -1114,6 +1463,15 @@ pub mod stream {
     assert_impl!(BufferUnordered<UnpinStream>: Unpin);
     assert_not_impl!(BufferUnordered<PinnedStream>: Unpin);
 
+    assert_impl!(BufferUnorderedIndexed<SendStream<()>>: Send);
+    assert_not_impl!(BufferUnorderedIndexed<SendStream>: Send);
+    assert_not_impl!(BufferUnorderedIndexed<LocalStream>: Send);
+    assert_impl!(BufferUnorderedIndexed<SyncStream<()>>: Sync);
+    assert_not_impl!(BufferUnorderedIndexed<SyncStream>: Sync);
+    assert_not_impl!(BufferUnorderedIndexed<LocalStream>: Sync);
+    assert_impl!(BufferUnorderedIndexed<UnpinStream>: Unpin);
+    assert_not_impl!(BufferUnorderedIndexed<PinnedStream>: Unpin);
+
     assert_impl!(Buffered<SendStream<SendFuture<()>>>: Send);
     assert_not_impl!(Buffered<SendStream<SendFuture>>: Send);
     assert_not_impl!(Buffered<SendStream<LocalFuture>>: Send);
@@ -1142,6 +1500,19 @@ pub mod stream {
     assert_not_impl!(Chain<(), PhantomPinned>: Unpin);
     assert_not_impl!(Chain<PhantomPinned, ()>: Unpin);
 
+    assert_impl!(ChunkBy<SendStream<()>, (), ()>: Send);
+    assert_not_impl!(ChunkBy<SendStream, (), ()>: Send);
+    assert_not_impl!(ChunkBy<LocalStream<()>, (), ()>: Send);
+    assert_not_impl!(ChunkBy<SendStream<()>, *const (), ()>: Send);
+    assert_not_impl!(ChunkBy<SendStream<()>, (), *const ()>: Send);
+    assert_impl!(ChunkBy<SyncStream<()>, (), ()>: Sync);
+    assert_not_impl!(ChunkBy<SyncStream, (), ()>: Sync);
+    assert_not_impl!(ChunkBy<LocalStream<()>, (), ()>: Sync);
+    assert_not_impl!(ChunkBy<SyncStream<()>, *const (), ()>: Sync);
+    assert_not_impl!(ChunkBy<SyncStream<()>, (), *const ()>: Sync);
+    assert_impl!(ChunkBy<UnpinStream, PhantomPinned, PhantomPinned>: Unpin);
+    assert_not_impl!(ChunkBy<PinnedStream, (), ()>: Unpin);
+
     assert_impl!(Chunks<SendStream<()>>: Send);
     assert_not_impl!(Chunks<SendStream>: Send);
     assert_not_impl!(Chunks<LocalStream>: Send);
@@ -1151,6 +1522,14 @@ pub mod stream {
     assert_impl!(Chunks<UnpinStream>: Unpin);
     assert_not_impl!(Chunks<PinnedStream>: Unpin);
 
+    assert_impl!(ChunksTimeout<SendStream<()>, ()>: Send);
+    assert_not_impl!(ChunksTimeout<SendStream, ()>: Send);
+    assert_not_impl!(ChunksTimeout<LocalStream<()>, ()>: Send);
+    assert_not_impl!(ChunksTimeout<SendStream<()>, *const ()>: Send);
+    assert_not_impl!(ChunksTimeout<SyncStream<()>, ()>: Sync);
+    assert_impl!(ChunksTimeout<UnpinStream, PhantomPinned>: Unpin);
+    assert_not_impl!(ChunksTimeout<PinnedStream, ()>: Unpin);
+
     assert_impl!(Collect<(), ()>: Send);
     assert_not_impl!(Collect<*const (), ()>: Send);
     assert_not_impl!(Collect<(), *const ()>: Send);
@@ -1160,6 +1539,19 @@ pub mod stream {
     assert_impl!(Collect<(), PhantomPinned>: Unpin);
     assert_not_impl!(Collect<PhantomPinned, ()>: Unpin);
 
+    assert_impl!(CollectInto<'_, (), ()>: Send);
+    assert_not_impl!(CollectInto<'_, *const (), ()>: Send);
+    assert_not_impl!(CollectInto<'_, (), *const ()>: Send);
+    assert_impl!(CollectInto<'_, (), ()>: Sync);
+    assert_not_impl!(CollectInto<'_, *const (), ()>: Sync);
+    assert_not_impl!(CollectInto<'_, (), *const ()>: Sync);
+    assert_impl!(CollectInto<'_, (), PhantomPinned>: Unpin);
+    assert_not_impl!(CollectInto<'_, PhantomPinned, ()>: Unpin);
+
+    assert_impl!(ConcurrencyLimit: Send);
+    assert_impl!(ConcurrencyLimit: Sync);
+    assert_impl!(ConcurrencyLimit: Unpin);
+
     assert_impl!(Concat<SendStream<()>>: Send);
     assert_not_impl!(Concat<SendStream>: Send);
     assert_not_impl!(Concat<LocalStream>: Send);
@@ -1176,6 +1568,38 @@ pub mod stream {
     assert_impl!(Cycle<()>: Unpin);
     assert_not_impl!(Cycle<PhantomPinned>: Unpin);
 
+    assert_impl!(Dedup<SendStream<()>>: Send);
+    assert_not_impl!(Dedup<SendStream>: Send);
+    assert_not_impl!(Dedup<LocalStream<()>>: Send);
+    assert_impl!(Dedup<SyncStream<()>>: Sync);
+    assert_not_impl!(Dedup<SyncStream>: Sync);
+    assert_not_impl!(Dedup<LocalStream<()>>: Sync);
+    assert_impl!(Dedup<UnpinStream>: Unpin);
+    assert_not_impl!(Dedup<PinnedStream>: Unpin);
+
+    assert_impl!(DedupByKey<(), (), ()>: Send);
+    assert_not_impl!(DedupByKey<*const (), (), ()>: Send);
+    assert_not_impl!(DedupByKey<(), *const (), ()>: Send);
+    assert_not_impl!(DedupByKey<(), (), *const ()>: Send);
+    assert_impl!(DedupByKey<(), (), ()>: Sync);
+    assert_not_impl!(DedupByKey<*const (), (), ()>: Sync);
+    assert_not_impl!(DedupByKey<(), *const (), ()>: Sync);
+    assert_not_impl!(DedupByKey<(), (), *const ()>: Sync);
+    assert_impl!(DedupByKey<(), PhantomPinned, PhantomPinned>: Unpin);
+    assert_not_impl!(DedupByKey<PhantomPinned, (), ()>: Unpin);
+
+    assert_impl!(Debounce<SendStream<()>, ()>: Send);
+    assert_not_impl!(Debounce<SendStream, ()>: Send);
+    assert_not_impl!(Debounce<LocalStream<()>, ()>: Send);
+    assert_not_impl!(Debounce<SendStream<()>, *const ()>: Send);
+    assert_not_impl!(Debounce<SyncStream<()>, ()>: Sync);
+    assert_impl!(Debounce<UnpinStream, PhantomPinned>: Unpin);
+    assert_not_impl!(Debounce<PinnedStream, ()>: Unpin);
+
+    assert_impl!(Elapsed: Send);
+    assert_impl!(Elapsed: Sync);
+    assert_impl!(Elapsed: Unpin);
+
     assert_impl!(Empty<()>: Send);
     assert_not_impl!(Empty<*const ()>: Send);
     assert_impl!(Empty<()>: Sync);
@@ -1234,6 +1658,14 @@ pub mod stream {
     assert_not_impl!(FlatMap<PhantomPinned, (), ()>: Unpin);
     assert_not_impl!(FlatMap<(), PhantomPinned, ()>: Unpin);
 
+    assert_impl!(FlatMapUnorderedKeyed<SendStream, (), (), (), ()>: Send);
+    assert_not_impl!(FlatMapUnorderedKeyed<LocalStream, (), (), (), ()>: Send);
+    assert_not_impl!(FlatMapUnorderedKeyed<SendStream, *const (), (), (), ()>: Send);
+    assert_not_impl!(FlatMapUnorderedKeyed<SendStream, (), *const (), (), ()>: Send);
+    assert_not_impl!(FlatMapUnorderedKeyed<SendStream, (), (), *const (), ()>: Send);
+    assert_impl!(FlatMapUnorderedKeyed<UnpinStream, (), (), PhantomPinned, PhantomPinned>: Unpin);
+    assert_not_impl!(FlatMapUnorderedKeyed<PinnedStream, (), (), (), ()>: Unpin);
+
     assert_impl!(Flatten<SendStream<()>>: Send);
     assert_not_impl!(Flatten<SendStream>: Send);
     assert_not_impl!(Flatten<SendStream>: Send);
@@ -1326,6 +1758,26 @@ pub mod stream {
     assert_not_impl!(FuturesUnordered<*const ()>: Sync);
     assert_impl!(FuturesUnordered<PhantomPinned>: Unpin);
 
+    assert_impl!(Group<SendStream<()>, (), ()>: Send);
+    assert_not_impl!(Group<SendStream, (), ()>: Send);
+    assert_not_impl!(Group<LocalStream<()>, (), ()>: Send);
+    assert_not_impl!(Group<SendStream<()>, *const (), ()>: Send);
+    assert_not_impl!(Group<SendStream<()>, (), *const ()>: Send);
+    assert_impl!(Group<SendStream<()>, (), ()>: Sync);
+    assert_not_impl!(Group<SendStream, (), ()>: Sync);
+    assert_not_impl!(Group<LocalStream<()>, (), ()>: Sync);
+    assert_impl!(Group<PinnedStream, PhantomPinned, PhantomPinned>: Unpin);
+
+    assert_impl!(GroupBy<SendStream<()>, (), ()>: Send);
+    assert_not_impl!(GroupBy<SendStream, (), ()>: Send);
+    assert_not_impl!(GroupBy<LocalStream<()>, (), ()>: Send);
+    assert_not_impl!(GroupBy<SendStream<()>, *const (), ()>: Send);
+    assert_not_impl!(GroupBy<SendStream<()>, (), *const ()>: Send);
+    assert_impl!(GroupBy<SendStream<()>, (), ()>: Sync);
+    assert_not_impl!(GroupBy<SendStream, (), ()>: Sync);
+    assert_not_impl!(GroupBy<LocalStream<()>, (), ()>: Sync);
+    assert_impl!(GroupBy<PinnedStream, PhantomPinned, PhantomPinned>: Unpin);
+
     assert_impl!(Inspect<(), ()>: Send);
     assert_not_impl!(Inspect<*const (), ()>: Send);
     assert_not_impl!(Inspect<(), *const ()>: Send);
@@ -1374,6 +1826,24 @@ pub mod stream {
     assert_not_impl!(Iter<*const ()>: Sync);
     assert_impl!(Iter<PhantomPinned>: Unpin);
 
+    assert_impl!(KMerge<SendStream<()>>: Send);
+    assert_not_impl!(KMerge<SendStream>: Send);
+    assert_not_impl!(KMerge<LocalStream<()>>: Send);
+    assert_impl!(KMerge<SyncStream<()>>: Sync);
+    assert_not_impl!(KMerge<SyncStream>: Sync);
+    assert_not_impl!(KMerge<LocalStream<()>>: Sync);
+    assert_impl!(KMerge<PinnedStream>: Unpin);
+
+    assert_impl!(KMergeBy<SendStream<()>, ()>: Send);
+    assert_not_impl!(KMergeBy<SendStream, ()>: Send);
+    assert_not_impl!(KMergeBy<LocalStream<()>, ()>: Send);
+    assert_not_impl!(KMergeBy<SendStream<()>, *const ()>: Send);
+    assert_impl!(KMergeBy<SyncStream<()>, ()>: Sync);
+    assert_not_impl!(KMergeBy<SyncStream, ()>: Sync);
+    assert_not_impl!(KMergeBy<LocalStream<()>, ()>: Sync);
+    assert_not_impl!(KMergeBy<SyncStream<()>, *const ()>: Sync);
+    assert_impl!(KMergeBy<PinnedStream, PhantomPinned>: Unpin);
+
     assert_impl!(Map<(), ()>: Send);
     assert_not_impl!(Map<*const (), ()>: Send);
     assert_not_impl!(Map<(), *const ()>: Send);
@@ -1401,6 +1871,15 @@ pub mod stream {
     assert_impl!(MapOk<(), PhantomPinned>: Unpin);
     assert_not_impl!(MapOk<PhantomPinned, ()>: Unpin);
 
+    assert_impl!(MapWhile<(), ()>: Send);
+    assert_not_impl!(MapWhile<*const (), ()>: Send);
+    assert_not_impl!(MapWhile<(), *const ()>: Send);
+    assert_impl!(MapWhile<(), ()>: Sync);
+    assert_not_impl!(MapWhile<*const (), ()>: Sync);
+    assert_not_impl!(MapWhile<(), *const ()>: Sync);
+    assert_impl!(MapWhile<(), PhantomPinned>: Unpin);
+    assert_not_impl!(MapWhile<PhantomPinned, ()>: Unpin);
+
     assert_impl!(Next<'_, ()>: Send);
     assert_not_impl!(Next<'_, *const ()>: Send);
     assert_impl!(Next<'_, ()>: Sync);
@@ -1428,6 +1907,16 @@ pub mod stream {
     assert_not_impl!(NextIfEq<'_, LocalStream<()>, ()>: Send);
     assert_impl!(NextIfEq<'_, PinnedStream, PhantomPinned>: Unpin);
 
+    assert_impl!(NextNIf<'_, SendStream<()>, ()>: Send);
+    assert_not_impl!(NextNIf<'_, SendStream<()>, *const ()>: Send);
+    assert_not_impl!(NextNIf<'_, SendStream, ()>: Send);
+    assert_not_impl!(NextNIf<'_, LocalStream<()>, ()>: Send);
+    assert_impl!(NextNIf<'_, SyncStream<()>, ()>: Sync);
+    assert_not_impl!(NextNIf<'_, SyncStream<()>, *const ()>: Sync);
+    assert_not_impl!(NextNIf<'_, SyncStream, ()>: Sync);
+    assert_not_impl!(NextNIf<'_, LocalStream<()>, ()>: Send);
+    assert_impl!(NextNIf<'_, PinnedStream, PhantomPinned>: Unpin);
+
     assert_impl!(Once<()>: Send);
     assert_not_impl!(Once<*const ()>: Send);
     assert_impl!(Once<()>: Sync);
@@ -1447,6 +1936,16 @@ pub mod stream {
     assert_not_impl!(OrElse<PhantomPinned, (), ()>: Unpin);
     assert_not_impl!(OrElse<(), PhantomPinned, ()>: Unpin);
 
+    assert_impl!(Partition<SendStream<()>, ()>: Send);
+    assert_not_impl!(Partition<SendStream, ()>: Send);
+    assert_not_impl!(Partition<LocalStream<()>, ()>: Send);
+    assert_not_impl!(Partition<SendStream<()>, *const ()>: Send);
+    assert_impl!(Partition<SendStream<()>, ()>: Sync);
+    assert_not_impl!(Partition<SendStream, ()>: Sync);
+    assert_not_impl!(Partition<LocalStream<()>, ()>: Sync);
+    assert_not_impl!(Partition<SendStream<()>, *const ()>: Sync);
+    assert_impl!(Partition<PinnedStream, PhantomPinned>: Unpin);
+
     assert_impl!(Peek<'_, SendStream<()>>: Send);
     assert_not_impl!(Peek<'_, SendStream>: Send);
     assert_not_impl!(Peek<'_, LocalStream<()>>: Send);
@@ -1463,6 +1962,14 @@ pub mod stream {
     assert_not_impl!(PeekMut<'_, LocalStream<()>>: Sync);
     assert_impl!(PeekMut<'_, PinnedStream>: Unpin);
 
+    assert_impl!(PeekN<'_, SendStream<()>>: Send);
+    assert_not_impl!(PeekN<'_, SendStream>: Send);
+    assert_not_impl!(PeekN<'_, LocalStream<()>>: Send);
+    assert_impl!(PeekN<'_, SyncStream<()>>: Sync);
+    assert_not_impl!(PeekN<'_, SyncStream>: Sync);
+    assert_not_impl!(PeekN<'_, LocalStream<()>>: Sync);
+    assert_impl!(PeekN<'_, PinnedStream>: Unpin);
+
     assert_impl!(Peekable<SendStream<()>>: Send);
     assert_not_impl!(Peekable<SendStream>: Send);
     assert_not_impl!(Peekable<LocalStream>: Send);
@@ -1491,6 +1998,13 @@ pub mod stream {
     assert_impl!(PollImmediate<UnpinStream>: Unpin);
     assert_not_impl!(PollImmediate<PinnedStream>: Unpin);
 
+    assert_impl!(RateLimit<SendStream, ()>: Send);
+    assert_not_impl!(RateLimit<LocalStream, ()>: Send);
+    assert_not_impl!(RateLimit<SendStream, *const ()>: Send);
+    assert_not_impl!(RateLimit<SyncStream, ()>: Sync);
+    assert_impl!(RateLimit<UnpinStream, PhantomPinned>: Unpin);
+    assert_not_impl!(RateLimit<PinnedStream, ()>: Unpin);
+
     assert_impl!(ReadyChunks<SendStream<()>>: Send);
     assert_impl!(ReadyChunks<SendStream>: Send);
     assert_not_impl!(ReadyChunks<LocalStream>: Send);
@@ -1558,6 +2072,14 @@ pub mod stream {
     assert_not_impl!(SelectNextSome<'_, *const ()>: Sync);
     assert_impl!(SelectNextSome<'_, PhantomPinned>: Unpin);
 
+    assert_impl!(Share<SendStream<()>>: Send);
+    assert_not_impl!(Share<SendStream>: Send);
+    assert_not_impl!(Share<LocalStream<()>>: Send);
+    assert_impl!(Share<SendStream<()>>: Sync);
+    assert_not_impl!(Share<SendStream>: Sync);
+    assert_not_impl!(Share<LocalStream<()>>: Sync);
+    assert_impl!(Share<PinnedStream>: Unpin);
+
     assert_impl!(Skip<()>: Send);
     assert_not_impl!(Skip<*const ()>: Send);
     assert_impl!(Skip<()>: Sync);
@@ -1600,6 +2122,18 @@ pub mod stream {
     assert_impl!(StreamFuture<()>: Unpin);
     assert_not_impl!(StreamFuture<PhantomPinned>: Unpin);
 
+    assert_impl!(SwitchMap<SendStream, (), ()>: Send);
+    assert_not_impl!(SwitchMap<LocalStream<()>, (), ()>: Send);
+    assert_not_impl!(SwitchMap<SendStream<()>, *const (), ()>: Send);
+    assert_not_impl!(SwitchMap<SendStream<()>, (), *const ()>: Send);
+    assert_impl!(SwitchMap<SyncStream, (), ()>: Sync);
+    assert_not_impl!(SwitchMap<LocalStream<()>, (), ()>: Sync);
+    assert_not_impl!(SwitchMap<SyncStream<()>, *const (), ()>: Sync);
+    assert_not_impl!(SwitchMap<SyncStream<()>, (), *const ()>: Sync);
+    assert_impl!(SwitchMap<UnpinStream, (), PhantomPinned>: Unpin);
+    assert_not_impl!(SwitchMap<PinnedStream, (), ()>: Unpin);
+    assert_not_impl!(SwitchMap<UnpinStream, PhantomPinned, ()>: Unpin);
+
     assert_impl!(Take<()>: Send);
     assert_not_impl!(Take<*const ()>: Send);
     assert_impl!(Take<()>: Sync);
@@ -1617,6 +2151,13 @@ pub mod stream {
     assert_not_impl!(TakeUntil<LocalStream, SyncFuture<()>>: Sync);
     assert_impl!(TakeUntil<UnpinStream, UnpinFuture>: Unpin);
     assert_not_impl!(TakeUntil<PinnedStream, UnpinFuture>: Unpin);
+
+    assert_impl!(TakeUntilCancelled<SendStream>: Send);
+    assert_not_impl!(TakeUntilCancelled<LocalStream>: Send);
+    assert_impl!(TakeUntilCancelled<SyncStream>: Sync);
+    assert_not_impl!(TakeUntilCancelled<LocalStream>: Sync);
+    assert_impl!(TakeUntilCancelled<UnpinStream>: Unpin);
+    assert_not_impl!(TakeUntilCancelled<PinnedStream>: Unpin);
     assert_not_impl!(TakeUntil<UnpinStream, PinnedFuture>: Unpin);
 
     assert_impl!(TakeWhile<SendStream<()>, (), ()>: Send);
@@ -1633,6 +2174,14 @@ pub mod stream {
     assert_not_impl!(TakeWhile<PinnedStream, (), ()>: Unpin);
     assert_not_impl!(TakeWhile<UnpinStream, PhantomPinned, ()>: Unpin);
 
+    assert_impl!(Tee<SendStream<()>>: Send);
+    assert_not_impl!(Tee<SendStream>: Send);
+    assert_not_impl!(Tee<LocalStream<()>>: Send);
+    assert_impl!(Tee<SendStream<()>>: Sync);
+    assert_not_impl!(Tee<SendStream>: Sync);
+    assert_not_impl!(Tee<LocalStream<()>>: Sync);
+    assert_impl!(Tee<PinnedStream>: Unpin);
+
     assert_impl!(Then<SendStream, (), ()>: Send);
     assert_not_impl!(Then<LocalStream<()>, (), ()>: Send);
     assert_not_impl!(Then<SendStream<()>, *const (), ()>: Send);
@@ -1645,6 +2194,22 @@ pub mod stream {
     assert_not_impl!(Then<PinnedStream, (), ()>: Unpin);
     assert_not_impl!(Then<UnpinStream, PhantomPinned, ()>: Unpin);
 
+    assert_impl!(Timeout<SendStream, ()>: Send);
+    assert_not_impl!(Timeout<LocalStream, ()>: Send);
+    assert_not_impl!(Timeout<SendStream, *const ()>: Send);
+    assert_not_impl!(Timeout<SyncStream, ()>: Sync);
+    assert_impl!(Timeout<UnpinStream, PhantomPinned>: Unpin);
+    assert_not_impl!(Timeout<PinnedStream, ()>: Unpin);
+
+    assert_impl!(Throttle<SendStream, ()>: Send);
+    assert_not_impl!(Throttle<LocalStream, ()>: Send);
+    assert_not_impl!(Throttle<SendStream, *const ()>: Send);
+    assert_impl!(Throttle<SyncStream, ()>: Sync);
+    assert_not_impl!(Throttle<LocalStream, ()>: Sync);
+    assert_not_impl!(Throttle<SyncStream, *const ()>: Sync);
+    assert_impl!(Throttle<UnpinStream, PhantomPinned>: Unpin);
+    assert_not_impl!(Throttle<PinnedStream, ()>: Unpin);
+
     assert_impl!(TryBufferUnordered<SendTryStream<()>>: Send);
     assert_not_impl!(TryBufferUnordered<SendTryStream>: Send);
     assert_not_impl!(TryBufferUnordered<LocalTryStream>: Send);
@@ -1676,6 +2241,15 @@ pub mod stream {
     assert_impl!(TryCollect<(), PhantomPinned>: Unpin);
     assert_not_impl!(TryCollect<PhantomPinned, ()>: Unpin);
 
+    assert_impl!(TryCollectInto<'_, (), ()>: Send);
+    assert_not_impl!(TryCollectInto<'_, *const (), ()>: Send);
+    assert_not_impl!(TryCollectInto<'_, (), *const ()>: Send);
+    assert_impl!(TryCollectInto<'_, (), ()>: Sync);
+    assert_not_impl!(TryCollectInto<'_, *const (), ()>: Sync);
+    assert_not_impl!(TryCollectInto<'_, (), *const ()>: Sync);
+    assert_impl!(TryCollectInto<'_, (), PhantomPinned>: Unpin);
+    assert_not_impl!(TryCollectInto<'_, PhantomPinned, ()>: Unpin);
+
     assert_impl!(TryConcat<SendTryStream<()>>: Send);
     assert_not_impl!(TryConcat<SendTryStream>: Send);
     assert_not_impl!(TryConcat<LocalTryStream>: Send);
@@ -1826,6 +2400,15 @@ pub mod stream {
     assert_impl!(Unzip<(), PhantomPinned, PhantomPinned>: Unpin);
     assert_not_impl!(Unzip<PhantomPinned, (), ()>: Unpin);
 
+    assert_impl!(Windows<SendStream<()>>: Send);
+    assert_not_impl!(Windows<SendStream>: Send);
+    assert_not_impl!(Windows<LocalStream>: Send);
+    assert_impl!(Windows<SyncStream<()>>: Sync);
+    assert_not_impl!(Windows<SyncStream>: Sync);
+    assert_not_impl!(Windows<LocalStream>: Sync);
+    assert_impl!(Windows<UnpinStream>: Unpin);
+    assert_not_impl!(Windows<PinnedStream>: Unpin);
+
     assert_impl!(Zip<SendStream<()>, SendStream<()>>: Send);
     assert_not_impl!(Zip<SendStream, SendStream<()>>: Send);
     assert_not_impl!(Zip<SendStream<()>, SendStream>: Send);
@@ -1840,6 +2423,20 @@ pub mod stream {
     assert_not_impl!(Zip<UnpinStream, PinnedStream>: Unpin);
     assert_not_impl!(Zip<PinnedStream, UnpinStream>: Unpin);
 
+    assert_impl!(ZipLongest<SendStream<()>, SendStream<()>>: Send);
+    assert_not_impl!(ZipLongest<SendStream, SendStream<()>>: Send);
+    assert_not_impl!(ZipLongest<SendStream<()>, SendStream>: Send);
+    assert_not_impl!(ZipLongest<LocalStream, SendStream<()>>: Send);
+    assert_not_impl!(ZipLongest<SendStream<()>, LocalStream>: Send);
+    assert_impl!(ZipLongest<SyncStream<()>, SyncStream<()>>: Sync);
+    assert_not_impl!(ZipLongest<SyncStream, SyncStream<()>>: Sync);
+    assert_not_impl!(ZipLongest<SyncStream<()>, SyncStream>: Sync);
+    assert_not_impl!(ZipLongest<LocalStream, SyncStream<()>>: Sync);
+    assert_not_impl!(ZipLongest<SyncStream<()>, LocalStream>: Sync);
+    assert_impl!(ZipLongest<UnpinStream, UnpinStream>: Unpin);
+    assert_not_impl!(ZipLongest<UnpinStream, PinnedStream>: Unpin);
+    assert_not_impl!(ZipLongest<PinnedStream, UnpinStream>: Unpin);
+
     assert_impl!(futures_unordered::Iter<()>: Send);
     assert_not_impl!(futures_unordered::Iter<*const ()>: Send);
     assert_impl!(futures_unordered::Iter<()>: Sync);
@@ -1881,23 +2478,123 @@ pub mod task {
     use super::*;
     use futures::task::*;
 
+    assert_impl!(AccessError: Send);
+    assert_impl!(AccessError: Sync);
+    assert_impl!(AccessError: Unpin);
+
     assert_impl!(AtomicWaker: Send);
     assert_impl!(AtomicWaker: Sync);
     assert_impl!(AtomicWaker: Unpin);
 
+    assert_impl!(CountingWaker: Send);
+    assert_impl!(CountingWaker: Sync);
+    assert_impl!(CountingWaker: Unpin);
+
+    assert_impl!(Extensions: Send);
+    assert_impl!(Extensions: Sync);
+    assert_impl!(Extensions: Unpin);
+
     assert_impl!(FutureObj<*const ()>: Send);
     assert_not_impl!(FutureObj<()>: Sync);
     assert_impl!(FutureObj<PhantomPinned>: Unpin);
 
+    assert_impl!(JoinError: Send);
+    assert_not_impl!(JoinError: Sync);
+    assert_impl!(JoinError: Unpin);
+
+    assert_impl!(JoinHandle<()>: Send);
+    assert_not_impl!(JoinHandle<*const ()>: Send);
+    assert_impl!(JoinHandle<()>: Sync);
+    assert_not_impl!(JoinHandle<*const ()>: Sync);
+    assert_impl!(JoinHandle<PhantomPinned>: Unpin);
+
+    assert_impl!(Joinable<SendFuture<()>>: Send);
+    assert_not_impl!(Joinable<SendFuture>: Send);
+    assert_not_impl!(Joinable<LocalFuture<()>>: Send);
+    assert_impl!(Joinable<SyncFuture<()>>: Sync);
+    assert_not_impl!(Joinable<SyncFuture>: Sync);
+    assert_not_impl!(Joinable<LocalFuture<()>>: Sync);
+    assert_impl!(Joinable<UnpinFuture>: Unpin);
+    assert_not_impl!(Joinable<PinnedFuture>: Unpin);
+
+    assert_impl!(LocalAtomicWaker: Send);
+    assert_not_impl!(LocalAtomicWaker: Sync);
+    assert_impl!(LocalAtomicWaker: Unpin);
+
     assert_not_impl!(LocalFutureObj<()>: Send);
     assert_not_impl!(LocalFutureObj<()>: Sync);
     assert_impl!(LocalFutureObj<PhantomPinned>: Unpin);
 
+    assert_impl!(LocalKey<()>: Send);
+    assert_impl!(LocalKey<()>: Sync);
+    assert_impl!(LocalKey<()>: Unpin);
+
+    assert_impl!(LocalWakerRef<'_>: Send);
+    assert_impl!(LocalWakerRef<'_>: Sync);
+    assert_impl!(LocalWakerRef<'_>: Unpin);
+
+    assert_impl!(Priority: Send);
+    assert_impl!(Priority: Sync);
+    assert_impl!(Priority: Unpin);
+
+    assert_impl!(coop::Proceed: Send);
+    assert_impl!(coop::Proceed: Sync);
+    assert_impl!(coop::Proceed: Unpin);
+
+    assert_impl!(Scope<'_>: Send);
+    assert_impl!(Scope<'_>: Sync);
+    assert_impl!(Scope<'_>: Unpin);
+
+    assert_impl!(Scoped<'_, SendFuture<()>>: Send);
+    assert_not_impl!(Scoped<'_, SendFuture>: Send);
+    assert_not_impl!(Scoped<'_, LocalFuture<()>>: Send);
+    assert_not_impl!(Scoped<'_, SyncFuture<()>>: Sync);
+    assert_impl!(Scoped<'_, UnpinFuture>: Unpin);
+    assert_not_impl!(Scoped<'_, PinnedFuture>: Unpin);
+
     assert_impl!(SpawnError: Send);
     assert_impl!(SpawnError: Sync);
     assert_impl!(SpawnError: Unpin);
 
+    assert_impl!(TaskGroup<()>: Send);
+    assert_not_impl!(TaskGroup<*const ()>: Send);
+    assert_impl!(TaskGroup<()>: Sync);
+    assert_not_impl!(TaskGroup<*const ()>: Sync);
+    assert_impl!(TaskGroup<PhantomPinned>: Unpin);
+
+    assert_impl!(TaskLocalFuture<(), SendFuture>: Send);
+    assert_not_impl!(TaskLocalFuture<*const (), SendFuture>: Send);
+    assert_not_impl!(TaskLocalFuture<(), LocalFuture>: Send);
+    assert_impl!(TaskLocalFuture<(), SyncFuture>: Sync);
+    assert_not_impl!(TaskLocalFuture<*const (), SyncFuture>: Sync);
+    assert_not_impl!(TaskLocalFuture<(), LocalFuture>: Sync);
+    assert_impl!(TaskLocalFuture<PhantomPinned, UnpinFuture>: Unpin);
+    assert_not_impl!(TaskLocalFuture<(), PinnedFuture>: Unpin);
+
+    assert_impl!(Unconstrained<SendFuture>: Send);
+    assert_not_impl!(Unconstrained<LocalFuture>: Send);
+    assert_impl!(Unconstrained<SyncFuture>: Sync);
+    assert_not_impl!(Unconstrained<LocalFuture>: Sync);
+    assert_impl!(Unconstrained<UnpinFuture>: Unpin);
+    assert_not_impl!(Unconstrained<PinnedFuture>: Unpin);
+
     assert_impl!(WakerRef<'_>: Send);
     assert_impl!(WakerRef<'_>: Sync);
     assert_impl!(WakerRef<'_>: Unpin);
+
+    assert_impl!(WakerSet: Send);
+    assert_impl!(WakerSet: Sync);
+    assert_impl!(WakerSet: Unpin);
+
+    assert_impl!(WakerSlab: Send);
+    assert_impl!(WakerSlab: Sync);
+    assert_impl!(WakerSlab: Unpin);
+
+    assert_impl!(WakerStats: Send);
+    assert_impl!(WakerStats: Sync);
+    assert_impl!(WakerStats: Unpin);
+
+    assert_impl!(YieldNow: Send);
+    assert_impl!(YieldNow: Sync);
+    assert_impl!(YieldNow: Unpin);
 }
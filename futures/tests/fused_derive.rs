@@ -0,0 +1,83 @@
+use futures::future::{self, FusedFuture, Future, FutureExt};
+use futures::stream::{self, FusedStream, Stream, StreamExt};
+use futures::task::{Context, Poll};
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+#[derive(FusedFuture)]
+struct Wrapper<Fut>(Fut);
+
+impl<Fut: Future + Unpin> Future for Wrapper<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_unpin(cx)
+    }
+}
+
+#[derive(FusedFuture)]
+struct Tagged<'a, Fut, T> {
+    tag: &'a str,
+    #[fused(delegate)]
+    future: Fut,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<Fut: Future + Unpin, T> Future for Tagged<'_, Fut, T> {
+    type Output = (String, Fut::Output);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let tag = self.tag.to_owned();
+        self.future.poll_unpin(cx).map(|out| (tag, out))
+    }
+}
+
+#[derive(FusedStream)]
+#[fused(crate = "::futures")]
+struct Counted<St> {
+    count: usize,
+    #[fused(delegate)]
+    stream: St,
+}
+
+impl<St: Stream + Unpin> Stream for Counted<St> {
+    type Item = St::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<St::Item>> {
+        let item = futures::ready!(self.stream.poll_next_unpin(cx));
+        self.count += item.is_some() as usize;
+        Poll::Ready(item)
+    }
+}
+
+#[test]
+fn single_field() {
+    futures::executor::block_on(async {
+        let mut fut = Wrapper(future::ready(1).fuse());
+        assert!(!fut.is_terminated());
+        assert_eq!((&mut fut).await, 1);
+        assert!(fut.is_terminated());
+    });
+}
+
+#[test]
+fn delegate_field() {
+    futures::executor::block_on(async {
+        let mut fut =
+            Tagged { tag: "a", future: future::ready(2).fuse(), _marker: PhantomData::<fn()> };
+        assert!(!fut.is_terminated());
+        assert_eq!((&mut fut).await, ("a".to_owned(), 2));
+        assert!(fut.is_terminated());
+    });
+}
+
+#[test]
+fn stream() {
+    futures::executor::block_on(async {
+        let mut st = Counted { count: 0, stream: stream::iter(1..=3).fuse() };
+        assert!(!st.is_terminated());
+        assert_eq!((&mut st).collect::<Vec<_>>().await, vec![1, 2, 3]);
+        assert_eq!(st.count, 3);
+        assert!(st.is_terminated());
+    });
+}
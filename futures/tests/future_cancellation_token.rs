@@ -0,0 +1,108 @@
+use futures::executor::block_on;
+use futures::future::{self, CancellationToken, Cancelled, FutureExt};
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::new_count_waker;
+use std::task::Context;
+use std::thread;
+
+#[test]
+fn cancel() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+    assert!(!token.is_cancelled());
+
+    clone.cancel();
+    assert!(token.is_cancelled());
+    clone.cancel();
+    assert!(token.is_cancelled());
+}
+
+#[test]
+fn cancelled_wakes_waiters() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let token = CancellationToken::new();
+    let mut a = token.cancelled();
+    let mut b = token.cancelled();
+
+    assert_eq!(a.poll_unpin(&mut cx), Poll::Pending);
+    assert_eq!(b.poll_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count, 0);
+
+    token.cancel();
+    assert_eq!(count, 2);
+    assert_eq!(a.poll_unpin(&mut cx), Poll::Ready(()));
+    assert_eq!(b.poll_unpin(&mut cx), Poll::Ready(()));
+
+    // Futures created after cancellation complete right away.
+    assert_eq!(token.cancelled().poll_unpin(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn dropped_waiter_is_not_woken() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let token = CancellationToken::new();
+    let mut fut = token.cancelled();
+
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Pending);
+    drop(fut);
+    token.cancel();
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn child_tokens() {
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+    let grandchild = child.child_token();
+
+    child.cancel();
+    assert!(child.is_cancelled());
+    assert!(grandchild.is_cancelled());
+    assert!(!parent.is_cancelled());
+
+    let other_child = parent.child_token();
+    parent.cancel();
+    assert!(other_child.is_cancelled());
+
+    // Children of a cancelled token are cancelled right away.
+    assert!(parent.child_token().is_cancelled());
+}
+
+#[test]
+fn cancel_from_other_thread() {
+    let token = CancellationToken::new();
+    let child = token.child_token();
+    let handle = thread::spawn(move || token.cancel());
+
+    block_on(child.cancelled());
+    handle.join().unwrap();
+}
+
+#[test]
+fn with_cancellation() {
+    let token = CancellationToken::new();
+    assert_eq!(block_on(future::ready(1).with_cancellation(token.clone())), Ok(1));
+
+    let (waker, _count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = future::pending::<()>().with_cancellation(token.clone());
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Pending);
+    token.cancel();
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Ready(Err(Cancelled)));
+}
+
+#[test]
+fn take_until_cancelled() {
+    let token = CancellationToken::new();
+    let mut stream = stream::repeat(1).take_until_cancelled(token.clone());
+
+    block_on(async {
+        assert_eq!(stream.next().await, Some(1));
+        assert_eq!(stream.next().await, Some(1));
+        token.cancel();
+        assert_eq!(stream.next().await, None);
+    });
+}
@@ -0,0 +1,75 @@
+use futures::executor::block_on;
+use futures::future::{self, Either3, Either8, FusedFuture, FutureExt};
+use futures::sink::SinkExt;
+use futures::stream::{self, Stream, StreamExt};
+use futures::task::Context;
+
+type Branch = future::Fuse<future::Ready<u8>>;
+
+type Three =
+    Either3<future::Ready<u8>, future::Lazy<fn(&mut Context<'_>) -> u8>, future::Pending<u8>>;
+
+fn three(n: u8) -> Three {
+    match n {
+        0 => Either3::A(future::ready(0)),
+        1 => Either3::B(future::lazy(|_| 1)),
+        _ => Either3::C(future::pending()),
+    }
+}
+
+#[test]
+fn polls_the_active_branch() {
+    assert_eq!(three(0).now_or_never(), Some(0));
+    assert_eq!(three(1).now_or_never(), Some(1));
+    assert_eq!(three(2).now_or_never(), None);
+}
+
+#[test]
+fn into_inner() {
+    let e: Either3<u8, u8, u8> = Either3::C(3);
+    assert_eq!(e.into_inner(), 3);
+}
+
+#[test]
+fn fused_future() {
+    let mut e: Either8<Branch, Branch, Branch, Branch, Branch, Branch, Branch, Branch> =
+        Either8::H(future::ready(8).fuse());
+    assert!(!e.is_terminated());
+    assert_eq!(block_on(&mut e), 8);
+    assert!(e.is_terminated());
+}
+
+#[test]
+fn stream() {
+    let s: Either3<_, stream::Empty<u8>, stream::Pending<u8>> =
+        Either3::A(stream::iter(vec![1, 2, 3]));
+    assert_eq!(s.size_hint(), (3, Some(3)));
+    assert_eq!(block_on(s.collect::<Vec<_>>()), vec![1, 2, 3]);
+}
+
+#[test]
+fn sink() {
+    let mut sink: Either3<Vec<u8>, Vec<u8>, Vec<u8>> = Either3::B(Vec::new());
+    block_on(sink.send(4)).unwrap();
+    assert_eq!(sink.into_inner(), vec![4]);
+}
+
+#[test]
+fn constructors() {
+    let futs: Vec<Three> = vec![
+        Either3::a(future::ready(0)),
+        Either3::b(future::lazy(|_| 1)),
+        Either3::c(future::pending()),
+    ];
+    let outputs: Vec<_> = futs.into_iter().map(FutureExt::now_or_never).collect();
+    assert_eq!(outputs, vec![Some(0), Some(1), None]);
+
+    let items: Vec<Either8<u8, u8, u8, u8, u8, u8, u8, u8>> = block_on(
+        stream::iter(vec![1])
+            .map(Either8::a)
+            .chain(stream::iter(vec![8]).map(Either8::h))
+            .collect(),
+    );
+    assert!(matches!(items[0], Either8::A(1)));
+    assert!(matches!(items[1], Either8::H(8)));
+}
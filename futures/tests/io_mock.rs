@@ -0,0 +1,96 @@
+use futures::executor::block_on;
+use futures::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use futures::task::Poll;
+use futures_test::io::MockIo;
+use futures_test::task::noop_context;
+use std::io;
+use std::pin::Pin;
+
+#[test]
+fn reads_scripted_data() {
+    let mut io = MockIo::builder().read(b"hello ").pending().read(b"world").read_eof().build();
+    let mut buf = String::new();
+    block_on(io.read_to_string(&mut buf)).unwrap();
+    assert_eq!(buf, "hello world");
+    assert!(io.is_done());
+}
+
+#[test]
+fn read_split_over_small_buffers() {
+    let mut io = MockIo::builder().read(b"abcdef").build();
+    let mut buf = [0; 4];
+    assert_eq!(block_on(io.read(&mut buf)).unwrap(), 4);
+    assert_eq!(&buf, b"abcd");
+    assert_eq!(block_on(io.read(&mut buf)).unwrap(), 2);
+    assert_eq!(&buf[..2], b"ef");
+}
+
+#[test]
+fn pending_point() {
+    use futures::io::AsyncRead;
+
+    let mut cx = noop_context();
+    let mut io = MockIo::builder().pending().read(b"x").build();
+    let mut buf = [0; 1];
+    assert!(Pin::new(&mut io).poll_read(&mut cx, &mut buf).is_pending());
+    assert!(matches!(Pin::new(&mut io).poll_read(&mut cx, &mut buf), Poll::Ready(Ok(1))));
+}
+
+#[test]
+fn scripted_errors() {
+    let mut io = MockIo::builder()
+        .read_error(io::ErrorKind::ConnectionReset.into())
+        .write_error(io::ErrorKind::BrokenPipe.into())
+        .build();
+    let mut buf = [0; 4];
+    assert_eq!(block_on(io.read(&mut buf)).unwrap_err().kind(), io::ErrorKind::ConnectionReset);
+    assert_eq!(block_on(io.write(b"data")).unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn buf_writer_writes_once() {
+    let io = MockIo::builder().write(b"hello world").close().build();
+    let mut writer = BufWriter::new(io);
+    block_on(async {
+        writer.write_all(b"hello ").await?;
+        writer.write_all(b"world").await?;
+        writer.close().await
+    })
+    .unwrap();
+    assert!(writer.get_ref().is_done());
+}
+
+#[test]
+fn buf_reader_lines() {
+    let io = MockIo::builder().read(b"one\ntw").pending().read(b"o\n").read_eof().build();
+    let mut lines = Vec::new();
+    let mut reader = BufReader::new(io);
+    block_on(async {
+        let mut line = String::new();
+        while reader.read_line(&mut line).await.unwrap() != 0 {
+            lines.push(line.clone());
+            line.clear();
+        }
+    });
+    assert_eq!(lines, ["one\n", "two\n"]);
+}
+
+#[test]
+#[should_panic(expected = "write of unexpected data")]
+fn unexpected_write_data() {
+    let mut io = MockIo::builder().write(b"abc").build();
+    let _ = block_on(io.write(b"abd"));
+}
+
+#[test]
+#[should_panic(expected = "unexpected read on a mock expecting a write")]
+fn unexpected_operation() {
+    let mut io = MockIo::builder().write(b"abc").build();
+    let _ = block_on(io.read(&mut [0; 3]));
+}
+
+#[test]
+#[should_panic(expected = "mock dropped before performing the expected operations")]
+fn remaining_operations() {
+    let _io = MockIo::builder().read(b"abc").build();
+}
@@ -0,0 +1,110 @@
+use futures::channel::oneshot;
+use futures::executor::{LocalPool, SchedulingPolicy};
+use futures::task::{LocalSpawnExt, Priority, SpawnPriorityExt};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+fn message_order(policy: SchedulingPolicy) -> Vec<&'static str> {
+    let mut pool = LocalPool::builder().scheduling_policy(policy).create();
+    let spawner = pool.spawner();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (tx, rx) = oneshot::channel();
+
+    let log_ = log.clone();
+    spawner
+        .spawn_local(async move {
+            rx.await.unwrap();
+            log_.borrow_mut().push("receiver");
+        })
+        .unwrap();
+    let log_ = log.clone();
+    spawner
+        .spawn_local(async move {
+            tx.send(()).unwrap();
+            log_.borrow_mut().push("sender");
+        })
+        .unwrap();
+    let log_ = log.clone();
+    spawner.spawn_local(async move { log_.borrow_mut().push("other") }).unwrap();
+
+    pool.run();
+    let log = log.borrow().clone();
+    log
+}
+
+#[test]
+fn fifo_is_the_default() {
+    let mut pool = LocalPool::builder().create();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    for i in 0..4 {
+        let log = log.clone();
+        pool.spawner().spawn_local(async move { log.borrow_mut().push(i) }).unwrap();
+    }
+    pool.run();
+    assert_eq!(*log.borrow(), [0, 1, 2, 3]);
+
+    assert_eq!(message_order(SchedulingPolicy::Fifo), ["sender", "other", "receiver"]);
+}
+
+#[test]
+fn lifo_slot_runs_woken_task_next() {
+    assert_eq!(message_order(SchedulingPolicy::LifoSlot), ["sender", "receiver", "other"]);
+}
+
+#[test]
+fn lifo_slot_does_not_starve_other_tasks() {
+    let mut pool = LocalPool::builder().scheduling_policy(SchedulingPolicy::LifoSlot).create();
+    let spawner = pool.spawner();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    // Two tasks passing a message back and forth, always waking each other.
+    let (mut ping_tx, mut ping_rx) = futures::channel::mpsc::channel::<()>(0);
+    let (mut pong_tx, mut pong_rx) = futures::channel::mpsc::channel::<()>(0);
+    let log_ = log.clone();
+    spawner
+        .spawn_local(async move {
+            use futures::{SinkExt, StreamExt};
+            for _ in 0..20 {
+                ping_tx.send(()).await.unwrap();
+                pong_rx.next().await.unwrap();
+            }
+            log_.borrow_mut().push("ping-pong");
+        })
+        .unwrap();
+    spawner
+        .spawn_local(async move {
+            use futures::{SinkExt, StreamExt};
+            while ping_rx.next().await.is_some() {
+                let _ = pong_tx.send(()).await;
+            }
+        })
+        .unwrap();
+    let log_ = log.clone();
+    spawner.spawn_local(async move { log_.borrow_mut().push("other") }).unwrap();
+
+    pool.run();
+    assert_eq!(*log.borrow(), ["other", "ping-pong"]);
+}
+
+#[test]
+fn priority_policy() {
+    let run = |policy| {
+        let mut pool = LocalPool::builder().scheduling_policy(policy).create();
+        let spawner = pool.spawner();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        for &priority in &[Priority::Low, Priority::Normal, Priority::High] {
+            let log = log.clone();
+            spawner
+                .spawn_with_priority(async move { log.lock().unwrap().push(priority) }, priority)
+                .unwrap();
+        }
+        pool.run();
+        let log = log.lock().unwrap().clone();
+        log
+    };
+
+    assert_eq!(run(SchedulingPolicy::Priority), [Priority::High, Priority::Normal, Priority::Low]);
+    // Other policies ignore priorities.
+    assert_eq!(run(SchedulingPolicy::Fifo), [Priority::Low, Priority::Normal, Priority::High]);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "metrics")]
+
+use futures::channel::oneshot;
+use futures::executor::{block_on, LocalPool, ThreadPool};
+use futures::task::LocalSpawnExt;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn local_pool_metrics() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let metrics = pool.metrics();
+    assert_eq!(metrics.num_workers(), 1);
+
+    let (tx, rx) = oneshot::channel::<()>();
+    spawner.spawn_local(async move { rx.await.unwrap() }).unwrap();
+    spawner.spawn_local(async { thread::sleep(Duration::from_millis(10)) }).unwrap();
+    pool.run_until_stalled();
+
+    assert_eq!(metrics.spawned_tasks(), 2);
+    assert_eq!(metrics.completed_tasks(), 1);
+    assert_eq!(metrics.alive_tasks(), 1);
+    assert_eq!(metrics.queue_depth(), 0);
+    assert!(metrics.busy_time() >= Duration::from_millis(10));
+
+    tx.send(()).unwrap();
+    assert_eq!(metrics.queue_depth(), 1);
+    pool.run();
+    assert_eq!(metrics.alive_tasks(), 0);
+    assert_eq!(metrics.queue_depth(), 0);
+}
+
+#[test]
+fn thread_pool_metrics() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let metrics = pool.metrics();
+    assert_eq!(metrics.num_workers(), 2);
+
+    let (tx, rx) = oneshot::channel::<()>();
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move { rx.await.unwrap() });
+    pool.spawn_ok(async move {
+        thread::sleep(Duration::from_millis(10));
+        done_tx.send(()).unwrap();
+    });
+    block_on(done_rx).unwrap();
+
+    assert_eq!(metrics.spawned_tasks(), 2);
+    tx.send(()).unwrap();
+    pool.shutdown(None);
+    assert_eq!(metrics.completed_tasks(), 2);
+    assert_eq!(metrics.alive_tasks(), 0);
+    assert_eq!(metrics.queue_depth(), 0);
+    assert!(metrics.busy_time() >= Duration::from_millis(10));
+    let total: Duration = (0..2).map(|worker| metrics.worker_busy_time(worker)).sum();
+    assert_eq!(total, metrics.busy_time());
+}
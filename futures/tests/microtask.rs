@@ -0,0 +1,76 @@
+#![cfg(feature = "wasm")]
+
+use futures::channel::oneshot;
+use futures::executor::{run_microtasks, set_microtask_scheduler, spawn_local};
+use futures::task::yield_now;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+static SCHEDULED: AtomicUsize = AtomicUsize::new(0);
+
+fn schedule() {
+    SCHEDULED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn runs_tasks_without_blocking() {
+    // Each test runs on its own thread, with its own tasks.
+    thread::spawn(|| {
+        set_microtask_scheduler(schedule);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (tx, rx) = oneshot::channel();
+
+        let log_ = log.clone();
+        spawn_local(async move {
+            let value = rx.await.unwrap();
+            log_.borrow_mut().push(value);
+        });
+        let log_ = log.clone();
+        spawn_local(async move {
+            log_.borrow_mut().push(1);
+            yield_now().await;
+            log_.borrow_mut().push(2);
+        });
+        assert_eq!(SCHEDULED.load(Ordering::SeqCst), 1);
+        assert!(log.borrow().is_empty());
+
+        // The yielding task is left for another run.
+        run_microtasks();
+        assert_eq!(*log.borrow(), [1]);
+        assert_eq!(SCHEDULED.load(Ordering::SeqCst), 2);
+        run_microtasks();
+        assert_eq!(*log.borrow(), [1, 2]);
+
+        // Returns right away when no task is ready.
+        run_microtasks();
+        assert_eq!(*log.borrow(), [1, 2]);
+
+        // Wakes from other threads schedule a run.
+        thread::spawn(move || tx.send(3).unwrap()).join().unwrap();
+        assert_eq!(SCHEDULED.load(Ordering::SeqCst), 3);
+        run_microtasks();
+        assert_eq!(*log.borrow(), [1, 2, 3]);
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn tasks_spawned_by_tasks() {
+    thread::spawn(|| {
+        let count = Rc::new(Cell::new(0));
+        let count_ = count.clone();
+        spawn_local(async move {
+            count_.set(count_.get() + 1);
+            let count = count_.clone();
+            spawn_local(async move { count.set(count.get() + 1) });
+        });
+        // Without a scheduler, tasks run when asked to.
+        run_microtasks();
+        assert_eq!(count.get(), 2);
+    })
+    .join()
+    .unwrap();
+}
@@ -5,6 +5,9 @@
 #[cfg(target_has_atomic = "ptr")]
 pub use futures_core::task::__internal::AtomicWaker as _;
 
+#[cfg(feature = "futures-core-critical-section")]
+pub use futures_core::task::__internal::AtomicWaker as _;
+
 #[cfg(feature = "futures-task-alloc")]
 #[cfg(target_has_atomic = "ptr")]
 pub use futures_task::ArcWake as _;
@@ -13,6 +16,18 @@ pub use futures_task::ArcWake as _;
 #[cfg(target_has_atomic = "ptr")]
 pub use futures_channel::oneshot as _;
 
+#[cfg(feature = "futures-channel-alloc")]
+#[cfg(target_has_atomic = "ptr")]
+pub use futures_channel::mpsc as _;
+
+#[cfg(feature = "futures-channel-alloc")]
+#[cfg(target_has_atomic = "ptr")]
+pub use futures_channel::spsc as _;
+
+#[cfg(feature = "futures-channel-portable-atomic")]
+#[cfg(target_has_atomic = "ptr")]
+pub use futures_channel::mpsc as _;
+
 #[cfg(any(feature = "futures", feature = "futures-alloc"))]
 #[cfg(target_has_atomic = "ptr")]
 pub use futures::task::AtomicWaker as _;
@@ -5,12 +5,12 @@ use std::rc::Rc;
 use std::sync::Arc;
 use std::task::Context;
 
-use futures::channel::mpsc;
+use futures::channel::{mpsc, oneshot};
 use futures::executor::block_on;
 use futures::future::{self, Future};
 use futures::lock::Mutex;
 use futures::sink::SinkExt;
-use futures::stream::{self, StreamExt};
+use futures::stream::{self, FusedStream, StreamExt};
 use futures::task::Poll;
 use futures::{ready, FutureExt};
 use futures_core::Stream;
@@ -442,6 +442,148 @@ fn ready_chunks() {
     });
 }
 
+#[test]
+fn chunk_by() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+
+    let mut s = rx.chunk_by(|x| x / 10);
+
+    let mut cx = noop_context();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert!(s.next().poll_unpin(&mut cx).is_pending());
+
+    // The item ending a run is kept for the next chunk.
+    tx.unbounded_send(11).unwrap();
+    assert_eq!(s.next().poll_unpin(&mut cx), Poll::Ready(Some((0, vec![1, 2]))));
+    assert!(s.next().poll_unpin(&mut cx).is_pending());
+
+    tx.unbounded_send(12).unwrap();
+    tx.unbounded_send(3).unwrap();
+    drop(tx);
+    block_on(async {
+        assert_eq!(s.next().await, Some((1, vec![11, 12])));
+        assert_eq!(s.next().await, Some((0, vec![3])));
+        assert_eq!(s.next().await, None);
+    });
+}
+
+#[test]
+fn group_by() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+
+    let mut groups = rx.group_by(|x| x / 10);
+
+    let mut cx = noop_context();
+    assert!(groups.poll_next_unpin(&mut cx).is_pending());
+    tx.unbounded_send(1).unwrap();
+    let (key, mut group) = match groups.poll_next_unpin(&mut cx) {
+        Poll::Ready(Some(group)) => group,
+        res => panic!("unexpected result: {:?}", res),
+    };
+    assert_eq!(key, 0);
+    assert_eq!(group.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert!(group.poll_next_unpin(&mut cx).is_pending());
+
+    // The item ending a run starts the next group.
+    tx.unbounded_send(2).unwrap();
+    tx.unbounded_send(11).unwrap();
+    assert_eq!(group.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(group.poll_next_unpin(&mut cx), Poll::Ready(None));
+
+    tx.unbounded_send(12).unwrap();
+    tx.unbounded_send(13).unwrap();
+    tx.unbounded_send(21).unwrap();
+    drop(tx);
+    block_on(async {
+        let (key, mut group) = groups.next().await.unwrap();
+        assert_eq!(key, 1);
+        assert_eq!(group.next().await, Some(11));
+
+        // Requesting the next group skips the rest of this one.
+        let (key, next) = groups.next().await.unwrap();
+        assert_eq!(key, 2);
+        assert_eq!(group.next().await, None);
+        assert_eq!(next.collect::<Vec<_>>().await, vec![21]);
+        assert!(groups.next().await.is_none());
+    });
+}
+
+#[test]
+fn switch_map() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (tx1, rx1) = oneshot::channel::<i32>();
+    let (tx2, rx2) = oneshot::channel::<i32>();
+    let mut receivers = vec![rx1, rx2].into_iter();
+
+    let mut s = rx.switch_map(|x| receivers.next().unwrap().map(move |y| x + y.unwrap()));
+
+    let mut cx = noop_context();
+    tx.unbounded_send(1).unwrap();
+    assert!(s.poll_next_unpin(&mut cx).is_pending());
+    assert!(!tx1.is_canceled());
+
+    // A newer item drops the in-flight future.
+    tx.unbounded_send(2).unwrap();
+    assert!(s.poll_next_unpin(&mut cx).is_pending());
+    assert!(tx1.is_canceled());
+
+    tx2.send(20).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(22)));
+    assert!(s.poll_next_unpin(&mut cx).is_pending());
+
+    drop(tx);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+#[should_panic]
+fn windows_panic_on_size_zero() {
+    let (_, rx1) = mpsc::channel::<()>(1);
+
+    let _ = rx1.windows(0);
+}
+
+#[test]
+fn windows() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+
+    let mut s = rx.windows(3);
+    assert_eq!(s.size_hint(), (0, None));
+
+    let mut cx = noop_context();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert!(s.next().poll_unpin(&mut cx).is_pending());
+
+    tx.unbounded_send(3).unwrap();
+    tx.unbounded_send(4).unwrap();
+    drop(tx);
+    block_on(async {
+        assert_eq!(s.next().await, Some(vec![1, 2, 3]));
+        assert_eq!(s.next().await, Some(vec![2, 3, 4]));
+        assert_eq!(s.next().await, None);
+    });
+
+    let s = stream::iter(1..=5).windows(3);
+    assert_eq!(s.size_hint(), (3, Some(3)));
+    assert_eq!(block_on(stream::iter(1..=2).windows(3).collect::<Vec<_>>()), Vec::<Vec<_>>::new());
+}
+
+#[test]
+fn dedup() {
+    block_on(async {
+        let stream = stream::iter(vec![1, 1, 2, 1, 1, 3, 3]).dedup();
+        assert_eq!(stream.collect::<Vec<_>>().await, vec![1, 2, 1, 3]);
+
+        // The items are never cloned.
+        struct NoClone(i32);
+        let stream = stream::iter(vec![NoClone(1), NoClone(1), NoClone(2)]).dedup_by_key(|x| x.0);
+        let items = stream.map(|x| x.0).collect::<Vec<_>>().await;
+        assert_eq!(items, vec![1, 2]);
+    });
+}
+
 struct SlowStream {
     times_should_poll: usize,
     times_polled: Rc<Cell<usize>>,
@@ -478,3 +620,83 @@ fn select_with_strategy_doesnt_terminate_early() {
         assert_eq!(count.get(), times_should_poll + 1);
     }
 }
+
+#[test]
+fn flat_map_unordered_keyed() {
+    let mut cx = noop_context();
+
+    // Each substream yields its id once its sender is dropped.
+    let (mut senders, mut receivers): (Vec<_>, Vec<_>) =
+        (0..4).map(|_| mpsc::unbounded::<usize>()).map(|(tx, rx)| (Some(tx), Some(rx))).unzip();
+    let items = vec![("a", 0), ("a", 1), ("b", 2), ("a", 3)];
+    let mut s = stream::iter(items).flat_map_unordered_keyed(
+        3,
+        1,
+        |(key, _)| *key,
+        move |(_, id)| receivers[id].take().unwrap().chain(stream::iter(Some(id))),
+    );
+
+    // Only the first "a" substream and the "b" one are polled.
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    senders[1].take();
+    senders[3].take();
+    senders[2].take();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    // The waiting "a" substreams are started in order.
+    senders[0].take();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(0)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(3)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn zip_longest() {
+    use futures::stream::EitherOrBoth::{Both, Left, Right};
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut cx = noop_context();
+
+    let mut s = rx.zip_longest(stream::iter(vec!["a", "b"]));
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Both(1, "a"))));
+
+    // An item isn't yielded alone while the other stream may yield one.
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    drop(tx);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Right("b"))));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+
+    let s = stream::iter(1..=3).zip_longest(stream::iter(vec!["a"]));
+    assert_eq!(block_on(s.collect::<Vec<_>>()), vec![Both(1, "a"), Left(2), Left(3)]);
+}
+
+#[test]
+fn map_while() {
+    let polled = Cell::new(0);
+    let s = stream::iter(1..=5).inspect(|_| polled.set(polled.get() + 1));
+    let mut s = s.map_while(|x| if x < 3 { Some(x * 10) } else { None });
+
+    assert_eq!(block_on(s.by_ref().collect::<Vec<_>>()), vec![10, 20]);
+    assert!(s.is_terminated());
+
+    // The underlying stream isn't polled once the closure returned `None`.
+    assert_eq!(block_on(s.next()), None);
+    assert_eq!(polled.get(), 3);
+}
+
+#[test]
+fn collect_into() {
+    let mut buffer = vec![1];
+    let output = block_on(stream::iter(2..=3).collect_into(&mut buffer));
+    assert_eq!(output, &[1, 2, 3]);
+
+    // The allocation of the collection is reused.
+    buffer.clear();
+    let capacity = buffer.capacity();
+    block_on(stream::iter(4..=6).collect_into(&mut buffer));
+    assert_eq!(buffer, vec![4, 5, 6]);
+    assert_eq!(buffer.capacity(), capacity);
+}
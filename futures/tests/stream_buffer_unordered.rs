@@ -1,8 +1,11 @@
 use futures::channel::{mpsc, oneshot};
 use futures::executor::{block_on, block_on_stream};
 use futures::sink::SinkExt;
-use futures::stream::StreamExt;
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::new_count_waker;
 use std::sync::mpsc as std_mpsc;
+use std::task::Context;
 use std::thread;
 
 #[test]
@@ -71,3 +74,53 @@ fn works() {
     t1.join().unwrap();
     t2.join().unwrap();
 }
+
+#[test]
+fn concurrency_limit_can_change() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let (tx1, rx1) = oneshot::channel::<i32>();
+    let (tx2, rx2) = oneshot::channel::<i32>();
+    let (tx3, rx3) = oneshot::channel::<i32>();
+    let mut s = stream::iter(vec![rx1, rx2, rx3]).buffer_unordered(1);
+    let limit = s.concurrency_limit();
+    assert_eq!(limit.get(), Some(1));
+
+    tx2.send(2).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    // Raising the limit starts the next future.
+    let awoken = count.get();
+    limit.set(2);
+    assert_eq!(count, awoken + 1);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(2))));
+
+    // Lowering it waits for the running future to complete.
+    limit.set(1);
+    tx3.send(3).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    tx1.send(1).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(1))));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(3))));
+
+    limit.set(None);
+    assert_eq!(limit.get(), None);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn indexed() {
+    let (tx1, rx1) = oneshot::channel::<i32>();
+    let (tx2, rx2) = oneshot::channel::<i32>();
+    let (tx3, rx3) = oneshot::channel::<i32>();
+    let mut s = stream::iter(vec![rx1, rx2, rx3]).buffer_unordered_indexed(2);
+
+    tx2.send(2).unwrap();
+    assert_eq!(block_on(s.next()), Some((1, Ok(2))));
+    tx3.send(3).unwrap();
+    assert_eq!(block_on(s.next()), Some((2, Ok(3))));
+    tx1.send(1).unwrap();
+    assert_eq!(block_on(s.next()), Some((0, Ok(1))));
+    assert_eq!(block_on(s.next()), None);
+}
@@ -0,0 +1,64 @@
+use futures::channel::mpsc;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, MockClock};
+use std::task::Context;
+use std::time::Duration;
+
+#[test]
+fn yields_full_chunks() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, _) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut s = rx.chunks_timeout(2, Duration::from_secs(1), &clock);
+    for i in 1..=5 {
+        tx.unbounded_send(i).unwrap();
+    }
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(vec![1, 2])));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(vec![3, 4])));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop(tx);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(vec![5])));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(clock.pending_sleeps(), 0);
+}
+
+#[test]
+fn yields_partial_chunk_after_timeout() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut s = rx.chunks_timeout(3, Duration::from_secs(1), &clock);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    // The timeout runs from the first buffered item.
+    clock.advance(Duration::from_secs(5));
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_millis(500));
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    let before = count.get();
+    clock.advance(Duration::from_millis(500));
+    assert_eq!(count.get(), before + 1);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(vec![1, 2])));
+
+    // No timeout runs without buffered items.
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(clock.pending_sleeps(), 0);
+}
+
+#[test]
+#[should_panic]
+fn panics_on_cap_zero() {
+    let clock = MockClock::new();
+    let (_, rx) = mpsc::unbounded::<()>();
+
+    let _ = rx.chunks_timeout(0, Duration::from_secs(1), &clock);
+}
@@ -0,0 +1,53 @@
+use futures::channel::mpsc;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, MockClock};
+use std::task::Context;
+use std::time::Duration;
+
+#[test]
+fn yields_latest_item_after_quiescence() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut s = rx.debounce(Duration::from_millis(100), &clock);
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    clock.advance(Duration::from_millis(50));
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    // The first deadline passed, but the second item moved it.
+    clock.advance(Duration::from_millis(50));
+    assert_eq!(count, 2);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_millis(49));
+    assert_eq!(count, 2);
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(count, 3);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(clock.pending_sleeps(), 0);
+}
+
+#[test]
+fn yields_latest_item_when_stream_ends() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, _) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut s = rx.debounce(Duration::from_secs(1), &clock);
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop(tx);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(clock.pending_sleeps(), 0);
+}
@@ -0,0 +1,52 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::stream::{self, Stream, StreamExt};
+use futures::task::Poll;
+use futures_test::task::noop_context;
+
+#[test]
+fn merges_sorted_streams() {
+    let merged = stream::kmerge(vec![
+        stream::iter(vec![1, 5, 9]),
+        stream::iter(vec![]),
+        stream::iter(vec![2, 3, 10]),
+        stream::iter(vec![4]),
+    ]);
+    assert_eq!(merged.size_hint(), (7, Some(7)));
+    assert_eq!(block_on(merged.collect::<Vec<_>>()), vec![1, 2, 3, 4, 5, 9, 10]);
+
+    let merged = stream::kmerge(Vec::<stream::Iter<std::vec::IntoIter<i32>>>::new());
+    assert_eq!(block_on(merged.collect::<Vec<_>>()), vec![]);
+}
+
+#[test]
+fn waits_for_every_stream() {
+    let (tx1, rx1) = mpsc::unbounded::<i32>();
+    let (tx2, rx2) = mpsc::unbounded::<i32>();
+    let mut cx = noop_context();
+
+    let mut merged = stream::kmerge(vec![rx1, rx2]);
+    tx1.unbounded_send(2).unwrap();
+    assert!(merged.poll_next_unpin(&mut cx).is_pending());
+
+    tx2.unbounded_send(1).unwrap();
+    tx2.unbounded_send(3).unwrap();
+    assert_eq!(merged.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(merged.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+
+    // The next item of the first stream is needed to order `3`.
+    assert!(merged.poll_next_unpin(&mut cx).is_pending());
+    drop(tx1);
+    assert_eq!(merged.poll_next_unpin(&mut cx), Poll::Ready(Some(3)));
+    drop(tx2);
+    assert_eq!(merged.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn merge_by_is_stable() {
+    let merged = stream::kmerge_by(
+        vec![stream::iter(vec![(1, 'a'), (2, 'a')]), stream::iter(vec![(1, 'b'), (2, 'b')])],
+        |a: &(i32, char), b: &(i32, char)| a.0 < b.0,
+    );
+    assert_eq!(block_on(merged.collect::<Vec<_>>()), vec![(1, 'a'), (1, 'b'), (2, 'a'), (2, 'b')]);
+}
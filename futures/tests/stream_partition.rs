@@ -0,0 +1,65 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future;
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+
+#[test]
+fn splits_by_predicate() {
+    let (even, odd) = stream::iter(1..=10).partition(2, |x| x % 2 == 0);
+    let (even, odd) = block_on(future::join(even.collect::<Vec<_>>(), odd.collect::<Vec<_>>()));
+    assert_eq!(even, vec![2, 4, 6, 8, 10]);
+    assert_eq!(odd, vec![1, 3, 5, 7, 9]);
+}
+
+#[test]
+fn applies_backpressure() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let (mut even, mut odd) = rx.partition(2, |x| x % 2 == 0);
+    for i in [1, 3, 5, 2].iter() {
+        tx.unbounded_send(*i).unwrap();
+    }
+
+    // Only two odd items are buffered.
+    assert_eq!(even.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count, 0);
+
+    assert_eq!(odd.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(count, 1);
+    assert_eq!(even.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(odd.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(3)));
+    assert_eq!(odd.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(5)));
+    assert_eq!(even.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+
+    drop(tx);
+    assert_eq!(even.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(odd.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn dropped_half_is_skipped() {
+    let (even, odd) = stream::iter(1..=10).partition(1, |x| x % 2 == 0);
+    drop(odd);
+    assert_eq!(block_on(even.collect::<Vec<_>>()), vec![2, 4, 6, 8, 10]);
+}
+
+#[test]
+fn dropping_half_wakes_other_half() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let (mut even, odd) = rx.partition(1, |x| x % 2 == 0);
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(even.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop(odd);
+    assert_eq!(count, 1);
+    assert_eq!(even.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+}
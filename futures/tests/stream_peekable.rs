@@ -1,6 +1,6 @@
 use futures::executor::block_on;
 use futures::pin_mut;
-use futures::stream::{self, Peekable, StreamExt};
+use futures::stream::{self, Peekable, Stream, StreamExt};
 
 #[test]
 fn peekable() {
@@ -56,3 +56,58 @@ fn peekable_next_if_eq() {
         assert_eq!(s.as_mut().next_if_eq("").await, None);
     });
 }
+
+#[test]
+fn peekable_peek_n() {
+    block_on(async {
+        let s = stream::iter(vec![1u8, 2, 3, 4]).peekable();
+        pin_mut!(s);
+        assert!(s.peek_slice().is_empty());
+        assert_eq!(s.as_mut().peek_n(2).await, &[1, 2]);
+        assert_eq!(s.as_mut().peek().await, Some(&1));
+        assert_eq!(s.as_mut().peek_n(1).await, &[1]);
+        assert_eq!(s.peek_slice(), &[1, 2]);
+        assert_eq!(s.as_mut().next().await, Some(1));
+        // `next_if` keeps the items peeked after the next one.
+        assert_eq!(s.as_mut().next_if(|&x| x == 3).await, None);
+        assert_eq!(s.peek_slice(), &[2]);
+        assert_eq!(s.as_mut().peek_n(5).await, &[2, 3, 4]);
+        assert_eq!(s.size_hint(), (3, Some(3)));
+        assert_eq!(s.collect::<Vec<_>>().await, vec![2, 3, 4]);
+    });
+}
+
+#[test]
+fn peekable_next_n_if() {
+    block_on(async {
+        let s = stream::iter(vec!["let", "x", "=", "1"]).peekable();
+        pin_mut!(s);
+        assert_eq!(s.as_mut().next_n_if(2, |t| t == ["let", "y"]).await, None);
+        assert_eq!(s.as_mut().next_n_if(2, |t| t == ["let", "x"]).await, Some(vec!["let", "x"]));
+        assert!(s.peek_slice().is_empty());
+        // The condition isn't checked if the stream ends first.
+        assert_eq!(s.as_mut().next_n_if(3, |_| true).await, None);
+        assert_eq!(s.peek_slice(), &["=", "1"]);
+        assert_eq!(s.as_mut().next_n_if(2, |_| true).await, Some(vec!["=", "1"]));
+        assert_eq!(s.next().await, None);
+    });
+}
+
+#[test]
+fn peekable_peek_n_wraps_around() {
+    block_on(async {
+        let s = stream::iter(0..100u32).peekable();
+        pin_mut!(s);
+        // Repeatedly consuming one item and peeking one more moves the peeked
+        // items around the buffer.
+        for i in 0..90 {
+            assert_eq!(s.as_mut().peek_n(4).await, &[i, i + 1, i + 2, i + 3]);
+            assert_eq!(s.as_mut().next().await, Some(i));
+            assert_eq!(s.peek_slice(), &[i + 1, i + 2, i + 3]);
+        }
+        // Putting an item back in front keeps the slice in order too.
+        assert_eq!(s.as_mut().next_if(|&x| x == 0).await, None);
+        assert_eq!(s.peek_slice(), &[90, 91, 92]);
+        assert_eq!(s.collect::<Vec<_>>().await, (90..100).collect::<Vec<_>>());
+    });
+}
@@ -0,0 +1,68 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{noop_context, MockClock};
+use std::time::Duration;
+
+#[test]
+fn yields_burst_then_delays() {
+    let clock = MockClock::new();
+    let mut cx = noop_context();
+
+    let mut s = stream::iter(1..=5).rate_limit(Duration::from_secs(1), 2, &clock);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(clock.pending_sleeps(), 1);
+
+    clock.advance(Duration::from_millis(999));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(3)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    // Tokens are gained back while the stream is idle, up to `burst`.
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(4)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(5)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn does_not_poll_stream_without_token() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut cx = noop_context();
+
+    let mut s = rx.rate_limit(Duration::from_secs(1), 1, &clock);
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    // The stream ending isn't noticed until it can be polled.
+    drop(tx);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn ends_with_stream() {
+    let clock = MockClock::new();
+    let s = stream::iter(1..=3).rate_limit(Duration::from_secs(1), 4, &clock);
+    assert_eq!(block_on(s.collect::<Vec<_>>()), vec![1, 2, 3]);
+}
+
+#[test]
+#[should_panic]
+fn panic_on_burst_zero() {
+    let clock = MockClock::new();
+    let _ = stream::empty::<()>().rate_limit(Duration::from_secs(1), 0, &clock);
+}
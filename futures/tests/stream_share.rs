@@ -0,0 +1,86 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future;
+use futures::stream::{self, LagPolicy, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+
+#[test]
+fn every_handle_sees_every_item() {
+    let first = stream::iter(1..=10).share(3);
+    let second = first.clone();
+    let third = second.clone();
+    let all = block_on(future::join_all(
+        vec![first, second, third].into_iter().map(|s| s.collect::<Vec<_>>()),
+    ));
+    for items in all {
+        assert_eq!(items, (1..=10).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn clone_starts_at_position_of_original() {
+    let mut first = stream::iter(1..=4).share(4);
+    assert_eq!(block_on(first.next()), Some(1));
+    let second = first.clone();
+    assert_eq!(block_on(first.collect::<Vec<_>>()), vec![2, 3, 4]);
+    assert_eq!(block_on(second.collect::<Vec<_>>()), vec![2, 3, 4]);
+}
+
+#[test]
+fn lagging_handle_blocks() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut fast = rx.share(2);
+    let mut slow = fast.clone();
+    for i in 1..=3 {
+        tx.unbounded_send(i).unwrap();
+    }
+
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count, 0);
+
+    assert_eq!(slow.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(count, 1);
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Ready(Some(3)));
+    assert_eq!(slow.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(2)));
+    assert_eq!(slow.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(3)));
+
+    drop(tx);
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(slow.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn lagging_handle_drops_oldest() {
+    let mut fast = stream::iter(1..=5).share(2);
+    let mut slow = fast.clone();
+    slow.set_lag_policy(LagPolicy::DropOldest);
+    assert_eq!(slow.lag_policy(), LagPolicy::DropOldest);
+
+    assert_eq!(block_on(fast.by_ref().collect::<Vec<_>>()), vec![1, 2, 3, 4, 5]);
+    assert_eq!(block_on(slow.collect::<Vec<_>>()), vec![4, 5]);
+}
+
+#[test]
+fn dropping_lagging_handle_wakes_others() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut fast = rx.share(1);
+    let slow = fast.clone();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop(slow);
+    assert_eq!(count, 1);
+    assert_eq!(fast.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+}
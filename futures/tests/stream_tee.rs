@@ -0,0 +1,59 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future;
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+
+#[test]
+fn both_halves_see_every_item() {
+    let (left, right) = stream::iter(1..=10).tee();
+    let (left, right) = block_on(future::join(left.collect::<Vec<_>>(), right.collect::<Vec<_>>()));
+    assert_eq!(left, (1..=10).collect::<Vec<_>>());
+    assert_eq!(right, left);
+}
+
+#[test]
+fn faster_half_waits_for_slower_half() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let (mut left, mut right) = rx.tee();
+    for i in 1..=2 {
+        tx.unbounded_send(i).unwrap();
+    }
+
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count, 0);
+
+    assert_eq!(right.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(1)));
+    assert_eq!(count, 1);
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    assert_eq!(right.poll_next_unpin(&mut noop_context()), Poll::Ready(Some(2)));
+
+    drop(tx);
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(right.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn dropping_half_wakes_other_half() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let (mut left, right) = rx.tee();
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop(right);
+    assert_eq!(count, 1);
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    drop(tx);
+    assert_eq!(left.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
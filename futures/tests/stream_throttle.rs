@@ -0,0 +1,37 @@
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{noop_context, MockClock};
+use std::time::Duration;
+
+#[test]
+fn drops_items_within_interval() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut cx = noop_context();
+
+    let mut s = rx.throttle(Duration::from_secs(1), &clock);
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    clock.advance(Duration::from_millis(999));
+    tx.unbounded_send(3).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    clock.advance(Duration::from_millis(1));
+    tx.unbounded_send(4).unwrap();
+    tx.unbounded_send(5).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(4)));
+    drop(tx);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn yields_first_item() {
+    let clock = MockClock::new();
+    let s = stream::iter(1..=3).throttle(Duration::from_secs(1), &clock);
+    assert_eq!(block_on(s.collect::<Vec<_>>()), vec![1]);
+}
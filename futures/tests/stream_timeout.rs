@@ -0,0 +1,52 @@
+use futures::channel::mpsc;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, MockClock};
+use std::task::Context;
+use std::time::Duration;
+
+#[test]
+fn yields_items_before_timeout() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, _) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut s = rx.timeout(Duration::from_secs(1), &clock);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_millis(999));
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(1))));
+
+    // The item reset the timeout.
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_millis(999));
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+
+    drop(tx);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(None));
+    assert_eq!(clock.pending_sleeps(), 0);
+}
+
+#[test]
+fn yields_elapsed_on_each_stalled_wait() {
+    let clock = MockClock::new();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut s = rx.timeout(Duration::from_secs(1), &clock);
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(count, 1);
+    assert!(matches!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Err(_)))));
+
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Pending);
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(count, 2);
+    assert!(matches!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Err(_)))));
+
+    // The stream keeps going after a timeout.
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(s.poll_next_unpin(&mut cx), Poll::Ready(Some(Ok(1))));
+}
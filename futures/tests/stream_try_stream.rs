@@ -1,4 +1,5 @@
 use futures::{
+    pin_mut,
     stream::{self, StreamExt, TryStreamExt},
     task::Poll,
 };
@@ -77,3 +78,16 @@ fn try_flatten_unordered() {
         )
     })
 }
+
+#[test]
+fn try_collect_into() {
+    let mut buffer = Vec::new();
+    let s = stream::iter(vec![Ok(1), Ok(2), Err("e"), Ok(3)]);
+    pin_mut!(s);
+
+    // The successful results read before the error are kept.
+    assert_eq!(block_on(s.as_mut().try_collect_into(&mut buffer)), Err("e"));
+    assert_eq!(buffer, vec![1, 2]);
+
+    assert_eq!(block_on(s.try_collect_into(&mut buffer)), Ok(&mut vec![1, 2, 3]));
+}
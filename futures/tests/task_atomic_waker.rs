@@ -1,6 +1,7 @@
 use futures::executor::block_on;
 use futures::future::poll_fn;
 use futures::task::{AtomicWaker, Poll};
+use futures_test::task::new_count_waker;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -46,3 +47,81 @@ fn basic() {
 
     t.join().unwrap();
 }
+
+#[test]
+fn register_same_waker() {
+    let atomic_waker = AtomicWaker::new();
+    let (waker, count) = new_count_waker();
+
+    atomic_waker.register(&waker);
+    atomic_waker.register(&waker);
+    atomic_waker.wake();
+    assert_eq!(count, 1);
+
+    // The registration was consumed by the wake above.
+    atomic_waker.wake();
+    assert_eq!(count, 1);
+
+    let (other_waker, other_count) = new_count_waker();
+    atomic_waker.register(&waker);
+    atomic_waker.register(&other_waker);
+    atomic_waker.wake();
+    assert_eq!(count, 1);
+    assert_eq!(other_count, 1);
+}
+
+#[test]
+fn swap() {
+    let atomic_waker = AtomicWaker::new();
+    let (waker, count) = new_count_waker();
+    let (other_waker, other_count) = new_count_waker();
+
+    assert!(atomic_waker.swap(&waker).is_none());
+
+    let old_waker = atomic_waker.swap(&other_waker).unwrap();
+    assert!(old_waker.will_wake(&waker));
+
+    atomic_waker.wake();
+    assert_eq!(count, 0);
+    assert_eq!(other_count, 1);
+
+    old_waker.wake();
+    assert_eq!(count, 1);
+
+    // The registration was consumed by the wake above.
+    assert!(atomic_waker.swap(&waker).is_none());
+}
+
+#[test]
+fn is_registered() {
+    let atomic_waker = AtomicWaker::new();
+    let (waker, _count) = new_count_waker();
+    assert!(!atomic_waker.is_registered());
+
+    atomic_waker.register(&waker);
+    assert!(atomic_waker.is_registered());
+
+    assert!(atomic_waker.take().is_some());
+    assert!(!atomic_waker.is_registered());
+
+    atomic_waker.swap(&waker);
+    assert!(atomic_waker.is_registered());
+
+    atomic_waker.wake();
+    assert!(!atomic_waker.is_registered());
+}
+
+#[test]
+fn wake_if_registered() {
+    let atomic_waker = AtomicWaker::new();
+    let (waker, count) = new_count_waker();
+
+    assert!(!atomic_waker.wake_if_registered());
+
+    atomic_waker.register(&waker);
+    assert!(atomic_waker.wake_if_registered());
+    assert_eq!(count, 1);
+
+    assert!(!atomic_waker.wake_if_registered());
+    assert_eq!(count, 1);
+}
@@ -0,0 +1,213 @@
+use futures::channel::mpsc;
+use futures::executor::{block_on, LocalPool};
+use futures::future::{self, FutureExt};
+use futures::stream::{self, FuturesUnordered, StreamExt};
+use futures::task::{coop, unconstrained, LocalSpawnExt, Poll};
+use futures_test::task::new_count_waker;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::task::Context;
+
+fn filled(n: usize) -> mpsc::UnboundedReceiver<usize> {
+    let (tx, rx) = mpsc::unbounded();
+    for i in 0..n {
+        tx.unbounded_send(i).unwrap();
+    }
+    rx
+}
+
+#[test]
+fn unlimited_outside_of_budget() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut stream = stream::repeat(());
+
+    for _ in 0..1000 {
+        assert_eq!(stream.next().poll_unpin(&mut cx), Poll::Ready(Some(())));
+    }
+    assert!(coop::has_budget_remaining());
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn exhausted_budget_yields() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut stream = stream::repeat(());
+
+    coop::budget(|| {
+        for _ in 0..coop::INITIAL_BUDGET {
+            assert_eq!(stream.next().poll_unpin(&mut cx), Poll::Ready(Some(())));
+        }
+        assert!(!coop::has_budget_remaining());
+        assert_eq!(stream.next().poll_unpin(&mut cx), Poll::Pending);
+        assert_eq!(count, 1);
+    });
+
+    // The budget is only set for the duration of the call.
+    assert!(coop::has_budget_remaining());
+}
+
+#[test]
+fn pending_doesnt_consume_budget() {
+    let (waker, _count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let (_tx, mut rx) = mpsc::unbounded::<()>();
+
+    coop::budget(|| {
+        for _ in 0..1000 {
+            assert_eq!(rx.next().poll_unpin(&mut cx), Poll::Pending);
+        }
+        assert!(coop::has_budget_remaining());
+    });
+}
+
+#[test]
+fn channel_receiver_consumes_budget() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let (tx, mut rx) = mpsc::unbounded();
+    for i in 0..1000 {
+        tx.unbounded_send(i).unwrap();
+    }
+
+    coop::budget(|| {
+        let mut received = 0;
+        while let Poll::Ready(Some(_)) = rx.poll_next_unpin(&mut cx) {
+            received += 1;
+        }
+        assert_eq!(received, coop::INITIAL_BUDGET as usize);
+        assert_eq!(count, 1);
+    });
+}
+
+#[test]
+fn receiver_in_next_consumes_budget_once() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut rx = filled(1000);
+
+    coop::budget(|| {
+        for i in 0..coop::INITIAL_BUDGET as usize {
+            assert_eq!(rx.next().poll_unpin(&mut cx), Poll::Ready(Some(i)));
+        }
+        assert!(!coop::has_budget_remaining());
+        assert_eq!(rx.next().poll_unpin(&mut cx), Poll::Pending);
+        assert_eq!(count, 1);
+    });
+}
+
+#[test]
+fn peeked_message_consumes_budget_once() {
+    let (waker, _count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut rx = filled(1000);
+
+    coop::budget(|| {
+        for i in 0..coop::INITIAL_BUDGET as usize {
+            assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Some(&i)));
+            assert_eq!(rx.poll_peek(&mut cx), Poll::Ready(Some(&i)));
+            assert_eq!(rx.poll_next_unpin(&mut cx), Poll::Ready(Some(i)));
+        }
+        assert!(!coop::has_budget_remaining());
+    });
+}
+
+#[test]
+fn futures_unordered_consumes_budget() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut futures = (0..1000).map(future::ready).collect::<FuturesUnordered<_>>();
+
+    coop::budget(|| {
+        let mut received = 0;
+        while let Poll::Ready(Some(_)) = futures.next().poll_unpin(&mut cx) {
+            received += 1;
+        }
+        assert_eq!(received, coop::INITIAL_BUDGET as usize);
+        assert_eq!(count, 1);
+    });
+}
+
+#[test]
+fn unconstrained_opts_out() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    coop::budget(|| {
+        let mut fut = Box::pin(unconstrained(async {
+            let mut stream = stream::repeat(());
+            for _ in 0..1000 {
+                stream.next().await;
+            }
+        }));
+        assert_eq!(fut.poll_unpin(&mut cx), Poll::Ready(()));
+        assert_eq!(count, 0);
+
+        // The enclosing budget is left untouched.
+        let mut stream = stream::repeat(());
+        for _ in 0..coop::INITIAL_BUDGET {
+            assert_eq!(stream.next().poll_unpin(&mut cx), Poll::Ready(Some(())));
+        }
+    });
+}
+
+#[test]
+fn ready_stream_doesnt_starve_local_pool() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let ticks = Rc::new(Cell::new(0));
+
+    {
+        let ticks = ticks.clone();
+        spawner
+            .spawn_local(async move {
+                let mut stream = stream::repeat(());
+                while ticks.get() == 0 {
+                    stream.next().await;
+                }
+            })
+            .unwrap();
+    }
+    {
+        let ticks = ticks.clone();
+        spawner.spawn_local(async move { ticks.set(ticks.get() + 1) }).unwrap();
+    }
+
+    pool.run();
+    assert_eq!(ticks.get(), 1);
+}
+
+#[test]
+fn local_pool_budgets_each_task() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let had_budget = Rc::new(Cell::new(false));
+
+    spawner
+        .spawn_local(async move {
+            let mut stream = stream::repeat(()).take(1000);
+            while stream.next().await.is_some() {}
+        })
+        .unwrap();
+    {
+        let had_budget = had_budget.clone();
+        spawner.spawn_local(async move { had_budget.set(coop::has_budget_remaining()) }).unwrap();
+    }
+
+    pool.run();
+    assert!(had_budget.get());
+}
+
+#[test]
+fn block_on_gives_a_budget() {
+    block_on(async {
+        let mut stream = stream::repeat(());
+        for _ in 0..coop::INITIAL_BUDGET {
+            stream.next().await;
+        }
+        assert!(!coop::has_budget_remaining());
+        stream.next().await;
+        assert!(coop::has_budget_remaining());
+    });
+}
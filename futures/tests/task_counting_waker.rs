@@ -0,0 +1,63 @@
+use futures::task::{CountingWaker, WakerStats};
+use futures_test::task::new_count_waker;
+use std::thread;
+
+#[test]
+fn counts_clones_wakes_and_drops() {
+    let (inner, count) = new_count_waker();
+    let counting = CountingWaker::new(inner);
+    assert_eq!(counting.stats(), WakerStats::default());
+
+    let waker = counting.waker();
+    let clone = waker.clone();
+    waker.wake_by_ref();
+    clone.wake();
+    assert_eq!(count, 2);
+
+    let stats = counting.stats();
+    assert_eq!(stats.clones, 2);
+    assert_eq!(stats.wakes, 2);
+    assert_eq!(stats.wakes_by_ref, 1);
+    assert_eq!(stats.drops, 1);
+    assert_eq!(stats.live(), 1);
+
+    drop(waker);
+    assert_eq!(counting.stats().live(), 0);
+}
+
+#[test]
+fn reset() {
+    let (inner, _count) = new_count_waker();
+    let counting = CountingWaker::new(inner);
+    counting.waker().wake();
+
+    counting.reset();
+    assert_eq!(counting.stats(), WakerStats::default());
+}
+
+#[test]
+fn counts_across_threads() {
+    let (inner, count) = new_count_waker();
+    let counting = CountingWaker::new(inner);
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let waker = counting.waker();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    let clone = waker.clone();
+                    clone.wake();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let stats = counting.stats();
+    assert_eq!(stats.clones, 404);
+    assert_eq!(stats.wakes, 400);
+    assert_eq!(stats.live(), 0);
+    assert_eq!(count, 400);
+}
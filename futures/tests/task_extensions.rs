@@ -0,0 +1,123 @@
+use futures::executor::{block_on, with_extensions, LocalPool, ThreadPool};
+use futures::future::{self, poll_fn};
+use futures::task::{
+    noop_waker, poll_with_extensions, Context, ContextExt, Extensions, LocalSpawnExt, Poll,
+};
+use std::sync::mpsc;
+use std::thread;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Deadline(u64);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Priority(u8);
+
+fn extensions(deadline: u64) -> Extensions {
+    let mut extensions = Extensions::new();
+    extensions.insert(Deadline(deadline));
+    extensions
+}
+
+#[test]
+fn extensions_map() {
+    let mut extensions = Extensions::new();
+    assert!(extensions.is_empty());
+
+    assert_eq!(extensions.insert(Deadline(1)), None);
+    assert_eq!(extensions.insert(Deadline(2)), Some(Deadline(1)));
+    assert_eq!(extensions.get::<Deadline>(), Some(&Deadline(2)));
+    assert_eq!(extensions.get::<Priority>(), None);
+
+    extensions.get_mut::<Deadline>().unwrap().0 = 3;
+    assert_eq!(extensions.len(), 1);
+    assert_eq!(extensions.remove::<Deadline>(), Some(Deadline(3)));
+    assert!(extensions.is_empty());
+}
+
+#[test]
+fn scoped_to_poll() {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let outer = extensions(1);
+    let mut inner = extensions(2);
+    inner.insert(Priority(7));
+
+    assert_eq!(cx.get_ext::<Deadline>(), None);
+    poll_with_extensions(&outer, &mut cx, |cx| {
+        assert_eq!(cx.get_ext::<Deadline>(), Some(Deadline(1)));
+        poll_with_extensions(&inner, cx, |cx| {
+            assert_eq!(cx.get_ext::<Deadline>(), Some(Deadline(2)));
+            assert_eq!(cx.get_ext::<Priority>(), Some(Priority(7)));
+        });
+        assert_eq!(cx.get_ext::<Deadline>(), Some(Deadline(1)));
+        assert_eq!(cx.get_ext::<Priority>(), None);
+    });
+    assert_eq!(cx.get_ext::<Deadline>(), None);
+}
+
+#[test]
+fn scoped_to_waker() {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let extensions = extensions(1);
+
+    poll_with_extensions(&extensions, &mut cx, |cx| {
+        // A context with the same waker sees the extensions.
+        let same = Context::from_waker(cx.waker());
+        assert_eq!(same.get_ext::<Deadline>(), Some(Deadline(1)));
+
+        // A nested executor polls with a waker of its own.
+        let other_waker = noop_waker();
+        let other = Context::from_waker(&other_waker);
+        assert_eq!(other.get_ext::<Deadline>(), None);
+
+        // Neither do other threads.
+        thread::spawn(|| {
+            let waker = noop_waker();
+            assert_eq!(Context::from_waker(&waker).get_ext::<Deadline>(), None);
+        })
+        .join()
+        .unwrap();
+    });
+}
+
+#[test]
+fn attached_to_tasks() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let (tx, rx) = mpsc::channel();
+
+    for deadline in 0..3 {
+        let tx = tx.clone();
+        let fut = async move {
+            future::ready(()).await;
+            let deadline = poll_fn(|cx| Poll::Ready(cx.get_ext::<Deadline>())).await;
+            tx.send(deadline).unwrap();
+        };
+        spawner.spawn_local(with_extensions(fut, extensions(deadline))).unwrap();
+    }
+    pool.run();
+
+    let mut deadlines: Vec<_> = rx.try_iter().map(Option::unwrap).collect();
+    deadlines.sort_by_key(|deadline| deadline.0);
+    assert_eq!(deadlines, vec![Deadline(0), Deadline(1), Deadline(2)]);
+
+    // The extensions are not visible outside of the task.
+    let deadline = block_on(poll_fn(|cx| Poll::Ready(cx.get_ext::<Deadline>())));
+    assert_eq!(deadline, None);
+}
+
+#[test]
+fn attached_to_thread_pool_tasks() {
+    let pool = ThreadPool::new().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    let fut = async move {
+        let deadline = poll_fn(|cx| Poll::Ready(cx.get_ext::<Deadline>())).await;
+        tx.send(deadline).unwrap();
+    };
+    pool.spawn_ok(with_extensions(fut, extensions(5)));
+
+    assert_eq!(rx.recv().unwrap(), Some(Deadline(5)));
+}
@@ -0,0 +1,109 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, LocalPool, ThreadPool};
+use futures::future;
+use futures::stream::StreamExt;
+use futures::task::{LocalSpawnExt, TaskGroup};
+use std::rc::Rc;
+
+#[test]
+fn yields_results_in_completion_order() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let (tx, rx) = oneshot::channel::<()>();
+
+    let mut group = TaskGroup::new();
+    group
+        .spawn_local(&spawner, async move {
+            rx.await.unwrap();
+            1
+        })
+        .unwrap();
+    group.spawn_local(&spawner, async { 2 }).unwrap();
+    assert_eq!(group.len(), 2);
+
+    spawner
+        .spawn_local(async move {
+            assert_eq!(group.next().await.unwrap().unwrap(), 2);
+            tx.send(()).unwrap();
+            assert_eq!(group.next().await.unwrap().unwrap(), 1);
+            assert!(group.next().await.is_none());
+        })
+        .unwrap();
+    pool.run();
+}
+
+#[test]
+fn spawn_on_thread_pool() {
+    let pool = ThreadPool::new().unwrap();
+    let mut group = TaskGroup::new();
+    for i in 0..10 {
+        group.spawn(&pool, async move { i }).unwrap();
+    }
+
+    let sum: usize = block_on(group.map(Result::unwrap).collect::<Vec<_>>()).into_iter().sum();
+    assert_eq!(sum, 45);
+}
+
+#[test]
+fn panics_are_errors() {
+    let mut pool = LocalPool::new();
+    let mut group = TaskGroup::<()>::new();
+    group.spawn_local(&pool.spawner(), async { panic!("boom") }).unwrap();
+
+    let err = pool.run_until(group.next()).unwrap().unwrap_err();
+    assert!(err.is_panic());
+    assert!(!err.is_cancelled());
+    let payload = err.try_into_panic().unwrap();
+    assert_eq!(*payload.downcast_ref::<&str>().unwrap(), "boom");
+}
+
+#[test]
+fn abort_single_task() {
+    let mut pool = LocalPool::new();
+    let mut group = TaskGroup::new();
+    let handle = group.spawn_local(&pool.spawner(), future::pending::<()>()).unwrap();
+
+    handle.abort();
+    let err = pool.run_until(group.next()).unwrap().unwrap_err();
+    assert!(err.is_cancelled());
+    assert!(err.try_into_panic().is_err());
+}
+
+#[test]
+fn abort_all() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+    let mut group = TaskGroup::new();
+    for _ in 0..3 {
+        group.spawn_local(&spawner, future::pending::<()>()).unwrap();
+    }
+    group.spawn_local(&spawner, future::ready(())).unwrap();
+    pool.run_until_stalled();
+
+    group.abort_all();
+    let results = pool.run_until(group.collect::<Vec<_>>());
+    assert_eq!(results.iter().filter(|res| res.is_ok()).count(), 1);
+    assert_eq!(results.iter().filter(|res| matches!(res, Err(e) if e.is_cancelled())).count(), 3);
+}
+
+#[test]
+fn drop_aborts_tasks() {
+    let mut pool = LocalPool::new();
+    let data = Rc::new(());
+    let mut group = TaskGroup::new();
+    for _ in 0..3 {
+        let data = data.clone();
+        group
+            .spawn_local(&pool.spawner(), async move {
+                let _data = data;
+                future::pending::<()>().await
+            })
+            .unwrap();
+    }
+    pool.run_until_stalled();
+    assert_eq!(Rc::strong_count(&data), 4);
+
+    drop(group);
+    pool.run_until_stalled();
+    assert_eq!(Rc::strong_count(&data), 1);
+}
@@ -0,0 +1,109 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, LocalPool, TaskHooks, TaskId, ThreadPool};
+use futures::task::{yield_now, LocalSpawnExt};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq)]
+enum Event {
+    PollStart(TaskId),
+    PollEnd(TaskId, bool),
+    Wake(TaskId),
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<Event>>>);
+
+impl Recorder {
+    fn events(&self) -> Vec<Event> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl TaskHooks for Recorder {
+    fn on_poll_start(&self, id: TaskId) {
+        self.0.lock().unwrap().push(Event::PollStart(id));
+    }
+
+    fn on_poll_end(&self, id: TaskId, completed: bool) {
+        self.0.lock().unwrap().push(Event::PollEnd(id, completed));
+    }
+
+    fn on_wake(&self, id: TaskId) {
+        self.0.lock().unwrap().push(Event::Wake(id));
+    }
+}
+
+#[test]
+fn local_pool() {
+    let recorder = Recorder::default();
+    let mut pool = LocalPool::with_task_hooks(recorder.clone());
+    pool.spawner().spawn_local(yield_now()).unwrap();
+    pool.run();
+
+    let events = recorder.events();
+    let id = match events[0] {
+        Event::PollStart(id) => id,
+        ref event => panic!("unexpected event {:?}", event),
+    };
+    assert_eq!(
+        events,
+        [
+            Event::PollStart(id),
+            Event::Wake(id),
+            Event::PollEnd(id, false),
+            Event::PollStart(id),
+            Event::PollEnd(id, true),
+        ]
+    );
+}
+
+#[test]
+fn local_pool_distinct_ids() {
+    let recorder = Recorder::default();
+    let mut pool = LocalPool::with_task_hooks(recorder.clone());
+    pool.spawner().spawn_local(async {}).unwrap();
+    pool.spawner().spawn_local(async {}).unwrap();
+    pool.run();
+
+    let mut ids: Vec<_> = recorder
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::PollStart(id) => Some(id),
+            _ => None,
+        })
+        .collect();
+    ids.dedup();
+    assert_eq!(ids.len(), 2);
+    assert_ne!(ids[0], ids[1]);
+}
+
+#[test]
+fn thread_pool() {
+    let recorder = Recorder::default();
+    let pool = ThreadPool::builder().pool_size(1).task_hooks(recorder.clone()).create().unwrap();
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move {
+        yield_now().await;
+        done_tx.send(()).unwrap();
+    });
+
+    block_on(done_rx).unwrap();
+    drop(pool);
+
+    // The task may complete after `done_rx`, wait for its last hook.
+    while recorder.events().last().map_or(true, |event| !matches!(event, Event::PollEnd(_, true))) {
+        std::thread::yield_now();
+    }
+
+    let events = recorder.events();
+    let id = match events[0] {
+        Event::PollStart(id) => id,
+        ref event => panic!("unexpected event {:?}", event),
+    };
+    assert!(events.iter().all(|event| match *event {
+        Event::PollStart(i) | Event::PollEnd(i, _) | Event::Wake(i) => i == id,
+    }));
+    assert!(events.contains(&Event::Wake(id)));
+    assert_eq!(events.iter().filter(|event| **event == Event::PollEnd(id, true)).count(), 1);
+}
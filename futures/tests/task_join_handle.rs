@@ -0,0 +1,90 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, LocalPool, ThreadPool};
+use futures::future::{self, FutureExt};
+use futures::task::{joinable, LocalSpawnExt, Poll, SpawnExt};
+use futures_test::task::noop_context;
+use std::rc::Rc;
+
+#[test]
+fn thread_pool_output() {
+    let pool = ThreadPool::new().unwrap();
+    let handle = pool.spawn_with_join_handle(async { 42 }).unwrap();
+    assert_eq!(block_on(handle).unwrap(), 42);
+}
+
+#[test]
+fn local_pool_output() {
+    let mut pool = LocalPool::new();
+    let data = Rc::new(5);
+    let handle = pool.spawner().spawn_local_with_join_handle(async move { *data * 2 }).unwrap();
+    assert!(!handle.is_finished());
+    pool.run_until_stalled();
+    assert!(handle.is_finished());
+    assert_eq!(pool.run_until(handle).unwrap(), 10);
+}
+
+#[test]
+fn panic_is_reported() {
+    let pool = ThreadPool::new().unwrap();
+    let handle = pool.spawn_with_join_handle(async { panic!("boom") }).unwrap();
+    let err = block_on(handle).unwrap_err();
+    assert!(err.is_panic());
+    assert_eq!(*err.try_into_panic().unwrap().downcast_ref::<&str>().unwrap(), "boom");
+
+    // The pool survives the panic.
+    assert_eq!(block_on(pool.spawn_with_join_handle(async { 1 }).unwrap()).unwrap(), 1);
+}
+
+#[test]
+fn abort() {
+    let mut pool = LocalPool::new();
+    let data = Rc::new(());
+    let handle = {
+        let data = data.clone();
+        pool.spawner()
+            .spawn_local_with_join_handle(async move {
+                let _data = data;
+                future::pending::<()>().await
+            })
+            .unwrap()
+    };
+    pool.run_until_stalled();
+    assert_eq!(Rc::strong_count(&data), 2);
+
+    handle.abort();
+    pool.run_until_stalled();
+    assert_eq!(Rc::strong_count(&data), 1);
+    assert!(handle.is_finished());
+    assert!(pool.run_until(handle).unwrap_err().is_cancelled());
+}
+
+#[test]
+fn abort_after_completion() {
+    let mut pool = LocalPool::new();
+    let handle = pool.spawner().spawn_local_with_join_handle(async { 1 }).unwrap();
+    pool.run_until_stalled();
+    handle.abort();
+    assert_eq!(pool.run_until(handle).unwrap(), 1);
+}
+
+#[test]
+fn drop_handle_detaches() {
+    let mut pool = LocalPool::new();
+    let (tx, rx) = oneshot::channel();
+    let handle =
+        pool.spawner().spawn_local_with_join_handle(async { tx.send(1).unwrap() }).unwrap();
+    drop(handle);
+    assert_eq!(pool.run_until(rx), Ok(1));
+}
+
+#[test]
+fn task_dropped_by_executor() {
+    let (task, mut handle) = joinable(future::pending::<()>());
+    let mut cx = noop_context();
+    assert!(handle.poll_unpin(&mut cx).is_pending());
+    drop(task);
+    match handle.poll_unpin(&mut cx) {
+        Poll::Ready(Err(e)) => assert!(e.is_cancelled()),
+        _ => panic!("expected a cancelled task"),
+    }
+}
@@ -0,0 +1,86 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, LocalPool, ThreadPool};
+use futures::future;
+use futures::task::{yield_now, LocalSpawnExt};
+use std::sync::mpsc;
+
+futures::task_local! {
+    static NUMBER: u32;
+    static NAME: String;
+}
+
+#[test]
+fn scope() {
+    block_on(NUMBER.scope(1, async {
+        assert_eq!(NUMBER.get(), 1);
+        yield_now().await;
+        assert_eq!(NUMBER.get(), 1);
+    }));
+}
+
+#[test]
+fn not_set_outside_of_scope() {
+    assert!(NUMBER.try_with(|_| ()).is_err());
+    block_on(NUMBER.scope(1, async {}));
+    assert!(NUMBER.try_with(|_| ()).is_err());
+}
+
+#[test]
+#[should_panic(expected = "outside of its scope")]
+fn with_panics_outside_of_scope() {
+    NUMBER.with(|_| ());
+}
+
+#[test]
+fn nested_scopes() {
+    block_on(NUMBER.scope(1, async {
+        NUMBER.scope(2, async { assert_eq!(NUMBER.get(), 2) }).await;
+        assert_eq!(NUMBER.get(), 1);
+    }));
+}
+
+#[test]
+fn sync_scope() {
+    let len = NAME.sync_scope("futures".to_string(), || NAME.with(|name| name.len()));
+    assert_eq!(len, 7);
+    assert!(NAME.try_with(|_| ()).is_err());
+}
+
+#[test]
+fn isolated_between_tasks_on_local_pool() {
+    let mut pool = LocalPool::new();
+    let spawner = pool.spawner();
+
+    for i in 0..4 {
+        spawner
+            .spawn_local(NUMBER.scope(i, async move {
+                for _ in 0..4 {
+                    assert_eq!(NUMBER.get(), i);
+                    yield_now().await;
+                }
+            }))
+            .unwrap();
+    }
+    pool.run();
+}
+
+#[test]
+fn preserved_across_polls_on_thread_pool() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    for i in 0..4 {
+        let tx = tx.clone();
+        let (ready_tx, ready_rx) = oneshot::channel::<()>();
+        pool.spawn_ok(NAME.scope(i.to_string(), async move {
+            let _ = ready_tx.send(());
+            yield_now().await;
+            future::ready(()).await;
+            tx.send(NAME.with(|name| name.clone()) == i.to_string()).unwrap();
+        }));
+        block_on(ready_rx).unwrap();
+    }
+    drop(tx);
+
+    assert_eq!(rx.iter().filter(|ok| *ok).count(), 4);
+}
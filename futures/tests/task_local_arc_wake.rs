@@ -0,0 +1,97 @@
+use futures::task::{local_waker, local_waker_ref, LocalArcWake};
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::thread;
+
+struct CountingTask {
+    wakes: Cell<usize>,
+}
+
+impl CountingTask {
+    fn new() -> Rc<Self> {
+        Rc::new(Self { wakes: Cell::new(0) })
+    }
+}
+
+impl LocalArcWake for CountingTask {
+    fn wake_by_ref(rc_self: &Rc<Self>) {
+        rc_self.wakes.set(rc_self.wakes.get() + 1);
+    }
+}
+
+#[test]
+fn wake_and_refcount() {
+    let task = CountingTask::new();
+    let waker = local_waker(task.clone());
+    assert_eq!(Rc::strong_count(&task), 2);
+
+    let clone = waker.clone();
+    assert_eq!(Rc::strong_count(&task), 3);
+
+    waker.wake_by_ref();
+    clone.wake();
+    assert_eq!(task.wakes.get(), 2);
+    assert_eq!(Rc::strong_count(&task), 2);
+
+    drop(waker);
+    assert_eq!(Rc::strong_count(&task), 1);
+}
+
+#[test]
+fn waker_ref() {
+    let task = CountingTask::new();
+    {
+        let waker = local_waker_ref(&task);
+        waker.wake_by_ref();
+        let clone = waker.clone();
+        drop(waker);
+        clone.wake();
+    }
+    assert_eq!(task.wakes.get(), 2);
+    assert_eq!(Rc::strong_count(&task), 1);
+}
+
+#[test]
+fn panics_on_other_thread() {
+    let task = CountingTask::new();
+    let waker = local_waker(task.clone());
+
+    let waker = thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| waker.wake_by_ref()));
+        assert!(result.is_err());
+        waker
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(task.wakes.get(), 0);
+    drop(waker);
+    assert_eq!(Rc::strong_count(&task), 1);
+}
+
+#[test]
+fn leaks_when_dropped_on_other_thread() {
+    let task = CountingTask::new();
+    let waker = local_waker(task.clone());
+
+    thread::spawn(move || drop(waker)).join().unwrap();
+
+    assert_eq!(task.wakes.get(), 0);
+    assert_eq!(Rc::strong_count(&task), 2);
+}
+
+#[test]
+fn waker_ref_alternating_tasks() {
+    let first = CountingTask::new();
+    let second = CountingTask::new();
+
+    for _ in 0..3 {
+        local_waker_ref(&first).wake_by_ref();
+        local_waker_ref(&second).wake_by_ref();
+    }
+    assert_eq!(first.wakes.get(), 3);
+    assert_eq!(second.wakes.get(), 3);
+    assert_eq!(Rc::strong_count(&first), 1);
+    assert_eq!(Rc::strong_count(&second), 1);
+}
@@ -0,0 +1,38 @@
+use futures::task::LocalAtomicWaker;
+use futures_test::task::new_count_waker;
+
+#[test]
+fn register_and_wake() {
+    let local_waker = LocalAtomicWaker::new();
+    let (waker, count) = new_count_waker();
+
+    assert!(!local_waker.is_registered());
+    assert!(!local_waker.wake_if_registered());
+
+    local_waker.register(&waker);
+    local_waker.register(&waker);
+    assert!(local_waker.is_registered());
+    assert!(local_waker.wake_if_registered());
+    assert_eq!(count, 1);
+
+    // The registration was consumed by the wake above.
+    assert!(!local_waker.is_registered());
+    assert!(!local_waker.wake_if_registered());
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn swap_and_take() {
+    let local_waker = LocalAtomicWaker::new();
+    let (waker, count) = new_count_waker();
+    let (other_waker, other_count) = new_count_waker();
+
+    assert!(local_waker.swap(&waker).is_none());
+    let old_waker = local_waker.swap(&other_waker).unwrap();
+    assert!(old_waker.will_wake(&waker));
+
+    local_waker.take().unwrap().wake();
+    assert_eq!(count, 0);
+    assert_eq!(other_count, 1);
+    assert!(local_waker.take().is_none());
+}
@@ -0,0 +1,91 @@
+use futures::channel::oneshot;
+use futures::executor::block_on;
+use futures::future::{self, FutureExt};
+use futures::task::{scope, Poll};
+use futures_test::task::noop_context;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+#[test]
+fn children_borrow_from_stack() {
+    let counter = AtomicUsize::new(0);
+    let counter_ref = &counter;
+    let out = block_on(scope(|s| async move {
+        for i in 1..=10 {
+            s.spawn(async move {
+                counter_ref.fetch_add(i, Ordering::SeqCst);
+            });
+        }
+        "done"
+    }));
+    assert_eq!(out, "done");
+    assert_eq!(counter.into_inner(), 55);
+}
+
+#[test]
+fn waits_for_children_after_body() {
+    let (tx, rx) = oneshot::channel::<()>();
+    let finished = AtomicUsize::new(0);
+    let mut cx = noop_context();
+
+    let mut fut = Box::pin(scope(|s| {
+        let finished = &finished;
+        s.spawn(async move {
+            rx.await.unwrap();
+            finished.fetch_add(1, Ordering::SeqCst);
+        });
+        future::ready(())
+    }));
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Pending);
+    assert_eq!(fut.len(), 1);
+
+    tx.send(()).unwrap();
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Ready(()));
+    assert!(fut.is_empty());
+    drop(fut);
+    assert_eq!(finished.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn children_spawn_children() {
+    let log = Mutex::new(Vec::new());
+    let log_ref = &log;
+    block_on(scope(|s| async move {
+        let inner = s.clone();
+        s.spawn(async move {
+            log_ref.lock().unwrap().push(1);
+            inner.spawn(async move { log_ref.lock().unwrap().push(2) });
+        });
+    }));
+    assert_eq!(*log.lock().unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn drop_cancels_children() {
+    struct SetOnDrop<'a>(&'a AtomicUsize);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let dropped = AtomicUsize::new(0);
+    let mut cx = noop_context();
+
+    let mut fut = Box::pin(scope(|s| {
+        for _ in 0..3 {
+            let guard = SetOnDrop(&dropped);
+            s.spawn(async move {
+                let _guard = guard;
+                future::pending::<()>().await;
+            });
+        }
+        future::pending::<()>()
+    }));
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Pending);
+    assert_eq!(dropped.load(Ordering::SeqCst), 0);
+
+    drop(fut);
+    assert_eq!(dropped.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,61 @@
+use futures::task::{waker_fn, waker_fn_on_drop};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+fn counter() -> (Arc<AtomicUsize>, impl Fn() + Clone + Send + Sync + 'static) {
+    let count = Arc::new(AtomicUsize::new(0));
+    let f = {
+        let count = count.clone();
+        move || {
+            count.fetch_add(1, Ordering::SeqCst);
+        }
+    };
+    (count, f)
+}
+
+#[test]
+fn waker_fn_calls_on_every_wake() {
+    let (count, f) = counter();
+    let waker = waker_fn(f);
+
+    waker.wake_by_ref();
+    let clone = waker.clone();
+    clone.wake();
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+
+    drop(waker);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn waker_fn_from_other_thread() {
+    let (count, f) = counter();
+    let waker = waker_fn(f);
+
+    thread::spawn(move || waker.wake()).join().unwrap();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn waker_fn_on_drop_calls_once_on_wake() {
+    let (count, f) = counter();
+    let waker = waker_fn_on_drop(f);
+
+    waker.wake_by_ref();
+    waker.wake_by_ref();
+    drop(waker);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn waker_fn_on_drop_calls_on_last_drop() {
+    let (count, f) = counter();
+    let waker = waker_fn_on_drop(f);
+    let clone = waker.clone();
+
+    drop(waker);
+    assert_eq!(count.load(Ordering::SeqCst), 0);
+    drop(clone);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
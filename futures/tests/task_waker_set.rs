@@ -0,0 +1,76 @@
+use futures::task::WakerSet;
+use futures_test::task::new_count_waker;
+
+#[test]
+fn wake_one() {
+    let set = WakerSet::new();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    assert!(!set.wake_one());
+
+    let key1 = set.insert(&waker1);
+    let key2 = set.insert(&waker2);
+    assert_ne!(key1, key2);
+
+    assert!(set.wake_one());
+    assert_eq!(count1, 1);
+    assert_eq!(count2, 0);
+
+    assert!(set.wake_one());
+    assert_eq!(count1, 1);
+    assert_eq!(count2, 1);
+
+    assert!(!set.wake_one());
+    assert!(set.is_empty());
+}
+
+#[test]
+fn wake_all() {
+    let set = WakerSet::new();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    assert!(!set.wake_all());
+
+    set.insert(&waker1);
+    set.insert(&waker2);
+    assert!(set.wake_all());
+    assert_eq!(count1, 1);
+    assert_eq!(count2, 1);
+
+    assert!(!set.wake_all());
+    assert_eq!(count1, 1);
+    assert_eq!(count2, 1);
+}
+
+#[test]
+fn update_and_remove() {
+    let set = WakerSet::new();
+    let (waker, count) = new_count_waker();
+
+    let key = set.insert(&waker);
+    assert!(!set.remove(key));
+    assert!(!set.wake_one());
+
+    // Keys of removed entries are reused.
+    assert_eq!(set.insert(&waker), key);
+    assert!(set.wake_one());
+    assert_eq!(count, 1);
+
+    // A woken entry can be re-armed.
+    set.update(key, &waker);
+    assert!(!set.is_empty());
+    assert!(set.wake_one());
+    assert_eq!(count, 2);
+
+    assert!(set.remove(key));
+    assert!(set.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "invalid key")]
+fn remove_invalid_key() {
+    let set = WakerSet::new();
+    set.remove(0);
+}
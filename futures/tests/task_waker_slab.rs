@@ -0,0 +1,46 @@
+use futures::task::WakerSlab;
+use futures_test::task::new_count_waker;
+
+#[test]
+fn wake_all_keeps_registrations() {
+    let slab = WakerSlab::new();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    assert_eq!(slab.wake_all(), 0);
+
+    let key1 = slab.register(&waker1);
+    let key2 = slab.register(&waker2);
+    assert_ne!(key1, key2);
+    assert_eq!(slab.len(), 2);
+
+    assert_eq!(slab.wake_all(), 2);
+    assert_eq!(slab.wake_all(), 2);
+    assert_eq!(count1, 2);
+    assert_eq!(count2, 2);
+
+    assert!(slab.unregister(key1).unwrap().will_wake(&waker1));
+    assert!(slab.unregister(key1).is_none());
+    assert_eq!(slab.wake_all(), 1);
+    assert_eq!(count1, 2);
+    assert_eq!(count2, 3);
+}
+
+#[test]
+fn reuse_and_update() {
+    let slab = WakerSlab::new();
+    let (waker1, count1) = new_count_waker();
+    let (waker2, count2) = new_count_waker();
+
+    let key = slab.register(&waker1);
+    slab.unregister(key);
+    assert!(slab.is_empty());
+
+    // Keys of unregistered wakers are reused.
+    assert_eq!(slab.register(&waker1), key);
+
+    slab.update(key, &waker2);
+    assert_eq!(slab.wake_all(), 1);
+    assert_eq!(count1, 0);
+    assert_eq!(count2, 1);
+}
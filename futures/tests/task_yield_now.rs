@@ -0,0 +1,39 @@
+use futures::future::FutureExt;
+use futures::stream::{FuturesUnordered, StreamExt};
+use futures::task::{yield_now, Poll};
+use futures_test::task::new_count_waker;
+use std::task::Context;
+
+#[test]
+fn yields_once() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = yield_now();
+
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count, 1);
+    assert_eq!(fut.poll_unpin(&mut cx), Poll::Ready(()));
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn yields_out_of_futures_unordered() {
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut futures: FuturesUnordered<_> = (0..4).map(|_| yield_now()).collect();
+
+    // Two yielding futures make `FuturesUnordered` yield to the executor
+    // before the other futures are polled.
+    assert_eq!(futures.poll_next_unpin(&mut cx), Poll::Pending);
+    assert!(count.get() > 0);
+
+    let mut completed = 0;
+    loop {
+        match futures.poll_next_unpin(&mut cx) {
+            Poll::Ready(Some(())) => completed += 1,
+            Poll::Ready(None) => break,
+            Poll::Pending => {}
+        }
+    }
+    assert_eq!(completed, 4);
+}
@@ -0,0 +1,71 @@
+use futures::executor::block_on;
+use futures::future::{FusedFuture, FutureExt};
+use futures::task::Poll;
+use futures_test::future::controlled;
+use futures_test::task::{new_count_waker, noop_context};
+use std::task::Context;
+use std::thread;
+
+#[test]
+fn resolve_wakes_task() {
+    let (controller, mut future) = controlled();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(future.poll_unpin(&mut cx), Poll::Pending);
+    assert!(!controller.is_resolved());
+    controller.resolve("done");
+    assert_eq!(count, 1);
+    assert!(controller.is_resolved());
+    assert_eq!(future.poll_unpin(&mut cx), Poll::Ready("done"));
+    assert!(future.is_terminated());
+    controller.assert_polled(2);
+}
+
+#[test]
+fn fail() {
+    let (controller, future) = controlled::<Result<(), &str>>();
+    controller.fail("boom");
+    assert_eq!(block_on(future), Err("boom"));
+}
+
+#[test]
+fn hold_pending() {
+    let (controller, mut future) = controlled();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    controller.hold_pending(true);
+    controller.resolve(1);
+    assert_eq!(future.poll_unpin(&mut cx), Poll::Pending);
+    assert_eq!(count, 0);
+
+    controller.hold_pending(false);
+    assert_eq!(count, 1);
+    assert_eq!(future.poll_unpin(&mut cx), Poll::Ready(1));
+    assert_eq!(controller.poll_count(), 2);
+}
+
+#[test]
+fn resolve_from_other_thread() {
+    let (controller, future) = controlled();
+    let handle = thread::spawn(move || controller.resolve(5));
+    assert_eq!(block_on(future), 5);
+    handle.join().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "controlled future was polled 1 times, expected 2")]
+fn assert_polled_mismatch() {
+    let (controller, mut future) = controlled::<()>();
+    let _ = future.poll_unpin(&mut noop_context());
+    controller.assert_polled(2);
+}
+
+#[test]
+#[should_panic(expected = "controlled future already resolved")]
+fn resolve_twice() {
+    let (controller, _future) = controlled();
+    controller.resolve(1);
+    controller.resolve(2);
+}
@@ -0,0 +1,107 @@
+use futures::channel::oneshot;
+use futures::future::{self, Either};
+use futures::task::{LocalSpawnExt, Timer};
+use futures_test::executor::DeterministicPool;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+#[test]
+fn run_jumps_to_deadlines() {
+    let mut pool = DeterministicPool::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    for secs in [30, 10, 20].iter().copied() {
+        let timer = pool.timer();
+        let log = log.clone();
+        pool.spawner()
+            .spawn_local(async move {
+                timer.sleep(Duration::from_secs(secs)).await;
+                log.borrow_mut().push(secs);
+            })
+            .unwrap();
+    }
+
+    pool.run();
+    assert_eq!(*log.borrow(), [10, 20, 30]);
+    assert_eq!(pool.elapsed(), Duration::from_secs(30));
+}
+
+#[test]
+fn clock_waits_for_stalled_tasks() {
+    let mut pool = DeterministicPool::new();
+    let timer = pool.timer();
+    let (tx, rx) = oneshot::channel::<()>();
+
+    pool.spawner()
+        .spawn_local(async move {
+            timer.sleep(Duration::from_secs(5)).await;
+            drop(tx);
+        })
+        .unwrap();
+
+    // The sender is only dropped once the clock has advanced.
+    assert!(pool.run_until(rx).is_err());
+    assert_eq!(pool.elapsed(), Duration::from_secs(5));
+}
+
+#[test]
+fn timeout_with_run_until() {
+    let mut pool = DeterministicPool::new();
+    let timer = pool.timer();
+    let (_tx, rx) = oneshot::channel::<()>();
+
+    let res = pool.run_until(async move {
+        match future::select(rx, timer.sleep(Duration::from_secs(3600))).await {
+            Either::Left(_) => "received",
+            Either::Right(_) => "timed out",
+        }
+    });
+    assert_eq!(res, "timed out");
+    assert_eq!(pool.elapsed(), Duration::from_secs(3600));
+}
+
+#[test]
+fn advance_runs_woken_tasks() {
+    let mut pool = DeterministicPool::new();
+    let timer = pool.timer();
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    {
+        let log = log.clone();
+        pool.spawner()
+            .spawn_local(async move {
+                let start = timer.now();
+                for _ in 0..3 {
+                    timer.sleep(Duration::from_secs(1)).await;
+                    log.borrow_mut().push(timer.now() - start);
+                }
+            })
+            .unwrap();
+    }
+
+    pool.advance(Duration::from_millis(2500));
+    assert_eq!(*log.borrow(), [Duration::from_secs(1), Duration::from_secs(2)]);
+    assert_eq!(pool.elapsed(), Duration::from_millis(2500));
+
+    pool.advance(Duration::from_secs(1));
+    assert_eq!(log.borrow().len(), 3);
+    assert_eq!(pool.elapsed(), Duration::from_millis(3500));
+}
+
+#[test]
+fn dropped_sleep_does_not_advance_clock() {
+    let mut pool = DeterministicPool::new();
+    let sleep = pool.sleep(Duration::from_secs(10));
+    drop(sleep);
+
+    pool.run();
+    assert_eq!(pool.elapsed(), Duration::from_secs(0));
+}
+
+#[test]
+#[should_panic(expected = "stalled with no pending sleep")]
+fn run_until_stalled_forever() {
+    let mut pool = DeterministicPool::new();
+    pool.run_until(future::pending::<()>());
+}
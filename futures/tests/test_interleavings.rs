@@ -0,0 +1,121 @@
+use futures::channel::mpsc;
+use futures::future;
+use futures::task::{LocalSpawnExt, Poll};
+use futures::StreamExt;
+use futures_test::executor::{InterleavingExplorer, InterleavingSpawner};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// Spawns two tasks pushing their name to `log` three times, yielding in
+/// between.
+fn spawn_writers(spawner: &InterleavingSpawner, log: &Rc<RefCell<String>>) {
+    for name in ['a', 'b'].iter().copied() {
+        let log = log.clone();
+        spawner
+            .spawn_local(async move {
+                for _ in 0..3 {
+                    log.borrow_mut().push(name);
+                    let mut yielded = false;
+                    future::poll_fn(|cx| {
+                        if yielded {
+                            Poll::Ready(())
+                        } else {
+                            yielded = true;
+                            cx.waker().wake_by_ref();
+                            Poll::Pending
+                        }
+                    })
+                    .await;
+                }
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn explores_several_orders() {
+    let log = Rc::new(RefCell::new(String::new()));
+    let mut orders = HashSet::new();
+    for seed in 0..50 {
+        log.borrow_mut().clear();
+        InterleavingExplorer::new().seed(seed).iterations(1).explore(|spawner| {
+            spawn_writers(spawner, &log);
+        });
+        assert_eq!(log.borrow().len(), 6);
+        orders.insert(log.borrow().clone());
+    }
+    assert!(orders.len() > 1);
+}
+
+#[test]
+fn replays_seed() {
+    let log = Rc::new(RefCell::new(String::new()));
+    let run = |seed| {
+        log.borrow_mut().clear();
+        InterleavingExplorer::new().seed(seed).iterations(1).explore(|spawner| {
+            spawn_writers(spawner, &log);
+        });
+        log.borrow().clone()
+    };
+    for seed in 0..20 {
+        assert_eq!(run(seed), run(seed));
+    }
+}
+
+#[test]
+fn runs_spawned_tasks_and_channels() {
+    InterleavingExplorer::new().iterations(100).explore(|spawner| {
+        let (tx, rx) = mpsc::unbounded();
+        for i in 0..3 {
+            let tx = tx.clone();
+            let inner = spawner.clone();
+            spawner
+                .spawn_local(async move {
+                    inner.spawn_local(async move { tx.unbounded_send(i).unwrap() }).unwrap();
+                })
+                .unwrap();
+        }
+        drop(tx);
+        spawner
+            .spawn_local(async move {
+                let mut received = rx.collect::<Vec<_>>().await;
+                received.sort_unstable();
+                assert_eq!(received, [0, 1, 2]);
+            })
+            .unwrap();
+    });
+}
+
+#[test]
+#[should_panic(expected = "stalled without being woken")]
+fn detects_deadlock() {
+    InterleavingExplorer::new().iterations(1).explore(|spawner| {
+        spawner.spawn_local(future::pending()).unwrap();
+    });
+}
+
+#[test]
+#[should_panic(expected = "order depends on the interleaving")]
+fn finds_failing_interleaving() {
+    InterleavingExplorer::new().iterations(1000).explore(|spawner| {
+        let log = Rc::new(RefCell::new(String::new()));
+        spawn_writers(spawner, &log);
+        let check = log.clone();
+        spawner
+            .spawn_local(async move {
+                // Runs last only once both writers are done.
+                future::poll_fn(|cx| {
+                    if check.borrow().len() == 6 {
+                        Poll::Ready(())
+                    } else {
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                })
+                .await;
+                assert_eq!(*check.borrow(), "ababab", "order depends on the interleaving");
+            })
+            .unwrap();
+    });
+}
@@ -0,0 +1,69 @@
+use futures::future::{self, Either, FutureExt};
+use futures::task::Timer;
+use futures_test::task::{new_count_waker, noop_context, MockClock};
+use std::task::Context;
+use std::time::Duration;
+
+#[test]
+fn nothing_fires_before_deadline() {
+    let clock = MockClock::new();
+    let (waker, count) = new_count_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut sleep = clock.sleep(Duration::from_secs(5));
+
+    assert!(sleep.poll_unpin(&mut cx).is_pending());
+    clock.advance(Duration::from_millis(4999));
+    assert_eq!(count, 0);
+    assert!(sleep.poll_unpin(&mut cx).is_pending());
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(count, 1);
+    assert!(sleep.poll_unpin(&mut cx).is_ready());
+    assert_eq!(clock.elapsed(), Duration::from_secs(5));
+}
+
+#[test]
+fn pending_sleep_introspection() {
+    let clock = MockClock::new();
+    let start = clock.now();
+    let mut cx = noop_context();
+    let mut short = clock.sleep(Duration::from_secs(1));
+    let long = clock.sleep(Duration::from_secs(10));
+
+    assert_eq!(clock.pending_sleeps(), 2);
+    assert_eq!(clock.next_deadline(), Some(start + Duration::from_secs(1)));
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(clock.pending_sleeps(), 1);
+    assert_eq!(clock.next_deadline(), Some(start + Duration::from_secs(10)));
+    assert!(short.poll_unpin(&mut cx).is_ready());
+
+    drop(long);
+    assert_eq!(clock.pending_sleeps(), 0);
+    assert_eq!(clock.next_deadline(), None);
+}
+
+#[test]
+fn elapsed_deadline_completes_immediately() {
+    let clock = MockClock::new();
+    let mut cx = noop_context();
+    let now = clock.now();
+    clock.advance(Duration::from_secs(1));
+
+    assert!(clock.sleep_until(now).poll_unpin(&mut cx).is_ready());
+    assert!(clock.sleep(Duration::from_secs(0)).poll_unpin(&mut cx).is_ready());
+    assert_eq!(clock.pending_sleeps(), 0);
+}
+
+#[test]
+fn clones_share_time() {
+    let clock = MockClock::new();
+    let clone = clock.clone();
+    let mut cx = noop_context();
+    let mut timeout =
+        future::select(future::pending::<()>(), clone.sleep(Duration::from_millis(100)));
+
+    clock.advance_to(clock.now() + Duration::from_millis(100));
+    assert_eq!(clone.elapsed(), Duration::from_millis(100));
+    assert!(matches!(timeout.poll_unpin(&mut cx), std::task::Poll::Ready(Either::Right(_))));
+}
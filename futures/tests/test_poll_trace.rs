@@ -0,0 +1,73 @@
+use futures::channel::oneshot;
+use futures::future::{self, FutureExt};
+use futures::task::Poll;
+use futures_test::future::{record_poll_trace, FutureTestExt, TraceEventKind};
+use futures_test::task::noop_context;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn records_wake_from_other_thread() {
+    let (tx, rx) = oneshot::channel();
+    let future = record_poll_trace(rx);
+    let trace = future.trace();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        tx.send(1).unwrap();
+    });
+
+    assert_eq!(futures::executor::block_on(future), Ok(1));
+    handle.join().unwrap();
+    trace.assert_ready();
+    trace.assert_woken_before_repoll();
+    assert_eq!(trace.wake_count(), 1);
+
+    let events = trace.events();
+    let kinds: Vec<_> = events.iter().map(|event| (event.kind(), event.poll())).collect();
+    assert_eq!(
+        kinds,
+        [
+            (TraceEventKind::Polled, 0),
+            (TraceEventKind::Pending, 0),
+            (TraceEventKind::Woken, 0),
+            (TraceEventKind::Polled, 1),
+            (TraceEventKind::Ready, 1),
+        ]
+    );
+    assert!(events.windows(2).all(|w| w[0].elapsed() <= w[1].elapsed()));
+    assert!(events[2].elapsed() >= Duration::from_millis(10));
+}
+
+#[test]
+#[should_panic(expected = "future was polled after returning `Poll::Ready` (poll 1)")]
+fn detects_poll_after_ready() {
+    let mut future = record_poll_trace(future::ready(()).fuse());
+    let mut cx = noop_context();
+    assert_eq!(future.poll_unpin(&mut cx), Poll::Ready(()));
+    assert_eq!(future.poll_unpin(&mut cx), Poll::Pending);
+    future.trace().assert_no_poll_after_ready();
+}
+
+#[test]
+#[should_panic(expected = "polled again (poll 1) without being woken")]
+fn detects_poll_without_wake() {
+    let mut future = record_poll_trace(future::pending::<()>());
+    let mut cx = noop_context();
+    assert!(future.poll_unpin(&mut cx).is_pending());
+    assert!(future.poll_unpin(&mut cx).is_pending());
+    future.trace().assert_woken_before_repoll();
+}
+
+#[test]
+fn counts_polls() {
+    let mut future = record_poll_trace(future::ready(1).interleave_pending());
+    let mut cx = noop_context();
+    assert!(future.poll_unpin(&mut cx).is_pending());
+    assert_eq!(future.poll_unpin(&mut cx), Poll::Ready(1));
+
+    let trace = future.trace();
+    drop(future);
+    trace.assert_poll_count(2);
+    trace.assert_ready();
+    trace.assert_woken_before_repoll();
+}
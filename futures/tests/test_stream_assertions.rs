@@ -0,0 +1,125 @@
+use futures::channel::mpsc;
+use futures::stream::{self, FusedStream, Stream, StreamExt};
+use futures::task::{Context, Poll};
+use futures_test::stream::{StreamTestExt, StreamTester};
+use futures_test::{assert_stream_eq, assert_stream_pending_then};
+use std::pin::Pin;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn stream_eq() {
+    let mut stream = stream::iter(vec![1, 2, 3]).interleave_pending();
+    assert_stream_eq!(stream, [1, 2, 3]);
+
+    let mut empty = stream::empty::<u8>();
+    assert_stream_eq!(empty, []);
+}
+
+#[test]
+#[should_panic(expected = "stream yielded unexpected items")]
+fn stream_eq_mismatch() {
+    let mut stream = stream::iter(vec![1, 2]);
+    assert_stream_eq!(stream, [1, 2, 3]);
+}
+
+#[test]
+fn pending_then_from_other_thread() {
+    let (tx, mut rx) = mpsc::unbounded();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        tx.unbounded_send(7).unwrap();
+    });
+    assert_stream_pending_then!(rx, 7);
+    handle.join().unwrap();
+}
+
+/// A stream returning `Poll::Pending` once without waking its task.
+struct LostWakeup(bool);
+
+impl Stream for LostWakeup {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<()>> {
+        if self.0 {
+            Poll::Ready(None)
+        } else {
+            self.0 = true;
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "lost wakeup")]
+fn tester_detects_lost_wakeup() {
+    StreamTester::new().wake_timeout(Duration::from_millis(10)).run(LostWakeup(false));
+}
+
+#[test]
+fn tester_runs_fused_streams() {
+    let items = StreamTester::new().run_fused(stream::iter(0..5).interleave_pending().fuse());
+    assert_eq!(items, [0, 1, 2, 3, 4]);
+
+    let (tx, rx) = mpsc::channel(1);
+    let handle = thread::spawn(move || {
+        futures::executor::block_on(async move {
+            let mut tx = tx;
+            for i in 0..3 {
+                futures::SinkExt::send(&mut tx, i).await.unwrap();
+            }
+        })
+    });
+    assert_eq!(StreamTester::new().run_fused(rx), [0, 1, 2]);
+    handle.join().unwrap();
+}
+
+/// A stream claiming to yield more items than it does.
+struct BadHint(usize);
+
+impl Stream for BadHint {
+    type Item = usize;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<usize>> {
+        if self.0 == 0 {
+            Poll::Ready(None)
+        } else {
+            self.0 -= 1;
+            Poll::Ready(Some(self.0))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0 + 1, None)
+    }
+}
+
+#[test]
+#[should_panic(expected = "size hint (3, None) returned after 0 items, while 2 more")]
+fn tester_checks_size_hint() {
+    StreamTester::new().run(BadHint(2));
+}
+
+/// A stream yielding an item after claiming to be terminated.
+struct NotFused(bool);
+
+impl Stream for NotFused {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<()>> {
+        self.0 = !self.0;
+        Poll::Ready(if self.0 { None } else { Some(()) })
+    }
+}
+
+impl FusedStream for NotFused {
+    fn is_terminated(&self) -> bool {
+        self.0
+    }
+}
+
+#[test]
+#[should_panic(expected = "fused stream yielded an item after `None`")]
+fn tester_checks_fused() {
+    StreamTester::new().run_fused(NotFused(false));
+}
@@ -0,0 +1,58 @@
+use futures::channel::{mpsc, oneshot};
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures_test::assert_no_waker_leaks;
+use futures_test::task::{WakerEventKind, WakerTracker};
+use std::task::Waker;
+
+#[test]
+fn records_events() {
+    let tracker = WakerTracker::new();
+    let waker = tracker.waker();
+    let clone = waker.clone();
+    clone.wake_by_ref();
+    clone.wake();
+    drop(waker);
+
+    let kinds: Vec<_> = tracker.events().iter().map(|event| (event.id(), event.kind())).collect();
+    assert_eq!(
+        kinds,
+        [
+            (0, WakerEventKind::Created),
+            (1, WakerEventKind::Cloned { from: 0 }),
+            (1, WakerEventKind::WokenByRef),
+            (1, WakerEventKind::Woken),
+            (0, WakerEventKind::Dropped),
+        ]
+    );
+    assert_eq!(tracker.wake_count(), 2);
+    assert_eq!(tracker.live_wakers(), 0);
+}
+
+#[test]
+fn channels_release_wakers() {
+    assert_no_waker_leaks!(|cx| {
+        let (tx, mut rx) = mpsc::channel::<i32>(1);
+        assert!(rx.poll_next_unpin(cx).is_pending());
+        drop(tx);
+        assert!(rx.poll_next_unpin(cx).is_ready());
+    });
+
+    let output = assert_no_waker_leaks!(|cx| {
+        let (tx, mut rx) = oneshot::channel();
+        assert!(rx.poll_unpin(cx).is_pending());
+        tx.send(3).unwrap();
+        rx.poll_unpin(cx)
+    });
+    assert_eq!(output, std::task::Poll::Ready(Ok(3)));
+}
+
+#[test]
+#[should_panic(expected = "1 waker(s) leaked:\n  waker #1 cloned from waker #0")]
+fn detects_leak() {
+    let mut stash: Option<Waker> = None;
+    assert_no_waker_leaks!(|cx| {
+        stash = Some(cx.waker().clone());
+    });
+    drop(stash);
+}
@@ -0,0 +1,74 @@
+#![cfg(all(feature = "thread-affinity", target_os = "linux"))]
+
+use futures::executor::{block_on, ThreadPool};
+use futures::task::SpawnExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn current_cores() -> Vec<usize> {
+    let status = std::fs::read_to_string("/proc/thread-self/status").unwrap();
+    let list = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+        .unwrap()
+        .trim()
+        .to_owned();
+    list.split(',')
+        .flat_map(|range| {
+            let mut bounds = range.split('-').map(|n| n.parse::<usize>().unwrap());
+            let start = bounds.next().unwrap();
+            let end = bounds.next().unwrap_or(start);
+            start..=end
+        })
+        .collect()
+}
+
+#[test]
+fn pin_to_cores() {
+    let core = *current_cores().last().unwrap();
+    let pool = ThreadPool::builder().pool_size(2).pin_to_cores(vec![core]).create().unwrap();
+    let cores = block_on(pool.spawn_with_handle(async { current_cores() }).unwrap());
+    assert_eq!(cores, vec![core]);
+}
+
+#[test]
+fn affinity_by_worker() {
+    let allowed = current_cores();
+    let pool = ThreadPool::builder()
+        .pool_size(2)
+        .affinity(move |idx| if idx == 0 { allowed.clone() } else { Vec::new() })
+        .create();
+    assert!(pool.is_ok());
+}
+
+#[test]
+fn invalid_core() {
+    assert!(ThreadPool::builder().pin_to_cores(vec![usize::MAX]).create().is_err());
+}
+
+#[test]
+fn invalid_core_stops_started_workers() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let stopped = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::builder()
+        .pool_size(2)
+        .affinity(|idx| if idx == 0 { Vec::new() } else { vec![usize::MAX] })
+        .after_start({
+            let started = started.clone();
+            move |_| {
+                started.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .before_stop({
+            let stopped = stopped.clone();
+            move |_| {
+                stopped.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .create();
+    assert!(pool.is_err());
+    // The worker which failed to be pinned never started, the other one was
+    // stopped.
+    assert_eq!(started.load(Ordering::SeqCst), 1);
+    assert_eq!(stopped.load(Ordering::SeqCst), 1);
+}
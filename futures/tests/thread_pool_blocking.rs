@@ -0,0 +1,87 @@
+use futures::executor::{block_on, ThreadPool};
+use futures::future;
+use futures::task::{SpawnBlocking, SpawnBlockingExt};
+use std::sync::{mpsc, Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn returns_result() {
+    let pool = ThreadPool::new().unwrap();
+    let handle = pool.spawn_blocking(|| 6 * 7).unwrap();
+    assert_eq!(block_on(handle).unwrap(), 42);
+}
+
+#[test]
+fn runs_off_worker_threads() {
+    let pool = ThreadPool::builder().pool_size(1).name_prefix("pool-").create().unwrap();
+    let handle = pool.spawn_blocking(|| thread::current().name().map(str::to_owned)).unwrap();
+    let name = block_on(handle).unwrap().unwrap();
+    assert!(name.starts_with("pool-blocking-"), "{}", name);
+}
+
+#[test]
+fn closures_run_concurrently() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let barrier = Arc::new(Barrier::new(4));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let barrier = barrier.clone();
+            pool.spawn_blocking(move || {
+                barrier.wait();
+            })
+            .unwrap()
+        })
+        .collect();
+    for res in block_on(future::join_all(handles)) {
+        res.unwrap();
+    }
+}
+
+#[test]
+fn queued_when_at_max_threads() {
+    let pool = ThreadPool::builder().max_blocking_threads(1).create().unwrap();
+    let (tx, rx) = mpsc::channel();
+    let first = pool.spawn_blocking(move || rx.recv().unwrap()).unwrap();
+    let second = pool.spawn_blocking(|| 2).unwrap();
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!second.is_finished());
+    tx.send(1).unwrap();
+    assert_eq!(block_on(first).unwrap(), 1);
+    assert_eq!(block_on(second).unwrap(), 2);
+}
+
+#[test]
+fn panic_is_reported() {
+    let pool = ThreadPool::builder().max_blocking_threads(1).create().unwrap();
+    let handle = pool.spawn_blocking(|| panic!("boom")).unwrap();
+    assert!(block_on(handle).map(|_: ()| ()).unwrap_err().is_panic());
+
+    // The blocking thread survives the panic.
+    assert_eq!(block_on(pool.spawn_blocking(|| 1).unwrap()).unwrap(), 1);
+}
+
+#[test]
+fn threads_stop_when_idle() {
+    let pool =
+        ThreadPool::builder().blocking_keep_alive(Duration::from_millis(10)).create().unwrap();
+    block_on(pool.spawn_blocking(|| ()).unwrap()).unwrap();
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(block_on(pool.spawn_blocking(|| 3).unwrap()).unwrap(), 3);
+}
+
+#[test]
+fn spawn_blocking_obj() {
+    let pool = ThreadPool::new().unwrap();
+    let (tx, rx) = mpsc::channel();
+    pool.spawn_blocking_obj(Box::new(move || tx.send(5).unwrap())).unwrap();
+    assert_eq!(rx.recv().unwrap(), 5);
+}
+
+#[test]
+fn rejected_after_shutdown() {
+    let pool = ThreadPool::new().unwrap();
+    pool.shutdown(None);
+    assert!(pool.spawn_blocking(|| ()).is_err());
+}
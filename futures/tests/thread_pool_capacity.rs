@@ -0,0 +1,135 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, ThreadPool};
+use futures::future::FutureExt;
+use futures::task::{waker, ArcWake, Context, Spawn, SpawnExt};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn try_spawn_fails_when_full() {
+    let pool = ThreadPool::builder().pool_size(1).capacity(2).create().unwrap();
+    let (tx1, rx1) = oneshot::channel::<()>();
+    let (tx2, rx2) = oneshot::channel::<()>();
+    pool.try_spawn(rx1.map(drop)).unwrap();
+    pool.try_spawn(rx2.map(drop)).unwrap();
+
+    let err = pool.try_spawn(async {}).unwrap_err();
+    assert!(err.is_full());
+    assert!(!err.is_shutdown());
+    assert!(pool.status().unwrap_err().is_full());
+    assert!(pool.spawn(async {}).unwrap_err().is_full());
+
+    // `spawn_ok` ignores the capacity.
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move { done_tx.send(()).unwrap() });
+    block_on(done_rx).unwrap();
+
+    drop(tx1);
+    drop(tx2);
+    pool.shutdown(None);
+}
+
+#[test]
+fn spawn_async_waits_for_capacity() {
+    let pool = ThreadPool::builder().pool_size(2).capacity(4).create().unwrap();
+    let running = Arc::new(AtomicUsize::new(0));
+    let max = Arc::new(AtomicUsize::new(0));
+    block_on(async {
+        for _ in 0..32 {
+            let running = running.clone();
+            let max = max.clone();
+            pool.spawn_async(async move {
+                let n = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max.fetch_max(n, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(1));
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+            .await
+            .unwrap();
+        }
+    });
+    assert_eq!(pool.shutdown(None), 0);
+    assert!(max.load(Ordering::SeqCst) <= 4);
+}
+
+#[test]
+fn spawn_async_fails_after_shutdown() {
+    let pool = ThreadPool::builder().capacity(1).create().unwrap();
+    let (_tx, rx) = oneshot::channel::<()>();
+    pool.try_spawn(rx.map(drop)).unwrap();
+
+    let spawner = pool.clone();
+    let waiting = std::thread::spawn(move || block_on(spawner.spawn_async(async {})));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(pool.shutdown(Some(Duration::from_millis(20))), 1);
+    assert!(waiting.join().unwrap().unwrap_err().is_shutdown());
+}
+
+#[test]
+fn freed_slot_wakes_one_waiter() {
+    struct Counter(AtomicUsize);
+
+    impl ArcWake for Counter {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let pool = ThreadPool::builder().capacity(1).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    pool.try_spawn(rx.map(drop)).unwrap();
+
+    let counts = [Arc::new(Counter(AtomicUsize::new(0))), Arc::new(Counter(AtomicUsize::new(0)))];
+    let wakers = [waker(counts[0].clone()), waker(counts[1].clone())];
+    let mut first = Box::pin(pool.spawn_async(async {}));
+    let mut second = Box::pin(pool.spawn_async(async {}));
+    for _ in 0..3 {
+        assert!(first.poll_unpin(&mut Context::from_waker(&wakers[0])).is_pending());
+    }
+    assert!(second.poll_unpin(&mut Context::from_waker(&wakers[1])).is_pending());
+
+    drop(tx);
+    while counts[0].0.load(Ordering::SeqCst) == 0 {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(counts[0].0.load(Ordering::SeqCst), 1);
+    assert_eq!(counts[1].0.load(Ordering::SeqCst), 0);
+
+    // Dropping the woken waiter passes the slot on.
+    drop(first);
+    assert_eq!(counts[1].0.load(Ordering::SeqCst), 1);
+    assert!(second.poll_unpin(&mut Context::from_waker(&wakers[1])).is_ready());
+    assert_eq!(pool.shutdown(None), 0);
+}
+
+#[test]
+fn waiters_take_freed_slots_before_new_spawns() {
+    let pool = ThreadPool::builder().capacity(1).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    pool.try_spawn(rx.map(drop)).unwrap();
+
+    let noop = futures::task::noop_waker();
+    let mut cx = Context::from_waker(&noop);
+    let mut first = Box::pin(pool.spawn_async(async {}));
+    let mut second = Box::pin(pool.spawn_async(async {}));
+    assert!(first.poll_unpin(&mut cx).is_pending());
+    assert!(second.poll_unpin(&mut cx).is_pending());
+
+    // Neither the second waiter nor a new spawn takes the freed slot, even
+    // once the second waiter has been polled again.
+    drop(tx);
+    loop {
+        assert!(second.poll_unpin(&mut cx).is_pending());
+        assert!(pool.try_spawn(async {}).unwrap_err().is_full());
+        if first.poll_unpin(&mut cx).is_ready() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    while second.poll_unpin(&mut cx).is_pending() {
+        assert!(pool.try_spawn(async {}).unwrap_err().is_full());
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(pool.shutdown(None), 0);
+}
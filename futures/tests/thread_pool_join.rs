@@ -0,0 +1,37 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, ThreadPool};
+use std::time::Duration;
+
+#[test]
+fn resolves_to_output() {
+    let pool = ThreadPool::new().unwrap();
+    assert_eq!(block_on(pool.spawn_joinable(async { 42 })).unwrap(), 42);
+}
+
+#[test]
+fn reports_panics() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let err = block_on(pool.spawn_joinable(async { panic!("boom") })).unwrap_err();
+    assert!(err.is_panic());
+    let payload = err.try_into_panic().unwrap();
+    assert_eq!(*payload.downcast::<&str>().unwrap(), "boom");
+
+    // The only worker survived the panic.
+    assert_eq!(block_on(pool.spawn_joinable(async { 1 })).unwrap(), 1);
+}
+
+#[test]
+fn reports_abort() {
+    let pool = ThreadPool::new().unwrap();
+    let (_tx, rx) = oneshot::channel::<()>();
+    let handle = pool.spawn_joinable(rx);
+    handle.abort();
+    assert!(block_on(handle).unwrap_err().is_cancelled());
+}
+
+#[test]
+fn cancelled_after_shutdown() {
+    let pool = ThreadPool::new().unwrap();
+    pool.shutdown(Some(Duration::from_secs(1)));
+    assert!(block_on(pool.spawn_joinable(async {})).unwrap_err().is_cancelled());
+}
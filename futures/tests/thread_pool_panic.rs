@@ -0,0 +1,80 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, ThreadPool};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn workers_survive_panics() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    for _ in 0..3 {
+        pool.spawn_ok(async { panic!("boom") });
+    }
+    let (tx, rx) = oneshot::channel();
+    pool.spawn_ok(async move { tx.send(1).unwrap() });
+    assert_eq!(block_on(rx).unwrap(), 1);
+}
+
+#[test]
+fn panic_handler_receives_payloads() {
+    let payloads = Arc::new(Mutex::new(Vec::new()));
+    let pool = {
+        let payloads = payloads.clone();
+        ThreadPool::builder()
+            .pool_size(2)
+            .panic_handler(move |payload| {
+                let msg = *payload.downcast::<&str>().unwrap();
+                payloads.lock().unwrap().push(msg);
+            })
+            .create()
+            .unwrap()
+    };
+    pool.spawn_ok(async { panic!("first") });
+    pool.spawn_ok(async { panic!("second") });
+    pool.spawn_ok(async {});
+    assert_eq!(pool.shutdown(None), 0);
+
+    let mut payloads = payloads.lock().unwrap().clone();
+    payloads.sort_unstable();
+    assert_eq!(payloads, vec!["first", "second"]);
+}
+
+#[test]
+fn panicking_handler() {
+    let pool =
+        ThreadPool::builder().pool_size(1).panic_handler(|_| panic!("handler")).create().unwrap();
+    pool.spawn_ok(async { panic!("task") });
+    let (tx, rx) = oneshot::channel();
+    pool.spawn_ok(async move { tx.send(()).unwrap() });
+    block_on(rx).unwrap();
+}
+
+#[test]
+fn task_dropped_on_panic() {
+    let pool = ThreadPool::new().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    pool.spawn_ok(async move {
+        let _tx = tx;
+        panic!("boom");
+    });
+    assert!(block_on(rx).is_err());
+}
+
+#[test]
+fn worker_replaced_after_panic_in_drop() {
+    struct PanicOnDrop;
+
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            panic!("drop");
+        }
+    }
+
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let guard = PanicOnDrop;
+    pool.spawn_ok(async move {
+        let _guard = guard;
+    });
+    let (tx, rx) = oneshot::channel();
+    pool.spawn_ok(async move { tx.send(1).unwrap() });
+    assert_eq!(block_on(rx).unwrap(), 1);
+    assert_eq!(pool.shutdown(None), 0);
+}
@@ -0,0 +1,87 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, ThreadPool};
+use futures::task::{yield_now, Priority, SpawnPriorityExt};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// Spawns a task blocking the only worker of `pool` until the returned sender
+/// is used.
+fn block_worker(pool: &ThreadPool) -> mpsc::Sender<()> {
+    let (started_tx, started_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    pool.spawn_ok(async move {
+        started_tx.send(()).unwrap();
+        release_rx.recv().unwrap();
+    });
+    started_rx.recv().unwrap();
+    release_tx
+}
+
+#[test]
+fn dispatch_by_priority() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let release = block_worker(&pool);
+
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let (done_tx, done_rx) = oneshot::channel();
+    let mut done_tx = Some(done_tx);
+    for priority in [Priority::Low, Priority::Normal, Priority::High].iter().copied() {
+        let order = order.clone();
+        let done_tx = if priority == Priority::Low { done_tx.take() } else { None };
+        pool.spawn_with_priority(
+            async move {
+                order.lock().unwrap().push(priority);
+                if let Some(done_tx) = done_tx {
+                    done_tx.send(()).unwrap();
+                }
+            },
+            priority,
+        )
+        .unwrap();
+    }
+
+    release.send(()).unwrap();
+    block_on(done_rx).unwrap();
+    assert_eq!(*order.lock().unwrap(), vec![Priority::High, Priority::Normal, Priority::Low]);
+}
+
+#[test]
+fn woken_tasks_keep_priority() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let (done_tx, done_rx) = oneshot::channel();
+
+    let release = block_worker(&pool);
+    {
+        let order = order.clone();
+        pool.spawn_with_priority(
+            async move {
+                order.lock().unwrap().push("high");
+                yield_now().await;
+                order.lock().unwrap().push("high");
+            },
+            Priority::High,
+        )
+        .unwrap();
+    }
+    {
+        let order = order.clone();
+        pool.spawn_with_priority(
+            async move {
+                order.lock().unwrap().push("low");
+                done_tx.send(()).unwrap();
+            },
+            Priority::Low,
+        )
+        .unwrap();
+    }
+
+    release.send(()).unwrap();
+    block_on(done_rx).unwrap();
+    assert_eq!(*order.lock().unwrap(), vec!["high", "high", "low"]);
+}
+
+#[test]
+fn default_priority_is_normal() {
+    assert_eq!(Priority::default(), Priority::Normal);
+    assert!(Priority::High > Priority::Normal && Priority::Normal > Priority::Low);
+}
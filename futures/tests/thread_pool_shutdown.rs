@@ -0,0 +1,115 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, ThreadPool};
+use futures::task::{Spawn, SpawnExt};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn waits_for_tasks() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let done = Arc::new(AtomicUsize::new(0));
+    for _ in 0..4 {
+        let done = done.clone();
+        pool.spawn_ok(async move {
+            thread::sleep(Duration::from_millis(50));
+            done.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+    assert_eq!(pool.shutdown(None), 0);
+    assert_eq!(done.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn abandons_tasks_after_deadline() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    let (wake_tx, wake_rx) = oneshot::channel::<()>();
+    pool.spawn_ok(async move {
+        let _tx = tx;
+        let _ = wake_rx.await;
+        unreachable!();
+    });
+    pool.spawn_ok(async {});
+
+    assert_eq!(pool.shutdown(Some(Duration::from_millis(50))), 1);
+    // The abandoned task is dropped once woken, without being polled.
+    drop(wake_tx);
+    assert!(block_on(rx).is_err());
+}
+
+#[test]
+fn rejects_spawns_after_shutdown() {
+    let pool = ThreadPool::new().unwrap();
+    pool.shutdown(None);
+    assert!(pool.status().is_err());
+    assert!(pool.spawn(async {}).is_err());
+}
+
+#[test]
+fn joins_worker_threads() {
+    let (tx, rx) = mpsc::channel();
+    let pool = ThreadPool::builder()
+        .pool_size(3)
+        .before_stop(move |idx| tx.send(idx).unwrap())
+        .create()
+        .unwrap();
+    pool.shutdown(None);
+    let mut stopped = rx.try_iter().collect::<Vec<_>>();
+    stopped.sort_unstable();
+    assert_eq!(stopped, vec![0, 1, 2]);
+}
+
+#[test]
+fn shutdown_on_drop() {
+    let done = Arc::new(AtomicUsize::new(0));
+    let pool = ThreadPool::builder().pool_size(1).shutdown_on_drop(None).create().unwrap();
+    {
+        let done = done.clone();
+        pool.spawn_ok(async move {
+            thread::sleep(Duration::from_millis(50));
+            done.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+    drop(pool);
+    assert_eq!(done.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn detached_pool_runs_remaining_tasks() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (tx, rx) = oneshot::channel();
+    pool.spawn_ok(async move {
+        thread::sleep(Duration::from_millis(50));
+        tx.send(()).unwrap();
+    });
+    drop(pool);
+    block_on(rx).unwrap();
+}
+
+#[test]
+fn detached_pool_stops_idle_workers() {
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let pool = ThreadPool::builder()
+        .pool_size(2)
+        .before_stop(move |idx| stop_tx.send(idx).unwrap())
+        .create()
+        .unwrap();
+    let (tx, rx) = oneshot::channel::<u32>();
+    let (done_tx, done_rx) = oneshot::channel();
+    pool.spawn_ok(async move {
+        done_tx.send(rx.await.unwrap() + 1).unwrap();
+    });
+    drop(pool);
+
+    // The workers exit although the task is still pending.
+    let mut stopped = (0..2).map(|_| stop_rx.recv().unwrap()).collect::<Vec<_>>();
+    stopped.sort_unstable();
+    assert_eq!(stopped, vec![0, 1]);
+
+    // A worker is started again once the task is woken.
+    tx.send(1).unwrap();
+    assert_eq!(block_on(done_rx), Ok(2));
+    stop_rx.recv().unwrap();
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "task-stats")]
+
+use futures::channel::oneshot;
+use futures::executor::{block_on, ThreadPool};
+use futures::task::yield_now;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn records_polls() {
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    let (polled_tx, polled_rx) = oneshot::channel();
+    pool.spawn_ok(async move {
+        thread::sleep(Duration::from_millis(20));
+        yield_now().await;
+        polled_tx.send(()).unwrap();
+        rx.await.unwrap();
+    });
+    block_on(polled_rx).unwrap();
+
+    let stats = pool.task_stats();
+    assert_eq!(stats.len(), 1);
+    let stats = &stats[0];
+    assert_eq!(stats.polls(), 2);
+    assert!(stats.busy_time() >= Duration::from_millis(20));
+    assert!(stats.longest_poll() >= Duration::from_millis(20));
+    assert!(stats.longest_poll() <= stats.busy_time());
+
+    tx.send(()).unwrap();
+    assert_eq!(pool.shutdown(None), 0);
+    assert!(pool.task_stats().is_empty());
+}
+
+#[test]
+fn lists_spawned_tasks() {
+    let pool = ThreadPool::new().unwrap();
+    let (tx, rx) = oneshot::channel::<()>();
+    pool.spawn_ok(async move { rx.await.unwrap() });
+    pool.spawn_ok(futures::future::ready(()));
+    tx.send(()).unwrap();
+    pool.shutdown(None);
+    assert!(pool.task_stats().is_empty());
+
+    let pool = ThreadPool::builder().pool_size(1).create().unwrap();
+    let mut senders = Vec::new();
+    for _ in 0..3 {
+        let (tx, rx) = oneshot::channel::<()>();
+        pool.spawn_ok(async move { rx.await.unwrap() });
+        senders.push(tx);
+    }
+    let mut ids = pool.task_stats().iter().map(|stats| stats.id()).collect::<Vec<_>>();
+    ids.dedup();
+    assert_eq!(ids.len(), 3);
+    for tx in senders {
+        tx.send(()).unwrap();
+    }
+    pool.shutdown(None);
+}
@@ -0,0 +1,60 @@
+use futures::channel::oneshot;
+use futures::executor::{block_on, ThreadPool};
+use futures::future::join_all;
+use futures::task::{yield_now, SpawnExt};
+use std::sync::mpsc;
+
+#[test]
+fn idle_workers_steal_queued_tasks() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let (tx, rx) = mpsc::channel();
+    let (done_tx, done_rx) = oneshot::channel();
+
+    let spawner = pool.clone();
+    pool.spawn_ok(async move {
+        // Spawned from a worker, so queued on that worker only.
+        spawner.spawn_ok(async move { tx.send(()).unwrap() });
+        // Keep the worker busy until another one has run the task.
+        rx.recv().unwrap();
+        done_tx.send(()).unwrap();
+    });
+    block_on(done_rx).unwrap();
+}
+
+#[test]
+fn runs_nested_spawns() {
+    let pool = ThreadPool::builder().pool_size(4).create().unwrap();
+    let spawner = pool.clone();
+    let results = (0..16usize).map(|i| {
+        let spawner = spawner.clone();
+        pool.spawn_with_handle(async move {
+            let children = (0..16usize).map(|j| {
+                spawner
+                    .spawn_with_handle(async move {
+                        yield_now().await;
+                        i * j
+                    })
+                    .unwrap()
+            });
+            join_all(children.collect::<Vec<_>>()).await.into_iter().sum::<usize>()
+        })
+        .unwrap()
+    });
+    let total: usize = block_on(join_all(results.collect::<Vec<_>>())).into_iter().sum();
+    assert_eq!(total, (0..16).sum::<usize>() * (0..16).sum::<usize>());
+}
+
+#[test]
+fn tasks_woken_from_outside_the_pool() {
+    let pool = ThreadPool::builder().pool_size(2).create().unwrap();
+    let (senders, handles): (Vec<_>, Vec<_>) = (0..8)
+        .map(|i| {
+            let (tx, rx) = oneshot::channel::<usize>();
+            (tx, pool.spawn_with_handle(async move { rx.await.unwrap() + i }).unwrap())
+        })
+        .unzip();
+    for tx in senders {
+        tx.send(1).unwrap();
+    }
+    assert_eq!(block_on(join_all(handles)), (1..9).collect::<Vec<_>>());
+}